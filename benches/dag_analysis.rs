@@ -0,0 +1,101 @@
+//! Benchmark for viability checks and DAG analysis on large instruction sets.
+//!
+//! Run with: `cargo bench --bench dag_analysis`
+//!
+//! Generates layered DAGs (10k instructions by default) where every node
+//! depends on a few nodes from the previous layer and references one of them
+//! via `${id.output}`, then times graph construction, `analyze_dag`, and the
+//! full `ViabilityChecker::check_all` pass.
+
+use std::time::{Duration, Instant};
+
+use plan_forge::models::{Instruction, OpCode};
+use plan_forge::orchestrator::ViabilityChecker;
+use plan_forge::orchestrator::viability::{InstructionGraph, analyze_dag};
+
+const OPS: [OpCode; 4] = [
+    OpCode::SearchCode,
+    OpCode::ReadFiles,
+    OpCode::EditCode,
+    OpCode::RunTest,
+];
+
+/// Build a layered DAG with `count` instructions, `width` nodes per layer,
+/// and up to `fan_in` dependencies per node.
+fn layered_dag(count: usize, width: usize, fan_in: usize) -> Vec<Instruction> {
+    (0..count)
+        .map(|i| {
+            let layer = i / width;
+            let dependencies: Vec<String> = if layer == 0 {
+                Vec::new()
+            } else {
+                (0..fan_in)
+                    .map(|k| (layer - 1) * width + (i + k) % width)
+                    .map(|d| format!("step_{}", d))
+                    .collect()
+            };
+            let params = match dependencies.first() {
+                Some(dep) => serde_json::json!({
+                    "query": "impl Handler",
+                    "paths": format!("${{{}.output}}", dep),
+                }),
+                None => serde_json::json!({ "query": "impl Handler" }),
+            };
+            Instruction {
+                id: format!("step_{}", i),
+                op: OPS[i % OPS.len()].clone(),
+                params,
+                dependencies,
+                description: format!("Generated step {}", i),
+                estimated_tokens: Some(500),
+            }
+        })
+        .collect()
+}
+
+fn time<T>(label: &str, iterations: u32, mut f: impl FnMut() -> T) {
+    // Warm up once so lazy statics (regexes) are initialized
+    std::hint::black_box(f());
+
+    let start = Instant::now();
+    for _ in 0..iterations {
+        std::hint::black_box(f());
+    }
+    let per_iter = start.elapsed() / iterations;
+    println!("{:<40} {:>12}", label, format_duration(per_iter));
+}
+
+fn format_duration(d: Duration) -> String {
+    if d.as_secs() > 0 {
+        format!("{:.2} s", d.as_secs_f64())
+    } else if d.as_millis() > 0 {
+        format!("{:.2} ms", d.as_secs_f64() * 1_000.0)
+    } else {
+        format!("{:.2} µs", d.as_secs_f64() * 1_000_000.0)
+    }
+}
+
+fn main() {
+    let checker = ViabilityChecker::new();
+
+    for &(count, width) in &[(1_000, 50), (10_000, 100), (10_000, 1)] {
+        let instructions = layered_dag(count, width, width.min(3));
+        println!(
+            "\n{} instructions, {} per layer ({} edges)",
+            count,
+            width,
+            instructions
+                .iter()
+                .map(|i| i.dependencies.len())
+                .sum::<usize>()
+        );
+
+        time("InstructionGraph::new", 10, || {
+            InstructionGraph::new(&instructions).len()
+        });
+        time("analyze_dag", 10, || analyze_dag(&instructions));
+        time("ViabilityChecker::check_all", 10, || {
+            checker.check_all(Some(&instructions), None, None)
+        });
+    }
+}
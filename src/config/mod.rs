@@ -1,5 +1,7 @@
 pub mod checklist;
+pub mod opcodes;
 pub mod settings;
 
 pub use checklist::*;
+pub use opcodes::*;
 pub use settings::*;
@@ -0,0 +1,48 @@
+use serde::{Deserialize, Serialize};
+
+use crate::models::{OpCode, OpCodeCategory};
+
+/// A user-defined opcode declared in config.
+///
+/// Plans reference it by `name` (SCREAMING_SNAKE_CASE, e.g. `RUN_MIGRATION`);
+/// it deserializes as `OpCode::Custom(name)` and viability rules treat it
+/// according to `category`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CustomOpCode {
+    /// Opcode name as it appears in plan JSON
+    pub name: String,
+    /// Category used by viability rules (context, execution, testing, ...)
+    pub category: OpCodeCategory,
+    /// Short explanation shown to the planner
+    #[serde(default)]
+    pub description: String,
+    /// JSON Schema for the instruction params (checked by V-009)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub params_schema: Option<serde_json::Value>,
+    /// Additional fields this opcode produces, referenceable as ${id.field}
+    #[serde(default)]
+    pub produces: Vec<String>,
+}
+
+/// Reject declarations that reuse a built-in opcode name, which plans could
+/// never reach (the name deserializes as the built-in).
+pub fn validate_custom_opcodes(custom_opcodes: &[CustomOpCode]) -> anyhow::Result<()> {
+    if let Some(custom) = custom_opcodes
+        .iter()
+        .find(|c| OpCode::is_builtin_name(&c.name))
+    {
+        anyhow::bail!(
+            "custom_opcodes: '{}' is a built-in opcode name; choose another name",
+            custom.name
+        );
+    }
+    Ok(())
+}
+
+/// Find the declaration for a custom opcode name
+pub fn find_custom_opcode<'a>(
+    custom_opcodes: &'a [CustomOpCode],
+    name: &str,
+) -> Option<&'a CustomOpCode> {
+    custom_opcodes.iter().find(|c| c.name == name)
+}
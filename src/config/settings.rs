@@ -1,6 +1,8 @@
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
+use super::CustomOpCode;
+
 /// Main CLI configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CliConfig {
@@ -13,6 +15,39 @@ pub struct CliConfig {
     /// Orchestrator mode configuration
     #[serde(default)]
     pub orchestrator: OrchestratorConfig,
+    /// User-defined opcodes available to plans in addition to the built-in set
+    #[serde(default)]
+    pub custom_opcodes: Vec<CustomOpCode>,
+    /// Execution schedule simulation settings
+    #[serde(default)]
+    pub schedule: ScheduleConfig,
+    /// Severities for the dangerous command analyzer
+    #[serde(default)]
+    pub command_safety: CommandSafetyConfig,
+    /// Operator runbook validation settings
+    #[serde(default)]
+    pub runbook: RunbookConfig,
+    /// Executable grounding gate settings
+    #[serde(default)]
+    pub gates: GatesConfig,
+    /// Symbol index / repo map settings
+    #[serde(default)]
+    pub repo_map: RepoMapConfig,
+    /// In-process SEARCH_CODE dry-run settings
+    #[serde(default)]
+    pub code_search: CodeSearchConfig,
+    /// Multiple reviewers whose scores are aggregated (empty = single reviewer)
+    #[serde(default)]
+    pub review_panel: ReviewPanelConfig,
+    /// Specialist review passes scored as separate dimensions
+    #[serde(default)]
+    pub review_dimensions: ReviewDimensionsConfig,
+    /// Best-of-N candidate plan generation
+    #[serde(default)]
+    pub candidates: CandidatesConfig,
+    /// Retries and provider fallbacks for planner, reviewer, and orchestrator calls
+    #[serde(default)]
+    pub retry: RetryConfig,
     // NOTE: loop_config and use_orchestrator removed.
     // Use guardrails.max_iterations and guardrails.score_threshold instead.
 }
@@ -25,6 +60,9 @@ pub struct PlanningConfig {
     pub provider_override: Option<String>,
     /// Override model from recipe (e.g., "claude-opus-4-5-20251101")
     pub model_override: Option<String>,
+    /// Escalation ladder and when to move up it
+    #[serde(default)]
+    pub ladder: LadderConfig,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -35,9 +73,85 @@ pub struct ReviewConfig {
     pub provider_override: Option<String>,
     /// Override model from recipe
     pub model_override: Option<String>,
+    /// Escalation ladder, cheapest first (empty = overrides only)
+    #[serde(default)]
+    pub ladder: Vec<ModelRung>,
     // NOTE: pass_threshold was removed. Use guardrails.score_threshold instead.
 }
 
+/// Planner escalation ladder, cheapest first (no rungs = overrides only).
+///
+/// Applies to the single planner only: candidate variants keep their own
+/// models. Also accepts a bare list of rungs with the default thresholds.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(from = "LadderRepr")]
+pub struct LadderConfig {
+    pub rungs: Vec<ModelRung>,
+    /// Consecutive viability failures on one rung before escalating
+    pub escalate_after_viability_failures: usize,
+    /// Reviews on one rung without improvement before escalating
+    pub escalate_after_stalled_reviews: usize,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum LadderRepr {
+    Rungs(Vec<ModelRung>),
+    Config {
+        #[serde(default)]
+        rungs: Vec<ModelRung>,
+        #[serde(default = "default_escalate_after")]
+        escalate_after_viability_failures: usize,
+        #[serde(default = "default_escalate_after")]
+        escalate_after_stalled_reviews: usize,
+    },
+}
+
+fn default_escalate_after() -> usize {
+    2
+}
+
+impl From<LadderRepr> for LadderConfig {
+    fn from(repr: LadderRepr) -> Self {
+        match repr {
+            LadderRepr::Rungs(rungs) => Self {
+                rungs,
+                ..Self::default()
+            },
+            LadderRepr::Config {
+                rungs,
+                escalate_after_viability_failures,
+                escalate_after_stalled_reviews,
+            } => Self {
+                rungs,
+                escalate_after_viability_failures,
+                escalate_after_stalled_reviews,
+            },
+        }
+    }
+}
+
+impl Default for LadderConfig {
+    fn default() -> Self {
+        Self {
+            rungs: Vec::new(),
+            escalate_after_viability_failures: default_escalate_after(),
+            escalate_after_stalled_reviews: default_escalate_after(),
+        }
+    }
+}
+
+/// One provider/model pair of an escalation ladder.
+///
+/// Unset fields fall back to the role's override, then its recipe.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ModelRung {
+    #[serde(default)]
+    pub provider: Option<String>,
+    #[serde(default)]
+    pub model: Option<String>,
+}
+
 // NOTE: LoopConfig struct removed. Use GuardrailsConfig.max_iterations instead.
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -54,11 +168,73 @@ pub struct OutputConfig {
     pub slug: Option<String>,
 }
 
+/// Retry with exponential backoff and provider fallback.
+///
+/// Retryable errors (rate limits, timeouts, 5xx) are retried up to
+/// `max_attempts` times per provider, then each `fallbacks` entry is tried
+/// in order. Fatal errors (auth, bad request, context length) fail at once.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RetryConfig {
+    /// Attempts per provider, including the first
+    #[serde(default = "default_retry_max_attempts")]
+    pub max_attempts: u32,
+    /// Delay before the first retry
+    #[serde(default = "default_retry_initial_backoff_ms")]
+    pub initial_backoff_ms: u64,
+    /// Upper bound on any single delay
+    #[serde(default = "default_retry_max_backoff_ms")]
+    pub max_backoff_ms: u64,
+    /// Delay growth per attempt
+    #[serde(default = "default_retry_multiplier")]
+    pub multiplier: f64,
+    /// Fraction of each delay randomized up or down
+    #[serde(default = "default_retry_jitter")]
+    pub jitter: f64,
+    /// Alternative providers tried in order once retries are exhausted;
+    /// unset fields keep the role's own provider/model
+    #[serde(default)]
+    pub fallbacks: Vec<ModelRung>,
+}
+
+fn default_retry_max_attempts() -> u32 {
+    3
+}
+
+fn default_retry_initial_backoff_ms() -> u64 {
+    1_000
+}
+
+fn default_retry_max_backoff_ms() -> u64 {
+    30_000
+}
+
+fn default_retry_multiplier() -> f64 {
+    2.0
+}
+
+fn default_retry_jitter() -> f64 {
+    0.25
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: default_retry_max_attempts(),
+            initial_backoff_ms: default_retry_initial_backoff_ms(),
+            max_backoff_ms: default_retry_max_backoff_ms(),
+            multiplier: default_retry_multiplier(),
+            jitter: default_retry_jitter(),
+            fallbacks: Vec::new(),
+        }
+    }
+}
+
 /// Configuration for orchestrator guardrails.
 ///
-/// Contains only numeric/deterministic limits. Pattern-based security checks
-/// (keywords, file patterns, API changes, data deletion) are handled by the
-/// LLM reviewer with full context awareness to avoid false positives.
+/// Contains only numeric/deterministic limits. Shell commands are checked
+/// deterministically by the dangerous command analyzer (see
+/// `CommandSafetyConfig`); other pattern-based concerns (API changes, data
+/// migrations) are left to the LLM reviewer.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GuardrailsConfig {
     /// Maximum iterations before hard stop
@@ -76,6 +252,23 @@ pub struct GuardrailsConfig {
     /// Score threshold for determining pass/fail (default 0.8)
     #[serde(default = "default_score_threshold")]
     pub score_threshold: f32,
+    /// Stop or pause when review scores plateau
+    #[serde(default)]
+    pub stagnation: StagnationConfig,
+    /// Total running time limit in seconds, summed across resumes; time
+    /// paused between runs doesn't count (None = no limit)
+    #[serde(default)]
+    pub max_session_secs: Option<u64>,
+    /// Spending limit in USD, priced from `pricing` (None = no limit).
+    /// Usage of a model without a price stops the session when set.
+    #[serde(default)]
+    pub max_cost_usd: Option<f64>,
+    /// Per-model prices used for the cost budget
+    #[serde(default)]
+    pub pricing: Vec<ModelPrice>,
+    /// Token limits per role and per iteration, within `max_total_tokens`
+    #[serde(default)]
+    pub token_budgets: TokenBudgetsConfig,
 }
 
 fn default_max_iterations() -> u32 {
@@ -106,6 +299,114 @@ impl Default for GuardrailsConfig {
             max_tool_calls: default_max_tool_calls(),
             execution_timeout_secs: default_execution_timeout_secs(),
             score_threshold: default_score_threshold(),
+            stagnation: StagnationConfig::default(),
+            max_session_secs: None,
+            max_cost_usd: None,
+            pricing: Vec::new(),
+            token_budgets: TokenBudgetsConfig::default(),
+        }
+    }
+}
+
+/// Token limits per role, so one role can't starve the others of the
+/// shared `max_total_tokens` pool. Unset limits are not enforced.
+///
+/// Checked before each planner or reviewer call, and between candidate
+/// generation and candidate reviews. A limit is spent once usage reaches it;
+/// per-iteration limits apply to one iteration's planner or reviewer usage.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TokenBudgetsConfig {
+    /// Orchestrator agent tokens for the whole session
+    #[serde(default)]
+    pub orchestrator: Option<u64>,
+    /// Planner tokens for the whole session
+    #[serde(default)]
+    pub planner: Option<u64>,
+    /// Reviewer tokens for the whole session
+    #[serde(default)]
+    pub reviewer: Option<u64>,
+    /// Planner tokens within one iteration (all candidates included)
+    #[serde(default)]
+    pub planner_per_iteration: Option<u64>,
+    /// Reviewer tokens within one iteration (panels and dimensions included)
+    #[serde(default)]
+    pub reviewer_per_iteration: Option<u64>,
+}
+
+/// Price of one model in USD per million tokens.
+///
+/// `model` matches either a full "provider/model" label or just the model
+/// name; `"*"` prices every model without its own entry.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ModelPrice {
+    pub model: String,
+    /// USD per million input tokens
+    pub input_per_mtok: f64,
+    /// USD per million output tokens
+    pub output_per_mtok: f64,
+}
+
+/// What to do when planning stagnates
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StagnationAction {
+    /// End the session with the best plan seen so far
+    #[default]
+    BestEffort,
+    /// Pause for human input with an explanation of the plateau
+    Pause,
+}
+
+/// Score-plateau detection.
+///
+/// Fires when the best review score has not improved by more than `epsilon`
+/// over the last `window` reviews, or when the same critical viability rules
+/// fail `repeated_violations` times in a row. Human input or a model
+/// escalation starts a fresh window.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StagnationConfig {
+    /// Enable stagnation detection
+    #[serde(default = "default_stagnation_enabled")]
+    pub enabled: bool,
+    /// Reviews without improvement before firing (K)
+    #[serde(default = "default_stagnation_window")]
+    pub window: u32,
+    /// Minimum improvement of the best score that counts as progress (ε)
+    #[serde(default = "default_stagnation_epsilon")]
+    pub epsilon: f32,
+    /// Consecutive viability failures on the same critical rules before
+    /// firing (0 = disabled)
+    #[serde(default = "default_stagnation_repeated_violations")]
+    pub repeated_violations: u32,
+    /// What to do when stagnation is detected
+    #[serde(default)]
+    pub action: StagnationAction,
+}
+
+fn default_stagnation_enabled() -> bool {
+    true
+}
+
+fn default_stagnation_window() -> u32 {
+    3
+}
+
+fn default_stagnation_epsilon() -> f32 {
+    0.02
+}
+
+fn default_stagnation_repeated_violations() -> u32 {
+    3
+}
+
+impl Default for StagnationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_stagnation_enabled(),
+            window: default_stagnation_window(),
+            epsilon: default_stagnation_epsilon(),
+            repeated_violations: default_stagnation_repeated_violations(),
+            action: StagnationAction::default(),
         }
     }
 }
@@ -120,22 +421,454 @@ pub struct OrchestratorConfig {
     pub provider_override: Option<String>,
     /// Override model for orchestrator
     pub model_override: Option<String>,
+    /// Apply mechanical viability fixes (V-006, V-010, V-012) before review
+    #[serde(default = "default_auto_fix")]
+    pub auto_fix: bool,
+    /// Build a deterministic repo snapshot before planning and inject it
+    /// into every planner prompt
+    #[serde(default = "default_pre_grounding")]
+    pub pre_grounding: bool,
+    /// Who drives the generate/review loop: an orchestrator LLM (`llm`) or
+    /// a Rust state machine (`deterministic`)
+    #[serde(default)]
+    pub mode: OrchestratorMode,
+    /// Escalation ladder, cheapest first (empty = overrides only)
+    #[serde(default)]
+    pub ladder: Vec<ModelRung>,
+}
+
+/// How orchestration decisions are made
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OrchestratorMode {
+    /// An orchestrator agent chooses each action via tool calls
+    #[default]
+    Llm,
+    /// A state machine calls the planner, reviewer, and finalize directly
+    Deterministic,
 }
 
 fn default_orchestrator_recipe() -> PathBuf {
     PathBuf::from("recipes/orchestrator.yaml")
 }
 
+fn default_auto_fix() -> bool {
+    true
+}
+
+fn default_pre_grounding() -> bool {
+    true
+}
+
 impl Default for OrchestratorConfig {
     fn default() -> Self {
         Self {
             recipe: default_orchestrator_recipe(),
             provider_override: None,
             model_override: None,
+            auto_fix: default_auto_fix(),
+            pre_grounding: default_pre_grounding(),
+            mode: OrchestratorMode::default(),
+            ladder: Vec::new(),
+        }
+    }
+}
+
+/// Configuration for the execution schedule simulation.
+///
+/// Steps cost their `estimated_tokens` (or `default_step_tokens`) and take
+/// `ceil(tokens / tokens_per_unit)` abstract time units.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduleConfig {
+    /// Number of workers executing steps concurrently
+    #[serde(default = "default_schedule_workers")]
+    pub workers: usize,
+    /// Per-step context token budget; larger steps are flagged
+    #[serde(default = "default_max_step_tokens")]
+    pub max_step_tokens: u32,
+    /// Tokens assumed for steps without `estimated_tokens`
+    #[serde(default = "default_step_tokens")]
+    pub default_step_tokens: u32,
+    /// Tokens processed per abstract time unit
+    #[serde(default = "default_tokens_per_unit")]
+    pub tokens_per_unit: u32,
+}
+
+fn default_schedule_workers() -> usize {
+    4
+}
+
+fn default_max_step_tokens() -> u32 {
+    32_000
+}
+
+fn default_step_tokens() -> u32 {
+    500
+}
+
+fn default_tokens_per_unit() -> u32 {
+    1_000
+}
+
+impl Default for ScheduleConfig {
+    fn default() -> Self {
+        Self {
+            workers: default_schedule_workers(),
+            max_step_tokens: default_max_step_tokens(),
+            default_step_tokens: default_step_tokens(),
+            tokens_per_unit: default_tokens_per_unit(),
+        }
+    }
+}
+
+/// Severity assigned to a risky command pattern
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum RiskSeverity {
+    /// Blocks approval (critical viability violation)
+    Critical,
+    /// Reported as a warning
+    Warning,
+    /// Pattern is not checked
+    Off,
+}
+
+/// Configuration for the dangerous command analyzer.
+///
+/// Applies to `RUN_COMMAND` params and the plan's operator runbook. Each
+/// pattern maps to a severity; `off` disables it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommandSafetyConfig {
+    /// `rm -rf` on paths outside the working directory
+    #[serde(default = "default_risk_critical")]
+    pub rm_rf_outside_workspace: RiskSeverity,
+    /// `git push --force` (including `-f`, `--force-with-lease`, `+refspec`)
+    #[serde(default = "default_risk_critical")]
+    pub force_push: RiskSeverity,
+    /// Downloaded content piped into a shell (`curl ... | sh`)
+    #[serde(default = "default_risk_critical")]
+    pub pipe_to_shell: RiskSeverity,
+    /// Privilege escalation via `sudo`/`doas`
+    #[serde(default = "default_risk_warning")]
+    pub sudo: RiskSeverity,
+    /// Writes to absolute paths outside the working directory
+    #[serde(default = "default_risk_warning")]
+    pub write_outside_workspace: RiskSeverity,
+    /// `DROP TABLE` / `DROP DATABASE` / `DROP SCHEMA` statements
+    #[serde(default = "default_risk_critical")]
+    pub drop_table: RiskSeverity,
+}
+
+fn default_risk_critical() -> RiskSeverity {
+    RiskSeverity::Critical
+}
+
+fn default_risk_warning() -> RiskSeverity {
+    RiskSeverity::Warning
+}
+
+impl Default for CommandSafetyConfig {
+    fn default() -> Self {
+        Self {
+            rm_rf_outside_workspace: default_risk_critical(),
+            force_push: default_risk_critical(),
+            pipe_to_shell: default_risk_critical(),
+            sudo: default_risk_warning(),
+            write_outside_workspace: default_risk_warning(),
+            drop_table: default_risk_critical(),
+        }
+    }
+}
+
+/// Configuration for operator runbook validation (V-016)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunbookConfig {
+    /// Binaries allowed in runbook commands beyond the detected build
+    /// system and the built-in allowlist (e.g., project scripts on PATH)
+    #[serde(default)]
+    pub extra_binaries: Vec<String>,
+    /// Check that paths referenced by runbook commands exist
+    #[serde(default = "default_check_runbook_paths")]
+    pub check_paths: bool,
+}
+
+fn default_check_runbook_paths() -> bool {
+    true
+}
+
+impl Default for RunbookConfig {
+    fn default() -> Self {
+        Self {
+            extra_binaries: Vec::new(),
+            check_paths: default_check_runbook_paths(),
+        }
+    }
+}
+
+/// Configuration for executable grounding gates (Phase 0.0)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GatesConfig {
+    /// Evaluate machine-checkable gates before a plan is finalized;
+    /// a failing gate blocks finalization
+    #[serde(default = "default_gates_enabled")]
+    pub enabled: bool,
+    /// Timeout for each gate command, in seconds
+    #[serde(default = "default_gate_command_timeout_secs")]
+    pub command_timeout_secs: u64,
+}
+
+fn default_gates_enabled() -> bool {
+    true
+}
+
+fn default_gate_command_timeout_secs() -> u64 {
+    120
+}
+
+impl Default for GatesConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_gates_enabled(),
+            command_timeout_secs: default_gate_command_timeout_secs(),
+        }
+    }
+}
+
+/// Configuration for the symbol index and repo map
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RepoMapConfig {
+    /// Index repository symbols and include a repo map in planner and
+    /// reviewer prompts
+    #[serde(default = "default_repo_map_enabled")]
+    pub enabled: bool,
+    /// Approximate token budget for the repo map
+    #[serde(default = "default_repo_map_token_budget")]
+    pub token_budget: usize,
+    /// Warn when EDIT_CODE goals name symbols missing from the index (V-017)
+    #[serde(default = "default_verify_symbols")]
+    pub verify_symbols: bool,
+}
+
+fn default_repo_map_enabled() -> bool {
+    true
+}
+
+fn default_repo_map_token_budget() -> usize {
+    2000
+}
+
+fn default_verify_symbols() -> bool {
+    true
+}
+
+impl Default for RepoMapConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_repo_map_enabled(),
+            token_budget: default_repo_map_token_budget(),
+            verify_symbols: default_verify_symbols(),
+        }
+    }
+}
+
+/// Configuration for dry-running SEARCH_CODE queries against the working dir
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CodeSearchConfig {
+    /// Run SEARCH_CODE queries during viability checks (V-018) and size
+    /// their token estimates by hit count
+    #[serde(default = "default_code_search_enabled")]
+    pub enabled: bool,
+    /// Matching lines above which a query is reported as too broad
+    #[serde(default = "default_code_search_max_hits")]
+    pub max_hits: usize,
+    /// Maximum number of files indexed for search
+    #[serde(default = "default_code_search_max_files")]
+    pub max_files: usize,
+}
+
+fn default_code_search_enabled() -> bool {
+    true
+}
+
+fn default_code_search_max_hits() -> usize {
+    1000
+}
+
+fn default_code_search_max_files() -> usize {
+    20_000
+}
+
+impl Default for CodeSearchConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_code_search_enabled(),
+            max_hits: default_code_search_max_hits(),
+            max_files: default_code_search_max_files(),
+        }
+    }
+}
+
+/// Configuration for a panel of reviewers run concurrently on each plan
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ReviewPanelConfig {
+    /// Panel members; when empty the single `review` reviewer is used
+    #[serde(default)]
+    pub reviewers: Vec<PanelReviewerConfig>,
+    /// How member scores combine into the score checked against the threshold
+    #[serde(default)]
+    pub aggregation: ScoreAggregation,
+}
+
+impl ReviewPanelConfig {
+    /// Reject duplicate member names, which would merge in recorded scores
+    pub fn validate(&self) -> anyhow::Result<()> {
+        for (i, member) in self.reviewers.iter().enumerate() {
+            if self.reviewers[..i].iter().any(|m| m.name == member.name) {
+                anyhow::bail!(
+                    "review_panel: reviewer name '{}' is used more than once",
+                    member.name
+                );
+            }
         }
+        Ok(())
     }
 }
 
+/// One reviewer in the review panel
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PanelReviewerConfig {
+    /// Name recorded with the reviewer's score (e.g., "fast", "strong")
+    pub name: String,
+    /// Reviewer recipe
+    #[serde(default = "default_panel_reviewer_recipe")]
+    pub recipe: PathBuf,
+    /// Provider for this reviewer (recipe default when unset)
+    #[serde(default)]
+    pub provider: Option<String>,
+    /// Model for this reviewer (recipe default when unset)
+    #[serde(default)]
+    pub model: Option<String>,
+}
+
+fn default_panel_reviewer_recipe() -> PathBuf {
+    PathBuf::from("recipes/reviewer.yaml")
+}
+
+/// Strategy for combining review panel scores
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScoreAggregation {
+    /// Average of all scores
+    #[default]
+    Mean,
+    /// Lowest score: every reviewer must pass
+    Min,
+    /// Middle score (mean of the two middle scores for even panels)
+    Median,
+    /// Passes when more than half of the reviewers pass
+    Majority,
+}
+
+/// Configuration for specialist review passes (security, testing, ...)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReviewDimensionsConfig {
+    /// Specialist passes run alongside the main review (empty = disabled)
+    #[serde(default)]
+    pub dimensions: Vec<ReviewDimensionConfig>,
+    /// Weight of the main review score in the combined score
+    #[serde(default = "default_primary_review_weight")]
+    pub primary_weight: f32,
+}
+
+fn default_primary_review_weight() -> f32 {
+    1.0
+}
+
+impl Default for ReviewDimensionsConfig {
+    fn default() -> Self {
+        Self {
+            dimensions: Vec::new(),
+            primary_weight: default_primary_review_weight(),
+        }
+    }
+}
+
+/// One specialist review pass
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReviewDimensionConfig {
+    /// Dimension name reported with the score (e.g., "security")
+    pub name: String,
+    /// Specialist recipe (e.g., recipes/review-security.yaml)
+    pub recipe: PathBuf,
+    /// Weight of this dimension in the combined score (0 = report only)
+    #[serde(default = "default_dimension_weight")]
+    pub weight: f32,
+    /// Minimum score for this dimension; below it the review fails
+    /// regardless of the combined score
+    #[serde(default)]
+    pub min_score: Option<f32>,
+    /// Provider for this pass (recipe default when unset)
+    #[serde(default)]
+    pub provider: Option<String>,
+    /// Model for this pass (recipe default when unset)
+    #[serde(default)]
+    pub model: Option<String>,
+}
+
+fn default_dimension_weight() -> f32 {
+    1.0
+}
+
+/// Configuration for best-of-N candidate plan generation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CandidatesConfig {
+    /// Candidate plans generated concurrently per `generate_plan` (1 = disabled)
+    #[serde(default = "default_candidate_count")]
+    pub count: usize,
+    /// Only generate candidates for the initial plan; revisions use one planner
+    #[serde(default = "default_candidates_initial_only")]
+    pub initial_only: bool,
+    /// Per-candidate planner settings, assigned round-robin
+    #[serde(default)]
+    pub variants: Vec<CandidateVariant>,
+}
+
+fn default_candidate_count() -> usize {
+    1
+}
+
+fn default_candidates_initial_only() -> bool {
+    true
+}
+
+impl Default for CandidatesConfig {
+    fn default() -> Self {
+        Self {
+            count: default_candidate_count(),
+            initial_only: default_candidates_initial_only(),
+            variants: Vec::new(),
+        }
+    }
+}
+
+impl CandidatesConfig {
+    /// Whether more than one candidate is generated
+    pub fn enabled(&self) -> bool {
+        self.count > 1
+    }
+}
+
+/// Planner settings for one candidate (unset fields use the planner's)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CandidateVariant {
+    #[serde(default)]
+    pub provider: Option<String>,
+    #[serde(default)]
+    pub model: Option<String>,
+    #[serde(default)]
+    pub temperature: Option<f32>,
+}
+
 impl Default for CliConfig {
     fn default() -> Self {
         Self {
@@ -143,11 +876,13 @@ impl Default for CliConfig {
                 recipe: PathBuf::from("recipes/planner.yaml"),
                 provider_override: None,
                 model_override: None,
+                ladder: LadderConfig::default(),
             },
             review: ReviewConfig {
                 recipe: PathBuf::from("recipes/reviewer.yaml"),
                 provider_override: None,
                 model_override: None,
+                ladder: Vec::new(),
             },
             output: OutputConfig {
                 runs_dir: PathBuf::from("./.plan-forge"),
@@ -156,6 +891,17 @@ impl Default for CliConfig {
             },
             guardrails: GuardrailsConfig::default(),
             orchestrator: OrchestratorConfig::default(),
+            custom_opcodes: Vec::new(),
+            schedule: ScheduleConfig::default(),
+            command_safety: CommandSafetyConfig::default(),
+            runbook: RunbookConfig::default(),
+            gates: GatesConfig::default(),
+            repo_map: RepoMapConfig::default(),
+            code_search: CodeSearchConfig::default(),
+            review_panel: ReviewPanelConfig::default(),
+            review_dimensions: ReviewDimensionsConfig::default(),
+            candidates: CandidatesConfig::default(),
+            retry: RetryConfig::default(),
         }
     }
 }
@@ -165,9 +911,16 @@ impl CliConfig {
     pub fn from_file(path: &PathBuf) -> anyhow::Result<Self> {
         let content = std::fs::read_to_string(path)?;
         let config: CliConfig = serde_yaml::from_str(&content)?;
+        config.validate()?;
         Ok(config)
     }
 
+    /// Check constraints serde can't express
+    pub fn validate(&self) -> anyhow::Result<()> {
+        super::validate_custom_opcodes(&self.custom_opcodes)?;
+        self.review_panel.validate()
+    }
+
     /// Load configuration, falling back to defaults if file doesn't exist
     pub fn load_or_default(path: Option<&PathBuf>) -> anyhow::Result<Self> {
         match path {
@@ -191,6 +944,7 @@ impl CliConfig {
     /// - PLAN_FORGE_RECIPE_DIR: Directory to search for recipes
     /// - PLAN_FORGE_ORCHESTRATOR_PROVIDER: Provider for orchestrator
     /// - PLAN_FORGE_ORCHESTRATOR_MODEL: Model for orchestrator
+    /// - PLAN_FORGE_ORCHESTRATOR_MODE: Orchestration mode ("llm" or "deterministic")
     /// - PLAN_FORGE_MAX_TOTAL_TOKENS: Maximum total tokens for orchestrator session
     /// - PLAN_FORGE_PLAN_DIR: Output directory for plan files (default: plans/active)
     pub fn apply_env_overrides(mut self) -> Self {
@@ -269,6 +1023,15 @@ impl CliConfig {
             self.orchestrator.model_override = Some(val);
         }
 
+        // Orchestrator mode
+        if let Ok(val) = std::env::var("PLAN_FORGE_ORCHESTRATOR_MODE") {
+            match val.to_lowercase().as_str() {
+                "llm" => self.orchestrator.mode = OrchestratorMode::Llm,
+                "deterministic" => self.orchestrator.mode = OrchestratorMode::Deterministic,
+                _ => {}
+            }
+        }
+
         // Max total tokens for orchestrator (-1 for unlimited)
         if let Ok(val) = std::env::var("PLAN_FORGE_MAX_TOTAL_TOKENS")
             && let Ok(tokens) = val.parse::<i64>()
@@ -1,9 +1,16 @@
 use anyhow::{Context, Result};
 use clap::{Parser, Subcommand};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use tokio::sync::broadcast;
+use tokio_util::sync::CancellationToken;
 use tracing::info;
 use tracing_subscriber::{EnvFilter, fmt, prelude::*};
 
+use plan_forge::config::OrchestratorMode;
+use plan_forge::orchestrator::{
+    Forecast, GateRunner, GateStatus, Guardrails, MigrationOutcome, OrchestrationStatus,
+    OrchestratorEvent, migrate_all,
+};
 use plan_forge::{
     CliConfig, GooseOrchestrator, HumanResponse, OrchestrationState, Plan, PlanForgeServer,
     ResumeState, SessionRegistry, generate_slug, slugify, slugify_truncate,
@@ -95,6 +102,37 @@ enum Command {
         #[arg(short, long)]
         config: Option<PathBuf>,
     },
+
+    /// Evaluate a plan's executable grounding gates (Phase 0.0)
+    #[command(name = "gates")]
+    Gates {
+        /// Plan JSON file or session directory (e.g., .plan-forge/<slug>)
+        plan: PathBuf,
+
+        /// Directory gates are evaluated in (default: current directory)
+        #[arg(short, long)]
+        working_dir: Option<PathBuf>,
+
+        /// Run `command_succeeds` gates (skipped otherwise)
+        #[arg(long)]
+        allow_commands: bool,
+
+        /// Path to configuration file
+        #[arg(short, long)]
+        config: Option<PathBuf>,
+    },
+
+    /// Upgrade saved session state to the current schema (originals are backed up)
+    #[command(name = "migrate")]
+    Migrate {
+        /// Sessions directory (default: `output.runs_dir`, usually .plan-forge)
+        #[arg(short, long)]
+        dir: Option<PathBuf>,
+
+        /// Path to configuration file
+        #[arg(short, long)]
+        config: Option<PathBuf>,
+    },
 }
 
 #[derive(Parser, Debug)]
@@ -176,6 +214,25 @@ struct RunArgs {
     /// - Request changes: "Please revise to use PostgreSQL"
     #[arg(long, value_name = "TEXT")]
     feedback: Option<String>,
+
+    /// Run `command_succeeds` grounding gates before the plan is finalized
+    #[arg(long)]
+    allow_commands: bool,
+
+    /// Drive generate/review/finalize with a state machine instead of an
+    /// orchestrator LLM (same as `orchestrator.mode: deterministic`)
+    #[arg(long)]
+    deterministic: bool,
+
+    /// Generate N candidate plans concurrently and keep the best reviewed one
+    /// (same as `candidates.count`)
+    #[arg(long, value_name = "N")]
+    candidates: Option<usize>,
+
+    /// Estimate tokens and cost from past sessions in .plan-forge/ against
+    /// the configured budgets, then exit without running
+    #[arg(long)]
+    estimate: bool,
 }
 
 #[tokio::main]
@@ -188,6 +245,13 @@ async fn main() -> Result<()> {
     match cli.command {
         Some(Command::Mcp { server, config }) => handle_mcp_command(server, config).await,
         Some(Command::Run { args }) => handle_run_command(*args).await,
+        Some(Command::Gates {
+            plan,
+            working_dir,
+            allow_commands,
+            config,
+        }) => handle_gates_command(plan, working_dir, allow_commands, config).await,
+        Some(Command::Migrate { dir, config }) => handle_migrate_command(dir, config),
         None => {
             // Default behavior: show help
             eprintln!("No command specified. Use --help for usage information.");
@@ -217,6 +281,134 @@ async fn handle_mcp_command(server: McpServer, config_path: Option<PathBuf>) ->
     Ok(())
 }
 
+/// Evaluate a plan's grounding gates and print pass/fail per gate ID
+async fn handle_gates_command(
+    plan_path: PathBuf,
+    working_dir: Option<PathBuf>,
+    allow_commands: bool,
+    config_path: Option<PathBuf>,
+) -> Result<()> {
+    let config = CliConfig::load_with_env(config_path.as_ref())?;
+
+    let plan = if plan_path.is_dir() {
+        // Prefer the finalized plan, fall back to the latest iteration
+        let slug = plan_path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or_default();
+        let final_path = plan_path.join(format!("{}-final.json", slug));
+        if final_path.is_file() {
+            load_plan_file(&final_path)?
+        } else {
+            load_latest_plan(&plan_path)?.0
+        }
+    } else {
+        load_plan_file(&plan_path)?
+    };
+
+    let gates = plan.grounding_gates.unwrap_or_default();
+    if gates.is_empty() {
+        println!("Plan has no grounding gates.");
+        return Ok(());
+    }
+
+    let working_dir = working_dir.unwrap_or_else(|| std::env::current_dir().unwrap_or_default());
+    let runner =
+        GateRunner::from_config(working_dir, &config.gates).with_allow_commands(allow_commands);
+    let report = runner.run(&gates).await;
+
+    for result in &report.results {
+        let label = match result.status {
+            GateStatus::Passed => "PASS",
+            GateStatus::Failed => "FAIL",
+            GateStatus::Skipped => "SKIP",
+        };
+        println!("{}  {}  {}", label, result.id, result.detail);
+    }
+    println!(
+        "\n{} passed, {} failed, {} skipped",
+        report.count(GateStatus::Passed),
+        report.count(GateStatus::Failed),
+        report.count(GateStatus::Skipped)
+    );
+
+    if !report.passed() {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+/// Upgrade every session's orchestration state and print the outcome per session
+fn handle_migrate_command(dir: Option<PathBuf>, config_path: Option<PathBuf>) -> Result<()> {
+    let runs_dir = match dir {
+        Some(dir) => dir,
+        None => {
+            CliConfig::load_with_env(config_path.as_ref())?
+                .output
+                .runs_dir
+        }
+    };
+    if !runs_dir.is_dir() {
+        println!("No sessions found in {}", runs_dir.display());
+        return Ok(());
+    }
+
+    let results = migrate_all(&runs_dir)?;
+    if results.is_empty() {
+        println!(
+            "No sessions with orchestration state in {}",
+            runs_dir.display()
+        );
+        return Ok(());
+    }
+
+    let mut migrated = 0;
+    let mut failed = 0;
+    for (session_dir, outcome) in &results {
+        let name = session_dir
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        match outcome {
+            Ok(MigrationOutcome::Migrated { from, to, backup }) => {
+                migrated += 1;
+                println!(
+                    "MIGRATED  {}  v{} -> v{} (backup: {})",
+                    name,
+                    from,
+                    to,
+                    backup.display()
+                );
+            }
+            Ok(MigrationOutcome::UpToDate | MigrationOutcome::NoState) => {
+                println!("CURRENT   {}", name);
+            }
+            Err(e) => {
+                failed += 1;
+                println!("FAILED    {}  {:#}", name, e);
+            }
+        }
+    }
+    println!(
+        "\n{} migrated, {} current, {} failed",
+        migrated,
+        results.len() - migrated - failed,
+        failed
+    );
+
+    if failed > 0 {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+/// Load a plan from a JSON file
+fn load_plan_file(path: &PathBuf) -> Result<Plan> {
+    let plan_json =
+        std::fs::read_to_string(path).context(format!("Failed to read plan file: {:?}", path))?;
+    serde_json::from_str(&plan_json).context(format!("Failed to parse plan JSON from {:?}", path))
+}
+
 /// Load the latest plan from a runs directory
 fn load_latest_plan(runs_dir: &PathBuf) -> Result<(Plan, u32)> {
     // Find highest plan-iteration-N.json
@@ -244,11 +436,7 @@ fn load_latest_plan(runs_dir: &PathBuf) -> Result<(Plan, u32)> {
     let plan_path =
         latest_plan_path.ok_or_else(|| anyhow::anyhow!("No plan files found in {:?}", runs_dir))?;
 
-    let plan_json = std::fs::read_to_string(&plan_path)
-        .context(format!("Failed to read plan file: {:?}", plan_path))?;
-
-    let plan: Plan = serde_json::from_str(&plan_json)
-        .context(format!("Failed to parse plan JSON from {:?}", plan_path))?;
+    let plan = load_plan_file(&plan_path)?;
 
     info!(
         "Loaded plan from {:?} (iteration {})",
@@ -360,6 +548,21 @@ async fn handle_run_command(args: RunArgs) -> Result<()> {
 
     info!("Plan-Review CLI starting");
 
+    // Pre-flight estimate needs no task
+    if args.estimate {
+        let mut config = CliConfig::load_with_env(args.config.as_ref())?;
+        config.guardrails.max_iterations = args.max_iterations;
+        if let Some(tokens) = args.max_total_tokens {
+            config.guardrails.max_total_tokens = if tokens < 0 { u64::MAX } else { tokens as u64 };
+        }
+        let working_dir = match args.working_dir.clone() {
+            Some(dir) => dir,
+            None => std::env::current_dir().context("Failed to get current directory")?,
+        };
+        let runs_root = working_dir.join(".plan-forge");
+        return print_estimate(&runs_root, &config);
+    }
+
     // Resolve task, slug, and resume state from --path and --task
     let (task, resolved_slug, _resume_state) = resolve_input(&args)?;
 
@@ -391,6 +594,12 @@ async fn handle_run_command(args: RunArgs) -> Result<()> {
         config.review.provider_override = Some(provider);
     }
     config.guardrails.max_iterations = args.max_iterations;
+    if args.deterministic {
+        config.orchestrator.mode = OrchestratorMode::Deterministic;
+    }
+    if let Some(count) = args.candidates {
+        config.candidates.count = count;
+    }
     config.guardrails.score_threshold = args.threshold;
 
     // Set up output directories using task slug (for legacy mode)
@@ -493,19 +702,148 @@ async fn handle_run_command(args: RunArgs) -> Result<()> {
         base_dir.clone(),
         runs_dir.clone(),
         session_registry,
-    );
+    )
+    .with_custom_opcodes(config.custom_opcodes.clone())
+    .with_schedule_config(config.schedule.clone())
+    .with_command_safety(config.command_safety.clone())
+    .with_runbook_config(config.runbook.clone())
+    .with_gates_config(config.gates.clone())
+    .with_allow_gate_commands(args.allow_commands)
+    .with_repo_map_config(config.repo_map.clone())
+    .with_code_search_config(config.code_search.clone())
+    .with_review_panel_config(config.review_panel.clone())
+    .with_review_dimensions_config(config.review_dimensions.clone())
+    .with_candidates_config(config.candidates.clone())
+    .with_planning_ladder(config.planning.ladder.clone())
+    .with_review_ladder(config.review.ladder.clone())
+    .with_retry_config(config.retry.clone());
+
+    // Ctrl-C / SIGTERM stop the session gracefully so it can be resumed
+    let cancel_token = CancellationToken::new();
+    tokio::spawn(cancel_on_signal(cancel_token.clone()));
+    let orchestrator = orchestrator.with_cancel_token(cancel_token);
+
+    // Progress lines while the session runs
+    let progress = tokio::spawn(render_progress(orchestrator.subscribe()));
 
     // Run orchestrator
     let working_dir_path = args.working_dir.clone();
     let result = orchestrator
         .run(task, working_dir_path, human_response, session_id)
-        .await?;
+        .await;
+
+    // Dropping the orchestrator closes the channel; let the renderer drain it
+    drop(orchestrator);
+    let _ = tokio::time::timeout(std::time::Duration::from_secs(1), progress).await;
+    let result = result?;
+
+    if matches!(result.status, OrchestrationStatus::Cancelled) {
+        println!(
+            "\nSession {} cancelled after {} iterations; state saved.",
+            result.session_id, result.iterations
+        );
+        println!("Resume with: plan-forge run --path {}", runs_dir.display());
+        std::process::exit(130);
+    }
 
     // Convert to LoopResult for consistent output
     let loop_result: plan_forge::LoopResult = result.into();
     print_result(loop_result)
 }
 
+/// Print one line per orchestration event until the channel closes.
+async fn render_progress(mut events: broadcast::Receiver<OrchestratorEvent>) {
+    loop {
+        match events.recv().await {
+            Ok(event) => println!("→ {}", event.summary()),
+            Err(broadcast::error::RecvError::Lagged(_)) => continue,
+            Err(broadcast::error::RecvError::Closed) => break,
+        }
+    }
+}
+
+/// Cancel `token` on the first SIGINT/SIGTERM; a second Ctrl-C exits at once.
+async fn cancel_on_signal(token: CancellationToken) {
+    shutdown_signal().await;
+    eprintln!("\nCancelling, saving session state (press Ctrl-C again to exit immediately)");
+    token.cancel();
+    if tokio::signal::ctrl_c().await.is_ok() {
+        std::process::exit(130);
+    }
+}
+
+/// Wait for Ctrl-C, or SIGTERM on Unix.
+async fn shutdown_signal() {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{SignalKind, signal};
+        if let Ok(mut terminate) = signal(SignalKind::terminate()) {
+            tokio::select! {
+                _ = tokio::signal::ctrl_c() => {}
+                _ = terminate.recv() => {}
+            }
+            return;
+        }
+    }
+    let _ = tokio::signal::ctrl_c().await;
+}
+
+/// Print the pre-flight budget estimate for `plan-forge run --estimate`.
+fn print_estimate(runs_root: &Path, config: &CliConfig) -> Result<()> {
+    let guardrails = Guardrails::from_config(&config.guardrails);
+    let forecast = Forecast::from_runs_dir(runs_root, None);
+    if forecast.is_empty() {
+        println!(
+            "No past sessions with iterations in {:?}; nothing to estimate from.",
+            runs_root
+        );
+        return Ok(());
+    }
+
+    let estimate = forecast.estimate(&guardrails);
+    let per_iteration = &forecast.tokens_per_iteration;
+    println!("\n========================================");
+    println!("Budget Estimate");
+    println!("========================================");
+    println!(
+        "History: {} sessions, {} iterations",
+        forecast.sessions, forecast.iterations
+    );
+    println!(
+        "Tokens per iteration: {:.0} (orchestrator {:.0}, planner {:.0}, reviewer {:.0})",
+        per_iteration.total(),
+        per_iteration.orchestrator,
+        per_iteration.planner,
+        per_iteration.reviewer
+    );
+    for (model, tokens) in &forecast.model_tokens_per_iteration {
+        println!(
+            "  {}: {:.0} input / {:.0} output per iteration",
+            model, tokens.input, tokens.output
+        );
+    }
+    println!(
+        "Projected run: {} iterations, {} tokens (limit {})",
+        estimate.iterations, estimate.tokens, guardrails.max_total_tokens
+    );
+    if let Some(cost) = estimate.cost_usd {
+        match guardrails.max_cost_usd {
+            Some(limit) => println!("Projected cost: ${:.2} (limit ${:.2})", cost, limit),
+            None => println!("Projected cost: ${:.2}", cost),
+        }
+    }
+
+    if estimate.fits_budget() {
+        println!("\nFits within budget.");
+    } else {
+        println!("\n⚠️  Projected to exceed:");
+        for over in &estimate.over_budget {
+            println!("  - {}", over);
+        }
+    }
+    Ok(())
+}
+
 fn print_result(result: plan_forge::LoopResult) -> Result<()> {
     println!("\n========================================");
     println!("Plan-Review Complete!");
@@ -3,27 +3,35 @@
 //! Exposes planning tools to AI assistants via MCP protocol.
 
 use rmcp::{
-    ServerHandler,
+    RoleServer, ServerHandler,
     handler::server::router::tool::ToolRouter,
     model::{
-        CallToolResult, Content, ErrorCode, ErrorData, Implementation, Role, ServerCapabilities,
-        ServerInfo,
+        CallToolResult, Content, ErrorCode, ErrorData, Implementation, LoggingLevel,
+        LoggingMessageNotificationParam, ProgressNotificationParam, ProgressToken, Role,
+        ServerCapabilities, ServerInfo,
     },
     schemars::JsonSchema,
+    service::{Peer, RequestContext},
     tool, tool_handler, tool_router,
 };
 use serde::{Deserialize, Serialize};
 use std::{
+    collections::HashMap,
     path::{Path, PathBuf},
-    sync::{Arc, RwLock},
+    sync::{Arc, Mutex, RwLock},
 };
+use tokio::sync::broadcast;
+use tokio_util::sync::CancellationToken;
 
 use crate::{
     CliConfig, FileOutputWriter, GooseOrchestrator, HumanResponse, OutputConfig, OutputWriter,
     Plan, SessionRegistry, generate_slug, slugify,
 };
 
-use super::status::{SessionInfo, derive_status, list_sessions};
+use super::status::{SessionInfo, derive_live_status, derive_status, list_sessions};
+use crate::orchestrator::{
+    Guardrails, OrchestrationState, OrchestratorEvent, SessionLock, ViabilityChecker, write_atomic,
+};
 
 // ============================================================================
 // Session Metadata
@@ -92,6 +100,17 @@ pub struct PlanRunParams {
     /// - Approve: "Looks good, proceed"
     /// - Request changes: "Please revise to use PostgreSQL"
     pub feedback: Option<String>,
+    /// Return immediately and run the loop in the background. Follow it with
+    /// plan_status and stop it with plan_cancel.
+    #[serde(default)]
+    pub background: bool,
+}
+
+/// Parameters for the plan_cancel tool
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct PlanCancelParams {
+    /// Session ID to cancel. If not provided, uses current session.
+    pub session_id: Option<String>,
 }
 
 /// Parameters for the plan_approve tool
@@ -101,6 +120,91 @@ pub struct PlanApproveParams {
     pub session_id: Option<String>,
 }
 
+// ============================================================================
+// Jobs
+// ============================================================================
+
+/// A plan_run execution, tracked for plan_status and plan_cancel.
+#[derive(Debug, Clone)]
+struct PlanJob {
+    /// Cancels the orchestrator (the request's token for foreground runs)
+    cancel_token: CancellationToken,
+    /// Started with `background: true`
+    background: bool,
+    started_at: String,
+    /// plan_run response, or the error, once the job has finished
+    outcome: Option<serde_json::Value>,
+}
+
+impl PlanJob {
+    fn is_running(&self) -> bool {
+        self.outcome.is_none()
+    }
+
+    /// Job section of the plan_status response
+    fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "running": self.is_running(),
+            "background": self.background,
+            "started_at": self.started_at,
+            "cancel_requested": self.cancel_token.is_cancelled(),
+            "outcome": self.outcome,
+        })
+    }
+}
+
+/// Records the outcome of a foreground plan_run job when dropped, so a
+/// request dropped mid-run (e.g. the client disconnected) does not leave the
+/// job `running` and the session blocked.
+struct JobGuard<'a> {
+    server: &'a PlanForgeServer,
+    session_id: &'a str,
+    finished: bool,
+}
+
+impl<'a> JobGuard<'a> {
+    fn new(server: &'a PlanForgeServer, session_id: &'a str) -> Self {
+        Self {
+            server,
+            session_id,
+            finished: false,
+        }
+    }
+
+    fn finish(&mut self, outcome: &Result<serde_json::Value, ErrorData>) {
+        self.server.finish_job(self.session_id, outcome);
+        self.finished = true;
+    }
+}
+
+impl Drop for JobGuard<'_> {
+    fn drop(&mut self) {
+        if self.finished {
+            return;
+        }
+        tracing::error!(
+            "plan_run request for '{}' was dropped before the run finished",
+            self.session_id
+        );
+        // The run never got to unregister its in-memory state
+        if let Ok(handle) = tokio::runtime::Handle::try_current() {
+            let registry = self.server.session_registry.clone();
+            let session_id = self.session_id.to_string();
+            handle.spawn(async move {
+                registry.remove(&session_id).await;
+            });
+        }
+        self.server.finish_job(
+            self.session_id,
+            &Err(ErrorData::new(
+                ErrorCode::INTERNAL_ERROR,
+                "plan_run request was dropped before the run finished".to_string(),
+                None,
+            )),
+        );
+    }
+}
+
 // ============================================================================
 // Server Implementation
 // ============================================================================
@@ -120,6 +224,8 @@ pub struct PlanForgeServer {
     base_dir: PathBuf,
     /// Session registry for orchestrator mode (shared across sessions)
     session_registry: Arc<SessionRegistry>,
+    /// plan_run jobs by session ID (finished jobs keep their outcome)
+    jobs: Arc<Mutex<HashMap<String, PlanJob>>>,
 }
 
 #[tool_handler(router = self.tool_router)]
@@ -136,8 +242,9 @@ This server provides tools for creating, reviewing, and managing development pla
 Plans are stored in .plan-forge/<session>/ and output to ./plans/active/<session>/.
 
 Available tools:
-- plan_run: Create a new planning session or resume an existing one
-- plan_status: Check the status of a planning session
+- plan_run: Create a new planning session or resume an existing one (background: true returns immediately)
+- plan_status: Check the status of a planning session, including live progress of running jobs
+- plan_cancel: Cancel a running plan_run job (the session stays resumable)
 - plan_list: List all planning sessions
 - plan_get: Read plan or dag files
 - plan_approve: Force approve a plan (write to plans/active/ even if review failed)
@@ -154,7 +261,10 @@ Current directory: {cwd}
                 icons: None,
                 website_url: None,
             },
-            capabilities: ServerCapabilities::builder().enable_tools().build(),
+            capabilities: ServerCapabilities::builder()
+                .enable_tools()
+                .enable_logging()
+                .build(),
             instructions: Some(instructions),
             ..Default::default()
         }
@@ -184,6 +294,7 @@ impl PlanForgeServer {
             config: Arc::new(config),
             base_dir,
             session_registry: Arc::new(SessionRegistry::new()),
+            jobs: Arc::new(Mutex::new(HashMap::new())),
         }
     }
 
@@ -198,6 +309,7 @@ impl PlanForgeServer {
             config: Arc::new(config),
             base_dir,
             session_registry: Arc::new(SessionRegistry::new()),
+            jobs: Arc::new(Mutex::new(HashMap::new())),
         }
     }
 
@@ -262,9 +374,11 @@ impl PlanForgeServer {
     ///
     /// Returns session status, iteration count, latest score, and other metadata.
     /// Status can be: ready, in_progress, needs_input, approved, or max_turns.
+    /// While a plan_run job is active, the status comes from the live session
+    /// state, and a `job` section reports the job and its final outcome.
     #[tool(
         name = "plan_status",
-        description = "Get the status of a planning session. Returns status (ready/in_progress/needs_input/approved/max_turns), iteration count, score, and other metadata."
+        description = "Get the status of a planning session. Returns status (ready/in_progress/needs_input/approved/max_turns/cancelled), iteration count, score, and other metadata. For sessions started with plan_run, includes a 'job' section (running, outcome) with live progress."
     )]
     pub async fn plan_status(
         &self,
@@ -273,12 +387,19 @@ impl PlanForgeServer {
         let session_id = self.resolve_session(params.0.session_id)?;
         let session_dir = self.session_dir(&session_id);
 
-        let info = derive_status(
-            &session_dir,
-            self.config.score_threshold(),
-            self.config.guardrails.max_iterations,
-            self.config.guardrails.max_total_tokens,
-        )
+        let guardrails = Guardrails::from_config(&self.config.guardrails);
+        let job = self.job(&session_id);
+        let live_state = match &job {
+            Some(job) if job.is_running() => self.session_registry.get(&session_id).await,
+            _ => None,
+        };
+        let info = match live_state {
+            Some(state) => {
+                let state = state.lock().await.clone();
+                derive_live_status(&session_dir, state, &guardrails)
+            }
+            None => derive_status(&session_dir, &guardrails),
+        }
         .map_err(|e| {
             ErrorData::new(
                 ErrorCode::INTERNAL_ERROR,
@@ -287,13 +408,17 @@ impl PlanForgeServer {
             )
         })?;
 
-        let response = serde_json::to_string_pretty(&info).map_err(|e| {
+        let mut status = serde_json::to_value(&info).map_err(|e| {
             ErrorData::new(
                 ErrorCode::INTERNAL_ERROR,
                 format!("Failed to serialize status: {}", e),
                 None,
             )
         })?;
+        if let Some(job) = &job {
+            status["job"] = job.to_json();
+        }
+        let response = serde_json::to_string_pretty(&status).unwrap_or_default();
 
         Ok(CallToolResult::success(vec![
             Content::text(response).with_audience(vec![Role::Assistant]),
@@ -322,16 +447,12 @@ impl PlanForgeServer {
             )
         })?;
 
+        let guardrails = Guardrails::from_config(&self.config.guardrails);
         let mut session_infos: Vec<SessionInfo> = Vec::new();
 
         for session_id in sessions.into_iter().take(limit) {
             let session_dir = self.session_dir(&session_id);
-            if let Ok(info) = derive_status(
-                &session_dir,
-                self.config.score_threshold(),
-                self.config.guardrails.max_iterations,
-                self.config.guardrails.max_total_tokens,
-            ) {
+            if let Ok(info) = derive_status(&session_dir, &guardrails) {
                 session_infos.push(info);
             }
         }
@@ -411,21 +532,147 @@ impl PlanForgeServer {
     /// - For resume: task is feedback or answer to continue planning
     ///
     /// This tool runs the plan-review loop until it reaches a pause point
-    /// (approved, needs_input, or max_turns).
+    /// (approved, needs_input, or max_turns). A cancelled request saves the
+    /// session as `cancelled` so it can be resumed. When the request carries a
+    /// progress token, orchestration events are sent as progress notifications.
+    ///
+    /// With `background: true` it returns the session ID immediately and runs
+    /// the loop in a spawned task; plan_status reports its live state and
+    /// plan_cancel stops it. The request's progress token expires when
+    /// plan_run returns, so background events are sent as logging messages
+    /// tagged with the session ID instead.
     #[tool(
         name = "plan_run",
-        description = "Create a new planning session or resume an existing one. For new sessions, 'task' is the task description. For resume, 'task' is feedback to incorporate. Runs the plan-review loop until approved, needs_input, or max_turns."
+        description = "Create a new planning session or resume an existing one. For new sessions, 'task' is the task description. For resume, 'task' is feedback to incorporate. Runs the plan-review loop until approved, needs_input, or max_turns. Set 'background' to true to return the session ID immediately; progress then arrives as logging notifications tagged with the session_id, and plan_cancel stops the job."
     )]
     pub async fn plan_run(
         &self,
         params: rmcp::handler::server::wrapper::Parameters<PlanRunParams>,
+        context: RequestContext<RoleServer>,
     ) -> Result<CallToolResult, ErrorData> {
-        let task = params.0.task.clone();
-        let session_id = params.0.session_id.clone();
-        let feedback = params.0.feedback;
+        let PlanRunParams {
+            task,
+            session_id,
+            feedback,
+            background,
+        } = params.0;
+        let task_slug = self.session_slug(&task, session_id.as_ref()).await;
+
+        // Foreground runs also stop when the MCP request is cancelled
+        let cancel_token = if background {
+            CancellationToken::new()
+        } else {
+            context.ct.child_token()
+        };
+        // Another process (CLI or a second server) is working on this
+        // session; checked before anything in it is written
+        if let Some(holder) = SessionLock::holder(&self.session_dir(&task_slug))
+            .filter(|holder| holder.pid != std::process::id())
+        {
+            return Err(ErrorData::new(
+                ErrorCode::INVALID_REQUEST,
+                format!("Session '{}' is locked by {}", task_slug, holder),
+                None,
+            ));
+        }
+        self.start_job(&task_slug, cancel_token.clone(), background)?;
+        let session_dir = match self.prepare_session(&task, &task_slug, session_id.is_none()) {
+            Ok(session_dir) => session_dir,
+            Err(e) => {
+                self.finish_job(&task_slug, &Err(e.clone()));
+                return Err(e);
+            }
+        };
 
         // Always use the orchestrator mode
-        self.run_orchestrator(task, session_id, feedback).await
+        if background {
+            let server = self.clone();
+            let job_id = task_slug.clone();
+            let events = (context.peer.clone(), EventTarget::Log(task_slug.clone()));
+            self.spawn_job(task_slug.clone(), async move {
+                server
+                    .run_orchestrator(
+                        task,
+                        job_id,
+                        session_dir,
+                        feedback,
+                        cancel_token,
+                        Some(events),
+                    )
+                    .await
+            });
+
+            let response = serde_json::json!({
+                "session_id": task_slug,
+                "status": "running",
+                "background": true,
+                "next": "Progress is sent as logging notifications tagged with this session_id; plan_status shows the outcome and plan_cancel stops the job.",
+            });
+            return Ok(CallToolResult::success(vec![
+                Content::text(serde_json::to_string_pretty(&response).unwrap_or_default())
+                    .with_audience(vec![Role::Assistant]),
+            ]));
+        }
+
+        let events = context
+            .meta
+            .get_progress_token()
+            .map(|token| (context.peer.clone(), EventTarget::Progress(token)));
+        // Records an outcome even if the request is dropped mid-run
+        let mut job = JobGuard::new(self, &task_slug);
+        let outcome = self
+            .run_orchestrator(
+                task,
+                task_slug.clone(),
+                session_dir,
+                feedback,
+                cancel_token,
+                events,
+            )
+            .await;
+        job.finish(&outcome);
+        let response = outcome?;
+
+        Ok(CallToolResult::success(vec![
+            Content::text(serde_json::to_string_pretty(&response).unwrap_or_default())
+                .with_audience(vec![Role::Assistant]),
+        ]))
+    }
+
+    /// Cancel a running plan_run job.
+    ///
+    /// The session is saved as `cancelled` with a draft plan and can be
+    /// resumed with plan_run.
+    #[tool(
+        name = "plan_cancel",
+        description = "Cancel a running plan_run job (background or foreground). The session state is saved as 'cancelled' with a draft plan and can be resumed later with plan_run."
+    )]
+    pub async fn plan_cancel(
+        &self,
+        params: rmcp::handler::server::wrapper::Parameters<PlanCancelParams>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let session_id = self.resolve_session(params.0.session_id)?;
+        let job = self
+            .job(&session_id)
+            .filter(|job| job.is_running())
+            .ok_or_else(|| {
+                ErrorData::new(
+                    ErrorCode::INVALID_PARAMS,
+                    format!("No running plan_run job for session '{}'", session_id),
+                    None,
+                )
+            })?;
+        job.cancel_token.cancel();
+
+        let response = serde_json::json!({
+            "session_id": session_id,
+            "status": "cancelling",
+            "next": "The job stops after the current step; plan_status shows 'cancelled' once the state is saved.",
+        });
+        Ok(CallToolResult::success(vec![
+            Content::text(serde_json::to_string_pretty(&response).unwrap_or_default())
+                .with_audience(vec![Role::Assistant]),
+        ]))
     }
 
     /// Force approve a session and write to plans/active/.
@@ -441,6 +688,8 @@ impl PlanForgeServer {
     ) -> Result<CallToolResult, ErrorData> {
         let session_id = self.resolve_session(params.0.session_id)?;
         let session_dir = self.session_dir(&session_id);
+        let _session_lock = SessionLock::acquire(&session_dir)
+            .map_err(|e| ErrorData::new(ErrorCode::INVALID_REQUEST, e.to_string(), None))?;
 
         // Load latest plan
         let plan = self.load_latest_plan(&session_dir)?;
@@ -451,12 +700,32 @@ impl PlanForgeServer {
             .map(|m| m.slug)
             .unwrap_or_else(|_| slugify(&plan.title));
 
+        // Dimension scores from the session's review of this plan, as run()
+        // writes them
+        let review_dimensions = OrchestrationState::load(&session_dir)
+            .ok()
+            .flatten()
+            .and_then(|state| {
+                let dimensions = state.current_plan_review()?.get("dimensions")?.clone();
+                serde_json::from_value(dimensions).ok()
+            })
+            .unwrap_or_default();
+
         // Write to active_dir with the session slug
         let output = FileOutputWriter::new(OutputConfig {
             runs_dir: session_dir.clone(),
             active_dir: self.config.output.active_dir.clone(),
             slug: Some(slug.clone()),
-        });
+        })
+        .with_schedule_config(self.config.schedule.clone())
+        .with_viability_checker(
+            ViabilityChecker::new()
+                .with_custom_opcodes(self.config.custom_opcodes.clone())
+                .with_command_safety(self.config.command_safety.clone())
+                .with_runbook_config(self.config.runbook.clone())
+                .with_working_dir(self.base_dir.clone()),
+        )
+        .with_review_dimensions(review_dimensions);
 
         output.write_final(&plan).await.map_err(|e| {
             ErrorData::new(
@@ -544,7 +813,7 @@ impl PlanForgeServer {
                 None,
             )
         })?;
-        std::fs::write(&meta_path, json).map_err(|e| {
+        write_atomic(&meta_path, json).map_err(|e| {
             ErrorData::new(
                 ErrorCode::INTERNAL_ERROR,
                 format!("Failed to write session metadata: {}", e),
@@ -590,33 +859,34 @@ impl PlanForgeServer {
         (provider, model)
     }
 
-    /// Run the orchestrator for a task.
-    async fn run_orchestrator(
-        &self,
-        task: String,
-        session_id: Option<String>,
-        feedback: Option<String>,
-    ) -> Result<CallToolResult, ErrorData> {
-        // Generate slug for new sessions or use provided session_id
-        let task_slug = if let Some(sid) = session_id.as_ref() {
-            sid.clone()
+    /// Resolve the session for plan_run: the given ID, the current session,
+    /// or a new LLM-generated slug. Writes nothing.
+    async fn session_slug(&self, task: &str, session_id: Option<&String>) -> String {
+        if let Some(sid) = session_id {
+            return sid.clone();
+        }
+        // Check if we have a current session
+        let current = self.current_session.read().ok().and_then(|c| c.clone());
+        if let Some(sid) = current {
+            sid
         } else {
-            // Check if we have a current session
-            let current = self.current_session.read().ok().and_then(|c| c.clone());
-            if let Some(sid) = current {
-                sid
-            } else {
-                // New session - generate slug using LLM
-                let (provider, model) = self.get_slug_provider_model();
-                generate_slug(&task, &provider, &model).await
-            }
-        };
+            // New session - generate slug using LLM
+            let (provider, model) = self.get_slug_provider_model();
+            generate_slug(task, &provider, &model).await
+        }
+    }
 
-        // Set current session
-        self.set_current_session(task_slug.clone());
+    /// Make `task_slug` the current session, create its directory, and save
+    /// metadata for new sessions.
+    fn prepare_session(
+        &self,
+        task: &str,
+        task_slug: &str,
+        new_session: bool,
+    ) -> Result<PathBuf, ErrorData> {
+        self.set_current_session(task_slug.to_string());
 
-        // Create session directory
-        let session_dir = self.session_dir(&task_slug);
+        let session_dir = self.session_dir(task_slug);
         std::fs::create_dir_all(&session_dir).map_err(|e| {
             ErrorData::new(
                 ErrorCode::INTERNAL_ERROR,
@@ -625,12 +895,111 @@ impl PlanForgeServer {
             )
         })?;
 
-        // Save session metadata for new sessions
-        if session_id.is_none() {
-            let meta = SessionMeta::new(task_slug.clone(), task.clone());
+        if new_session {
+            let meta = SessionMeta::new(task_slug.to_string(), task.to_string());
             self.save_session_meta(&session_dir, &meta)?;
         }
 
+        Ok(session_dir)
+    }
+
+    /// Register a plan_run job, refusing a second run of the same session.
+    fn start_job(
+        &self,
+        session_id: &str,
+        cancel_token: CancellationToken,
+        background: bool,
+    ) -> Result<(), ErrorData> {
+        let mut jobs = self.jobs.lock().map_err(|_| {
+            ErrorData::new(
+                ErrorCode::INTERNAL_ERROR,
+                "Failed to lock job registry".to_string(),
+                None,
+            )
+        })?;
+        if jobs.get(session_id).is_some_and(|job| job.is_running()) {
+            return Err(ErrorData::new(
+                ErrorCode::INVALID_REQUEST,
+                format!(
+                    "Session '{}' is already running; use plan_status to follow it or plan_cancel to stop it",
+                    session_id
+                ),
+                None,
+            ));
+        }
+        jobs.insert(
+            session_id.to_string(),
+            PlanJob {
+                cancel_token,
+                background,
+                started_at: chrono::Utc::now().to_rfc3339(),
+                outcome: None,
+            },
+        );
+        Ok(())
+    }
+
+    /// Run a background job registered with `start_job`.
+    ///
+    /// The outcome is recorded even when the task panics or is aborted, so
+    /// the job never reports `running` forever and the session can be run
+    /// again.
+    fn spawn_job<F>(&self, session_id: String, run: F)
+    where
+        F: std::future::Future<Output = Result<serde_json::Value, ErrorData>> + Send + 'static,
+    {
+        let handle = tokio::spawn(run);
+        let server = self.clone();
+        tokio::spawn(async move {
+            let outcome = match handle.await {
+                Ok(outcome) => outcome,
+                Err(e) => {
+                    let reason = if e.is_panic() {
+                        "panicked"
+                    } else {
+                        "was aborted"
+                    };
+                    tracing::error!("plan_run job for '{}' {}", session_id, reason);
+                    // The run never got to unregister its in-memory state
+                    server.session_registry.remove(&session_id).await;
+                    Err(ErrorData::new(
+                        ErrorCode::INTERNAL_ERROR,
+                        format!("plan_run job {}", reason),
+                        None,
+                    ))
+                }
+            };
+            server.finish_job(&session_id, &outcome);
+        });
+    }
+
+    /// Record the plan_run response (or error) of a finished job.
+    fn finish_job(&self, session_id: &str, outcome: &Result<serde_json::Value, ErrorData>) {
+        if let Ok(mut jobs) = self.jobs.lock()
+            && let Some(job) = jobs.get_mut(session_id)
+        {
+            job.outcome = Some(match outcome {
+                Ok(response) => response.clone(),
+                Err(e) => serde_json::json!({ "status": "failed", "error": e.message }),
+            });
+        }
+    }
+
+    /// Snapshot of a session's plan_run job, if one was started by this server
+    fn job(&self, session_id: &str) -> Option<PlanJob> {
+        self.jobs.lock().ok()?.get(session_id).cloned()
+    }
+
+    /// Run the orchestrator for a session and build the plan_run response.
+    async fn run_orchestrator(
+        &self,
+        task: String,
+        task_slug: String,
+        session_dir: PathBuf,
+        feedback: Option<String>,
+        cancel_token: CancellationToken,
+        events: Option<(Peer<RoleServer>, EventTarget)>,
+    ) -> Result<serde_json::Value, ErrorData> {
         // Convert feedback string to HumanResponse
         // Natural language in feedback handles approve/revise/answer semantics
         let hr = feedback.map(|text| HumanResponse {
@@ -646,7 +1015,25 @@ impl PlanForgeServer {
             self.base_dir.clone(),
             session_dir.clone(),
             self.session_registry.clone(),
-        );
+        )
+        .with_custom_opcodes(self.config.custom_opcodes.clone())
+        .with_schedule_config(self.config.schedule.clone())
+        .with_command_safety(self.config.command_safety.clone())
+        .with_runbook_config(self.config.runbook.clone())
+        .with_gates_config(self.config.gates.clone())
+        .with_repo_map_config(self.config.repo_map.clone())
+        .with_code_search_config(self.config.code_search.clone())
+        .with_review_panel_config(self.config.review_panel.clone())
+        .with_review_dimensions_config(self.config.review_dimensions.clone())
+        .with_candidates_config(self.config.candidates.clone())
+        .with_planning_ladder(self.config.planning.ladder.clone())
+        .with_review_ladder(self.config.review.ladder.clone())
+        .with_retry_config(self.config.retry.clone())
+        .with_cancel_token(cancel_token);
+
+        let forwarder = events.map(|(peer, target)| {
+            tokio::spawn(forward_events(peer, target, orchestrator.subscribe()))
+        });
 
         // Run orchestrator
         let working_dir = Some(self.base_dir.clone());
@@ -654,6 +1041,12 @@ impl PlanForgeServer {
             .run(task, working_dir, hr, Some(task_slug.clone()))
             .await;
 
+        // Dropping the orchestrator closes the channel; let the forwarder drain it
+        drop(orchestrator);
+        if let Some(forwarder) = forwarder {
+            let _ = tokio::time::timeout(std::time::Duration::from_secs(1), forwarder).await;
+        }
+
         match result {
             Ok(result) => {
                 // Determine status string
@@ -662,11 +1055,12 @@ impl PlanForgeServer {
                     crate::orchestrator::OrchestrationStatus::CompletedBestEffort => "best_effort",
                     crate::orchestrator::OrchestrationStatus::Running => "in_progress",
                     crate::orchestrator::OrchestrationStatus::Paused { .. } => "needs_input",
+                    crate::orchestrator::OrchestrationStatus::Cancelled => "cancelled",
                     crate::orchestrator::OrchestrationStatus::HardStopped { .. } => "hard_stopped",
                     crate::orchestrator::OrchestrationStatus::Failed { .. } => "failed",
                 };
 
-                let response = serde_json::json!({
+                Ok(serde_json::json!({
                     "session_id": task_slug,
                     "status": status,
                     "iterations": result.iterations,
@@ -674,12 +1068,7 @@ impl PlanForgeServer {
                     "total_tokens": result.total_tokens,
                     "best_score": result.best_score,
                     "has_plan": result.final_plan.is_some(),
-                });
-
-                Ok(CallToolResult::success(vec![
-                    Content::text(serde_json::to_string_pretty(&response).unwrap_or_default())
-                        .with_audience(vec![Role::Assistant]),
-                ]))
+                }))
             }
             Err(e) => Err(ErrorData::new(
                 ErrorCode::INTERNAL_ERROR,
@@ -695,3 +1084,202 @@ impl Default for PlanForgeServer {
         Self::new()
     }
 }
+
+/// Where plan_run sends orchestration events.
+enum EventTarget {
+    /// Progress notifications for the request's progress token (foreground
+    /// runs that asked for them)
+    Progress(ProgressToken),
+    /// Logging notifications tagged with this session ID (background runs,
+    /// which outlive the request)
+    Log(String),
+}
+
+/// Forward orchestration events to the client until the channel closes.
+/// Progress counts events, since the total is not known.
+async fn forward_events(
+    peer: Peer<RoleServer>,
+    target: EventTarget,
+    mut events: broadcast::Receiver<OrchestratorEvent>,
+) {
+    let mut progress = 0.0;
+    loop {
+        let event = match events.recv().await {
+            Ok(event) => event,
+            Err(broadcast::error::RecvError::Lagged(_)) => continue,
+            Err(broadcast::error::RecvError::Closed) => break,
+        };
+        progress += 1.0;
+        let sent = match &target {
+            EventTarget::Progress(progress_token) => {
+                peer.notify_progress(ProgressNotificationParam {
+                    progress_token: progress_token.clone(),
+                    progress,
+                    total: None,
+                    message: Some(event.summary()),
+                })
+                .await
+            }
+            EventTarget::Log(session_id) => {
+                peer.notify_logging_message(LoggingMessageNotificationParam {
+                    level: LoggingLevel::Info,
+                    logger: Some("plan-forge".to_string()),
+                    data: serde_json::json!({
+                        "session_id": session_id,
+                        "message": event.summary(),
+                        "event": event,
+                    }),
+                })
+                .await
+            }
+        };
+        if let Err(e) = sent {
+            tracing::warn!("Failed to send orchestration event: {}", e);
+            break;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rmcp::handler::server::wrapper::Parameters;
+
+    fn make_server() -> PlanForgeServer {
+        PlanForgeServer::with_config(CliConfig::default())
+    }
+
+    /// Wait until the job's outcome is recorded
+    async fn finished_job(server: &PlanForgeServer, session_id: &str) -> PlanJob {
+        for _ in 0..200 {
+            if let Some(job) = server.job(session_id).filter(|job| !job.is_running()) {
+                return job;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        panic!("job '{}' did not finish", session_id);
+    }
+
+    #[tokio::test]
+    async fn test_running_job_refuses_second_run() {
+        let server = make_server();
+        server
+            .start_job("demo", CancellationToken::new(), true)
+            .unwrap();
+
+        let err = server
+            .start_job("demo", CancellationToken::new(), true)
+            .unwrap_err();
+        assert!(err.message.contains("already running"));
+        // Other sessions are independent
+        assert!(
+            server
+                .start_job("other", CancellationToken::new(), false)
+                .is_ok()
+        );
+
+        // Once finished, the session can run again
+        server.finish_job("demo", &Ok(serde_json::json!({ "status": "approved" })));
+        assert!(
+            server
+                .start_job("demo", CancellationToken::new(), true)
+                .is_ok()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_plan_cancel_cancels_running_job() {
+        let server = make_server();
+        let cancel_token = CancellationToken::new();
+        server
+            .start_job("demo", cancel_token.clone(), true)
+            .unwrap();
+
+        let run_token = cancel_token.clone();
+        server.spawn_job("demo".to_string(), async move {
+            run_token.cancelled().await;
+            Ok(serde_json::json!({ "status": "cancelled" }))
+        });
+
+        let params = Parameters(PlanCancelParams {
+            session_id: Some("demo".to_string()),
+        });
+        server.plan_cancel(params).await.unwrap();
+        assert!(cancel_token.is_cancelled());
+
+        let job = finished_job(&server, "demo").await;
+        assert_eq!(job.outcome.unwrap()["status"], "cancelled");
+        assert!(job.to_json()["cancel_requested"].as_bool().unwrap());
+
+        // Nothing left to cancel
+        let params = Parameters(PlanCancelParams {
+            session_id: Some("demo".to_string()),
+        });
+        assert!(server.plan_cancel(params).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_job_outcome_is_recorded() {
+        let server = make_server();
+        server
+            .start_job("failing", CancellationToken::new(), true)
+            .unwrap();
+        server.spawn_job("failing".to_string(), async {
+            Err(ErrorData::new(
+                ErrorCode::INTERNAL_ERROR,
+                "Orchestrator failed: boom".to_string(),
+                None,
+            ))
+        });
+        let outcome = finished_job(&server, "failing").await.outcome.unwrap();
+        assert_eq!(outcome["status"], "failed");
+        assert_eq!(outcome["error"], "Orchestrator failed: boom");
+    }
+
+    async fn buggy_run() -> Result<serde_json::Value, ErrorData> {
+        panic!("orchestrator bug")
+    }
+
+    #[tokio::test]
+    async fn test_panicking_job_is_recorded_as_failed() {
+        let server = make_server();
+        server
+            .start_job("demo", CancellationToken::new(), true)
+            .unwrap();
+        server.spawn_job("demo".to_string(), buggy_run());
+
+        let outcome = finished_job(&server, "demo").await.outcome.unwrap();
+        assert_eq!(outcome["status"], "failed");
+        assert_eq!(outcome["error"], "plan_run job panicked");
+        // The session is no longer stuck
+        assert!(
+            server
+                .start_job("demo", CancellationToken::new(), true)
+                .is_ok()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_dropped_foreground_job_is_recorded_as_failed() {
+        let server = make_server();
+        server
+            .start_job("demo", CancellationToken::new(), false)
+            .unwrap();
+
+        let run = async {
+            let mut job = JobGuard::new(&server, "demo");
+            std::future::pending::<()>().await;
+            job.finish(&Ok(serde_json::json!({ "status": "approved" })));
+        };
+        // The request future is dropped mid-run
+        let _ = tokio::time::timeout(std::time::Duration::from_millis(10), run).await;
+
+        let outcome = server.job("demo").unwrap().outcome.unwrap();
+        assert_eq!(outcome["status"], "failed");
+        assert!(
+            server
+                .start_job("demo", CancellationToken::new(), false)
+                .is_ok()
+        );
+    }
+}
@@ -7,7 +7,7 @@ use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
 
 use crate::models::ReviewResult;
-use crate::orchestrator::GuardrailHardStop;
+use crate::orchestrator::{GuardrailHardStop, Guardrails, LockInfo, SessionLock};
 
 /// Session status derived from files in .plan-forge/<session>/
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -32,6 +32,8 @@ pub enum SessionStatus {
         /// Category of the input request
         category: String,
     },
+    /// Orchestrator was cancelled; the session can be resumed
+    Cancelled,
     /// Orchestrator hit a hard stop condition
     HardStopped {
         /// The reason for the hard stop
@@ -49,6 +51,7 @@ impl std::fmt::Display for SessionStatus {
             SessionStatus::BestEffort => write!(f, "best_effort"),
             SessionStatus::MaxTurns => write!(f, "max_turns"),
             SessionStatus::PausedForHumanInput { .. } => write!(f, "paused_for_human_input"),
+            SessionStatus::Cancelled => write!(f, "cancelled"),
             SessionStatus::HardStopped { .. } => write!(f, "hard_stopped"),
         }
     }
@@ -83,9 +86,24 @@ pub struct SessionInfo {
     /// Token budget remaining (for orchestrator sessions)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub token_budget_remaining: Option<u64>,
+    /// Seconds since the session started (for orchestrator sessions)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub elapsed_secs: Option<u64>,
+    /// Wall-clock time remaining (when `max_session_secs` is set)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub time_remaining_secs: Option<u64>,
+    /// Estimated spend in USD (for orchestrator sessions)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cost_usd: Option<f64>,
+    /// Cost budget remaining in USD (when `max_cost_usd` is set)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cost_budget_remaining_usd: Option<f64>,
     /// Pending human input request (for orchestrator sessions)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub pending_human_input: Option<PendingHumanInput>,
+    /// Process currently holding the session lock
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub locked_by: Option<LockInfo>,
 }
 
 /// Pending human input request information
@@ -109,27 +127,44 @@ pub struct PendingHumanInput {
 /// - Approved: Latest review passed (score >= threshold AND no requires_human_input)
 /// - MaxTurns: Iteration count >= max AND not Approved AND not NeedsInput
 /// - InProgress: Has plan files, none of the above
-pub fn derive_status(
-    session_dir: &Path,
-    score_threshold: f32,
-    max_iterations: u32,
-    max_total_tokens: u64,
-) -> anyhow::Result<SessionInfo> {
+pub fn derive_status(session_dir: &Path, guardrails: &Guardrails) -> anyhow::Result<SessionInfo> {
     use crate::orchestrator::OrchestrationState;
 
-    let session_id = session_dir
-        .file_name()
-        .and_then(|n| n.to_str())
-        .map(String::from)
-        .ok_or_else(|| anyhow::anyhow!("Invalid session directory: {:?}", session_dir))?;
+    let session_id = session_id_of(session_dir)?;
 
     // Check for orchestrator state file first
     if let Ok(Some(orch_state)) = OrchestrationState::load(session_dir) {
-        return derive_orchestrator_status(session_id, session_dir, orch_state, max_total_tokens);
+        return derive_orchestrator_status(session_id, session_dir, orch_state, guardrails);
     }
 
     // Fall back to legacy file-based derivation
-    derive_legacy_status(session_id, session_dir, score_threshold, max_iterations)
+    derive_legacy_status(
+        session_id,
+        session_dir,
+        guardrails.score_threshold,
+        guardrails.max_iterations,
+    )
+}
+
+/// Derive session status from the in-memory state of a running session.
+///
+/// Used while a `plan_run` job is active, since the state file is only
+/// written between tool calls.
+pub fn derive_live_status(
+    session_dir: &Path,
+    state: crate::orchestrator::OrchestrationState,
+    guardrails: &Guardrails,
+) -> anyhow::Result<SessionInfo> {
+    derive_orchestrator_status(session_id_of(session_dir)?, session_dir, state, guardrails)
+}
+
+/// Session ID (directory name) of a session directory
+fn session_id_of(session_dir: &Path) -> anyhow::Result<String> {
+    session_dir
+        .file_name()
+        .and_then(|n| n.to_str())
+        .map(String::from)
+        .ok_or_else(|| anyhow::anyhow!("Invalid session directory: {:?}", session_dir))
 }
 
 /// Derive status from orchestration state file.
@@ -137,7 +172,7 @@ fn derive_orchestrator_status(
     session_id: String,
     session_dir: &Path,
     state: crate::orchestrator::OrchestrationState,
-    max_total_tokens: u64,
+    guardrails: &Guardrails,
 ) -> anyhow::Result<SessionInfo> {
     use crate::orchestrator::OrchestrationStatus;
 
@@ -151,6 +186,7 @@ fn derive_orchestrator_status(
                 message: error.clone(),
             },
         },
+        OrchestrationStatus::Cancelled => SessionStatus::Cancelled,
         OrchestrationStatus::HardStopped { reason } => SessionStatus::HardStopped {
             reason: reason.clone(),
         },
@@ -177,8 +213,12 @@ fn derive_orchestrator_status(
         .and_then(|t| t.as_str())
         .map(String::from);
 
-    // Calculate token budget remaining
-    let token_budget_remaining = max_total_tokens.saturating_sub(state.total_tokens);
+    // Calculate remaining budgets
+    let token_budget_remaining = guardrails
+        .max_total_tokens
+        .saturating_sub(state.total_tokens);
+    let elapsed_secs = Guardrails::elapsed_secs(&state);
+    let cost_usd = guardrails.session_cost(&state).usd;
 
     // Map pending human input
     let pending_human_input = state
@@ -206,7 +246,16 @@ fn derive_orchestrator_status(
         total_tokens: Some(state.total_tokens),
         tool_calls: Some(state.tool_calls),
         token_budget_remaining: Some(token_budget_remaining),
+        elapsed_secs: Some(elapsed_secs),
+        time_remaining_secs: guardrails
+            .max_session_duration
+            .map(|limit| limit.as_secs().saturating_sub(elapsed_secs)),
+        cost_usd: Some(cost_usd),
+        cost_budget_remaining_usd: guardrails
+            .max_cost_usd
+            .map(|limit| (limit - cost_usd).max(0.0)),
         pending_human_input,
+        locked_by: SessionLock::holder(session_dir),
     })
 }
 
@@ -234,7 +283,12 @@ fn derive_legacy_status(
             total_tokens: None,
             tool_calls: None,
             token_budget_remaining: None,
+            elapsed_secs: None,
+            time_remaining_secs: None,
+            cost_usd: None,
+            cost_budget_remaining_usd: None,
             pending_human_input: None,
+            locked_by: SessionLock::holder(session_dir),
         });
     }
 
@@ -275,7 +329,12 @@ fn derive_legacy_status(
         total_tokens: None,
         tool_calls: None,
         token_budget_remaining: None,
+        elapsed_secs: None,
+        time_remaining_secs: None,
+        cost_usd: None,
+        cost_budget_remaining_usd: None,
         pending_human_input: None,
+        locked_by: SessionLock::holder(session_dir),
     })
 }
 
@@ -400,13 +459,20 @@ mod tests {
     use std::fs;
     use tempfile::TempDir;
 
+    fn make_guardrails() -> Guardrails {
+        Guardrails {
+            max_iterations: 5,
+            ..Default::default()
+        }
+    }
+
     #[test]
     fn test_derive_status_ready() {
         let temp = TempDir::new().unwrap();
         let session_dir = temp.path().join("my-session");
         fs::create_dir(&session_dir).unwrap();
 
-        let info = derive_status(&session_dir, 0.8, 5, 500_000).unwrap();
+        let info = derive_status(&session_dir, &make_guardrails()).unwrap();
         assert_eq!(info.status, SessionStatus::Ready);
         assert_eq!(info.iteration, 0);
     }
@@ -428,9 +494,30 @@ mod tests {
         )
         .unwrap();
 
-        let info = derive_status(&session_dir, 0.8, 5, 500_000).unwrap();
+        let info = derive_status(&session_dir, &make_guardrails()).unwrap();
         assert_eq!(info.status, SessionStatus::InProgress);
         assert_eq!(info.iteration, 1);
         assert_eq!(info.title, Some("Test Plan".to_string()));
     }
+
+    #[test]
+    fn test_derive_live_status() {
+        let temp = TempDir::new().unwrap();
+        let session_dir = temp.path().join("my-session");
+        fs::create_dir(&session_dir).unwrap();
+
+        // Nothing on disk yet; the running session's state is in memory
+        let mut state = crate::orchestrator::OrchestrationState::new(
+            "my-session".to_string(),
+            "task".to_string(),
+            PathBuf::new(),
+            "my-session".to_string(),
+        );
+        state.iteration = 2;
+
+        let info = derive_live_status(&session_dir, state, &make_guardrails()).unwrap();
+        assert_eq!(info.session_id, "my-session");
+        assert_eq!(info.status, SessionStatus::InProgress);
+        assert_eq!(info.iteration, 2);
+    }
 }
@@ -1,5 +1,7 @@
 pub mod plan;
 pub mod review;
+pub mod runbook;
 
 pub use plan::*;
 pub use review::*;
+pub use runbook::*;
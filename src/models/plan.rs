@@ -16,6 +16,9 @@ pub const STEP_RESULT_FIELDS: &[&str] = &[
 ];
 
 /// Operation codes for executable instructions
+///
+/// Names that don't match a built-in opcode deserialize as [`OpCode::Custom`]
+/// and are resolved against the `custom_opcodes` declared in config.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum OpCode {
@@ -50,6 +53,58 @@ pub enum OpCode {
     // Verification Operations
     /// Check file/target exists (grounding)
     VerifyExists,
+
+    // User-defined Operations
+    /// Config-declared opcode (e.g., "RUN_MIGRATION"), stored by name
+    #[serde(untagged)]
+    Custom(String),
+}
+
+/// Category an opcode belongs to, used by viability rules that reason about
+/// operation kinds rather than specific opcodes (e.g., V-011 grounding order).
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum OpCodeCategory {
+    Context,
+    Planning,
+    Execution,
+    Testing,
+    Verification,
+}
+
+impl OpCode {
+    /// Category of a built-in opcode. Returns None for custom opcodes, whose
+    /// category comes from their config declaration.
+    pub fn builtin_category(&self) -> Option<OpCodeCategory> {
+        match self {
+            OpCode::SearchSemantic
+            | OpCode::SearchCode
+            | OpCode::ReadFiles
+            | OpCode::GetDependencies => Some(OpCodeCategory::Context),
+            OpCode::DefineTask | OpCode::VerifyTask => Some(OpCodeCategory::Planning),
+            OpCode::EditCode | OpCode::RunCommand => Some(OpCodeCategory::Execution),
+            OpCode::GenerateTest | OpCode::RunTest => Some(OpCodeCategory::Testing),
+            OpCode::VerifyExists => Some(OpCodeCategory::Verification),
+            OpCode::Custom(_) => None,
+        }
+    }
+
+    /// Whether `name` is a built-in opcode name (and so can't be declared
+    /// as a custom opcode)
+    pub fn is_builtin_name(name: &str) -> bool {
+        serde_json::from_value::<OpCode>(serde_json::Value::String(name.to_string()))
+            .is_ok_and(|op| op.builtin_category().is_some())
+    }
+}
+
+/// Displays the name used in plan JSON (e.g., `EDIT_CODE`, `RUN_MIGRATION`)
+impl std::fmt::Display for OpCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match serde_json::to_value(self) {
+            Ok(serde_json::Value::String(name)) => f.write_str(&name),
+            _ => write!(f, "{:?}", self),
+        }
+    }
 }
 
 /// An executable instruction in the plan DAG
@@ -187,6 +242,34 @@ pub struct GroundingGate {
     pub pass_criteria: String,
     /// What to do if verification fails
     pub rule: String,
+    /// Machine-checkable form of the verification (None = prose only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub check: Option<GateCheck>,
+}
+
+/// Machine-checkable grounding gate. Paths and globs are relative to the
+/// working directory.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum GateCheck {
+    /// A file or directory exists
+    FileExists { path: String },
+    /// A glob matches between `min` and `max` files
+    GlobMatches {
+        pattern: String,
+        #[serde(default = "default_glob_min")]
+        min: usize,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        max: Option<usize>,
+    },
+    /// A regex matches somewhere in a file
+    RegexInFile { path: String, pattern: String },
+    /// A shell command exits 0 (only run when commands are allowed)
+    CommandSucceeds { command: String },
+}
+
+fn default_glob_min() -> usize {
+    1
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -327,6 +410,13 @@ impl Plan {
         }
     }
 
+    /// Parse the operator runbook into numbered steps (None if absent)
+    pub fn parsed_runbook(&self) -> Option<super::OperatorRunbook> {
+        self.operator_runbook
+            .as_deref()
+            .map(super::OperatorRunbook::parse)
+    }
+
     /// Update the last_updated timestamp and increment version
     pub fn touch(&mut self) {
         self.metadata.last_updated = chrono::Utc::now().to_rfc3339();
@@ -32,9 +32,28 @@ pub struct LlmReview {
     /// Explanation of what input is needed from human
     #[serde(default)]
     pub human_input_reason: Option<String>,
+    /// Specialist review dimensions (security, testing, ...)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub dimensions: Vec<DimensionScore>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Score and findings from one specialist review pass
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DimensionScore {
+    pub name: String,
+    pub score: f32,
+    /// Weight in the combined score
+    pub weight: f32,
+    /// Minimum score required for this dimension
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_score: Option<f32>,
+    /// Whether `score` meets `min_score` (always true without a minimum)
+    pub passed: bool,
+    #[serde(default)]
+    pub gaps: Vec<Gap>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Gap {
     pub description: String,
     pub location: Option<String>,
@@ -125,6 +144,7 @@ impl Default for LlmReview {
             score: 0.0,
             requires_human_input: false,
             human_input_reason: None,
+            dimensions: Vec::new(),
         }
     }
 }
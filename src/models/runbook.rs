@@ -0,0 +1,309 @@
+//! Structured view of `Plan.operator_runbook`.
+//!
+//! The planner writes the runbook as markdown: a preamble (e.g. "Rule 0"),
+//! then numbered steps (`### 1) Title`, `1. Title`, `**2) Title**`) with
+//! fenced bash blocks and optional "Expected: ..." lines. Parsing is lenient:
+//! anything that isn't a step header, command, or expected outcome is kept as
+//! a note (or, inside a fence, as a comment on the nearest command) so no
+//! content is lost when re-rendering.
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+/// `### 1) Title`, `1. Title`, `**Step 2.** Title`
+static STEP_HEADER: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)^(?:#{1,6}\s+)?(?:\*\*)?(?:step\s+)?(\d+)[.)](?:\*\*)?\s+(.*)$")
+        .expect("invalid STEP_HEADER regex")
+});
+
+/// `Expected: ...`, `Expected outcome: ...`, `Pass criteria: ...` (bold removed)
+static EXPECTED_LINE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?i)^(?:[-*]\s*)?(?:expected(?:\s+(?:outcome|result|output))?|pass criteria)\s*:\s*(.+)$",
+    )
+    .expect("invalid EXPECTED_LINE regex")
+});
+
+/// `- Run: ...`, `Command: ...` lines whose inline code is a command
+static RUN_LINE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)^(?:[-*]\s*)?(?:run|execute|command)\b").expect("invalid RUN_LINE regex")
+});
+
+/// Inline code span
+static INLINE_CODE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"`([^`]+)`").expect("invalid INLINE_CODE regex"));
+
+/// A shell command in the runbook
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RunbookCommand {
+    /// 1-based line in the runbook where the command starts
+    pub line: usize,
+    /// The command (continuation lines joined)
+    pub command: String,
+    /// `#` comment lines just before the command in its fence
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub comments_before: Vec<String>,
+    /// `#` comment lines after the last command of a fence
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub comments_after: Vec<String>,
+}
+
+/// A numbered runbook step
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RunbookStep {
+    /// Step number as written (or assigned sequentially for unnumbered commands)
+    pub number: u32,
+    /// Step title (may be empty)
+    pub title: String,
+    /// Commands to run, in order
+    pub commands: Vec<RunbookCommand>,
+    /// Expected outcome, if stated
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expected_outcome: Option<String>,
+    /// Any other prose in the step
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub notes: Vec<String>,
+}
+
+/// Parsed operator runbook
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OperatorRunbook {
+    /// Lines before the first step (e.g., "Rule 0: run from project root")
+    pub preamble: Vec<String>,
+    /// Numbered steps
+    pub steps: Vec<RunbookStep>,
+}
+
+impl OperatorRunbook {
+    /// Parse a markdown runbook into structured steps
+    pub fn parse(text: &str) -> Self {
+        let has_fences = text.lines().any(is_fence);
+        let mut runbook = OperatorRunbook::default();
+        let mut in_fence = false;
+        // Whether the previous command line ended with a trailing backslash
+        let mut continuing = false;
+        // Fence comments waiting for the command they precede
+        let mut comments = Vec::new();
+
+        for (idx, raw) in text.lines().enumerate() {
+            let line_no = idx + 1;
+            let trimmed = raw.trim();
+
+            if is_fence(raw) {
+                in_fence = !in_fence;
+                continuing = false;
+                runbook.keep_comments(std::mem::take(&mut comments));
+                continue;
+            }
+
+            if in_fence {
+                if trimmed.starts_with('#') {
+                    comments.push(trimmed.to_string());
+                    continue;
+                }
+                if trimmed.is_empty() {
+                    continue;
+                }
+                let command = trimmed.strip_prefix("$ ").unwrap_or(trimmed);
+                let (command, continues) = match command.strip_suffix('\\') {
+                    Some(head) => (head.trim_end(), true),
+                    None => (command, false),
+                };
+                let step = runbook.current_step();
+                match step.commands.last_mut() {
+                    Some(last) if continuing => {
+                        last.command.push(' ');
+                        last.command.push_str(command);
+                    }
+                    _ => step.commands.push(RunbookCommand {
+                        line: line_no,
+                        command: command.to_string(),
+                        comments_before: std::mem::take(&mut comments),
+                        comments_after: Vec::new(),
+                    }),
+                }
+                continuing = continues;
+                continue;
+            }
+
+            if trimmed.is_empty() {
+                continue;
+            }
+
+            if let Some(caps) = STEP_HEADER.captures(trimmed) {
+                let number = caps[1].parse().unwrap_or(runbook.steps.len() as u32 + 1);
+                let title = caps[2].trim().trim_matches('*').trim().to_string();
+                let mut commands = inline_commands(&title, line_no);
+                // Fence-less runbooks: a bare `1. cargo check` is itself a command
+                if commands.is_empty() && !has_fences && looks_like_command(&title) {
+                    commands.push(RunbookCommand {
+                        line: line_no,
+                        command: title.clone(),
+                        comments_before: Vec::new(),
+                        comments_after: Vec::new(),
+                    });
+                }
+                runbook.steps.push(RunbookStep {
+                    number,
+                    title,
+                    commands,
+                    expected_outcome: None,
+                    notes: Vec::new(),
+                });
+                continue;
+            }
+
+            let plain = trimmed.replace("**", "");
+            let Some(step) = runbook.steps.last_mut() else {
+                runbook.preamble.push(trimmed.to_string());
+                continue;
+            };
+            if let Some(caps) = EXPECTED_LINE.captures(&plain) {
+                step.expected_outcome = Some(caps[1].trim().to_string());
+            } else if let Some(rest) = plain.strip_prefix("→").or_else(|| plain.strip_prefix("->"))
+            {
+                step.expected_outcome = Some(rest.trim().to_string());
+            } else if RUN_LINE.is_match(&plain) && INLINE_CODE.is_match(trimmed) {
+                step.commands.extend(inline_commands(trimmed, line_no));
+            } else {
+                step.notes.push(trimmed.to_string());
+            }
+        }
+        // Unterminated fence
+        runbook.keep_comments(comments);
+
+        runbook
+    }
+
+    /// All commands in step order
+    pub fn commands(&self) -> impl Iterator<Item = &RunbookCommand> {
+        self.steps.iter().flat_map(|s| s.commands.iter())
+    }
+
+    /// Attach comments left at the end of a fence to the last command (or
+    /// keep them as notes when there is none)
+    fn keep_comments(&mut self, comments: Vec<String>) {
+        if comments.is_empty() {
+            return;
+        }
+        let step = self.current_step();
+        match step.commands.last_mut() {
+            Some(last) => last.comments_after.extend(comments),
+            None => step.notes.extend(comments),
+        }
+    }
+
+    /// Step to append commands to, creating an unnumbered one if needed
+    fn current_step(&mut self) -> &mut RunbookStep {
+        if self.steps.is_empty() {
+            self.steps.push(RunbookStep {
+                number: 1,
+                title: String::new(),
+                commands: Vec::new(),
+                expected_outcome: None,
+                notes: Vec::new(),
+            });
+        }
+        let last = self.steps.len() - 1;
+        &mut self.steps[last]
+    }
+}
+
+fn is_fence(line: &str) -> bool {
+    let trimmed = line.trim_start();
+    trimmed.starts_with("```") || trimmed.starts_with("~~~")
+}
+
+/// Inline code spans on a line, as commands
+fn inline_commands(line: &str, line_no: usize) -> Vec<RunbookCommand> {
+    INLINE_CODE
+        .captures_iter(line)
+        .map(|caps| RunbookCommand {
+            line: line_no,
+            command: caps[1].trim().to_string(),
+            comments_before: Vec::new(),
+            comments_after: Vec::new(),
+        })
+        .collect()
+}
+
+/// Heuristic for fence-less runbooks: commands start lowercase or with a path
+fn looks_like_command(text: &str) -> bool {
+    text.chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_lowercase() || c == '.' || c == '/')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_planner_format() {
+        let text = "**Rule 0**: Run all commands from project root.\n\n\
+                    ### 1) Phase 0.0 grounding gate\n\
+                    ```bash\n\
+                    # verify build\n\
+                    cargo check\n\
+                    ```\n\
+                    **Expected**: compiles without errors\n\n\
+                    ### 2) Validate\n\
+                    ```bash\n\
+                    cargo test --lib \\\n  --quiet\n\
+                    ```\n\
+                    Watch for flaky tests.\n";
+        let runbook = OperatorRunbook::parse(text);
+
+        assert_eq!(
+            runbook.preamble,
+            vec!["**Rule 0**: Run all commands from project root."]
+        );
+        assert_eq!(runbook.steps.len(), 2);
+
+        let first = &runbook.steps[0];
+        assert_eq!(first.number, 1);
+        assert_eq!(first.title, "Phase 0.0 grounding gate");
+        assert_eq!(
+            first.commands,
+            vec![RunbookCommand {
+                line: 6,
+                command: "cargo check".to_string(),
+                comments_before: vec!["# verify build".to_string()],
+                comments_after: Vec::new(),
+            }]
+        );
+        assert_eq!(
+            first.expected_outcome.as_deref(),
+            Some("compiles without errors")
+        );
+
+        let second = &runbook.steps[1];
+        assert_eq!(second.commands[0].command, "cargo test --lib --quiet");
+        assert_eq!(second.notes, vec!["Watch for flaky tests."]);
+    }
+
+    #[test]
+    fn test_parse_fenceless_list() {
+        let runbook = OperatorRunbook::parse(
+            "1. cargo check\n2) Run tests: `cargo test`\n3. Review output\n",
+        );
+
+        let commands: Vec<&str> = runbook.commands().map(|c| c.command.as_str()).collect();
+        assert_eq!(commands, vec!["cargo check", "cargo test"]);
+        assert_eq!(runbook.steps.len(), 3);
+        assert!(runbook.steps[2].commands.is_empty());
+    }
+
+    #[test]
+    fn test_parse_unnumbered_commands() {
+        let runbook = OperatorRunbook::parse("```bash\ncargo build\n# then check\n```\n");
+
+        assert_eq!(runbook.steps.len(), 1);
+        assert_eq!(runbook.steps[0].commands[0].line, 2);
+        assert_eq!(
+            runbook.steps[0].commands[0].comments_after,
+            vec!["# then check"]
+        );
+    }
+}
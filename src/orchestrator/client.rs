@@ -8,7 +8,7 @@ use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::Arc;
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use async_trait::async_trait;
 use rmcp::ServiceError;
 use rmcp::model::{
@@ -24,23 +24,39 @@ type Error = ServiceError;
 use schemars::JsonSchema;
 use schemars::schema_for;
 use serde::{Deserialize, Serialize};
-use tokio::sync::{Mutex, RwLock, mpsc};
+use tokio::sync::{Mutex, RwLock, broadcast, mpsc};
 use tokio_util::sync::CancellationToken;
 
 use goose::agents::extension::ExtensionConfig;
 use goose::agents::extension_manager::ExtensionManager;
 
-use super::guardrails::Guardrails;
+use super::escalation::{ModelLadder, escalation_reason};
+use super::events::OrchestratorEvent;
+use super::forecast::Forecast;
+use super::gates::GateRunner;
+use super::guardrails::{Guardrails, StagnationAction, TokenRole};
 use super::orchestration_state::{
-    HumanInputRecord, IterationOutcome, IterationRecord, OrchestrationState, OrchestrationStatus,
+    CachedReview, HumanInputRecord, IterationOutcome, IterationRecord, OrchestrationState,
+    OrchestrationStatus, RetryRecord, ReviewerScore,
+};
+use super::session_lock::write_atomic_async;
+use super::viability::{PlanAutoFixer, ViabilityChecker, ViabilitySeverity};
+use crate::config::{HardChecklist, LadderConfig};
+use crate::models::{DimensionScore, GroundingGate, Plan};
+use crate::phases::{
+    CandidateOutcome, CandidatePlanners, GoosePlanner, GooseReviewer, RetryPolicy, ReviewPanel,
+    SpecialistReviews, call_with_retry, combined_score, select_best, served_by,
 };
-use super::viability::{ViabilityChecker, ViabilitySeverity, analyze_dag};
-use crate::models::Plan;
-use crate::phases::{GoosePlanner, GooseReviewer};
 
 /// Extension name used for tool prefixing (tools become plan-forge-orchestrator__<name>)
 pub const EXTENSION_NAME: &str = "plan-forge-orchestrator";
 
+/// Category of the human input requested when planning stagnates
+const STAGNATION_INPUT_CATEGORY: &str = "stagnation";
+
+/// Category of human input requests raised by a projected budget shortfall
+pub(crate) const BUDGET_INPUT_CATEGORY: &str = "budget";
+
 // ============================================================================
 // Token Usage Tracking
 // ============================================================================
@@ -60,6 +76,59 @@ impl TokenUsage {
             output_tokens: output,
         }
     }
+
+    /// Add another run's usage; unknown counts stay `None` only if both are.
+    pub fn add(&mut self, other: &TokenUsage) {
+        fn sum(a: Option<i32>, b: Option<i32>) -> Option<i32> {
+            match (a, b) {
+                (None, None) => None,
+                (a, b) => Some(a.unwrap_or(0).saturating_add(b.unwrap_or(0))),
+            }
+        }
+        self.input_tokens = sum(self.input_tokens, other.input_tokens);
+        self.output_tokens = sum(self.output_tokens, other.output_tokens);
+    }
+}
+
+/// Sum of usage labelled by "provider/model"
+fn total_usage(usage: &[(String, TokenUsage)]) -> TokenUsage {
+    let mut total = TokenUsage::default();
+    for (_, usage) in usage {
+        total.add(usage);
+    }
+    total
+}
+
+/// Input and output tokens of `usage`, with missing counts as zero
+fn usage_tokens(usage: &TokenUsage) -> (u64, u64) {
+    (
+        usage.input_tokens.map(|t| t.max(0) as u64).unwrap_or(0),
+        usage.output_tokens.map(|t| t.max(0) as u64).unwrap_or(0),
+    )
+}
+
+/// Error context carrying the usage of calls that finished before a failure,
+/// labelled by "provider/model", so a failed call still counts toward budgets.
+#[derive(Debug, Clone)]
+pub struct SpentUsage {
+    pub message: String,
+    pub usage: Vec<(String, TokenUsage)>,
+}
+
+impl std::fmt::Display for SpentUsage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl SpentUsage {
+    /// Usage attached to `error` by a `SpentUsage` context (empty if none)
+    pub fn of(error: &anyhow::Error) -> Vec<(String, TokenUsage)> {
+        error
+            .downcast_ref::<SpentUsage>()
+            .map(|spent| spent.usage.clone())
+            .unwrap_or_default()
+    }
 }
 
 // ============================================================================
@@ -186,6 +255,42 @@ pub struct OrchestratorClient {
     planner: Arc<GoosePlanner>,
     /// Reviewer for reviewing plans
     reviewer: Arc<GooseReviewer>,
+    /// Planner escalation ladder used instead of `planner` when configured
+    planner_ladder: Option<Arc<ModelLadder<GoosePlanner>>>,
+    /// Viability failures in a row on one rung before escalating
+    escalate_after_viability_failures: usize,
+    /// Reviews on one rung without improvement before escalating
+    escalate_after_stalled_reviews: usize,
+    /// Reviewer escalation ladder used instead of `reviewer` when configured
+    reviewer_ladder: Option<Arc<ModelLadder<GooseReviewer>>>,
+    /// "provider/model" label of `planner`, used for retries and pricing
+    planner_label: String,
+    /// "provider/model" label of `reviewer`, used for retries and pricing
+    reviewer_label: String,
+    /// Backoff for retryable planner/reviewer errors
+    retry: RetryPolicy,
+    /// Planners tried in order once retries on the primary are exhausted
+    fallback_planners: Vec<(String, Arc<GoosePlanner>)>,
+    /// Reviewers tried in order once retries on the primary are exhausted
+    fallback_reviewers: Vec<(String, Arc<GooseReviewer>)>,
+    /// Best-of-N candidate planners (None = single planner)
+    candidates: Option<Arc<CandidatePlanners>>,
+    /// Review panel used instead of `reviewer` when configured
+    review_panel: Option<Arc<ReviewPanel>>,
+    /// Specialist review passes scored as separate dimensions (None = disabled)
+    specialists: Option<Arc<SpecialistReviews>>,
+    /// Deterministic V-* checks run before the LLM review
+    viability: ViabilityChecker,
+    /// Mechanical fixes applied before viability checks (None = disabled)
+    auto_fixer: Option<PlanAutoFixer>,
+    /// Grounding gate runner evaluated before finalize (None = disabled)
+    gate_runner: Option<GateRunner>,
+    /// Per-iteration usage of past sessions, for budget projections
+    forecast: Arc<Forecast>,
+    /// Cancelled on SIGINT/SIGTERM or MCP cancellation
+    cancel_token: CancellationToken,
+    /// Progress events for subscribers (None = not published)
+    events: Option<broadcast::Sender<OrchestratorEvent>>,
     /// MCP initialization info
     info: InitializeResult,
 }
@@ -231,10 +336,313 @@ impl OrchestratorClient {
             guardrails,
             planner,
             reviewer,
+            planner_ladder: None,
+            escalate_after_viability_failures: LadderConfig::default()
+                .escalate_after_viability_failures,
+            escalate_after_stalled_reviews: LadderConfig::default().escalate_after_stalled_reviews,
+            reviewer_ladder: None,
+            planner_label: "planner".to_string(),
+            reviewer_label: "reviewer".to_string(),
+            retry: RetryPolicy::default(),
+            fallback_planners: Vec::new(),
+            fallback_reviewers: Vec::new(),
+            candidates: None,
+            review_panel: None,
+            specialists: None,
+            viability: ViabilityChecker::new(),
+            auto_fixer: None,
+            gate_runner: None,
+            forecast: Arc::new(Forecast::default()),
+            cancel_token: CancellationToken::new(),
+            events: None,
             info,
         }
     }
 
+    /// Use a configured viability checker (e.g., with custom opcodes registered).
+    pub fn with_viability_checker(mut self, viability: ViabilityChecker) -> Self {
+        self.viability = viability;
+        self
+    }
+
+    /// Apply mechanical viability fixes to plans before they are reviewed.
+    pub fn with_auto_fixer(mut self, auto_fixer: PlanAutoFixer) -> Self {
+        self.auto_fixer = Some(auto_fixer);
+        self
+    }
+
+    /// Escalate the planner through a ladder of models (rung 0 first).
+    pub fn with_planner_ladder(mut self, ladder: Arc<ModelLadder<GoosePlanner>>) -> Self {
+        self.planner_ladder = Some(ladder);
+        self
+    }
+
+    /// Set when the planner moves up its ladder (see `escalation_reason`).
+    pub fn with_escalation_thresholds(
+        mut self,
+        viability_failures: usize,
+        stalled_reviews: usize,
+    ) -> Self {
+        self.escalate_after_viability_failures = viability_failures;
+        self.escalate_after_stalled_reviews = stalled_reviews;
+        self
+    }
+
+    /// Review with the session's rung of a reviewer ladder.
+    pub fn with_reviewer_ladder(mut self, ladder: Arc<ModelLadder<GooseReviewer>>) -> Self {
+        self.reviewer_ladder = Some(ladder);
+        self
+    }
+
+    /// Project budgets from past sessions' per-iteration usage.
+    pub fn with_forecast(mut self, forecast: Arc<Forecast>) -> Self {
+        self.forecast = forecast;
+        self
+    }
+
+    /// Refuse new tool calls, stop retry backoffs, and mark the session
+    /// cancelled once `cancel_token` is cancelled.
+    pub fn with_cancel_token(mut self, cancel_token: CancellationToken) -> Self {
+        self.retry.cancel_token = Some(cancel_token.clone());
+        self.cancel_token = cancel_token;
+        self
+    }
+
+    /// Publish progress events to the orchestrator's event channel.
+    pub fn with_events(mut self, events: broadcast::Sender<OrchestratorEvent>) -> Self {
+        self.events = Some(events);
+        self
+    }
+
+    /// Set the backoff used for retryable planner and reviewer errors.
+    pub fn with_retry_policy(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry.with_cancel_token(self.cancel_token.clone());
+        self
+    }
+
+    /// Set the "provider/model" labels of the default planner and reviewer,
+    /// used in retry records and to price their token usage.
+    pub fn with_model_labels(
+        mut self,
+        planner: impl Into<String>,
+        reviewer: impl Into<String>,
+    ) -> Self {
+        self.planner_label = planner.into();
+        self.reviewer_label = reviewer.into();
+        self
+    }
+
+    /// Planners tried in order when the primary planner keeps failing.
+    pub fn with_fallback_planners(mut self, planners: Vec<(String, Arc<GoosePlanner>)>) -> Self {
+        self.fallback_planners = planners;
+        self
+    }
+
+    /// Reviewers tried in order when the primary reviewer keeps failing.
+    pub fn with_fallback_reviewers(mut self, reviewers: Vec<(String, Arc<GooseReviewer>)>) -> Self {
+        self.fallback_reviewers = reviewers;
+        self
+    }
+
+    /// Generate several candidate plans per generate_plan call and keep the best.
+    pub fn with_candidates(mut self, candidates: Arc<CandidatePlanners>) -> Self {
+        self.candidates = Some(candidates);
+        self
+    }
+
+    /// Review plans with a panel of reviewers instead of the single reviewer.
+    pub fn with_review_panel(mut self, review_panel: Arc<ReviewPanel>) -> Self {
+        self.review_panel = Some(review_panel);
+        self
+    }
+
+    /// Run specialist review passes alongside the main review.
+    pub fn with_specialist_reviews(mut self, specialists: Arc<SpecialistReviews>) -> Self {
+        self.specialists = Some(specialists);
+        self
+    }
+
+    /// Evaluate the plan's executable grounding gates before finalizing.
+    pub fn with_gate_runner(mut self, gate_runner: GateRunner) -> Self {
+        self.gate_runner = Some(gate_runner);
+        self
+    }
+
+    /// Call a tool in-process and return its JSON response.
+    ///
+    /// Used by the deterministic orchestrator, which runs the same handlers
+    /// without an agent. Tool errors (hard stops, planner or reviewer
+    /// failures) are returned as `Err`.
+    pub async fn invoke(&self, tool: &str, arguments: Value) -> Result<Value> {
+        let arguments = match arguments {
+            Value::Object(map) => Some(map),
+            _ => None,
+        };
+        let result = self.dispatch(tool, arguments).await;
+        let text = result
+            .content
+            .iter()
+            .filter_map(|c| c.raw.as_text())
+            .map(|t| t.text.as_str())
+            .collect::<Vec<_>>()
+            .join("\n");
+        if result.is_error == Some(true) {
+            anyhow::bail!("{} failed: {}", tool, text);
+        }
+        serde_json::from_str(&text).with_context(|| format!("Invalid {} response", tool))
+    }
+
+    /// Route a tool call to its handler
+    async fn dispatch(&self, name: &str, arguments: Option<JsonObject>) -> CallToolResult {
+        if self.mark_cancelled().await {
+            return CallToolResult::error(vec![Content::text(
+                "Session cancelled; state saved for resume",
+            )]);
+        }
+        let result = match name {
+            "generate_plan" => self.handle_generate_plan(arguments).await,
+            "review_plan" => self.handle_review_plan(arguments).await,
+            "request_human_input" => self.handle_request_human_input(arguments).await,
+            "finalize" => self.handle_finalize(arguments).await,
+            "check_limits" => self.handle_check_limits().await,
+            _ => CallToolResult::error(vec![Content::text(format!("Unknown tool: {}", name))]),
+        };
+        // A planner or reviewer stream cut short by cancellation leaves the
+        // session resumable rather than failed
+        self.mark_cancelled().await;
+        result
+    }
+
+    /// Send a progress event; having no subscribers is not an error.
+    fn publish(&self, event: OrchestratorEvent) {
+        if let Some(events) = &self.events {
+            let _ = events.send(event);
+        }
+    }
+
+    /// Mark a running session `Cancelled` and persist it once the cancel
+    /// token fires. Returns whether the session is cancelled.
+    async fn mark_cancelled(&self) -> bool {
+        if !self.cancel_token.is_cancelled() {
+            return false;
+        }
+        {
+            let mut state = self.state.lock().await;
+            if matches!(state.status, OrchestrationStatus::Running) {
+                state.status = OrchestrationStatus::Cancelled;
+            }
+        }
+        self.persist_state().await;
+        true
+    }
+
+    /// Append failed provider attempts to the session state.
+    async fn record_retries(&self, retries: Vec<RetryRecord>) {
+        if !retries.is_empty() {
+            self.state.lock().await.retries.extend(retries);
+        }
+    }
+
+    /// Attribute token usage to a model for the cost budget.
+    async fn record_model_usage(&self, model: &str, usage: &TokenUsage) {
+        self.state
+            .lock()
+            .await
+            .add_model_usage(model, usage.input_tokens, usage.output_tokens);
+    }
+
+    /// Attribute usage labelled by "provider/model" for the cost budget.
+    async fn record_models_usage(&self, usage: &[(String, TokenUsage)]) {
+        let mut state = self.state.lock().await;
+        for (model, usage) in usage {
+            state.add_model_usage(model, usage.input_tokens, usage.output_tokens);
+        }
+    }
+
+    /// Projection of the remaining iterations for check_limits
+    fn forecast_limits(&self, state: &OrchestrationState) -> Value {
+        let forecast = self.forecast.for_session(state);
+        if forecast.is_empty() {
+            return Value::Null;
+        }
+        let tokens_per_iteration = forecast.tokens_per_iteration.total();
+        let remaining_iterations = self
+            .guardrails
+            .max_iterations
+            .saturating_sub(state.iteration);
+        let cost_per_iteration = forecast.cost_per_iteration(&self.guardrails);
+        serde_json::json!({
+            "source": if state.iteration > 0 { "session" } else { "history" },
+            "tokens_per_iteration": forecast.tokens_per_iteration,
+            "cost_per_iteration_usd": cost_per_iteration,
+            "remaining_iterations": remaining_iterations,
+            "projected_remaining_tokens": (tokens_per_iteration * remaining_iterations as f64).round() as u64,
+            "projected_remaining_cost_usd": cost_per_iteration.map(|cost| cost * remaining_iterations as f64),
+            "shortfall": self.forecast.shortfall(&self.guardrails, state),
+        })
+    }
+
+    /// Hard-stop the session if `role` is out of token budget.
+    ///
+    /// Returns the tool error to send back when the call must not start.
+    async fn check_token_budget(&self, role: TokenRole) -> Option<CallToolResult> {
+        let mut state = self.state.lock().await;
+        let hard_stop = self.guardrails.check_token_budget(&state, role).err()?;
+        tracing::warn!("Hard stop before {} call: {:?}", role, hard_stop);
+        state.status = OrchestrationStatus::HardStopped {
+            reason: hard_stop.clone(),
+        };
+        Some(CallToolResult::error(vec![Content::text(format!(
+            "Hard stop: {:?}",
+            hard_stop
+        ))]))
+    }
+
+    /// Pause for human input when the next iteration is projected to overrun
+    /// a remaining budget, rather than hard-stopping partway through it.
+    ///
+    /// Skipped once a human has answered a budget pause at this iteration.
+    async fn pause_for_budget(&self) -> Option<CallToolResult> {
+        let shortfall = {
+            let mut state = self.state.lock().await;
+            let answered = state
+                .human_inputs
+                .iter()
+                .any(|h| h.category == BUDGET_INPUT_CATEGORY && h.iteration == state.iteration);
+            if answered {
+                return None;
+            }
+            let shortfall = self.forecast.shortfall(&self.guardrails, &state)?;
+            tracing::warn!("Projected budget shortfall: {}", shortfall.explanation);
+
+            let reason = format!("{}: {}", BUDGET_INPUT_CATEGORY, shortfall.explanation);
+            state.pending_human_input = Some(HumanInputRecord {
+                question: shortfall.explanation.clone(),
+                category: BUDGET_INPUT_CATEGORY.to_string(),
+                response: None,
+                reason: Some(reason.clone()),
+                iteration: state.iteration,
+                timestamp: chrono::Utc::now().to_rfc3339(),
+                approved: false,
+            });
+            state.status = OrchestrationStatus::Paused { reason };
+            shortfall
+        };
+        self.persist_state().await;
+        self.publish(OrchestratorEvent::HumanInputRequested {
+            question: shortfall.explanation.clone(),
+            category: BUDGET_INPUT_CATEGORY.to_string(),
+        });
+
+        let response = serde_json::json!({
+            "status": "paused",
+            "budget_shortfall": shortfall,
+        });
+        Some(CallToolResult::success(vec![Content::text(
+            serde_json::to_string_pretty(&response).unwrap_or_else(|_| response.to_string()),
+        )]))
+    }
+
     /// Save current state to disk. Called after every tool operation.
     async fn persist_state(&self) {
         let state = self.state.lock().await;
@@ -338,13 +746,14 @@ impl OrchestratorClient {
         };
 
         // 3. Read current state for context (short lock)
-        let (iteration, working_dir, task, current_plan) = {
+        let (iteration, working_dir, task, current_plan, rung) = {
             let state = self.state.lock().await;
             (
                 state.iteration,
                 state.working_dir.clone(),
                 state.task.clone(),
                 state.current_plan.clone(),
+                state.model_rung,
             )
         }; // lock released
 
@@ -355,16 +764,85 @@ impl OrchestratorClient {
             input.task
         };
 
-        let (plan_json, token_usage) = match self
-            .planner
-            .generate_plan_json(
-                &task_str,
-                input.feedback.as_deref(),
-                current_plan.as_ref(),
-                working_dir.to_str(),
-            )
-            .await
+        let use_candidates = self
+            .candidates
+            .as_ref()
+            .is_some_and(|c| current_plan.is_none() || !c.initial_only());
+        if let Some(stop) = self.check_token_budget(TokenRole::Planner).await {
+            return stop;
+        }
+        // Candidate selection also reviews each candidate
+        if use_candidates && let Some(stop) = self.check_token_budget(TokenRole::Reviewer).await {
+            return stop;
+        }
+        if let Some(paused) = self.pause_for_budget().await {
+            return paused;
+        }
+        self.publish(OrchestratorEvent::IterationStarted {
+            iteration: iteration + 1,
+        });
+
+        if let Some(candidates) = &self.candidates
+            && use_candidates
+        {
+            return self
+                .handle_generate_candidates(
+                    candidates,
+                    &task_str,
+                    input.feedback.as_deref(),
+                    current_plan.as_ref(),
+                    &working_dir,
+                    iteration,
+                )
+                .await;
+        }
+
+        let primary = match self.planner_ladder.as_ref().and_then(|l| l.get(rung)) {
+            Some((label, planner)) => {
+                info!("Planner rung {}: {}", rung, label);
+                (label.to_string(), planner.clone())
+            }
+            None => (self.planner_label.clone(), self.planner.clone()),
+        };
+        let mut chain = vec![primary];
+        chain.extend(self.fallback_planners.iter().cloned());
+
+        let (task_ref, feedback, previous_plan, working_dir_str) = (
+            task_str.as_str(),
+            input.feedback.as_deref(),
+            current_plan.as_ref(),
+            working_dir.to_str(),
+        );
+        // Usage of failed attempts, which callers never see; charged below
+        let spent: &std::sync::Mutex<Vec<(String, TokenUsage)>> =
+            &std::sync::Mutex::new(Vec::new());
+        let mut retries = Vec::new();
+        let result = call_with_retry(
+            &self.retry,
+            "planner",
+            &chain,
+            move |planner| async move {
+                let result = planner
+                    .generate_plan_json(task_ref, feedback, previous_plan, working_dir_str)
+                    .await;
+                if let Err(e) = &result {
+                    spent.lock().unwrap().extend(SpentUsage::of(e));
+                }
+                result
+            },
+            &mut retries,
+        )
+        .await;
+        if let Ok((_, usage)) = &result
+            && let Some(label) = served_by(&self.retry, &chain, &retries)
         {
+            self.record_model_usage(label, usage).await;
+        }
+        self.record_retries(retries).await;
+        let spent = std::mem::take(&mut *spent.lock().unwrap());
+        self.charge_planner_usage(iteration + 1, &spent).await;
+
+        let (plan_json, token_usage) = match result {
             Ok(v) => v,
             Err(e) => {
                 return CallToolResult::error(vec![Content::text(format!(
@@ -395,6 +873,7 @@ impl OrchestratorClient {
                 .map(|t| t.max(0) as u64)
                 .unwrap_or(0);
             state.token_breakdown.add_planner(input, output);
+            state.add_iteration_tokens(iteration + 1, input + output, 0);
 
             // Regenerate context summary after each iteration for efficient context passing
             state.context_summary = state.generate_context_summary();
@@ -402,6 +881,11 @@ impl OrchestratorClient {
 
         // Persist state after plan generation
         self.persist_state().await;
+        self.publish(OrchestratorEvent::PlanGenerated {
+            iteration: iteration + 1,
+            title: plan_title(&plan_json),
+            candidates: 1,
+        });
 
         // Return plan without validation - all checks happen in review_plan
         let response = serde_json::json!({
@@ -414,6 +898,245 @@ impl OrchestratorClient {
         )])
     }
 
+    /// Best-of-N generate_plan: generate candidates concurrently, screen each
+    /// with auto-fix, viability, and hard checks, review the survivors, and
+    /// keep the top scorer as the current plan.
+    ///
+    /// The winner's review is cached so the following review_plan call
+    /// doesn't pay for it again. Runners-up are saved under
+    /// `<session_dir>/candidates/`.
+    async fn handle_generate_candidates(
+        &self,
+        candidates: &CandidatePlanners,
+        task: &str,
+        feedback: Option<&[String]>,
+        current_plan: Option<&Value>,
+        working_dir: &std::path::Path,
+        iteration: u32,
+    ) -> CallToolResult {
+        let generated = candidates
+            .generate(task, feedback, current_plan, working_dir.to_str())
+            .await;
+
+        let mut planner_usage = TokenUsage::default();
+        let mut outcomes = Vec::new();
+        for (label, result) in generated {
+            match result {
+                Ok((mut plan, usage)) => {
+                    planner_usage.add(&usage);
+                    let model = candidates
+                        .model_label(&label)
+                        .unwrap_or_else(|| self.planner_label.clone());
+                    self.record_model_usage(&model, &usage).await;
+                    let disqualified = self.screen_candidate(&mut plan);
+                    outcomes.push(CandidateOutcome {
+                        label,
+                        plan,
+                        score: None,
+                        disqualified,
+                        review: None,
+                    });
+                }
+                Err(e) => {
+                    tracing::warn!("Candidate {} failed: {:#}", label, e);
+                    for (model, usage) in SpentUsage::of(&e) {
+                        planner_usage.add(&usage);
+                        self.record_model_usage(&model, &usage).await;
+                    }
+                }
+            }
+        }
+        {
+            let mut state = self.state.lock().await;
+            let (input, output) = usage_tokens(&planner_usage);
+            state.add_tokens(planner_usage.input_tokens, planner_usage.output_tokens);
+            state.token_breakdown.add_planner(input, output);
+            state.add_iteration_tokens(iteration + 1, input + output, 0);
+        }
+        if outcomes.is_empty() {
+            self.persist_state().await;
+            return CallToolResult::error(vec![Content::text(format!(
+                "Planner failed: all {} candidates failed",
+                candidates.len()
+            ))]);
+        }
+        // The candidates may have used up the iteration's budget; don't
+        // start the reviews then
+        if let Some(stop) = self.check_token_budget(TokenRole::Reviewer).await {
+            self.persist_state().await;
+            return stop;
+        }
+
+        // Review the survivors concurrently
+        let qualified: Vec<usize> = outcomes
+            .iter()
+            .enumerate()
+            .filter(|(_, o)| o.disqualified.is_none())
+            .map(|(i, _)| i)
+            .collect();
+        let reviews = futures::future::join_all(
+            qualified
+                .iter()
+                .map(|&i| self.run_review(&outcomes[i].plan, iteration + 1)),
+        )
+        .await;
+        let mut reviewer_usage = TokenUsage::default();
+        for (&i, result) in qualified.iter().zip(reviews) {
+            match result {
+                Ok((review, usage)) => {
+                    reviewer_usage.add(&usage);
+                    outcomes[i].score = review
+                        .get("score")
+                        .and_then(|v| v.as_f64())
+                        .map(|v| v as f32);
+                    outcomes[i].review = Some(review);
+                }
+                Err(e) => outcomes[i].disqualified = Some(format!("review failed: {:#}", e)),
+            }
+        }
+
+        // Without a reviewed candidate, fall back to the first one; the normal
+        // review_plan call will report its problems
+        let selected = select_best(&outcomes).unwrap_or(0);
+        let winner = outcomes[selected].clone();
+        info!(
+            "Selected {} of {} candidates (score {:?})",
+            winner.label,
+            outcomes.len(),
+            winner.score
+        );
+
+        {
+            let mut state = self.state.lock().await;
+            state.current_plan = Some(winner.plan.clone());
+            state.iteration = iteration + 1;
+            state.tool_calls += 1;
+            state.add_tokens(reviewer_usage.input_tokens, reviewer_usage.output_tokens);
+            state.needs_review = true;
+            state.cached_review = winner.review.clone().map(|review| CachedReview {
+                plan: winner.plan.clone(),
+                review,
+            });
+
+            let (input, output) = usage_tokens(&reviewer_usage);
+            state.token_breakdown.add_reviewer(input, output);
+            state.add_iteration_tokens(iteration + 1, 0, input + output);
+
+            state.context_summary = state.generate_context_summary();
+        }
+        self.persist_state().await;
+        self.save_runners_up(iteration + 1, &outcomes, selected)
+            .await;
+        self.publish(OrchestratorEvent::PlanGenerated {
+            iteration: iteration + 1,
+            title: plan_title(&winner.plan),
+            candidates: outcomes.len(),
+        });
+
+        let summary: Vec<Value> = outcomes
+            .iter()
+            .enumerate()
+            .map(|(i, o)| {
+                serde_json::json!({
+                    "label": o.label,
+                    "score": o.score,
+                    "disqualified": o.disqualified,
+                    "selected": i == selected,
+                })
+            })
+            .collect();
+        let tokens_used = [&planner_usage, &reviewer_usage]
+            .iter()
+            .map(|u| u.input_tokens.unwrap_or(0) + u.output_tokens.unwrap_or(0))
+            .sum::<i32>();
+        let response = serde_json::json!({
+            "plan": winner.plan,
+            "tokens_used": tokens_used,
+            "selected": winner.label,
+            "candidates": summary,
+            "note": if winner.review.is_some() {
+                "The selected candidate was already reviewed; review_plan reuses that review."
+            } else {
+                "No candidate passed screening; review_plan will report the problems."
+            },
+        });
+
+        CallToolResult::success(vec![Content::text(
+            serde_json::to_string_pretty(&response).unwrap_or_else(|_| response.to_string()),
+        )])
+    }
+
+    /// Apply auto-fixes and run viability and hard checks on a candidate.
+    /// Returns the reason it is disqualified, if any.
+    fn screen_candidate(&self, plan_json: &mut Value) -> Option<String> {
+        if let Some(fixer) = &self.auto_fixer
+            && let Err(e) = fixer.fix_plan_json(plan_json)
+        {
+            tracing::warn!("Auto-fix skipped for candidate: {}", e);
+        }
+        let plan = match serde_json::from_value::<Plan>(plan_json.clone()) {
+            Ok(plan) => plan,
+            Err(e) => return Some(format!("plan does not match the schema: {}", e)),
+        };
+
+        let critical: Vec<_> = self
+            .viability
+            .check_plan(&plan)
+            .violations
+            .into_iter()
+            .filter(|v| v.severity == ViabilitySeverity::Critical)
+            .collect();
+        if let Some(first) = critical.first() {
+            return Some(format!(
+                "{} critical viability violation(s), e.g. {}: {}",
+                critical.len(),
+                first.rule_id,
+                first.message
+            ));
+        }
+
+        HardChecklist::default()
+            .run_all(&plan)
+            .into_iter()
+            .find(|r| !r.passed && r.severity == crate::models::Severity::Error)
+            .map(|r| format!("hard check {} failed: {}", r.check_name, r.message))
+    }
+
+    /// Save every candidate except the selected one to the session directory.
+    async fn save_runners_up(
+        &self,
+        iteration: u32,
+        outcomes: &[CandidateOutcome],
+        selected: usize,
+    ) {
+        let dir = self.session_dir.join("candidates");
+        for (i, outcome) in outcomes.iter().enumerate() {
+            if i == selected {
+                continue;
+            }
+            let name: String = outcome
+                .label
+                .chars()
+                .map(|c| {
+                    if c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_') {
+                        c
+                    } else {
+                        '_'
+                    }
+                })
+                .collect();
+            let path = dir.join(format!("iteration-{}-{}.json", iteration, name));
+            let json = serde_json::to_string_pretty(outcome).unwrap_or_default();
+            let result = match tokio::fs::create_dir_all(&dir).await {
+                Ok(()) => write_atomic_async(&path, json).await,
+                Err(e) => Err(e),
+            };
+            if let Err(e) = result {
+                tracing::warn!("Failed to save candidate {:?}: {}", path, e);
+            }
+        }
+    }
+
     /// Handle review_plan tool call.
     /// Runs V-* viability checks first (deterministic), then Q-* quality checks (LLM).
     /// If V-* fails, skips expensive LLM review.
@@ -434,7 +1157,7 @@ impl OrchestratorClient {
         }
 
         // 2. Extract plan from arguments
-        let input: ReviewPlanInput = match arguments {
+        let mut input: ReviewPlanInput = match arguments {
             Some(args) => match serde_json::from_value(Value::Object(args)) {
                 Ok(v) => v,
                 Err(e) => {
@@ -455,16 +1178,34 @@ impl OrchestratorClient {
             (state.iteration, state.tool_calls, state.total_tokens)
         };
 
+        // 3b. Apply mechanical fixes so they don't cost a planner round-trip
+        let auto_fixes = match &self.auto_fixer {
+            Some(fixer) => match fixer.fix_plan_json(&mut input.plan_json) {
+                Ok(fixes) => fixes,
+                Err(e) => {
+                    tracing::warn!("Auto-fix skipped: {}", e);
+                    Vec::new()
+                }
+            },
+            None => Vec::new(),
+        };
+        if !auto_fixes.is_empty() {
+            info!("Auto-fixed {} viability issue(s)", auto_fixes.len());
+            let mut state = self.state.lock().await;
+            state.current_plan = Some(input.plan_json.clone());
+        }
+        let auto_fix_json = (!auto_fixes.is_empty()).then(|| {
+            serde_json::json!({
+                "applied": auto_fixes,
+                "plan_json": input.plan_json,
+            })
+        });
+
         // 4. Run V-* viability checks FIRST (deterministic, cheap)
         let viability_result = match serde_json::from_value::<Plan>(input.plan_json.clone()) {
             Ok(plan) => {
-                let checker = ViabilityChecker::new();
-                let result = checker.check_all(
-                    plan.instructions.as_deref(),
-                    plan.grounding_snapshot.as_ref(),
-                    Some(&plan.file_references),
-                );
-                let metrics = plan.instructions.as_ref().map(|i| analyze_dag(i));
+                let result = self.viability.check_plan(&plan);
+                let metrics = result.metrics.clone();
 
                 // Determine if validation passed (no Critical-severity violations)
                 let passed = result
@@ -476,6 +1217,18 @@ impl OrchestratorClient {
             }
             Err(_) => None, // Plan doesn't parse - skip viability checks
         };
+        if let Some((viability, _, passed)) = &viability_result {
+            self.publish(OrchestratorEvent::ViabilityResult {
+                iteration,
+                passed: *passed,
+                violations: viability.violations.len() as u32,
+                critical: viability
+                    .violations
+                    .iter()
+                    .filter(|v| v.severity == ViabilitySeverity::Critical)
+                    .count() as u32,
+            });
+        }
 
         // 5. If V-* critical failures, skip expensive LLM review
         if let Some((ref viability, ref metrics, passed)) = viability_result
@@ -488,9 +1241,17 @@ impl OrchestratorClient {
                 .iter()
                 .filter(|v| v.severity == ViabilitySeverity::Critical)
                 .count() as u32;
+            let mut critical_rule_ids: Vec<String> = viability
+                .violations
+                .iter()
+                .filter(|v| v.severity == ViabilitySeverity::Critical)
+                .map(|v| v.rule_id.clone())
+                .collect();
+            critical_rule_ids.sort();
+            critical_rule_ids.dedup();
 
             // Update state with viability failure (short lock)
-            {
+            let (escalation, stagnation) = {
                 let mut state = self.state.lock().await;
                 state.tool_calls += 1;
                 // Reset validation flags - viability failed means review not passed
@@ -511,10 +1272,21 @@ impl OrchestratorClient {
                     review_passed: None,
                     tool_calls_this_iteration: state.tool_calls - starting_tool_calls,
                     tokens_this_iteration: state.total_tokens - starting_tokens,
+                    reviewer_scores: Vec::new(),
+                    critical_rule_ids,
+                    rung: state.model_rung,
+                    role_tokens: Some(state.role_tokens_for(iteration)),
                     outcome: IterationOutcome::ViabilityFailed,
                 };
                 state.iteration_history.push(record);
-            }
+                let escalation = self.escalate(&mut state);
+                let stagnation = if escalation.is_none() {
+                    self.apply_stagnation(&mut state)
+                } else {
+                    None
+                };
+                (escalation, stagnation)
+            };
             self.persist_state().await;
 
             let response = serde_json::json!({
@@ -523,9 +1295,12 @@ impl OrchestratorClient {
                     "metrics": metrics,
                     "passed": false
                 },
+                "auto_fix": auto_fix_json,
                 "llm_review": null,  // Skipped - plan not viable
                 "passed": false,
                 "requires_human_input": false,
+                "escalation": escalation,
+                "stagnation": stagnation,
                 "summary": "Plan failed viability checks. Fix violations before review."
             });
 
@@ -534,17 +1309,35 @@ impl OrchestratorClient {
             )]);
         }
 
-        // 6. Run LLM review (expensive) only if plan is viable
-        let (review_json, token_usage) =
-            match self.reviewer.review_plan_json(&input.plan_json).await {
-                Ok(v) => v,
-                Err(e) => {
-                    return CallToolResult::error(vec![Content::text(format!(
-                        "Reviewer failed: {}",
-                        e
-                    ))]);
+        // 6. Run LLM review (expensive) only if plan is viable, reusing the
+        // review from candidate selection when the plan is unchanged
+        let cached_review = {
+            let mut state = self.state.lock().await;
+            state
+                .cached_review
+                .take()
+                .filter(|cached| cached.plan == input.plan_json)
+        };
+        let (review_json, token_usage) = match cached_review {
+            Some(cached) => {
+                info!("Reusing candidate selection review");
+                (cached.review, TokenUsage::default())
+            }
+            None => {
+                if let Some(stop) = self.check_token_budget(TokenRole::Reviewer).await {
+                    return stop;
                 }
-            };
+                match self.run_review(&input.plan_json, iteration).await {
+                    Ok(v) => v,
+                    Err(e) => {
+                        return CallToolResult::error(vec![Content::text(format!(
+                            "Reviewer failed: {:#}",
+                            e
+                        ))]);
+                    }
+                }
+            }
+        };
 
         // 7. Extract score and check if passed DETERMINISTICALLY
         let score = review_json
@@ -552,9 +1345,27 @@ impl OrchestratorClient {
             .and_then(|v| v.as_f64())
             .unwrap_or(0.0) as f32;
 
+        // Dimension minimums are checked separately from the weighted score
+        let dimensions: Vec<DimensionScore> = review_json
+            .get("dimensions")
+            .and_then(|d| serde_json::from_value(d.clone()).ok())
+            .unwrap_or_default();
+        let failed_dimensions: Vec<String> = dimensions
+            .iter()
+            .filter(|d| !d.passed)
+            .map(|d| d.name.clone())
+            .collect();
+
         // CRITICAL: Use deterministic score threshold check, not LLM's "passed" field
         // The LLM reviewer's "passed" field is informational only - we enforce the threshold
-        let score_passed = self.guardrails.score_passes(score);
+        // and every dimension minimum
+        let score_passed = self.guardrails.score_passes(score) && failed_dimensions.is_empty();
+        self.publish(OrchestratorEvent::ReviewScored {
+            iteration,
+            score,
+            threshold: self.guardrails.score_threshold,
+            passed: score_passed,
+        });
 
         // 8. Build response with viability + LLM review
         // Human input requirement only comes from reviewer LLM (security, ambiguity, etc.)
@@ -568,7 +1379,8 @@ impl OrchestratorClient {
             .get("passed")
             .and_then(|v| v.as_bool())
             .unwrap_or(false);
-        if llm_passed != score_passed {
+        // (dimension passes change the score, so only compare plain reviews)
+        if dimensions.is_empty() && llm_passed != score_passed {
             tracing::warn!(
                 "LLM reviewer passed={} but score {} {} threshold {} (using deterministic check)",
                 llm_passed,
@@ -578,22 +1390,39 @@ impl OrchestratorClient {
             );
         }
 
-        let response = serde_json::json!({
+        let mut response = serde_json::json!({
             "viability": viability_result.as_ref().map(|(v, m, p)| serde_json::json!({
                 "violations": v.violations,
                 "metrics": m,
                 "passed": p
             })),
+            "auto_fix": auto_fix_json,
             "llm_review": review_json,
             "passed": score_passed,  // Deterministic: score >= threshold
             "score": score,
             "threshold": self.guardrails.score_threshold,
             "requires_human_input": requires_human_input,
-            "summary": review_json.get("summary").and_then(|v| v.as_str()).unwrap_or("Review complete"),
+            "dimensions": dimensions,
+            "failed_dimensions": failed_dimensions,
+            "summary": if failed_dimensions.is_empty() {
+                review_json.get("summary").and_then(|v| v.as_str()).unwrap_or("Review complete").to_string()
+            } else {
+                format!(
+                    "Review score: {:.2} - NEEDS REVISION: below minimum on {}",
+                    score,
+                    failed_dimensions.join(", ")
+                )
+            },
         });
 
+        // Per-reviewer scores (only present for panel reviews)
+        let reviewer_scores: Vec<ReviewerScore> = review_json
+            .get("panel")
+            .and_then(|p| serde_json::from_value(p.clone()).ok())
+            .unwrap_or_default();
+
         // 9. Update state (short lock)
-        {
+        let (escalation, stagnation) = {
             let mut state = self.state.lock().await;
             state.reviews.push(review_json);
             state.tool_calls += 1;
@@ -616,6 +1445,7 @@ impl OrchestratorClient {
                 .map(|t| t.max(0) as u64)
                 .unwrap_or(0);
             state.token_breakdown.add_reviewer(input, output);
+            state.add_iteration_tokens(iteration, 0, input + output);
 
             // Track best plan (highest score seen)
             if score > state.best_score {
@@ -649,6 +1479,10 @@ impl OrchestratorClient {
                 review_passed: Some(score_passed), // Deterministic check
                 tool_calls_this_iteration: state.tool_calls - starting_tool_calls,
                 tokens_this_iteration: state.total_tokens - starting_tokens,
+                reviewer_scores,
+                critical_rule_ids: Vec::new(),
+                rung: state.model_rung,
+                role_tokens: Some(state.role_tokens_for(iteration)),
                 outcome: if requires_human_input {
                     IterationOutcome::HumanInputRequested
                 } else if score_passed {
@@ -661,6 +1495,24 @@ impl OrchestratorClient {
 
             // Regenerate context summary with latest review data
             state.context_summary = state.generate_context_summary();
+
+            // A reviewer-requested pause takes precedence; a stronger model
+            // gets a chance before stagnation is declared
+            if score_passed || requires_human_input {
+                (None, None)
+            } else if let Some(escalation) = self.escalate(&mut state) {
+                (Some(escalation), None)
+            } else {
+                (None, self.apply_stagnation(&mut state))
+            }
+        };
+        if let Some(obj) = response.as_object_mut() {
+            if let Some(escalation) = escalation {
+                obj.insert("escalation".to_string(), escalation);
+            }
+            if let Some(stagnation) = stagnation {
+                obj.insert("stagnation".to_string(), stagnation);
+            }
         }
 
         // Persist state after review
@@ -671,6 +1523,251 @@ impl OrchestratorClient {
         )])
     }
 
+    /// Run the configured review (single reviewer or panel) and any
+    /// specialist dimension passes concurrently.
+    ///
+    /// With dimensions, `score` in the returned review is the weighted
+    /// combined score and the main review's score is kept as `primary_score`.
+    async fn run_review(&self, plan_json: &Value, iteration: u32) -> Result<(Value, TokenUsage)> {
+        let rung = self.state.lock().await.model_rung;
+        let primary = match self.reviewer_ladder.as_ref().and_then(|l| l.get(rung)) {
+            Some((label, reviewer)) => (label.to_string(), reviewer.clone()),
+            None => (self.reviewer_label.clone(), self.reviewer.clone()),
+        };
+        let mut chain = vec![primary];
+        chain.extend(self.fallback_reviewers.iter().cloned());
+
+        // Usage of failed attempts, which callers never see; charged below
+        let spent: &std::sync::Mutex<Vec<(String, TokenUsage)>> =
+            &std::sync::Mutex::new(Vec::new());
+
+        // Panels and specialist passes are retried as a unit (no fallbacks)
+        let mut main_retries = Vec::new();
+        let mut specialist_retries = Vec::new();
+        let main_review = async {
+            match &self.review_panel {
+                Some(panel) => {
+                    call_with_retry(
+                        &self.retry,
+                        "reviewer",
+                        &[("panel".to_string(), panel.clone())],
+                        move |panel| async move {
+                            let result = panel.review_plan_json(plan_json).await;
+                            if let Err(e) = &result {
+                                spent.lock().unwrap().extend(SpentUsage::of(e));
+                            }
+                            result
+                        },
+                        &mut main_retries,
+                    )
+                    .await
+                }
+                None => {
+                    let result = call_with_retry(
+                        &self.retry,
+                        "reviewer",
+                        &chain,
+                        move |reviewer| async move {
+                            let result = reviewer.review_plan_json(plan_json).await;
+                            if let Err(e) = &result {
+                                spent.lock().unwrap().extend(SpentUsage::of(e));
+                            }
+                            result
+                        },
+                        &mut main_retries,
+                    )
+                    .await;
+                    let label = served_by(&self.retry, &chain, &main_retries)
+                        .unwrap_or(chain[0].0.as_str())
+                        .to_string();
+                    result.map(|(review, usage)| (review, vec![(label, usage)]))
+                }
+            }
+        };
+        let specialist_reviews = async {
+            match &self.specialists {
+                Some(specialists) => call_with_retry(
+                    &self.retry,
+                    "reviewer",
+                    &[("specialists".to_string(), specialists.clone())],
+                    move |specialists| async move {
+                        let result = specialists.review_plan_json(plan_json).await;
+                        if let Err(e) = &result {
+                            spent.lock().unwrap().extend(SpentUsage::of(e));
+                        }
+                        result
+                    },
+                    &mut specialist_retries,
+                )
+                .await
+                .map(Some),
+                None => Ok(None),
+            }
+        };
+        let (review, specialist_review) = tokio::join!(main_review, specialist_reviews);
+
+        main_retries.extend(specialist_retries);
+        self.record_retries(main_retries).await;
+
+        // If either side failed, what the other side spent is charged here
+        // along with the failed attempts
+        let mut spent = std::mem::take(&mut *spent.lock().unwrap());
+        let returned = match (&review, &specialist_review) {
+            (Ok((_, main)), Ok(specialists)) => {
+                let mut usage = main.clone();
+                if let Some((_, specialist_usage)) = specialists {
+                    usage.extend(specialist_usage.iter().cloned());
+                }
+                usage
+            }
+            (main, specialists) => {
+                if let Ok((_, usage)) = main {
+                    spent.extend(usage.iter().cloned());
+                }
+                if let Ok(Some((_, usage))) = specialists {
+                    spent.extend(usage.iter().cloned());
+                }
+                Vec::new()
+            }
+        };
+        self.charge_reviewer_usage(iteration, &spent).await;
+        // Each reviewer, panel member, and specialist pass is priced as its
+        // own model
+        self.record_models_usage(&returned).await;
+
+        let (mut review_json, usage) = review?;
+        let mut token_usage = total_usage(&usage);
+
+        if let Some(specialists) = &self.specialists
+            && let Some((dimensions, usage)) = specialist_review?
+        {
+            token_usage.add(&total_usage(&usage));
+            let primary_score = review_json
+                .get("score")
+                .and_then(|v| v.as_f64())
+                .unwrap_or(0.0) as f32;
+            let score = combined_score(primary_score, specialists.primary_weight(), &dimensions);
+            if let Some(obj) = review_json.as_object_mut() {
+                obj.insert(
+                    "primary_score".to_string(),
+                    serde_json::json!(primary_score),
+                );
+                obj.insert("score".to_string(), serde_json::json!(score));
+                obj.insert("dimensions".to_string(), serde_json::json!(dimensions));
+            }
+        }
+
+        Ok((review_json, token_usage))
+    }
+
+    /// Charge planner usage that isn't returned to the caller (failed
+    /// or cancelled attempts) to the session, its models, and `iteration`.
+    async fn charge_planner_usage(&self, iteration: u32, usage: &[(String, TokenUsage)]) {
+        if usage.is_empty() {
+            return;
+        }
+        self.record_models_usage(usage).await;
+        let total = total_usage(usage);
+        let (input, output) = usage_tokens(&total);
+        let mut state = self.state.lock().await;
+        state.add_tokens(total.input_tokens, total.output_tokens);
+        state.token_breakdown.add_planner(input, output);
+        state.add_iteration_tokens(iteration, input + output, 0);
+    }
+
+    /// Charge reviewer usage that isn't returned to the caller (failed
+    /// attempts) to the session, its models, and `iteration`.
+    async fn charge_reviewer_usage(&self, iteration: u32, usage: &[(String, TokenUsage)]) {
+        if usage.is_empty() {
+            return;
+        }
+        self.record_models_usage(usage).await;
+        let total = total_usage(usage);
+        let (input, output) = usage_tokens(&total);
+        let mut state = self.state.lock().await;
+        state.add_tokens(total.input_tokens, total.output_tokens);
+        state.token_breakdown.add_reviewer(input, output);
+        state.add_iteration_tokens(iteration, 0, input + output);
+    }
+
+    /// Move the session up one rung of the planner ladder when the current
+    /// rung keeps failing viability or its review scores stall.
+    ///
+    /// Returns the response fragment when the rung changes.
+    fn escalate(&self, state: &mut OrchestrationState) -> Option<Value> {
+        let ladder = self.planner_ladder.as_ref()?;
+        if state.model_rung as usize + 1 >= ladder.len() {
+            return None;
+        }
+        let reason = escalation_reason(
+            &state.iteration_history,
+            state.model_rung,
+            self.escalate_after_viability_failures,
+            self.escalate_after_stalled_reviews,
+            self.guardrails.stagnation.epsilon,
+        )?;
+
+        let from = state.model_rung;
+        state.model_rung += 1;
+        let (label, _) = ladder.get(state.model_rung)?;
+        info!(
+            "Escalating planner from rung {} to rung {} ({}): {}",
+            from, state.model_rung, label, reason
+        );
+        Some(serde_json::json!({
+            "from_rung": from,
+            "to_rung": state.model_rung,
+            "planner": label,
+            "reason": reason,
+        }))
+    }
+
+    /// Apply the stagnation guardrail after a failed review.
+    ///
+    /// Ends the session best-effort or pauses for human input with a
+    /// generated explanation, depending on config. Returns the response
+    /// fragment when stagnation is detected.
+    fn apply_stagnation(&self, state: &mut OrchestrationState) -> Option<Value> {
+        let stagnation = self.guardrails.check_stagnation(state)?;
+        let explanation = stagnation.explanation();
+        tracing::warn!("Stagnation detected: {}", explanation);
+
+        let action = match self.guardrails.stagnation.action {
+            StagnationAction::BestEffort => {
+                state.status = OrchestrationStatus::CompletedBestEffort;
+                "best_effort"
+            }
+            StagnationAction::Pause => {
+                let reason = format!("{}: {}", STAGNATION_INPUT_CATEGORY, explanation);
+                let question = format!(
+                    "{} Best score so far: {:.2}. What should change to make progress?",
+                    explanation, state.best_score
+                );
+                self.publish(OrchestratorEvent::HumanInputRequested {
+                    question: question.clone(),
+                    category: STAGNATION_INPUT_CATEGORY.to_string(),
+                });
+                state.pending_human_input = Some(HumanInputRecord {
+                    question,
+                    category: STAGNATION_INPUT_CATEGORY.to_string(),
+                    response: None,
+                    reason: Some(reason.clone()),
+                    iteration: state.iteration,
+                    timestamp: chrono::Utc::now().to_rfc3339(),
+                    approved: false,
+                });
+                state.status = OrchestrationStatus::Paused { reason };
+                "pause"
+            }
+        };
+
+        Some(serde_json::json!({
+            "detected": stagnation,
+            "explanation": explanation,
+            "action": action,
+        }))
+    }
+
     /// Handle request_human_input tool call.
     /// Validates that the reviewer authorized this pause via requires_human_input=true.
     async fn handle_request_human_input(&self, arguments: Option<JsonObject>) -> CallToolResult {
@@ -753,6 +1850,10 @@ impl OrchestratorClient {
 
         // Persist state after requesting human input
         self.persist_state().await;
+        self.publish(OrchestratorEvent::HumanInputRequested {
+            question: input.question.clone(),
+            category: input.category.clone(),
+        });
 
         CallToolResult::success(vec![Content::text(
             serde_json::to_string_pretty(&response).unwrap_or_else(|_| response.to_string()),
@@ -803,6 +1904,46 @@ impl OrchestratorClient {
             )]);
         }
 
+        // 2. Phase 0.0: executable grounding gates must not fail
+        let gate_report = match (&self.gate_runner, input.plan_json.get("grounding_gates")) {
+            (Some(runner), Some(gates)) => {
+                match serde_json::from_value::<Vec<GroundingGate>>(gates.clone()) {
+                    Ok(gates) => Some(runner.run(&gates).await),
+                    Err(e) => {
+                        tracing::warn!("Skipping grounding gates: {}", e);
+                        None
+                    }
+                }
+            }
+            _ => None,
+        };
+        if let Some(report) = &gate_report
+            && !report.passed()
+        {
+            let failed: Vec<&str> = report.failed().map(|r| r.id.as_str()).collect();
+            tracing::warn!("Grounding gates failed: {}", failed.join(", "));
+
+            {
+                let mut state = self.state.lock().await;
+                state.tool_calls += 1;
+                // Plan must be revised and re-reviewed before finalizing
+                state.last_review_passed = false;
+            }
+            self.persist_state().await;
+
+            let response = serde_json::json!({
+                "status": "rejected",
+                "error": "GROUNDING_GATES_FAILED",
+                "message": format!("Cannot finalize - grounding gate(s) failed: {}", failed.join(", ")),
+                "required_action": "Call generate_plan with the gate results as feedback so the plan matches the repository, then call review_plan.",
+                "gates": report,
+            });
+
+            return CallToolResult::success(vec![Content::text(
+                serde_json::to_string_pretty(&response).unwrap_or_else(|_| response.to_string()),
+            )]);
+        }
+
         // Update state to completed (short lock)
         {
             let mut state = self.state.lock().await;
@@ -817,6 +1958,7 @@ impl OrchestratorClient {
         let response = serde_json::json!({
             "success": true,
             "message": "Plan finalized successfully",
+            "gates": gate_report,
         });
 
         CallToolResult::success(vec![Content::text(
@@ -829,23 +1971,46 @@ impl OrchestratorClient {
     /// Returns current iteration state including the task for context recovery
     /// in case of conversation compaction.
     async fn handle_check_limits(&self) -> CallToolResult {
-        let (iterations, tool_calls, total_tokens, task) = {
+        let (
+            iterations,
+            tool_calls,
+            total_tokens,
+            task,
+            elapsed_secs,
+            cost,
+            breakdown,
+            current,
+            forecast,
+        ) = {
             let state = self.state.lock().await;
             (
                 state.iteration,
                 state.tool_calls,
                 state.total_tokens,
                 state.task.clone(),
+                Guardrails::elapsed_secs(&state),
+                self.guardrails.session_cost(&state),
+                state.token_breakdown.clone(),
+                state.iteration_tokens.clone(),
+                self.forecast_limits(&state),
             )
         };
 
         let max_iterations = self.guardrails.max_iterations;
         let max_tool_calls = self.guardrails.max_tool_calls;
         let max_total_tokens = self.guardrails.max_total_tokens;
+        let max_session_secs = self.guardrails.max_session_duration.map(|d| d.as_secs());
+        let max_cost_usd = self.guardrails.max_cost_usd;
 
+        let time_exceeded = max_session_secs.is_some_and(|limit| elapsed_secs >= limit);
+        // With a cost cap, unpriced usage fails closed
+        let cost_unknown = max_cost_usd.is_some() && !cost.unpriced_models.is_empty();
+        let cost_exceeded = cost_unknown || max_cost_usd.is_some_and(|limit| cost.usd >= limit);
         let exceeded = iterations >= max_iterations
             || tool_calls >= max_tool_calls
-            || total_tokens >= max_total_tokens;
+            || total_tokens >= max_total_tokens
+            || time_exceeded
+            || cost_exceeded;
 
         let exceeded_reason = if iterations >= max_iterations {
             Some(format!(
@@ -862,6 +2027,23 @@ impl OrchestratorClient {
                 "Token budget exhausted: {} >= {}",
                 total_tokens, max_total_tokens
             ))
+        } else if time_exceeded {
+            Some(format!(
+                "Session time limit reached: {}s >= {}s",
+                elapsed_secs,
+                max_session_secs.unwrap_or_default()
+            ))
+        } else if cost_unknown {
+            Some(format!(
+                "Cost budget cannot be enforced: no price for {}",
+                cost.unpriced_models.join(", ")
+            ))
+        } else if cost_exceeded {
+            Some(format!(
+                "Cost budget exhausted: ${:.2} >= ${:.2}",
+                cost.usd,
+                max_cost_usd.unwrap_or_default()
+            ))
         } else {
             None
         };
@@ -871,14 +2053,29 @@ impl OrchestratorClient {
             "iterations": iterations,
             "tool_calls": tool_calls,
             "total_tokens": total_tokens,
+            "elapsed_secs": elapsed_secs,
+            "cost_usd": cost.usd,
+            "unpriced_models": cost.unpriced_models,
+            "role_tokens": {
+                "orchestrator": breakdown.role_total(TokenRole::Orchestrator),
+                "planner": breakdown.role_total(TokenRole::Planner),
+                "reviewer": breakdown.role_total(TokenRole::Reviewer),
+            },
+            "iteration_tokens": current,
+            "forecast": forecast,
             "limits": {
                 "max_iterations": max_iterations,
                 "max_tool_calls": max_tool_calls,
                 "max_total_tokens": max_total_tokens,
+                "max_session_secs": max_session_secs,
+                "max_cost_usd": max_cost_usd,
+                "token_budgets": self.guardrails.token_budgets,
             },
             "exceeded": exceeded,
             "exceeded_reason": exceeded_reason,
             "token_budget_remaining": max_total_tokens.saturating_sub(total_tokens),
+            "time_remaining_secs": max_session_secs.map(|limit| limit.saturating_sub(elapsed_secs)),
+            "cost_budget_remaining_usd": max_cost_usd.map(|limit| (limit - cost.usd).max(0.0)),
         });
 
         CallToolResult::success(vec![Content::text(
@@ -930,16 +2127,7 @@ impl goose::agents::mcp_client::McpClientTrait for OrchestratorClient {
     ) -> Result<CallToolResult, Error> {
         // Note: ExtensionManager strips the prefix before calling,
         // so we match on unprefixed tool names
-        let result = match name {
-            "generate_plan" => self.handle_generate_plan(arguments).await,
-            "review_plan" => self.handle_review_plan(arguments).await,
-            "request_human_input" => self.handle_request_human_input(arguments).await,
-            "finalize" => self.handle_finalize(arguments).await,
-            "check_limits" => self.handle_check_limits().await,
-            _ => CallToolResult::error(vec![Content::text(format!("Unknown tool: {}", name))]),
-        };
-
-        Ok(result)
+        Ok(self.dispatch(name, arguments).await)
     }
 
     async fn list_prompts(
@@ -1015,6 +2203,14 @@ pub async fn register_orchestrator_extension(
         .await;
 }
 
+/// Title of a generated plan, for progress events
+fn plan_title(plan_json: &Value) -> Option<String> {
+    plan_json
+        .get("title")
+        .and_then(|t| t.as_str())
+        .map(String::from)
+}
+
 /// Factory function for creating session-scoped OrchestratorClient.
 pub fn create_orchestrator_client(
     session_id: String,
@@ -1037,6 +2233,7 @@ pub fn create_orchestrator_client(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::phases::retry::testing::{ScriptedProvider, write_recipe};
     use std::path::PathBuf;
 
     #[test]
@@ -1275,4 +2472,246 @@ mod tests {
         assert_eq!(input.task, "Create a new feature");
         assert!(input.feedback.is_none());
     }
+
+    fn make_budget_client(session_dir: &std::path::Path) -> OrchestratorClient {
+        let mut state = OrchestrationState::new(
+            "budget".to_string(),
+            "test task".to_string(),
+            PathBuf::from("/test"),
+            "budget".to_string(),
+        );
+        // Two recorded iterations of 30k tokens, 90k of 100k spent
+        state.iteration = 2;
+        state.total_tokens = 90_000;
+        for iteration in 1..=2 {
+            state.iteration_history.push(IterationRecord {
+                iteration,
+                timestamp: String::new(),
+                viability_violations: 0,
+                viability_critical: 0,
+                viability_passed: true,
+                review_score: Some(0.5),
+                review_passed: Some(false),
+                tool_calls_this_iteration: 2,
+                tokens_this_iteration: 10_000,
+                outcome: IterationOutcome::ReviewFailed,
+                reviewer_scores: Vec::new(),
+                critical_rule_ids: Vec::new(),
+                rung: 0,
+                role_tokens: Some(crate::orchestrator::RoleTokenCounts {
+                    orchestrator: 0,
+                    planner: 20_000,
+                    reviewer: 10_000,
+                }),
+            });
+        }
+        let guardrails = Guardrails {
+            max_total_tokens: 100_000,
+            ..Default::default()
+        };
+        let config = crate::config::Config::default();
+        let base_dir = session_dir.to_path_buf();
+        OrchestratorClient::new(
+            "budget".to_string(),
+            session_dir.to_path_buf(),
+            Arc::new(Mutex::new(state)),
+            Arc::new(guardrails),
+            Arc::new(GoosePlanner::new(config.planning, base_dir.clone())),
+            Arc::new(GooseReviewer::new(config.review, base_dir, 0.8)),
+        )
+    }
+
+    #[test]
+    fn test_spent_usage_survives_context() {
+        let error = anyhow::anyhow!("provider down").context(SpentUsage {
+            message: "Panel reviewer 'b' failed".to_string(),
+            usage: vec![(
+                "anthropic/a".to_string(),
+                TokenUsage::new(Some(10), Some(5)),
+            )],
+        });
+        let error = error.context("Reviewer failed");
+
+        let spent = SpentUsage::of(&error);
+        assert_eq!(spent.len(), 1);
+        assert_eq!(spent[0].1.input_tokens, Some(10));
+        assert!(format!("{:#}", error).contains("Panel reviewer 'b' failed: provider down"));
+        assert!(SpentUsage::of(&anyhow::anyhow!("no usage")).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_charge_reviewer_usage() {
+        let dir = tempfile::tempdir().unwrap();
+        let client = make_budget_client(dir.path());
+
+        client
+            .charge_reviewer_usage(
+                3,
+                &[(
+                    "anthropic/a".to_string(),
+                    TokenUsage::new(Some(700), Some(300)),
+                )],
+            )
+            .await;
+
+        let state = client.state.lock().await;
+        assert_eq!(state.total_tokens, 91_000);
+        assert_eq!(state.token_breakdown.reviewer_input, 700);
+        assert_eq!(state.token_breakdown.reviewer_output, 300);
+        assert_eq!(state.iteration_tokens.iteration, 3);
+        assert_eq!(state.iteration_tokens.reviewer, 1_000);
+    }
+
+    #[tokio::test]
+    async fn test_pause_for_budget() {
+        let dir = tempfile::tempdir().unwrap();
+        let client = make_budget_client(dir.path());
+
+        let paused = client.pause_for_budget().await.expect("budget pause");
+        assert_ne!(paused.is_error, Some(true));
+        {
+            let mut state = client.state.lock().await;
+            match &state.status {
+                OrchestrationStatus::Paused { reason } => assert!(reason.starts_with("budget:")),
+                other => panic!("unexpected status {:?}", other),
+            }
+            let mut input = state.pending_human_input.take().expect("pending input");
+            assert_eq!(input.category, BUDGET_INPUT_CATEGORY);
+            assert!(input.question.contains("30000 tokens"));
+
+            // "Continue anyway" for this iteration
+            input.response = Some("continue anyway".to_string());
+            state.human_inputs.push(input);
+            state.status = OrchestrationStatus::Running;
+        }
+        assert!(dir.path().join("orchestration-state.json").exists());
+
+        // An answered pause isn't raised again for the same iteration
+        assert!(client.pause_for_budget().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_charge_planner_usage() {
+        let dir = tempfile::tempdir().unwrap();
+        let client = make_budget_client(dir.path());
+
+        client
+            .charge_planner_usage(
+                3,
+                &[(
+                    "anthropic/a".to_string(),
+                    TokenUsage::new(Some(700), Some(300)),
+                )],
+            )
+            .await;
+
+        let state = client.state.lock().await;
+        assert_eq!(state.total_tokens, 91_000);
+        assert_eq!(state.token_breakdown.planner_input, 700);
+        assert_eq!(state.token_breakdown.planner_output, 300);
+        assert_eq!(state.iteration_tokens.iteration, 3);
+        assert_eq!(state.iteration_tokens.planner, 1_000);
+    }
+
+    #[tokio::test]
+    async fn test_dispatch_when_cancelled_saves_state() {
+        let dir = tempfile::tempdir().unwrap();
+        let cancel_token = CancellationToken::new();
+        let client = make_budget_client(dir.path()).with_cancel_token(cancel_token.clone());
+        cancel_token.cancel();
+
+        let result = client.dispatch("generate_plan", None).await;
+
+        assert_eq!(result.is_error, Some(true));
+        let text = result.content[0].raw.as_text().unwrap().text.clone();
+        assert!(text.contains("Session cancelled"));
+        let state = OrchestrationState::load(dir.path()).unwrap().unwrap();
+        assert!(matches!(state.status, OrchestrationStatus::Cancelled));
+        assert!(state.can_resume());
+    }
+
+    #[tokio::test]
+    async fn test_tools_publish_progress_events() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut config = crate::config::Config::default();
+        config.planning.recipe = write_recipe(dir.path());
+        config.review.recipe = config.planning.recipe.clone();
+        let plan = serde_json::to_value(Plan::new(
+            "Add caching".to_string(),
+            "Cache repository lookups".to_string(),
+            crate::models::PlanTier::Quick,
+        ))
+        .unwrap()
+        .to_string();
+        let planner = GoosePlanner::new(config.planning, dir.path().to_path_buf()).with_provider(
+            Arc::new(ScriptedProvider::new("planner", vec![Ok(plan.as_str())])),
+        );
+        let reviewer = GooseReviewer::new(config.review, dir.path().to_path_buf(), 0.8)
+            .with_provider(Arc::new(ScriptedProvider::new(
+                "reviewer",
+                vec![Ok(r#"{"overall_assessment": "good", "score": 0.9}"#)],
+            )));
+        let state = OrchestrationState::new(
+            "events".to_string(),
+            "Add caching".to_string(),
+            dir.path().to_path_buf(),
+            "events".to_string(),
+        );
+        let (events, mut receiver) = broadcast::channel(16);
+        let client = OrchestratorClient::new(
+            "events".to_string(),
+            dir.path().to_path_buf(),
+            Arc::new(Mutex::new(state)),
+            Arc::new(Guardrails::default()),
+            Arc::new(planner),
+            Arc::new(reviewer),
+        )
+        .with_events(events);
+
+        let generated = client
+            .invoke(
+                "generate_plan",
+                serde_json::json!({ "task": "Add caching" }),
+            )
+            .await
+            .unwrap();
+        client
+            .invoke(
+                "review_plan",
+                serde_json::json!({ "plan_json": generated["plan"] }),
+            )
+            .await
+            .unwrap();
+
+        let mut published = Vec::new();
+        while let Ok(event) = receiver.try_recv() {
+            published.push(event);
+        }
+        assert_eq!(published.len(), 4, "{:?}", published);
+        assert!(matches!(
+            published[0],
+            OrchestratorEvent::IterationStarted { iteration: 1 }
+        ));
+        assert!(matches!(
+            &published[1],
+            OrchestratorEvent::PlanGenerated { iteration: 1, title: Some(title), candidates: 1 }
+                if title == "Add caching"
+        ));
+        assert!(matches!(
+            published[2],
+            OrchestratorEvent::ViabilityResult {
+                iteration: 1,
+                passed: true,
+                ..
+            }
+        ));
+        assert!(matches!(
+            published[3],
+            OrchestratorEvent::ReviewScored {
+                iteration: 1,
+                passed: true,
+                ..
+            }
+        ));
+    }
 }
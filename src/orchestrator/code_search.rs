@@ -0,0 +1,391 @@
+//! In-process code search over the working directory.
+//!
+//! Dry-runs `SEARCH_CODE` queries without an agent: files are listed once
+//! (respecting `.gitignore` files and the pre-grounding ignore list), then
+//! each query is matched line by line as a regex, falling back to a literal
+//! match when the query isn't a valid regex. Hit counts drive V-018 (dead or
+//! overly broad searches) and SEARCH_CODE token estimates.
+
+use anyhow::{Context, Result};
+use glob::{MatchOptions, Pattern};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::pre_grounding::skip_dir;
+use crate::config::CodeSearchConfig;
+
+/// Files larger than this are not searched
+const MAX_FILE_BYTES: u64 = 1024 * 1024;
+
+/// Base token cost of a search result listing
+const BASE_SEARCH_TOKENS: u32 = 200;
+
+/// Token cost per matching line (path, line number, and the line itself)
+const TOKENS_PER_HIT: u32 = 30;
+
+/// Upper bound for a hit-based estimate
+const MAX_SEARCH_TOKENS: u32 = 20_000;
+
+// ============================================================================
+// Search Results
+// ============================================================================
+
+/// Result of dry-running one query
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SearchHits {
+    /// Matching lines (capped at the search limit)
+    pub matches: usize,
+    /// Files with at least one match
+    pub files: usize,
+    /// Search stopped at the limit; the real count is higher
+    pub truncated: bool,
+}
+
+impl SearchHits {
+    /// Tokens needed to read this result listing
+    pub fn estimated_tokens(&self) -> u32 {
+        let hits = u32::try_from(self.matches).unwrap_or(u32::MAX);
+        BASE_SEARCH_TOKENS
+            .saturating_add(hits.saturating_mul(TOKENS_PER_HIT))
+            .min(MAX_SEARCH_TOKENS)
+    }
+}
+
+// ============================================================================
+// .gitignore
+// ============================================================================
+
+/// One `.gitignore` pattern
+#[derive(Debug, Clone)]
+struct IgnoreRule {
+    /// Directory of the `.gitignore`, relative to the search root
+    base: String,
+    pattern: Pattern,
+    /// `!pattern` re-includes a previously ignored path
+    negated: bool,
+    /// Trailing `/`: matches directories only
+    dir_only: bool,
+    /// Contains a non-trailing `/`: matched against the path relative to `base`
+    anchored: bool,
+}
+
+/// Subset of gitignore semantics: globs, `**`, `!` negation, trailing `/`
+/// for directories, and leading or inner `/` for anchoring. Later rules win.
+#[derive(Debug, Clone, Default)]
+struct IgnoreRules {
+    rules: Vec<IgnoreRule>,
+}
+
+impl IgnoreRules {
+    /// Add the rules of a `.gitignore` located in `base` (relative to root)
+    fn add_file(&mut self, base: &str, content: &str) {
+        for line in content.lines() {
+            let line = line.trim_end();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (negated, line) = match line.strip_prefix('!') {
+                Some(rest) => (true, rest),
+                None => (false, line.strip_prefix('\\').unwrap_or(line)),
+            };
+            let dir_only = line.ends_with('/');
+            let line = line.trim_end_matches('/');
+            let anchored = line.contains('/');
+            let line = line.trim_start_matches('/');
+            let Ok(pattern) = Pattern::new(line) else {
+                continue;
+            };
+            self.rules.push(IgnoreRule {
+                base: base.to_string(),
+                pattern,
+                negated,
+                dir_only,
+                anchored,
+            });
+        }
+    }
+
+    /// Whether `rel` (relative to the search root) is ignored
+    fn is_ignored(&self, rel: &str, is_dir: bool) -> bool {
+        let options = MatchOptions {
+            require_literal_separator: true,
+            ..MatchOptions::new()
+        };
+        let name = rel.rsplit('/').next().unwrap_or(rel);
+
+        let mut ignored = false;
+        for rule in &self.rules {
+            if rule.dir_only && !is_dir {
+                continue;
+            }
+            let local = if rule.base.is_empty() {
+                rel
+            } else {
+                match rel
+                    .strip_prefix(rule.base.as_str())
+                    .and_then(|r| r.strip_prefix('/'))
+                {
+                    Some(local) => local,
+                    None => continue,
+                }
+            };
+            let matched = if rule.anchored {
+                rule.pattern.matches_with(local, options)
+            } else {
+                rule.pattern.matches_with(name, options)
+            };
+            if matched {
+                ignored = !rule.negated;
+            }
+        }
+        ignored
+    }
+}
+
+// ============================================================================
+// Code Search
+// ============================================================================
+
+/// Searchable file list for a working directory
+#[derive(Debug, Clone)]
+pub struct CodeSearch {
+    root: PathBuf,
+    /// Paths relative to `root`, `/`-separated, sorted
+    files: Vec<String>,
+    /// Matching lines after which a search stops
+    max_hits: usize,
+}
+
+impl CodeSearch {
+    /// List searchable files under `root`
+    pub fn build(root: &Path) -> Result<Self> {
+        Self::from_config(root, &CodeSearchConfig::default())
+    }
+
+    /// List searchable files under `root` using the `code_search` config section
+    pub fn from_config(root: &Path, config: &CodeSearchConfig) -> Result<Self> {
+        let mut files = Vec::new();
+        let mut rules = IgnoreRules::default();
+        collect_files(root, root, &mut rules, config.max_files, &mut files)
+            .with_context(|| format!("Failed to walk {:?}", root))?;
+        files.sort();
+        Ok(Self {
+            root: root.to_path_buf(),
+            files,
+            max_hits: config.max_hits,
+        })
+    }
+
+    /// Number of searchable files
+    pub fn file_count(&self) -> usize {
+        self.files.len()
+    }
+
+    /// Matching lines after which a search is considered too broad
+    pub fn max_hits(&self) -> usize {
+        self.max_hits
+    }
+
+    /// Run `query` over files under `scope` (path prefix or glob).
+    ///
+    /// Counting stops one past `max_hits` so broad queries stay cheap.
+    pub fn search(&self, query: &str, scope: Option<&str>) -> SearchHits {
+        let re = Regex::new(query)
+            .or_else(|_| Regex::new(&regex::escape(query)))
+            .expect("escaped query is a valid regex");
+        let scope = scope.map(Scope::new);
+        let limit = self.max_hits.saturating_add(1);
+
+        let mut hits = SearchHits {
+            matches: 0,
+            files: 0,
+            truncated: false,
+        };
+        for rel in &self.files {
+            if scope.as_ref().is_some_and(|s| !s.contains(rel)) {
+                continue;
+            }
+            let Ok(content) = fs::read_to_string(self.root.join(rel)) else {
+                continue; // binary or unreadable
+            };
+            let count = content.lines().filter(|line| re.is_match(line)).count();
+            if count == 0 {
+                continue;
+            }
+            hits.files += 1;
+            hits.matches += count;
+            if hits.matches >= limit {
+                hits.matches = limit;
+                hits.truncated = true;
+                break;
+            }
+        }
+        hits
+    }
+}
+
+/// `scope` param of a SEARCH_CODE instruction
+enum Scope {
+    Prefix(String),
+    Glob(Pattern),
+}
+
+impl Scope {
+    fn new(scope: &str) -> Self {
+        let scope = scope.trim().trim_start_matches("./");
+        if scope.contains(['*', '?', '['])
+            && let Ok(pattern) = Pattern::new(scope)
+        {
+            return Scope::Glob(pattern);
+        }
+        Scope::Prefix(scope.trim_end_matches('/').to_string())
+    }
+
+    fn contains(&self, rel: &str) -> bool {
+        match self {
+            Scope::Glob(pattern) => pattern.matches(rel),
+            Scope::Prefix(prefix) if prefix.is_empty() || prefix == "." => true,
+            Scope::Prefix(prefix) => {
+                rel == prefix
+                    || rel
+                        .strip_prefix(prefix.as_str())
+                        .is_some_and(|r| r.starts_with('/'))
+            }
+        }
+    }
+}
+
+fn collect_files(
+    root: &Path,
+    dir: &Path,
+    rules: &mut IgnoreRules,
+    max_files: usize,
+    files: &mut Vec<String>,
+) -> Result<()> {
+    let rel_dir = relative(root, dir);
+    if let Ok(content) = fs::read_to_string(dir.join(".gitignore")) {
+        rules.add_file(&rel_dir, &content);
+    }
+
+    let mut entries: Vec<_> = fs::read_dir(dir)?.filter_map(|e| e.ok()).collect();
+    entries.sort_by_key(|e| e.file_name());
+
+    for entry in entries {
+        if files.len() >= max_files {
+            return Ok(());
+        }
+        let name = entry.file_name().to_string_lossy().to_string();
+        if name.starts_with('.') {
+            continue;
+        }
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+        let path = entry.path();
+        let rel = relative(root, &path);
+
+        if file_type.is_dir() {
+            if skip_dir(&name) || rules.is_ignored(&rel, true) {
+                continue;
+            }
+            collect_files(root, &path, rules, max_files, files)?;
+        } else if file_type.is_file()
+            && !rules.is_ignored(&rel, false)
+            && entry.metadata().is_ok_and(|m| m.len() <= MAX_FILE_BYTES)
+        {
+            files.push(rel);
+        }
+    }
+    Ok(())
+}
+
+/// `/`-separated path relative to `root` ("" for the root itself)
+fn relative(root: &Path, path: &Path) -> String {
+    path.strip_prefix(root)
+        .unwrap_or(path)
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn workspace() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("src/handlers")).unwrap();
+        fs::create_dir_all(root.join("generated")).unwrap();
+        fs::create_dir_all(root.join("target/debug")).unwrap();
+        fs::write(root.join(".gitignore"), "generated/\n*.log\n!keep.log\n").unwrap();
+        fs::write(root.join("src/handlers/.gitignore"), "/scratch.rs\n").unwrap();
+        fs::write(
+            root.join("src/handlers/api.rs"),
+            "impl ApiHandler {}\nimpl AuthHandler {}\n",
+        )
+        .unwrap();
+        fs::write(root.join("src/handlers/scratch.rs"), "impl TmpHandler {}\n").unwrap();
+        fs::write(root.join("src/lib.rs"), "pub mod handlers;\n").unwrap();
+        fs::write(root.join("generated/out.rs"), "impl GenHandler {}\n").unwrap();
+        fs::write(root.join("target/debug/x.rs"), "impl BuildHandler {}\n").unwrap();
+        fs::write(root.join("debug.log"), "impl LogHandler\n").unwrap();
+        fs::write(root.join("keep.log"), "impl KeepHandler\n").unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_search_respects_gitignore_and_scope() {
+        let dir = workspace();
+        let search = CodeSearch::build(dir.path()).unwrap();
+        assert_eq!(
+            search.files,
+            vec!["keep.log", "src/handlers/api.rs", "src/lib.rs"]
+        );
+
+        let hits = search.search(r"impl \w+Handler", None);
+        assert_eq!(hits.matches, 3);
+        assert_eq!(hits.files, 2);
+        assert!(!hits.truncated);
+
+        assert_eq!(search.search(r"impl \w+Handler", Some("src/")).matches, 2);
+        assert_eq!(
+            search
+                .search(r"impl \w+Handler", Some("src/**/*.rs"))
+                .matches,
+            2
+        );
+        assert_eq!(search.search("Handler", Some("tests")).matches, 0);
+        // Invalid regex falls back to a literal match
+        assert_eq!(search.search("pub mod handlers;(", None).matches, 0);
+        assert_eq!(search.search("impl ApiHandler {", None).matches, 1);
+    }
+
+    #[test]
+    fn test_search_stops_past_max_hits() {
+        let dir = workspace();
+        let config = CodeSearchConfig {
+            max_hits: 1,
+            ..CodeSearchConfig::default()
+        };
+        let search = CodeSearch::from_config(dir.path(), &config).unwrap();
+        let hits = search.search("Handler", None);
+        assert_eq!(hits.matches, 2);
+        assert!(hits.truncated);
+
+        let none = SearchHits {
+            matches: 0,
+            files: 0,
+            truncated: false,
+        };
+        assert_eq!(none.estimated_tokens(), 200);
+        let many = SearchHits {
+            matches: 10_000,
+            ..none
+        };
+        assert_eq!(many.estimated_tokens(), 20_000);
+    }
+}
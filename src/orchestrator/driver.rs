@@ -0,0 +1,463 @@
+//! Deterministic orchestration mode (`orchestrator.mode: deterministic`).
+//!
+//! The LLM orchestrator only ever chooses between the fixed actions listed in
+//! its iteration prompt. `next_action` encodes that decision list as a state
+//! machine over `OrchestrationState`, and `DeterministicDriver` executes it by
+//! calling the `OrchestratorClient` tools in-process, so guardrails, viability
+//! and policy checks, grounding gates, and state persistence behave exactly as
+//! in LLM mode without spending orchestrator tokens.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use serde_json::Value;
+use tokio::sync::Mutex;
+use tracing::{info, warn};
+
+use super::client::{BUDGET_INPUT_CATEGORY, OrchestratorClient};
+use super::guardrails::GuardrailHardStop;
+use super::orchestration_state::{OrchestrationState, OrchestrationStatus};
+
+/// Category used when the reviewer requests human input
+const REVIEW_INPUT_CATEGORY: &str = "review";
+
+// ============================================================================
+// State Machine
+// ============================================================================
+
+/// Next step chosen by the deterministic orchestrator
+#[derive(Debug, Clone, PartialEq)]
+pub enum OrchestratorAction {
+    /// Generate (or revise) the plan with optional feedback
+    GeneratePlan { feedback: Option<Vec<String>> },
+    /// Review the current plan
+    ReviewPlan,
+    /// Pause for the human input the reviewer asked for
+    RequestHumanInput { question: String, category: String },
+    /// Finalize the current plan
+    Finalize,
+    /// Session reached a terminal state
+    Stop,
+}
+
+/// Decide the next action from the session state.
+///
+/// Mirrors the LLM orchestrator's decision list: generate when there is no
+/// plan, review every new plan, pause when the reviewer asks for input,
+/// finalize a passing plan, and otherwise revise with feedback. A human
+/// response recorded since the last plan is fed back to the planner unless
+/// it approves an already-passing plan; replies to budget pauses never are.
+///
+/// `last_response` is the most recent `review_plan` or rejected `finalize`
+/// response; when None, feedback comes from the last stored review.
+pub fn next_action(
+    state: &OrchestrationState,
+    last_response: Option<&Value>,
+) -> OrchestratorAction {
+    if !matches!(state.status, OrchestrationStatus::Running) {
+        return OrchestratorAction::Stop;
+    }
+    if state.current_plan.is_none() {
+        return OrchestratorAction::GeneratePlan { feedback: None };
+    }
+    if state.needs_review {
+        return OrchestratorAction::ReviewPlan;
+    }
+    if state.requires_human_input_pending {
+        let question = state
+            .reviews
+            .last()
+            .and_then(|r| r.get("human_input_reason"))
+            .and_then(|v| v.as_str())
+            .unwrap_or("The reviewer requested human input before continuing")
+            .to_string();
+        return OrchestratorAction::RequestHumanInput {
+            question,
+            category: REVIEW_INPUT_CATEGORY.to_string(),
+        };
+    }
+
+    // Human response to a pause on the current plan
+    let human_response = state
+        .human_inputs
+        .iter()
+        .rfind(|input| input.category != BUDGET_INPUT_CATEGORY)
+        .filter(|input| input.iteration == state.iteration)
+        .and_then(|input| Some((input.response.as_deref()?, input.approved)));
+
+    if state.last_review_passed && human_response.is_none_or(|(_, approved)| approved) {
+        return OrchestratorAction::Finalize;
+    }
+
+    let mut feedback = last_response
+        .or(state.reviews.last())
+        .map(review_feedback)
+        .unwrap_or_default();
+    if let Some((response, _)) = human_response {
+        feedback.push(format!("Human feedback: {}", response));
+    }
+    OrchestratorAction::GeneratePlan {
+        feedback: (!feedback.is_empty()).then_some(feedback),
+    }
+}
+
+/// Planner feedback from a `review_plan` response, a rejected `finalize`
+/// response, or a stored reviewer JSON.
+///
+/// Includes viability violations, failed grounding gates, reviewer gaps,
+/// unclear areas, and non-optional suggestions.
+pub fn review_feedback(response: &Value) -> Vec<String> {
+    let mut feedback = Vec::new();
+
+    for violation in array(response.pointer("/viability/violations")) {
+        let rule = str_field(violation, "rule_id").unwrap_or("VIABILITY");
+        let message = str_field(violation, "message").unwrap_or_default();
+        match str_field(violation, "remediation") {
+            Some(fix) => feedback.push(format!("[{}] {} (fix: {})", rule, message, fix)),
+            None => feedback.push(format!("[{}] {}", rule, message)),
+        }
+    }
+
+    for gate in array(response.pointer("/gates/results")) {
+        if str_field(gate, "status") == Some("failed") {
+            feedback.push(format!(
+                "[GATE {}] {}",
+                str_field(gate, "id").unwrap_or("?"),
+                str_field(gate, "detail").unwrap_or_default()
+            ));
+        }
+    }
+
+    for dimension in array(response.get("dimensions")) {
+        let name = str_field(dimension, "name").unwrap_or("dimension");
+        if dimension.get("passed").and_then(|p| p.as_bool()) == Some(false) {
+            feedback.push(format!(
+                "[DIMENSION {}] score {:.2} is below the minimum {:.2}",
+                name,
+                dimension
+                    .get("score")
+                    .and_then(|s| s.as_f64())
+                    .unwrap_or(0.0),
+                dimension
+                    .get("min_score")
+                    .and_then(|s| s.as_f64())
+                    .unwrap_or(0.0)
+            ));
+        }
+        for gap in array(dimension.get("gaps")) {
+            let mut line = format!(
+                "Gap ({}): {}",
+                name,
+                str_field(gap, "description").unwrap_or_default()
+            );
+            if let Some(fix) = str_field(gap, "suggested_fix") {
+                line.push_str(&format!(" - fix: {}", fix));
+            }
+            feedback.push(line);
+        }
+    }
+
+    let review = response
+        .get("llm_review")
+        .filter(|r| !r.is_null())
+        .unwrap_or(response);
+
+    for gap in array(review.get("gaps")) {
+        let mut line = format!("Gap: {}", str_field(gap, "description").unwrap_or_default());
+        if let Some(location) = str_field(gap, "location") {
+            line.push_str(&format!(" ({})", location));
+        }
+        if let Some(fix) = str_field(gap, "suggested_fix") {
+            line.push_str(&format!(" - fix: {}", fix));
+        }
+        feedback.push(line);
+    }
+    for area in array(review.get("unclear_areas")) {
+        let questions: Vec<&str> = array(area.get("questions"))
+            .iter()
+            .filter_map(|q| q.as_str())
+            .collect();
+        let mut line = format!(
+            "Unclear: {}",
+            str_field(area, "description").unwrap_or_default()
+        );
+        if !questions.is_empty() {
+            line.push_str(&format!(" ({})", questions.join("; ")));
+        }
+        feedback.push(line);
+    }
+    for suggestion in array(review.get("suggestions")) {
+        if str_field(suggestion, "priority") != Some("optional") {
+            feedback.push(format!(
+                "Suggestion: {}",
+                str_field(suggestion, "description").unwrap_or_default()
+            ));
+        }
+    }
+
+    feedback
+}
+
+fn array(value: Option<&Value>) -> &[Value] {
+    value
+        .and_then(|v| v.as_array())
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+}
+
+fn str_field<'a>(value: &'a Value, key: &str) -> Option<&'a str> {
+    value.get(key).and_then(|v| v.as_str())
+}
+
+// ============================================================================
+// Driver
+// ============================================================================
+
+/// Runs the orchestration state machine against an `OrchestratorClient`
+pub struct DeterministicDriver {
+    client: OrchestratorClient,
+    state: Arc<Mutex<OrchestrationState>>,
+    session_dir: PathBuf,
+    /// Maximum time for a single step (planner or reviewer call)
+    step_timeout: Duration,
+}
+
+impl DeterministicDriver {
+    /// Create a driver over a session-scoped client and its shared state
+    pub fn new(
+        client: OrchestratorClient,
+        state: Arc<Mutex<OrchestrationState>>,
+        session_dir: PathBuf,
+        step_timeout: Duration,
+    ) -> Self {
+        Self {
+            client,
+            state,
+            session_dir,
+            step_timeout,
+        }
+    }
+
+    /// Execute actions until the session reaches a terminal state.
+    ///
+    /// Hard stops raised by the client's guardrails end the loop normally;
+    /// planner/reviewer errors mark the session failed and are returned.
+    pub async fn run(&self) -> Result<()> {
+        let mut last_response: Option<Value> = None;
+
+        loop {
+            let (action, task, plan) = {
+                let state = self.state.lock().await;
+                (
+                    next_action(&state, last_response.as_ref()),
+                    state.task.clone(),
+                    state.current_plan.clone(),
+                )
+            };
+
+            let (tool, arguments) = match &action {
+                OrchestratorAction::Stop => return Ok(()),
+                OrchestratorAction::GeneratePlan { feedback } => (
+                    "generate_plan",
+                    serde_json::json!({ "task": task, "feedback": feedback }),
+                ),
+                OrchestratorAction::ReviewPlan => {
+                    ("review_plan", serde_json::json!({ "plan_json": plan }))
+                }
+                OrchestratorAction::RequestHumanInput { question, category } => (
+                    "request_human_input",
+                    serde_json::json!({ "question": question, "category": category }),
+                ),
+                OrchestratorAction::Finalize => {
+                    ("finalize", serde_json::json!({ "plan_json": plan }))
+                }
+            };
+            info!("Deterministic orchestrator: {}", tool);
+
+            let response =
+                match tokio::time::timeout(self.step_timeout, self.client.invoke(tool, arguments))
+                    .await
+                {
+                    Ok(Ok(response)) => response,
+                    Ok(Err(e)) => {
+                        let mut state = self.state.lock().await;
+                        if matches!(
+                            state.status,
+                            OrchestrationStatus::HardStopped { .. }
+                                | OrchestrationStatus::Cancelled
+                        ) {
+                            warn!("{}", e);
+                            state.save(&self.session_dir)?;
+                            continue;
+                        }
+                        state.status = OrchestrationStatus::Failed {
+                            error: e.to_string(),
+                        };
+                        state.save(&self.session_dir)?;
+                        return Err(e);
+                    }
+                    Err(_) => {
+                        let mut state = self.state.lock().await;
+                        state.status = OrchestrationStatus::HardStopped {
+                            reason: GuardrailHardStop::ExecutionTimeout,
+                        };
+                        state.save(&self.session_dir)?;
+                        return Err(anyhow::anyhow!("Orchestrator step '{}' timed out", tool));
+                    }
+                };
+
+            match action {
+                OrchestratorAction::ReviewPlan => last_response = Some(response),
+                OrchestratorAction::Finalize
+                    if response.get("status").and_then(|s| s.as_str()) == Some("rejected") =>
+                {
+                    last_response = Some(response);
+                }
+                OrchestratorAction::GeneratePlan { .. } => last_response = None,
+                _ => {}
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::orchestrator::HumanInputRecord;
+
+    fn make_state() -> OrchestrationState {
+        OrchestrationState::new(
+            "session".to_string(),
+            "Add caching".to_string(),
+            PathBuf::from("/tmp"),
+            "add-caching".to_string(),
+        )
+    }
+
+    fn review(score: f64, requires_human_input: bool) -> Value {
+        serde_json::json!({
+            "score": score,
+            "requires_human_input": requires_human_input,
+            "human_input_reason": "Confirm the cache backend",
+            "gaps": [{"description": "No eviction policy", "location": "Phase 2", "severity": "error", "confidence": "high"}],
+            "unclear_areas": [],
+            "suggestions": [
+                {"description": "Add metrics", "rationale": "", "priority": "recommended", "confidence": "high"},
+                {"description": "Rename module", "rationale": "", "priority": "optional", "confidence": "low"}
+            ]
+        })
+    }
+
+    #[test]
+    fn test_next_action_follows_decision_list() {
+        let mut state = make_state();
+        assert_eq!(
+            next_action(&state, None),
+            OrchestratorAction::GeneratePlan { feedback: None }
+        );
+
+        state.current_plan = Some(serde_json::json!({ "title": "Plan" }));
+        state.needs_review = true;
+        assert_eq!(next_action(&state, None), OrchestratorAction::ReviewPlan);
+
+        // Failed review: revise with feedback from the stored review
+        state.needs_review = false;
+        state.reviews.push(review(0.5, false));
+        assert_eq!(
+            next_action(&state, None),
+            OrchestratorAction::GeneratePlan {
+                feedback: Some(vec![
+                    "Gap: No eviction policy (Phase 2)".to_string(),
+                    "Suggestion: Add metrics".to_string(),
+                ])
+            }
+        );
+
+        // Viability failure response takes precedence over the stored review
+        let response = serde_json::json!({
+            "viability": {"violations": [{"rule_id": "VIABILITY-006", "message": "Missing dependency", "remediation": "Add it"}]},
+            "llm_review": null
+        });
+        assert_eq!(
+            next_action(&state, Some(&response)),
+            OrchestratorAction::GeneratePlan {
+                feedback: Some(vec![
+                    "[VIABILITY-006] Missing dependency (fix: Add it)".to_string()
+                ])
+            }
+        );
+
+        state.last_review_passed = true;
+        assert_eq!(next_action(&state, None), OrchestratorAction::Finalize);
+
+        state.status = OrchestrationStatus::Completed;
+        assert_eq!(next_action(&state, None), OrchestratorAction::Stop);
+    }
+
+    #[test]
+    fn test_next_action_human_input_round_trip() {
+        let mut state = make_state();
+        state.iteration = 2;
+        state.current_plan = Some(serde_json::json!({ "title": "Plan" }));
+        state.reviews.push(review(0.9, true));
+        state.last_review_passed = true;
+        state.requires_human_input_pending = true;
+        assert_eq!(
+            next_action(&state, None),
+            OrchestratorAction::RequestHumanInput {
+                question: "Confirm the cache backend".to_string(),
+                category: "review".to_string(),
+            }
+        );
+
+        // Human answered with changes: revise even though the review passed
+        state.requires_human_input_pending = false;
+        state.human_inputs.push(HumanInputRecord {
+            question: "Confirm the cache backend".to_string(),
+            category: "review".to_string(),
+            response: Some("Use Redis".to_string()),
+            reason: None,
+            iteration: 2,
+            timestamp: String::new(),
+            approved: false,
+        });
+        match next_action(&state, None) {
+            OrchestratorAction::GeneratePlan {
+                feedback: Some(feedback),
+            } => assert_eq!(feedback.last().unwrap(), "Human feedback: Use Redis"),
+            other => panic!("unexpected action {:?}", other),
+        }
+
+        // Approval of a passing plan finalizes
+        state.human_inputs[0].approved = true;
+        assert_eq!(next_action(&state, None), OrchestratorAction::Finalize);
+    }
+
+    #[test]
+    fn test_next_action_ignores_budget_replies() {
+        let mut state = make_state();
+        state.iteration = 2;
+        state.current_plan = Some(serde_json::json!({ "title": "Plan" }));
+        state.reviews.push(review(0.5, false));
+        state.human_inputs.push(HumanInputRecord {
+            question: "The next iteration is projected to exceed the budget".to_string(),
+            category: BUDGET_INPUT_CATEGORY.to_string(),
+            response: Some("continue anyway".to_string()),
+            reason: None,
+            iteration: 2,
+            timestamp: String::new(),
+            approved: false,
+        });
+        match next_action(&state, None) {
+            OrchestratorAction::GeneratePlan { feedback } => assert!(
+                feedback
+                    .unwrap_or_default()
+                    .iter()
+                    .all(|item| !item.starts_with("Human feedback"))
+            ),
+            other => panic!("unexpected action {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,222 @@
+//! Model escalation ladder.
+//!
+//! `planning`, `review`, and `orchestrator` config can each list provider/model
+//! rungs, cheapest first. The session tracks one rung index
+//! (`OrchestrationState::model_rung`): the planner moves up a rung after
+//! repeated viability failures or stalled review scores on its current rung,
+//! and every role uses its own ladder at that index (clamped to its top rung).
+//!
+//! Best-of-N candidate planners and review panel members are built once from
+//! their own configured models and do not follow the rung.
+
+use std::sync::Arc;
+
+use crate::config::ModelRung;
+
+use super::guardrails::score_plateau;
+use super::orchestration_state::{IterationOutcome, IterationRecord};
+
+/// Components built for each rung of a ladder (planners, reviewers)
+pub struct ModelLadder<T> {
+    rungs: Vec<(String, Arc<T>)>,
+}
+
+impl<T> ModelLadder<T> {
+    /// Create an empty ladder
+    pub fn new() -> Self {
+        Self { rungs: Vec::new() }
+    }
+
+    /// Add the next (more capable) rung
+    pub fn with_rung(mut self, label: impl Into<String>, component: Arc<T>) -> Self {
+        self.rungs.push((label.into(), component));
+        self
+    }
+
+    /// Number of rungs
+    pub fn len(&self) -> usize {
+        self.rungs.len()
+    }
+
+    /// Whether the ladder has no rungs
+    pub fn is_empty(&self) -> bool {
+        self.rungs.is_empty()
+    }
+
+    /// Label and component for `rung`, clamped to the top rung.
+    ///
+    /// Returns None for an empty ladder.
+    pub fn get(&self, rung: u32) -> Option<(&str, &Arc<T>)> {
+        let index = (rung as usize).min(self.rungs.len().checked_sub(1)?);
+        let (label, component) = &self.rungs[index];
+        Some((label.as_str(), component))
+    }
+}
+
+impl<T> Default for ModelLadder<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Config rung for `rung`, clamped to the top of `ladder`
+pub fn rung_config(ladder: &[ModelRung], rung: u32) -> Option<&ModelRung> {
+    ladder.get((rung as usize).min(ladder.len().checked_sub(1)?))
+}
+
+/// Display label for a rung ("provider/model", with "default" for unset parts)
+pub fn rung_label(rung: &ModelRung) -> String {
+    format!(
+        "{}/{}",
+        rung.provider.as_deref().unwrap_or("default"),
+        rung.model.as_deref().unwrap_or("default")
+    )
+}
+
+/// Why the planner should move up from `rung`, judged on that rung's iterations.
+///
+/// Escalates after `viability_failures` viability failures in a row, or when
+/// the last `stalled_reviews` review scores did not beat the rung's earlier
+/// best by more than `epsilon`. Both thresholds are at least 1.
+pub fn escalation_reason(
+    history: &[IterationRecord],
+    rung: u32,
+    viability_failures: usize,
+    stalled_reviews: usize,
+    epsilon: f32,
+) -> Option<String> {
+    let records: Vec<&IterationRecord> = history
+        .iter()
+        .filter(|r| r.rung == rung && r.outcome != IterationOutcome::TextResponseDetected)
+        .collect();
+
+    let failures = viability_failures.max(1);
+    if records.len() >= failures
+        && records[records.len() - failures..]
+            .iter()
+            .all(|r| r.outcome == IterationOutcome::ViabilityFailed)
+    {
+        return Some(format!(
+            "plan failed viability checks {} times in a row on rung {}",
+            failures, rung
+        ));
+    }
+
+    let stalled = stalled_reviews.max(1);
+    let scores: Vec<f32> = records.iter().filter_map(|r| r.review_score).collect();
+    score_plateau(&scores, stalled, epsilon).map(|best| {
+        format!(
+            "review score stalled at {:.2} for {} reviews on rung {}",
+            best, stalled, rung
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::LadderConfig;
+
+    fn make_record(rung: u32, score: Option<f32>) -> IterationRecord {
+        IterationRecord {
+            iteration: 1,
+            timestamp: String::new(),
+            viability_violations: 0,
+            viability_critical: if score.is_none() { 1 } else { 0 },
+            viability_passed: score.is_some(),
+            review_score: score,
+            review_passed: score.map(|_| false),
+            tool_calls_this_iteration: 2,
+            tokens_this_iteration: 0,
+            reviewer_scores: Vec::new(),
+            critical_rule_ids: Vec::new(),
+            rung,
+            outcome: if score.is_none() {
+                IterationOutcome::ViabilityFailed
+            } else {
+                IterationOutcome::ReviewFailed
+            },
+            role_tokens: None,
+        }
+    }
+
+    #[test]
+    fn test_escalation_reason() {
+        // Two viability failures in a row on the current rung
+        let mut history = vec![make_record(0, Some(0.5)), make_record(0, None)];
+        assert_eq!(escalation_reason(&history, 0, 2, 2, 0.02), None);
+        history.push(make_record(0, None));
+        assert!(
+            escalation_reason(&history, 0, 2, 2, 0.02)
+                .unwrap()
+                .contains("viability")
+        );
+
+        // Failures on a lower rung don't count after escalating
+        history.push(make_record(1, Some(0.6)));
+        assert_eq!(escalation_reason(&history, 1, 2, 2, 0.02), None);
+
+        // Stalled scores on the current rung
+        history.push(make_record(1, Some(0.61)));
+        history.push(make_record(1, Some(0.55)));
+        assert!(
+            escalation_reason(&history, 1, 2, 2, 0.02)
+                .unwrap()
+                .contains("stalled")
+        );
+
+        history.push(make_record(1, Some(0.7)));
+        assert_eq!(escalation_reason(&history, 1, 2, 2, 0.02), None);
+    }
+
+    #[test]
+    fn test_escalation_thresholds() {
+        let history = vec![make_record(0, None)];
+        assert_eq!(escalation_reason(&history, 0, 2, 2, 0.02), None);
+        assert!(escalation_reason(&history, 0, 1, 2, 0.02).is_some());
+        assert!(escalation_reason(&history, 0, 0, 2, 0.02).is_some());
+
+        let history = vec![
+            make_record(0, Some(0.5)),
+            make_record(0, Some(0.5)),
+            make_record(0, Some(0.5)),
+        ];
+        assert!(escalation_reason(&history, 0, 2, 2, 0.02).is_some());
+        assert_eq!(escalation_reason(&history, 0, 2, 3, 0.02), None);
+    }
+
+    #[test]
+    fn test_ladder_config_accepts_rung_list() {
+        let ladder: LadderConfig = serde_yaml::from_str("- { model: haiku }").unwrap();
+        assert_eq!(ladder.rungs.len(), 1);
+        assert_eq!(ladder.escalate_after_viability_failures, 2);
+
+        let ladder: LadderConfig = serde_yaml::from_str(
+            "{ rungs: [{ model: haiku }, { model: opus }], escalate_after_stalled_reviews: 4 }",
+        )
+        .unwrap();
+        assert_eq!(ladder.rungs.len(), 2);
+        assert_eq!(ladder.escalate_after_viability_failures, 2);
+        assert_eq!(ladder.escalate_after_stalled_reviews, 4);
+    }
+
+    #[test]
+    fn test_ladder_clamps_to_top_rung() {
+        let ladder = ModelLadder::new()
+            .with_rung("cheap", Arc::new(1))
+            .with_rung("strong", Arc::new(2));
+        assert_eq!(ladder.get(0).map(|(label, _)| label), Some("cheap"));
+        assert_eq!(
+            ladder.get(5).map(|(label, c)| (label, **c)),
+            Some(("strong", 2))
+        );
+        assert!(ModelLadder::<i32>::new().get(0).is_none());
+
+        let rungs = vec![ModelRung {
+            provider: None,
+            model: Some("haiku".to_string()),
+        }];
+        assert_eq!(rung_label(rung_config(&rungs, 3).unwrap()), "default/haiku");
+        assert!(rung_config(&[], 0).is_none());
+    }
+}
@@ -0,0 +1,348 @@
+//! Structured progress events published while a session runs.
+//!
+//! `GooseOrchestrator` owns a broadcast channel; `OrchestratorClient`
+//! publishes progress from the tool handlers, and the orchestrator publishes
+//! the terminal event. Subscribers render progress in the CLI, append to
+//! `events.jsonl` in the session directory, or forward MCP progress
+//! notifications.
+
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use tokio::fs::{File, OpenOptions};
+use tokio::io::AsyncWriteExt;
+use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+
+use super::guardrails::GuardrailHardStop;
+use super::orchestration_state::{OrchestrationState, OrchestrationStatus};
+
+/// Event log file name in the session directory
+pub const EVENTS_FILE: &str = "events.jsonl";
+
+/// Events buffered per subscriber before the slowest one starts lagging
+pub const EVENT_CHANNEL_CAPACITY: usize = 256;
+
+/// Progress event for one orchestration session.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum OrchestratorEvent {
+    /// generate_plan started a new plan iteration
+    IterationStarted { iteration: u32 },
+    /// The planner produced the plan for an iteration
+    PlanGenerated {
+        iteration: u32,
+        title: Option<String>,
+        /// Number of candidates generated (1 without best-of-N)
+        candidates: usize,
+    },
+    /// V-* viability checks ran on the current plan
+    ViabilityResult {
+        iteration: u32,
+        passed: bool,
+        violations: u32,
+        critical: u32,
+    },
+    /// The reviewer scored the current plan
+    ReviewScored {
+        iteration: u32,
+        score: f32,
+        threshold: f32,
+        passed: bool,
+    },
+    /// The session paused for a human answer
+    HumanInputRequested { question: String, category: String },
+    /// A guardrail ended the session
+    HardStop { reason: GuardrailHardStop },
+    /// The session completed and the plan was written
+    Finalized {
+        /// "approved" or "best_effort"
+        status: String,
+        score: f32,
+        iterations: u32,
+    },
+}
+
+impl OrchestratorEvent {
+    /// Terminal event for a finished session, if its status has one.
+    ///
+    /// Pauses are announced when they happen; cancelled and failed sessions
+    /// have no terminal event.
+    pub fn terminal(state: &OrchestrationState) -> Option<Self> {
+        match &state.status {
+            OrchestrationStatus::HardStopped { reason } => Some(OrchestratorEvent::HardStop {
+                reason: reason.clone(),
+            }),
+            OrchestrationStatus::Completed => Some(OrchestratorEvent::Finalized {
+                status: "approved".to_string(),
+                score: state
+                    .reviews
+                    .last()
+                    .and_then(|r| r.get("score"))
+                    .and_then(|s| s.as_f64())
+                    .map_or(state.best_score, |s| s as f32),
+                iterations: state.iteration,
+            }),
+            OrchestrationStatus::CompletedBestEffort => Some(OrchestratorEvent::Finalized {
+                status: "best_effort".to_string(),
+                score: state.best_score,
+                iterations: state.iteration,
+            }),
+            _ => None,
+        }
+    }
+
+    /// One-line description for progress output.
+    pub fn summary(&self) -> String {
+        match self {
+            OrchestratorEvent::IterationStarted { iteration } => {
+                format!("Iteration {}: generating plan", iteration)
+            }
+            OrchestratorEvent::PlanGenerated {
+                iteration,
+                title,
+                candidates,
+            } => {
+                let title = title.as_deref().unwrap_or("untitled");
+                if *candidates > 1 {
+                    format!(
+                        "Iteration {}: plan \"{}\" selected from {} candidates",
+                        iteration, title, candidates
+                    )
+                } else {
+                    format!("Iteration {}: plan \"{}\" generated", iteration, title)
+                }
+            }
+            OrchestratorEvent::ViabilityResult {
+                iteration,
+                passed,
+                violations,
+                critical,
+            } => format!(
+                "Iteration {}: viability {} ({} violations, {} critical)",
+                iteration,
+                if *passed { "passed" } else { "failed" },
+                violations,
+                critical
+            ),
+            OrchestratorEvent::ReviewScored {
+                iteration,
+                score,
+                threshold,
+                passed,
+            } => format!(
+                "Iteration {}: review score {:.2} (threshold {:.2}) - {}",
+                iteration,
+                score,
+                threshold,
+                if *passed { "PASSED" } else { "needs revision" }
+            ),
+            OrchestratorEvent::HumanInputRequested { question, category } => {
+                format!("Paused for human input [{}]: {}", category, question)
+            }
+            OrchestratorEvent::HardStop { reason } => format!("Hard stop: {:?}", reason),
+            OrchestratorEvent::Finalized {
+                status,
+                score,
+                iterations,
+            } => format!(
+                "Finalized ({}) with score {:.2} after {} iterations",
+                status, score, iterations
+            ),
+        }
+    }
+}
+
+/// Timestamped line of the session event log
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EventRecord {
+    pub timestamp: String,
+    #[serde(flatten)]
+    pub event: OrchestratorEvent,
+}
+
+/// Subscriber appending every event to `<session_dir>/events.jsonl`.
+pub struct EventLog {
+    handle: JoinHandle<()>,
+    stop: CancellationToken,
+}
+
+impl EventLog {
+    /// Start appending events received on `events` to the session log.
+    pub fn spawn(session_dir: &Path, mut events: broadcast::Receiver<OrchestratorEvent>) -> Self {
+        if let Err(e) = std::fs::create_dir_all(session_dir) {
+            tracing::warn!("Failed to create session directory for event log: {}", e);
+        }
+        let path = session_dir.join(EVENTS_FILE);
+        let stop = CancellationToken::new();
+        let stopped = stop.clone();
+        let handle = tokio::spawn(async move {
+            // One handle for the whole session instead of reopening per event
+            let mut file = match OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&path)
+                .await
+            {
+                Ok(file) => file,
+                Err(e) => {
+                    tracing::warn!("Failed to open event log {:?}: {}", path, e);
+                    return;
+                }
+            };
+            loop {
+                tokio::select! {
+                    // Drain queued events before honoring the stop request
+                    biased;
+                    received = events.recv() => match received {
+                        Ok(event) => append_event(&mut file, &path, event).await,
+                        Err(broadcast::error::RecvError::Lagged(missed)) => {
+                            tracing::warn!("Event log skipped {} events", missed);
+                        }
+                        Err(broadcast::error::RecvError::Closed) => break,
+                    },
+                    _ = stopped.cancelled() => {
+                        while let Ok(event) = events.try_recv() {
+                            append_event(&mut file, &path, event).await;
+                        }
+                        break;
+                    }
+                }
+            }
+        });
+        Self { handle, stop }
+    }
+
+    /// Write the events published so far and stop the subscriber.
+    pub async fn finish(self) {
+        self.stop.cancel();
+        if let Err(e) = self.handle.await {
+            tracing::warn!("Event log task failed: {}", e);
+        }
+    }
+}
+
+async fn append_event(file: &mut File, path: &Path, event: OrchestratorEvent) {
+    let record = EventRecord {
+        timestamp: chrono::Utc::now().to_rfc3339(),
+        event,
+    };
+    let result = match serde_json::to_string(&record) {
+        Ok(mut line) => {
+            line.push('\n');
+            // tokio buffers writes in the background; flush so the line is
+            // on disk for read_event_log
+            match file.write_all(line.as_bytes()).await {
+                Ok(()) => file.flush().await,
+                Err(e) => Err(e),
+            }
+        }
+        Err(e) => Err(std::io::Error::other(e)),
+    };
+    if let Err(e) = result {
+        tracing::warn!("Failed to append to event log {:?}: {}", path, e);
+    }
+}
+
+/// Read the events logged for a session (empty if none were recorded).
+pub fn read_event_log(session_dir: &Path) -> Vec<EventRecord> {
+    std::fs::read_to_string(session_dir.join(EVENTS_FILE))
+        .map(|content| {
+            content
+                .lines()
+                .filter_map(|line| serde_json::from_str(line).ok())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_serialization() {
+        let event = OrchestratorEvent::ReviewScored {
+            iteration: 2,
+            score: 0.75,
+            threshold: 0.8,
+            passed: false,
+        };
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["type"], "review_scored");
+        assert_eq!(json["iteration"], 2);
+        assert_eq!(
+            event.summary(),
+            "Iteration 2: review score 0.75 (threshold 0.80) - needs revision"
+        );
+
+        let hard_stop = OrchestratorEvent::HardStop {
+            reason: GuardrailHardStop::ExecutionTimeout,
+        };
+        let json = serde_json::to_string(&hard_stop).unwrap();
+        let parsed: OrchestratorEvent = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, hard_stop);
+    }
+
+    #[test]
+    fn test_terminal_event() {
+        let mut state = OrchestrationState::new(
+            "test".to_string(),
+            "task".to_string(),
+            std::path::PathBuf::new(),
+            "slug".to_string(),
+        );
+        assert_eq!(OrchestratorEvent::terminal(&state), None);
+
+        state.iteration = 3;
+        state.best_score = 0.7;
+        state.status = OrchestrationStatus::CompletedBestEffort;
+        assert_eq!(
+            OrchestratorEvent::terminal(&state),
+            Some(OrchestratorEvent::Finalized {
+                status: "best_effort".to_string(),
+                score: 0.7,
+                iterations: 3,
+            })
+        );
+
+        state.status = OrchestrationStatus::HardStopped {
+            reason: GuardrailHardStop::ExecutionTimeout,
+        };
+        assert!(matches!(
+            OrchestratorEvent::terminal(&state),
+            Some(OrchestratorEvent::HardStop { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_event_log_writes_queued_events() {
+        let dir = tempfile::tempdir().unwrap();
+        let (sender, receiver) = broadcast::channel(EVENT_CHANNEL_CAPACITY);
+        let log = EventLog::spawn(dir.path(), receiver);
+
+        sender
+            .send(OrchestratorEvent::IterationStarted { iteration: 1 })
+            .unwrap();
+        sender
+            .send(OrchestratorEvent::Finalized {
+                status: "approved".to_string(),
+                score: 0.9,
+                iterations: 1,
+            })
+            .unwrap();
+        log.finish().await;
+
+        let records = read_event_log(dir.path());
+        assert_eq!(records.len(), 2);
+        assert_eq!(
+            records[0].event,
+            OrchestratorEvent::IterationStarted { iteration: 1 }
+        );
+        assert!(matches!(
+            records[1].event,
+            OrchestratorEvent::Finalized { .. }
+        ));
+    }
+}
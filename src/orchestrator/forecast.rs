@@ -0,0 +1,519 @@
+//! Budget forecasting from past sessions.
+//!
+//! Averages the tokens of recorded iterations (`iteration_history`) by role
+//! and by model over the `orchestration-state.json` files in
+//! `.plan-forge/*/`, and projects them
+//! against the guardrail budgets: before a run (`plan-forge run --estimate`),
+//! in `check_limits`, and before each new plan, where a shortfall pauses the
+//! session instead of letting it hard-stop mid-iteration.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use serde::Serialize;
+
+use super::guardrails::{Guardrails, TokenRole, role_limit};
+use super::orchestration_state::{OrchestrationState, OrchestrationStatus};
+
+/// Mean tokens per iteration for each role
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct RoleTokens {
+    pub orchestrator: f64,
+    pub planner: f64,
+    pub reviewer: f64,
+}
+
+impl RoleTokens {
+    /// Tokens for `role`
+    pub fn get(&self, role: TokenRole) -> f64 {
+        match role {
+            TokenRole::Orchestrator => self.orchestrator,
+            TokenRole::Planner => self.planner,
+            TokenRole::Reviewer => self.reviewer,
+        }
+    }
+
+    /// Tokens across all roles
+    pub fn total(&self) -> f64 {
+        self.orchestrator + self.planner + self.reviewer
+    }
+}
+
+/// Mean input/output tokens per iteration for one model
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ModelTokens {
+    pub input: f64,
+    pub output: f64,
+}
+
+/// Per-iteration usage averaged over sessions.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Forecast {
+    /// Sessions with at least one iteration
+    pub sessions: usize,
+    /// Iterations across those sessions
+    pub iterations: u32,
+    /// Mean iterations of completed sessions (None = no completed session)
+    pub iterations_per_session: Option<f64>,
+    /// Mean tokens per iteration by role
+    pub tokens_per_iteration: RoleTokens,
+    /// Mean tokens per iteration by "provider/model" label
+    pub model_tokens_per_iteration: BTreeMap<String, ModelTokens>,
+}
+
+/// Projected usage of a run against the configured budgets.
+#[derive(Debug, Clone, Serialize)]
+pub struct Estimate {
+    /// Iterations the run is expected to take
+    pub iterations: u32,
+    pub tokens: u64,
+    pub role_tokens: RoleTokens,
+    /// Projected spend (None when no model usage is recorded)
+    pub cost_usd: Option<f64>,
+    /// Budgets the projection exceeds
+    pub over_budget: Vec<String>,
+}
+
+impl Estimate {
+    /// Whether the run is projected to finish within every budget
+    pub fn fits_budget(&self) -> bool {
+        self.over_budget.is_empty()
+    }
+}
+
+/// The next iteration is not expected to fit in a remaining budget.
+#[derive(Debug, Clone, Serialize)]
+pub struct BudgetShortfall {
+    /// Iterations left before `max_iterations`
+    pub remaining_iterations: u32,
+    /// Whole iterations the tightest remaining budget can pay for
+    pub affordable_iterations: u32,
+    pub explanation: String,
+}
+
+impl Forecast {
+    /// Forecast from every session under `runs_root` (e.g. `.plan-forge/`),
+    /// skipping `exclude` (the current session) and unreadable state files.
+    pub fn from_runs_dir(runs_root: &Path, exclude: Option<&Path>) -> Self {
+        let Ok(entries) = std::fs::read_dir(runs_root) else {
+            return Self::default();
+        };
+        let states: Vec<OrchestrationState> = entries
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| path.is_dir() && exclude.is_none_or(|ex| ex != path.as_path()))
+            .filter_map(|path| OrchestrationState::load(&path).ok().flatten())
+            .collect();
+        Self::from_states(&states)
+    }
+
+    /// Forecast from loaded session states.
+    ///
+    /// Per-iteration tokens come from `iteration_history` records that carry
+    /// role tokens; each session's model usage only sets the mix of models
+    /// those tokens are spread over.
+    pub fn from_states<'a>(states: impl IntoIterator<Item = &'a OrchestrationState>) -> Self {
+        let mut forecast = Self::default();
+        let mut totals = RoleTokens::default();
+        let mut completed = Vec::new();
+
+        for state in states {
+            let mut session = RoleTokens::default();
+            let mut recorded = 0u32;
+            for tokens in state.iteration_history.iter().filter_map(|r| r.role_tokens) {
+                recorded += 1;
+                session.orchestrator += tokens.orchestrator as f64;
+                session.planner += tokens.planner as f64;
+                session.reviewer += tokens.reviewer as f64;
+            }
+            if recorded == 0 {
+                continue;
+            }
+            forecast.sessions += 1;
+            forecast.iterations += recorded;
+            if matches!(
+                state.status,
+                OrchestrationStatus::Completed | OrchestrationStatus::CompletedBestEffort
+            ) {
+                completed.push(state.iteration as f64);
+            }
+
+            totals.orchestrator += session.orchestrator;
+            totals.planner += session.planner;
+            totals.reviewer += session.reviewer;
+            let model_total: u64 = state
+                .model_usage
+                .values()
+                .map(|usage| usage.input_tokens + usage.output_tokens)
+                .sum();
+            if model_total == 0 {
+                continue;
+            }
+            let scale = session.total() / model_total as f64;
+            for (label, usage) in &state.model_usage {
+                let model = forecast
+                    .model_tokens_per_iteration
+                    .entry(label.clone())
+                    .or_default();
+                model.input += usage.input_tokens as f64 * scale;
+                model.output += usage.output_tokens as f64 * scale;
+            }
+        }
+
+        if forecast.iterations == 0 {
+            return forecast;
+        }
+        let iterations = forecast.iterations as f64;
+        forecast.tokens_per_iteration = RoleTokens {
+            orchestrator: totals.orchestrator / iterations,
+            planner: totals.planner / iterations,
+            reviewer: totals.reviewer / iterations,
+        };
+        for model in forecast.model_tokens_per_iteration.values_mut() {
+            model.input /= iterations;
+            model.output /= iterations;
+        }
+        forecast.iterations_per_session =
+            (!completed.is_empty()).then(|| completed.iter().sum::<f64>() / completed.len() as f64);
+        forecast
+    }
+
+    /// Whether there is any history to forecast from
+    pub fn is_empty(&self) -> bool {
+        self.iterations == 0
+    }
+
+    /// Projected spend per iteration (None when no model usage is recorded).
+    /// Unpriced models count as free, as in the cost budget.
+    pub fn cost_per_iteration(&self, guardrails: &Guardrails) -> Option<f64> {
+        if self.model_tokens_per_iteration.is_empty() {
+            return None;
+        }
+        Some(
+            self.model_tokens_per_iteration
+                .iter()
+                .filter_map(|(label, tokens)| {
+                    let price = guardrails.price_for(label)?;
+                    Some(
+                        (tokens.input * price.input_per_mtok
+                            + tokens.output * price.output_per_mtok)
+                            / 1_000_000.0,
+                    )
+                })
+                .sum(),
+        )
+    }
+
+    /// Project a new run: completed sessions' mean iteration count (or
+    /// `max_iterations` without one), capped at `max_iterations`.
+    pub fn estimate(&self, guardrails: &Guardrails) -> Estimate {
+        let iterations = self
+            .iterations_per_session
+            .map_or(guardrails.max_iterations, |mean| mean.ceil() as u32)
+            .min(guardrails.max_iterations);
+        let n = iterations as f64;
+        let per_iteration = &self.tokens_per_iteration;
+        let role_tokens = RoleTokens {
+            orchestrator: per_iteration.orchestrator * n,
+            planner: per_iteration.planner * n,
+            reviewer: per_iteration.reviewer * n,
+        };
+        let tokens = role_tokens.total().round() as u64;
+        let cost_usd = self.cost_per_iteration(guardrails).map(|cost| cost * n);
+
+        let mut over_budget = Vec::new();
+        if tokens > guardrails.max_total_tokens {
+            over_budget.push(format!(
+                "max_total_tokens: {} projected > {}",
+                tokens, guardrails.max_total_tokens
+            ));
+        }
+        for role in [
+            TokenRole::Orchestrator,
+            TokenRole::Planner,
+            TokenRole::Reviewer,
+        ] {
+            if let Some(limit) = role_limit(&guardrails.token_budgets, role)
+                && role_tokens.get(role) > limit as f64
+            {
+                over_budget.push(format!(
+                    "token_budgets.{}: {:.0} projected > {}",
+                    role,
+                    role_tokens.get(role),
+                    limit
+                ));
+            }
+        }
+        let budgets = &guardrails.token_budgets;
+        for (role, limit) in [
+            (TokenRole::Planner, budgets.planner_per_iteration),
+            (TokenRole::Reviewer, budgets.reviewer_per_iteration),
+        ] {
+            if let Some(limit) = limit
+                && per_iteration.get(role) > limit as f64
+            {
+                over_budget.push(format!(
+                    "token_budgets.{}_per_iteration: {:.0} projected > {}",
+                    role,
+                    per_iteration.get(role),
+                    limit
+                ));
+            }
+        }
+        if let (Some(cost), Some(limit)) = (cost_usd, guardrails.max_cost_usd)
+            && cost > limit
+        {
+            over_budget.push(format!(
+                "max_cost_usd: ${:.2} projected > ${:.2}",
+                cost, limit
+            ));
+        }
+
+        Estimate {
+            iterations,
+            tokens,
+            role_tokens,
+            cost_usd,
+            over_budget,
+        }
+    }
+
+    /// Per-iteration forecast for a running session: its own recorded
+    /// iterations when it has any, otherwise this history.
+    pub fn for_session(&self, state: &OrchestrationState) -> Self {
+        let own = Self::from_states([state]);
+        if own.is_empty() { self.clone() } else { own }
+    }
+
+    /// Check whether the next iteration of `state` fits in its remaining
+    /// token, role, and cost budgets.
+    pub fn shortfall(
+        &self,
+        guardrails: &Guardrails,
+        state: &OrchestrationState,
+    ) -> Option<BudgetShortfall> {
+        let forecast = self.for_session(state);
+        let per_iteration = &forecast.tokens_per_iteration;
+        let remaining_iterations = guardrails.max_iterations.saturating_sub(state.iteration);
+        if forecast.is_empty() || remaining_iterations == 0 {
+            return None;
+        }
+
+        // (budget name, remaining, projected per iteration)
+        let mut budgets = vec![(
+            "token".to_string(),
+            guardrails
+                .max_total_tokens
+                .saturating_sub(state.total_tokens) as f64,
+            per_iteration.total(),
+        )];
+        let breakdown = &state.token_breakdown;
+        for role in [
+            TokenRole::Orchestrator,
+            TokenRole::Planner,
+            TokenRole::Reviewer,
+        ] {
+            let used = breakdown.role_total(role);
+            if let Some(limit) = role_limit(&guardrails.token_budgets, role) {
+                budgets.push((
+                    format!("{} token", role),
+                    limit.saturating_sub(used) as f64,
+                    per_iteration.get(role),
+                ));
+            }
+        }
+        if let (Some(limit), Some(per_iteration)) = (
+            guardrails.max_cost_usd,
+            forecast.cost_per_iteration(guardrails),
+        ) {
+            let spent = guardrails.session_cost(state).usd;
+            budgets.push(("cost".to_string(), (limit - spent).max(0.0), per_iteration));
+        }
+
+        let (name, remaining, projected) = budgets
+            .into_iter()
+            .filter(|(_, _, projected)| *projected > 0.0)
+            .min_by(|a, b| (a.1 / a.2).total_cmp(&(b.1 / b.2)))?;
+        let affordable_iterations = (remaining / projected).floor() as u32;
+        if affordable_iterations >= 1 {
+            return None;
+        }
+
+        let amount = |value: f64| {
+            if name == "cost" {
+                format!("${:.2}", value)
+            } else {
+                format!("{:.0} tokens", value)
+            }
+        };
+        Some(BudgetShortfall {
+            remaining_iterations,
+            affordable_iterations,
+            explanation: format!(
+                "The next iteration is projected to use {} but only {} of the {} budget remains ({} iteration(s) left before max_iterations). Raise the budget and resume, or reply to continue anyway.",
+                amount(projected),
+                amount(remaining),
+                name,
+                remaining_iterations
+            ),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{ModelPrice, TokenBudgetsConfig};
+    use crate::orchestrator::{IterationOutcome, IterationRecord, RoleTokenCounts};
+
+    fn make_record(iteration: u32, role_tokens: Option<RoleTokenCounts>) -> IterationRecord {
+        IterationRecord {
+            iteration,
+            timestamp: String::new(),
+            viability_violations: 0,
+            viability_critical: 0,
+            viability_passed: true,
+            review_score: Some(0.5),
+            review_passed: Some(false),
+            tool_calls_this_iteration: 2,
+            tokens_this_iteration: 0,
+            outcome: IterationOutcome::ReviewFailed,
+            reviewer_scores: Vec::new(),
+            critical_rule_ids: Vec::new(),
+            rung: 0,
+            role_tokens,
+        }
+    }
+
+    /// Session with `iterations` records splitting the tokens evenly
+    fn make_state(iterations: u32, planner: u64, reviewer: u64) -> OrchestrationState {
+        let mut state = OrchestrationState::new(
+            "test".to_string(),
+            "task".to_string(),
+            std::path::PathBuf::new(),
+            "slug".to_string(),
+        );
+        state.iteration = iterations;
+        state.status = OrchestrationStatus::Completed;
+        for iteration in 1..=iterations {
+            let tokens = RoleTokenCounts {
+                orchestrator: 0,
+                planner: planner / iterations as u64,
+                reviewer: reviewer / iterations as u64,
+            };
+            state
+                .iteration_history
+                .push(make_record(iteration, Some(tokens)));
+        }
+        state.token_breakdown.add_planner(planner, 0);
+        state.token_breakdown.add_reviewer(reviewer, 0);
+        state.total_tokens = planner + reviewer;
+        state.add_model_usage(
+            "anthropic/claude-sonnet-4-5",
+            Some((planner + reviewer) as i32),
+            Some(0),
+        );
+        state
+    }
+
+    #[test]
+    fn test_forecast_and_estimate() {
+        let history = [make_state(2, 40_000, 20_000), make_state(4, 80_000, 60_000)];
+        let forecast = Forecast::from_states(&history);
+        assert_eq!(forecast.sessions, 2);
+        assert_eq!(forecast.tokens_per_iteration.planner, 20_000.0);
+        assert_eq!(forecast.tokens_per_iteration.reviewer, 80_000.0 / 6.0);
+        assert_eq!(forecast.iterations_per_session, Some(3.0));
+
+        let guardrails = Guardrails {
+            max_total_tokens: 90_000,
+            pricing: vec![ModelPrice {
+                model: "claude-sonnet-4-5".to_string(),
+                input_per_mtok: 3.0,
+                output_per_mtok: 15.0,
+            }],
+            token_budgets: TokenBudgetsConfig {
+                planner: Some(100_000),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let estimate = forecast.estimate(&guardrails);
+        assert_eq!(estimate.iterations, 3);
+        assert_eq!(estimate.tokens, 100_000);
+        assert!((estimate.cost_usd.unwrap() - 0.3).abs() < 1e-9);
+        assert_eq!(estimate.over_budget.len(), 1);
+        assert!(estimate.over_budget[0].starts_with("max_total_tokens"));
+        assert!(!estimate.fits_budget());
+    }
+
+    #[test]
+    fn test_shortfall_before_next_iteration() {
+        let guardrails = Guardrails {
+            max_total_tokens: 100_000,
+            ..Default::default()
+        };
+        let history = Forecast::from_states(&[make_state(2, 40_000, 20_000)]);
+
+        // A fresh session uses history: 30k per iteration fits in 100k
+        let mut state = make_state(0, 0, 0);
+        assert!(history.shortfall(&guardrails, &state).is_none());
+
+        // The session's own average takes over once it has iterations
+        state = make_state(2, 60_000, 20_000);
+        let shortfall = history.shortfall(&guardrails, &state).expect("shortfall");
+        assert_eq!(shortfall.affordable_iterations, 0);
+        assert!(shortfall.explanation.contains("40000 tokens"));
+        assert!(shortfall.explanation.contains("20000 tokens"));
+
+        // No history and no iterations yet: nothing to project
+        assert!(
+            Forecast::default()
+                .shortfall(&guardrails, &make_state(0, 0, 0))
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_forecast_skips_records_without_role_tokens() {
+        // Records from before v5 carry no role tokens
+        let mut legacy = make_state(0, 0, 0);
+        legacy.iteration = 3;
+        legacy.token_breakdown.add_planner(90_000, 0);
+        legacy.iteration_history = (1..=3).map(|i| make_record(i, None)).collect();
+        assert!(Forecast::from_states(&[legacy.clone()]).is_empty());
+
+        // Tokens of an unfinished iteration don't inflate the average
+        let mut state = make_state(2, 40_000, 20_000);
+        state.iteration = 3;
+        state.token_breakdown.add_planner(50_000, 0);
+        let forecast = Forecast::from_states(&[legacy, state]);
+        assert_eq!(forecast.sessions, 1);
+        assert_eq!(forecast.iterations, 2);
+        assert_eq!(forecast.tokens_per_iteration.planner, 20_000.0);
+    }
+
+    #[test]
+    fn test_from_runs_dir() {
+        let runs = tempfile::tempdir().unwrap();
+        make_state(2, 40_000, 20_000)
+            .save(&runs.path().join("first"))
+            .unwrap();
+        make_state(4, 80_000, 60_000)
+            .save(&runs.path().join("second"))
+            .unwrap();
+        std::fs::create_dir_all(runs.path().join("empty")).unwrap();
+        std::fs::write(runs.path().join("notes.txt"), "not a session").unwrap();
+
+        let forecast = Forecast::from_runs_dir(runs.path(), None);
+        assert_eq!(forecast.sessions, 2);
+        assert_eq!(forecast.iterations, 6);
+
+        // The current session is left out
+        let current = runs.path().join("second");
+        let forecast = Forecast::from_runs_dir(runs.path(), Some(current.as_path()));
+        assert_eq!(forecast.sessions, 1);
+        assert_eq!(forecast.tokens_per_iteration.planner, 20_000.0);
+
+        assert!(Forecast::from_runs_dir(&runs.path().join("missing"), None).is_empty());
+    }
+}
@@ -0,0 +1,492 @@
+//! Executable grounding gates.
+//!
+//! Phase 0.0 gates may carry a machine-checkable `GateCheck` (file exists,
+//! glob count, regex in file, command exit status). `GateRunner` evaluates
+//! them against the working directory and reports pass/fail per gate ID.
+//! Gates without a check, and command gates when commands aren't allowed,
+//! are reported as skipped rather than failed. Paths must stay inside the
+//! working directory, and commands with V-015 findings are refused.
+
+use std::path::{Component, Path, PathBuf};
+use std::time::Duration;
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::config::GatesConfig;
+use crate::models::{GateCheck, GroundingGate};
+
+use super::viability::analyze_command;
+
+// ============================================================================
+// Gate Results
+// ============================================================================
+
+/// Outcome of evaluating a single gate
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GateStatus {
+    Passed,
+    Failed,
+    /// Not evaluated (prose-only gate, or command gate without `--allow-commands`)
+    Skipped,
+}
+
+/// Result for one grounding gate
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GateResult {
+    /// Gate identifier (e.g., "0.0.1")
+    pub id: String,
+    pub status: GateStatus,
+    /// What was checked and why it passed/failed
+    pub detail: String,
+}
+
+/// Results for all gates in a plan
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GateReport {
+    pub results: Vec<GateResult>,
+}
+
+impl GateReport {
+    /// True when no gate failed (skipped gates don't block)
+    pub fn passed(&self) -> bool {
+        self.failed().next().is_none()
+    }
+
+    /// Gates that failed
+    pub fn failed(&self) -> impl Iterator<Item = &GateResult> {
+        self.results
+            .iter()
+            .filter(|r| r.status == GateStatus::Failed)
+    }
+
+    /// Number of gates with the given status
+    pub fn count(&self, status: GateStatus) -> usize {
+        self.results.iter().filter(|r| r.status == status).count()
+    }
+}
+
+// ============================================================================
+// Gate Runner
+// ============================================================================
+
+/// Evaluates machine-checkable grounding gates in a working directory
+#[derive(Debug, Clone)]
+pub struct GateRunner {
+    working_dir: PathBuf,
+    allow_commands: bool,
+    command_timeout: Duration,
+}
+
+impl GateRunner {
+    /// Create a runner for `working_dir` (commands disabled)
+    pub fn new(working_dir: PathBuf) -> Self {
+        Self {
+            working_dir,
+            allow_commands: false,
+            command_timeout: Duration::from_secs(120),
+        }
+    }
+
+    /// Create a runner using the `gates` config section (commands disabled)
+    pub fn from_config(working_dir: PathBuf, config: &GatesConfig) -> Self {
+        Self::new(working_dir)
+            .with_command_timeout(Duration::from_secs(config.command_timeout_secs))
+    }
+
+    /// Allow `command_succeeds` gates to run shell commands.
+    ///
+    /// Only the `--allow-commands` CLI flag should turn this on; config
+    /// files and MCP callers cannot.
+    pub fn with_allow_commands(mut self, allow_commands: bool) -> Self {
+        self.allow_commands = allow_commands;
+        self
+    }
+
+    /// Maximum time a gate command may run before it fails
+    pub fn with_command_timeout(mut self, command_timeout: Duration) -> Self {
+        self.command_timeout = command_timeout;
+        self
+    }
+
+    /// Evaluate all gates in order
+    pub async fn run(&self, gates: &[GroundingGate]) -> GateReport {
+        let mut report = GateReport::default();
+        for gate in gates {
+            let (status, detail) = match &gate.check {
+                Some(check) => self.check(check).await,
+                None => (
+                    GateStatus::Skipped,
+                    "No machine-checkable form; verify manually".to_string(),
+                ),
+            };
+            report.results.push(GateResult {
+                id: gate.id.clone(),
+                status,
+                detail,
+            });
+        }
+        report
+    }
+
+    /// Evaluate a single check
+    pub async fn check(&self, check: &GateCheck) -> (GateStatus, String) {
+        match check {
+            GateCheck::FileExists { path } => {
+                let full = match self.resolve(path) {
+                    Ok(full) => full,
+                    Err(detail) => return (GateStatus::Failed, detail),
+                };
+                if full.exists() {
+                    (GateStatus::Passed, format!("`{}` exists", path))
+                } else {
+                    (GateStatus::Failed, format!("`{}` does not exist", path))
+                }
+            }
+            GateCheck::GlobMatches { pattern, min, max } => self.check_glob(pattern, *min, *max),
+            GateCheck::RegexInFile { path, pattern } => self.check_regex(path, pattern),
+            GateCheck::CommandSucceeds { command } => self.check_command(command).await,
+        }
+    }
+
+    /// Join `path` onto the working directory, rejecting absolute paths and
+    /// `..` components so gates cannot look outside it
+    fn resolve(&self, path: &str) -> Result<PathBuf, String> {
+        let escapes = Path::new(path).components().any(|c| {
+            matches!(
+                c,
+                Component::ParentDir | Component::RootDir | Component::Prefix(_)
+            )
+        });
+        if escapes {
+            return Err(format!(
+                "`{}` is outside the working directory (absolute paths and `..` are not allowed)",
+                path
+            ));
+        }
+        Ok(self.working_dir.join(path))
+    }
+
+    fn check_glob(&self, pattern: &str, min: usize, max: Option<usize>) -> (GateStatus, String) {
+        let full = match self.resolve(pattern) {
+            Ok(full) => full,
+            Err(detail) => return (GateStatus::Failed, detail),
+        };
+        let paths = match glob::glob(&full.to_string_lossy()) {
+            Ok(paths) => paths,
+            Err(e) => {
+                return (
+                    GateStatus::Failed,
+                    format!("Invalid glob `{}`: {}", pattern, e),
+                );
+            }
+        };
+        let count = paths.filter_map(Result::ok).filter(|p| p.is_file()).count();
+        let expected = match max {
+            Some(max) if max == min => format!("{}", min),
+            Some(max) => format!("{}..={}", min, max),
+            None => format!(">= {}", min),
+        };
+        let detail = format!(
+            "`{}` matched {} file(s) (expected {})",
+            pattern, count, expected
+        );
+        if count >= min && max.is_none_or(|max| count <= max) {
+            (GateStatus::Passed, detail)
+        } else {
+            (GateStatus::Failed, detail)
+        }
+    }
+
+    fn check_regex(&self, path: &str, pattern: &str) -> (GateStatus, String) {
+        let re = match Regex::new(pattern) {
+            Ok(re) => re,
+            Err(e) => {
+                return (
+                    GateStatus::Failed,
+                    format!("Invalid regex `{}`: {}", pattern, e),
+                );
+            }
+        };
+        let full = match self.resolve(path) {
+            Ok(full) => full,
+            Err(detail) => return (GateStatus::Failed, detail),
+        };
+        match std::fs::read_to_string(full) {
+            Ok(content) => match content.lines().position(|line| re.is_match(line)) {
+                Some(idx) => (
+                    GateStatus::Passed,
+                    format!("`{}` found in {}:{}", pattern, path, idx + 1),
+                ),
+                None if re.is_match(&content) => (
+                    GateStatus::Passed,
+                    format!("`{}` found in {}", pattern, path),
+                ),
+                None => (
+                    GateStatus::Failed,
+                    format!("`{}` not found in {}", pattern, path),
+                ),
+            },
+            Err(e) => (
+                GateStatus::Failed,
+                format!("Failed to read {}: {}", path, e),
+            ),
+        }
+    }
+
+    async fn check_command(&self, command: &str) -> (GateStatus, String) {
+        if !self.allow_commands {
+            return (
+                GateStatus::Skipped,
+                format!(
+                    "Command gates require --allow-commands (would run `{}`)",
+                    command
+                ),
+            );
+        }
+
+        // Never run a planner-written command that trips the V-015 checks
+        let findings = analyze_command(command, Some(&self.working_dir));
+        if !findings.is_empty() {
+            let reasons = findings
+                .iter()
+                .map(|f| format!("{} ({})", f.risk.label(), f.detail))
+                .collect::<Vec<_>>()
+                .join(", ");
+            return (
+                GateStatus::Failed,
+                format!("Refused to run `{}`: {}", command, reasons),
+            );
+        }
+
+        let child = tokio::process::Command::new("sh")
+            .arg("-c")
+            .arg(command)
+            .current_dir(&self.working_dir)
+            .stdin(std::process::Stdio::null())
+            .kill_on_drop(true)
+            .output();
+
+        match tokio::time::timeout(self.command_timeout, child).await {
+            Ok(Ok(output)) if output.status.success() => {
+                (GateStatus::Passed, format!("`{}` exited 0", command))
+            }
+            Ok(Ok(output)) => {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                let last_line = stderr.lines().rev().find(|l| !l.trim().is_empty());
+                let code = output
+                    .status
+                    .code()
+                    .map(|c| c.to_string())
+                    .unwrap_or_else(|| "signal".to_string());
+                let mut detail = format!("`{}` exited {}", command, code);
+                if let Some(line) = last_line {
+                    detail.push_str(&format!(": {}", line.trim()));
+                }
+                (GateStatus::Failed, detail)
+            }
+            Ok(Err(e)) => (
+                GateStatus::Failed,
+                format!("Failed to run `{}`: {}", command, e),
+            ),
+            Err(_) => (
+                GateStatus::Failed,
+                format!(
+                    "`{}` timed out after {}s",
+                    command,
+                    self.command_timeout.as_secs()
+                ),
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_gate(id: &str, check: Option<GateCheck>) -> GroundingGate {
+        GroundingGate {
+            id: id.to_string(),
+            verification: format!("Gate {}", id),
+            pass_criteria: "passes".to_string(),
+            rule: "stop".to_string(),
+            check,
+        }
+    }
+
+    fn workspace() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("src")).unwrap();
+        std::fs::write(dir.path().join("src/lib.rs"), "pub fn parse() {}\n").unwrap();
+        std::fs::write(dir.path().join("src/main.rs"), "fn main() {}\n").unwrap();
+        dir
+    }
+
+    #[tokio::test]
+    async fn test_file_glob_and_regex_gates() {
+        let dir = workspace();
+        let runner = GateRunner::new(dir.path().to_path_buf());
+        let gates = vec![
+            make_gate(
+                "0.0.1",
+                Some(GateCheck::FileExists {
+                    path: "src/lib.rs".to_string(),
+                }),
+            ),
+            make_gate(
+                "0.0.2",
+                Some(GateCheck::FileExists {
+                    path: "src/missing.rs".to_string(),
+                }),
+            ),
+            make_gate(
+                "0.0.3",
+                Some(GateCheck::GlobMatches {
+                    pattern: "src/*.rs".to_string(),
+                    min: 2,
+                    max: Some(2),
+                }),
+            ),
+            make_gate(
+                "0.0.4",
+                Some(GateCheck::RegexInFile {
+                    path: "src/lib.rs".to_string(),
+                    pattern: r"pub fn \w+".to_string(),
+                }),
+            ),
+            make_gate(
+                "0.0.5",
+                Some(GateCheck::RegexInFile {
+                    path: "src/main.rs".to_string(),
+                    pattern: "async fn".to_string(),
+                }),
+            ),
+            make_gate("0.0.6", None),
+        ];
+
+        let report = runner.run(&gates).await;
+        let statuses: Vec<GateStatus> = report.results.iter().map(|r| r.status).collect();
+        assert_eq!(
+            statuses,
+            vec![
+                GateStatus::Passed,
+                GateStatus::Failed,
+                GateStatus::Passed,
+                GateStatus::Passed,
+                GateStatus::Failed,
+                GateStatus::Skipped,
+            ]
+        );
+        assert!(!report.passed());
+        assert_eq!(
+            report.failed().map(|r| r.id.as_str()).collect::<Vec<_>>(),
+            vec!["0.0.2", "0.0.5"]
+        );
+        assert_eq!(
+            report.results[3].detail,
+            r"`pub fn \w+` found in src/lib.rs:1"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_command_gates_require_opt_in() {
+        let dir = workspace();
+        let gates = vec![
+            make_gate(
+                "0.0.1",
+                Some(GateCheck::CommandSucceeds {
+                    command: "test -f src/lib.rs".to_string(),
+                }),
+            ),
+            make_gate(
+                "0.0.2",
+                Some(GateCheck::CommandSucceeds {
+                    command: "exit 3".to_string(),
+                }),
+            ),
+        ];
+
+        let report = GateRunner::new(dir.path().to_path_buf()).run(&gates).await;
+        assert!(report.passed());
+        assert_eq!(report.count(GateStatus::Skipped), 2);
+
+        let report = GateRunner::new(dir.path().to_path_buf())
+            .with_allow_commands(true)
+            .run(&gates)
+            .await;
+        assert_eq!(report.results[0].status, GateStatus::Passed);
+        assert_eq!(report.results[1].status, GateStatus::Failed);
+        assert!(report.results[1].detail.contains("exited 3"));
+    }
+
+    #[tokio::test]
+    async fn test_paths_outside_working_dir_fail() {
+        let dir = workspace();
+        let runner = GateRunner::new(dir.path().join("src"));
+        let checks = [
+            GateCheck::FileExists {
+                path: "../src/lib.rs".to_string(),
+            },
+            GateCheck::FileExists {
+                path: dir.path().join("src/lib.rs").to_string_lossy().into_owned(),
+            },
+            GateCheck::GlobMatches {
+                pattern: "../**/*.rs".to_string(),
+                min: 1,
+                max: None,
+            },
+            GateCheck::RegexInFile {
+                path: "../src/lib.rs".to_string(),
+                pattern: "parse".to_string(),
+            },
+        ];
+        for check in &checks {
+            let (status, detail) = runner.check(check).await;
+            assert_eq!(status, GateStatus::Failed, "{:?}", check);
+            assert!(detail.contains("outside the working directory"));
+        }
+
+        let (status, _) = runner
+            .check(&GateCheck::FileExists {
+                path: "lib.rs".to_string(),
+            })
+            .await;
+        assert_eq!(status, GateStatus::Passed);
+    }
+
+    #[tokio::test]
+    async fn test_dangerous_commands_are_refused() {
+        let dir = workspace();
+        let marker = dir.path().join("ran");
+        let runner = GateRunner::new(dir.path().to_path_buf()).with_allow_commands(true);
+
+        let command = format!("touch {} && sudo true", marker.display());
+        let (status, detail) = runner.check(&GateCheck::CommandSucceeds { command }).await;
+        assert_eq!(status, GateStatus::Failed);
+        assert!(detail.starts_with("Refused to run"), "{}", detail);
+        assert!(detail.contains("sudo"));
+        assert!(!marker.exists());
+    }
+
+    #[test]
+    fn test_from_config_never_allows_commands() {
+        let runner = GateRunner::from_config(PathBuf::from("."), &GatesConfig::default());
+        assert!(!runner.allow_commands);
+    }
+
+    #[test]
+    fn test_gate_check_serde() {
+        let check: GateCheck =
+            serde_json::from_str(r#"{"type": "glob_matches", "pattern": "src/**/*.rs"}"#).unwrap();
+        assert_eq!(
+            check,
+            GateCheck::GlobMatches {
+                pattern: "src/**/*.rs".to_string(),
+                min: 1,
+                max: None
+            }
+        );
+    }
+}
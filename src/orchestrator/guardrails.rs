@@ -5,10 +5,12 @@
 //! ## Design Philosophy
 //!
 //! Guardrails enforce **hard stops** - non-bypassable limits:
-//! - Token budget exhaustion
+//! - Token budget exhaustion (total, per role, and per iteration)
 //! - Maximum iterations reached
 //! - Maximum tool calls exceeded
 //! - Execution timeout
+//! - Total session wall-clock time (across resumes)
+//! - Monetary budget, priced per model from the `pricing` table
 //!
 //! Human input requirements are handled separately by the LLM reviewer through
 //! the `requires_human_input` field, which has full context awareness for:
@@ -18,34 +20,142 @@
 //!
 //! This approach avoids the previous complexity of "soft limits" that would pause
 //! for human approval based on iteration counts or score thresholds.
+//!
+//! The one score-based check is stagnation detection: when review scores
+//! plateau or the same critical viability rules keep failing, further
+//! iterations are unlikely to help, so the session ends best-effort or pauses
+//! for human input (see `StagnationConfig`).
+//!
+//! Dangerous shell commands (`rm -rf` outside the workspace, force pushes,
+//! `curl | sh`, `sudo`, writes outside `working_dir`, `DROP TABLE`) are not
+//! left to the reviewer: they are detected deterministically by the viability
+//! checker (V-015) with severities from `CommandSafetyConfig`.
 
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
 
-use super::orchestration_state::OrchestrationState;
+use super::orchestration_state::{IterationOutcome, IterationRecord, OrchestrationState};
 
 // Re-export from config for convenience
-pub use crate::config::GuardrailsConfig;
+pub use crate::config::{
+    GuardrailsConfig, ModelPrice, StagnationAction, StagnationConfig, TokenBudgetsConfig,
+};
 
 // ============================================================================
 // Hard Stops (non-bypassable limits)
 // ============================================================================
 
+/// Component whose token usage is budgeted separately.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TokenRole {
+    Orchestrator,
+    Planner,
+    Reviewer,
+}
+
+impl std::fmt::Display for TokenRole {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TokenRole::Orchestrator => write!(f, "orchestrator"),
+            TokenRole::Planner => write!(f, "planner"),
+            TokenRole::Reviewer => write!(f, "reviewer"),
+        }
+    }
+}
+
 /// Hard stops that terminate the session - cannot be approved by human input.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum GuardrailHardStop {
     /// Token budget exhausted
     TokenBudgetExhausted { used: u64, limit: u64 },
+    /// One role's session token budget exhausted
+    RoleTokenBudgetExhausted {
+        role: TokenRole,
+        used: u64,
+        limit: u64,
+    },
+    /// One role used up its per-iteration token budget
+    IterationTokenBudgetExceeded {
+        role: TokenRole,
+        iteration: u32,
+        used: u64,
+        limit: u64,
+    },
     /// Maximum iterations exceeded
     MaxIterationsExceeded { iteration: u32, limit: u32 },
     /// Maximum tool calls exceeded
     MaxToolCallsExceeded { calls: u32, limit: u32 },
     /// Execution timeout
     ExecutionTimeout,
+    /// Total session wall-clock limit reached
+    SessionTimeExceeded { elapsed_secs: u64, limit_secs: u64 },
+    /// Monetary budget spent
+    CostBudgetExceeded { spent_usd: f64, limit_usd: f64 },
+    /// Monetary budget set, but some usage has no price so spend is unknown
+    UnpricedModelUsage { models: Vec<String> },
     /// Execution error (agent/provider failure)
     ExecutionError { message: String },
 }
 
+/// Session token limit configured for `role`
+pub(crate) fn role_limit(budgets: &TokenBudgetsConfig, role: TokenRole) -> Option<u64> {
+    match role {
+        TokenRole::Orchestrator => budgets.orchestrator,
+        TokenRole::Planner => budgets.planner,
+        TokenRole::Reviewer => budgets.reviewer,
+    }
+}
+
+// ============================================================================
+// Stagnation
+// ============================================================================
+
+/// Why planning was judged to have stalled.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Stagnation {
+    /// The best score did not improve by more than `epsilon` over the last
+    /// `window` reviews
+    ScorePlateau {
+        best_score: f32,
+        window: u32,
+        epsilon: f32,
+        recent_scores: Vec<f32>,
+    },
+    /// The same critical viability rules failed on consecutive iterations
+    RepeatedViolations { rule_ids: Vec<String>, times: u32 },
+}
+
+impl Stagnation {
+    /// Explanation used in logs, the pause reason, and the human question.
+    pub fn explanation(&self) -> String {
+        match self {
+            Stagnation::ScorePlateau {
+                best_score,
+                window,
+                epsilon,
+                recent_scores,
+            } => {
+                let scores: Vec<String> =
+                    recent_scores.iter().map(|s| format!("{:.2}", s)).collect();
+                format!(
+                    "Review scores have plateaued: the best score ({:.2}) did not improve by more than {:.2} over the last {} reviews (scores: {}).",
+                    best_score,
+                    epsilon,
+                    window,
+                    scores.join(", ")
+                )
+            }
+            Stagnation::RepeatedViolations { rule_ids, times } => format!(
+                "The same critical viability violations ({}) failed {} iterations in a row; the planner is not resolving them.",
+                rule_ids.join(", "),
+                times
+            ),
+        }
+    }
+}
+
 // ============================================================================
 // Guardrails Configuration
 // ============================================================================
@@ -66,6 +176,25 @@ pub struct Guardrails {
     pub execution_timeout: Duration,
     /// Review score threshold for determining pass/fail (default 0.8)
     pub score_threshold: f32,
+    /// Score-plateau detection
+    pub stagnation: StagnationConfig,
+    /// Total session wall-clock limit (None = no limit)
+    pub max_session_duration: Option<Duration>,
+    /// Spending limit in USD (None = no limit)
+    pub max_cost_usd: Option<f64>,
+    /// Per-model prices for the cost budget
+    pub pricing: Vec<ModelPrice>,
+    /// Token limits per role and per iteration
+    pub token_budgets: TokenBudgetsConfig,
+}
+
+/// Estimated spend of a session.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct SessionCost {
+    pub usd: f64,
+    /// Models with usage but no matching price (counted as free in `usd`;
+    /// with `max_cost_usd` set they stop the session)
+    pub unpriced_models: Vec<String>,
 }
 
 impl Default for Guardrails {
@@ -76,6 +205,11 @@ impl Default for Guardrails {
             max_total_tokens: 500_000,
             execution_timeout: Duration::from_secs(600), // 10 minutes
             score_threshold: 0.8,
+            stagnation: StagnationConfig::default(),
+            max_session_duration: None,
+            max_cost_usd: None,
+            pricing: Vec::new(),
+            token_budgets: TokenBudgetsConfig::default(),
         }
     }
 }
@@ -89,6 +223,11 @@ impl Guardrails {
             max_total_tokens: config.max_total_tokens,
             execution_timeout: Duration::from_secs(config.execution_timeout_secs),
             score_threshold: config.score_threshold,
+            stagnation: config.stagnation.clone(),
+            max_session_duration: config.max_session_secs.map(Duration::from_secs),
+            max_cost_usd: config.max_cost_usd,
+            pricing: config.pricing.clone(),
+            token_budgets: config.token_budgets.clone(),
         }
     }
 
@@ -122,9 +261,133 @@ impl Guardrails {
             });
         }
 
+        self.check_budgets(state)
+    }
+
+    /// Check the wall-clock and cost budgets.
+    ///
+    /// Also checked between orchestrator turns, since the orchestrator agent
+    /// spends time and money without calling tools.
+    pub fn check_budgets(&self, state: &OrchestrationState) -> Result<(), GuardrailHardStop> {
+        if let Some(limit) = self.max_session_duration {
+            let elapsed = Self::elapsed_secs(state);
+            if elapsed >= limit.as_secs() {
+                return Err(GuardrailHardStop::SessionTimeExceeded {
+                    elapsed_secs: elapsed,
+                    limit_secs: limit.as_secs(),
+                });
+            }
+        }
+
+        // Fail closed: spend on an unpriced model can't be counted
+        if let Some(limit) = self.max_cost_usd {
+            let cost = self.session_cost(state);
+            if !cost.unpriced_models.is_empty() {
+                return Err(GuardrailHardStop::UnpricedModelUsage {
+                    models: cost.unpriced_models,
+                });
+            }
+            if cost.usd >= limit {
+                return Err(GuardrailHardStop::CostBudgetExceeded {
+                    spent_usd: cost.usd,
+                    limit_usd: limit,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Check role token budgets before a planner or reviewer call.
+    ///
+    /// Checks the session budget of `role` and of the orchestrator, then the
+    /// per-iteration budgets of the latest iteration. A budget is spent once
+    /// usage reaches its limit; no further calls are made after that.
+    pub fn check_token_budget(
+        &self,
+        state: &OrchestrationState,
+        role: TokenRole,
+    ) -> Result<(), GuardrailHardStop> {
+        let budgets = &self.token_budgets;
+        let breakdown = &state.token_breakdown;
+        let session_limits = [
+            (role, role_limit(budgets, role)),
+            (TokenRole::Orchestrator, budgets.orchestrator),
+        ];
+        for (role, limit) in session_limits {
+            let used = breakdown.role_total(role);
+            if let Some(limit) = limit
+                && used >= limit
+            {
+                return Err(GuardrailHardStop::RoleTokenBudgetExhausted { role, used, limit });
+            }
+        }
+
+        let current = &state.iteration_tokens;
+        let iteration_limits = [
+            (
+                TokenRole::Planner,
+                current.planner,
+                budgets.planner_per_iteration,
+            ),
+            (
+                TokenRole::Reviewer,
+                current.reviewer,
+                budgets.reviewer_per_iteration,
+            ),
+        ];
+        for (role, used, limit) in iteration_limits {
+            if let Some(limit) = limit
+                && used >= limit
+            {
+                return Err(GuardrailHardStop::IterationTokenBudgetExceeded {
+                    role,
+                    iteration: current.iteration,
+                    used,
+                    limit,
+                });
+            }
+        }
+
         Ok(())
     }
 
+    /// Whole seconds the session has been running, across resumes
+    /// (pauses between runs don't count)
+    pub fn elapsed_secs(state: &OrchestrationState) -> u64 {
+        state.active_secs()
+    }
+
+    // ========================================================================
+    // Cost
+    // ========================================================================
+
+    /// Price for a "provider/model" label: an exact label match, then the
+    /// model name alone, then the `"*"` entry.
+    pub fn price_for(&self, label: &str) -> Option<&ModelPrice> {
+        let model = label.rsplit_once('/').map_or(label, |(_, model)| model);
+        self.pricing
+            .iter()
+            .find(|p| p.model == label)
+            .or_else(|| self.pricing.iter().find(|p| p.model == model))
+            .or_else(|| self.pricing.iter().find(|p| p.model == "*"))
+    }
+
+    /// Estimate the session's spend from its per-model token usage.
+    pub fn session_cost(&self, state: &OrchestrationState) -> SessionCost {
+        let mut cost = SessionCost::default();
+        for (label, usage) in &state.model_usage {
+            match self.price_for(label) {
+                Some(price) => {
+                    cost.usd += usage.input_tokens as f64 * price.input_per_mtok / 1_000_000.0
+                        + usage.output_tokens as f64 * price.output_per_mtok / 1_000_000.0;
+                }
+                None => cost.unpriced_models.push(label.clone()),
+            }
+        }
+        cost
+    }
+
     // ========================================================================
     // Score Threshold Check
     // ========================================================================
@@ -133,11 +396,77 @@ impl Guardrails {
     pub fn score_passes(&self, score: f32) -> bool {
         score >= self.score_threshold
     }
+
+    // ========================================================================
+    // Stagnation Check
+    // ========================================================================
+
+    /// Check whether planning has stalled since the last human input.
+    ///
+    /// Only iterations after the most recent human response and on the
+    /// current escalation rung count, so a human decision or a stronger
+    /// model gives the planner a fresh window.
+    pub fn check_stagnation(&self, state: &OrchestrationState) -> Option<Stagnation> {
+        let config = &self.stagnation;
+        if !config.enabled {
+            return None;
+        }
+
+        let since = state.human_inputs.last().map(|input| input.iteration);
+        let records: Vec<&IterationRecord> = state
+            .iteration_history
+            .iter()
+            .filter(|r| since.is_none_or(|iteration| r.iteration > iteration))
+            .filter(|r| r.rung == state.model_rung)
+            .filter(|r| r.outcome != IterationOutcome::TextResponseDetected)
+            .collect();
+
+        // Same critical rules failing on every recent iteration
+        let times = config.repeated_violations as usize;
+        if times > 0 && records.len() >= times {
+            let recent = &records[records.len() - times..];
+            let rule_ids = &recent[0].critical_rule_ids;
+            if !rule_ids.is_empty()
+                && recent.iter().all(|r| {
+                    r.outcome == IterationOutcome::ViabilityFailed
+                        && &r.critical_rule_ids == rule_ids
+                })
+            {
+                return Some(Stagnation::RepeatedViolations {
+                    rule_ids: rule_ids.clone(),
+                    times: config.repeated_violations,
+                });
+            }
+        }
+
+        // Best score of the last `window` reviews vs. the best before them
+        let window = config.window as usize;
+        let scores: Vec<f32> = records.iter().filter_map(|r| r.review_score).collect();
+        score_plateau(&scores, window, config.epsilon).map(|best_score| Stagnation::ScorePlateau {
+            best_score,
+            window: config.window,
+            epsilon: config.epsilon,
+            recent_scores: scores[scores.len() - window..].to_vec(),
+        })
+    }
+}
+
+/// Best score when the last `window` scores did not beat the best before them
+/// by more than `epsilon`; `None` while there are not more than `window` scores.
+pub(crate) fn score_plateau(scores: &[f32], window: usize, epsilon: f32) -> Option<f32> {
+    if window == 0 || scores.len() <= window {
+        return None;
+    }
+    let (earlier, recent) = scores.split_at(scores.len() - window);
+    let best_earlier = earlier.iter().copied().fold(f32::MIN, f32::max);
+    let best_recent = recent.iter().copied().fold(f32::MIN, f32::max);
+    (best_recent - best_earlier <= epsilon).then_some(best_earlier.max(best_recent))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::orchestrator::HumanInputRecord;
 
     #[test]
     fn test_score_passes() {
@@ -272,4 +601,278 @@ mod tests {
         let result = guardrails.check_before_tool_call(&state);
         assert!(result.is_ok(), "Should pass just below boundary");
     }
+
+    #[test]
+    fn test_hard_stop_session_time() {
+        let guardrails = Guardrails {
+            max_session_duration: Some(Duration::from_secs(3600)),
+            ..Default::default()
+        };
+
+        let mut state = make_state();
+        state.active_secs = 20 * 60;
+        state.run_started_iso =
+            Some((chrono::Utc::now() - chrono::Duration::minutes(30)).to_rfc3339());
+        assert!(guardrails.check_before_tool_call(&state).is_ok());
+
+        state.run_started_iso =
+            Some((chrono::Utc::now() - chrono::Duration::minutes(41)).to_rfc3339());
+        assert!(matches!(
+            guardrails.check_before_tool_call(&state),
+            Err(GuardrailHardStop::SessionTimeExceeded {
+                limit_secs: 3600,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_session_time_excludes_pauses() {
+        let guardrails = Guardrails {
+            max_session_duration: Some(Duration::from_secs(3600)),
+            ..Default::default()
+        };
+
+        // Started yesterday, ran 10 minutes, then paused overnight
+        let mut state = make_state();
+        state.start_time_iso = (chrono::Utc::now() - chrono::Duration::hours(16)).to_rfc3339();
+        state.run_started_iso =
+            Some((chrono::Utc::now() - chrono::Duration::minutes(10)).to_rfc3339());
+        state.end_run();
+        assert!(state.run_started_iso.is_none());
+        assert!((599..=601).contains(&state.active_secs));
+
+        // Resumed the next morning
+        state.start_run();
+        assert!(guardrails.check_before_tool_call(&state).is_ok());
+        assert!(Guardrails::elapsed_secs(&state) < 3600);
+    }
+
+    #[test]
+    fn test_hard_stop_cost_budget() {
+        let guardrails = Guardrails {
+            max_cost_usd: Some(1.0),
+            pricing: vec![
+                ModelPrice {
+                    model: "anthropic/claude-opus-4-5".to_string(),
+                    input_per_mtok: 5.0,
+                    output_per_mtok: 25.0,
+                },
+                ModelPrice {
+                    model: "claude-haiku-4-5".to_string(),
+                    input_per_mtok: 1.0,
+                    output_per_mtok: 5.0,
+                },
+            ],
+            ..Default::default()
+        };
+
+        let mut state = make_state();
+        state.add_model_usage("anthropic/claude-opus-4-5", Some(100_000), Some(10_000));
+        state.add_model_usage("openrouter/claude-haiku-4-5", Some(50_000), Some(0));
+
+        // 0.5 + 0.25 + 0.05
+        let cost = guardrails.session_cost(&state);
+        assert!((cost.usd - 0.8).abs() < 1e-9);
+        assert!(cost.unpriced_models.is_empty());
+        assert!(guardrails.check_before_tool_call(&state).is_ok());
+
+        state.add_model_usage("anthropic/claude-opus-4-5", Some(0), Some(10_000));
+        assert!(matches!(
+            guardrails.check_before_tool_call(&state),
+            Err(GuardrailHardStop::CostBudgetExceeded { limit_usd, .. }) if limit_usd == 1.0
+        ));
+    }
+
+    #[test]
+    fn test_cost_budget_fails_closed_on_unpriced_usage() {
+        let mut guardrails = Guardrails {
+            max_cost_usd: Some(100.0),
+            pricing: vec![ModelPrice {
+                model: "claude-haiku-4-5".to_string(),
+                input_per_mtok: 1.0,
+                output_per_mtok: 5.0,
+            }],
+            ..Default::default()
+        };
+
+        let mut state = make_state();
+        state.add_model_usage("anthropic/claude-haiku-4-5", Some(1_000), Some(0));
+        state.add_model_usage("openai/gpt-5", Some(1_000), Some(0));
+
+        let cost = guardrails.session_cost(&state);
+        assert_eq!(cost.unpriced_models, vec!["openai/gpt-5".to_string()]);
+        assert_eq!(
+            guardrails.check_before_tool_call(&state),
+            Err(GuardrailHardStop::UnpricedModelUsage {
+                models: vec!["openai/gpt-5".to_string()]
+            })
+        );
+
+        // Without a cost cap, unpriced usage is only reported
+        guardrails.max_cost_usd = None;
+        assert!(guardrails.check_before_tool_call(&state).is_ok());
+    }
+
+    #[test]
+    fn test_hard_stop_role_token_budget() {
+        let guardrails = Guardrails {
+            token_budgets: TokenBudgetsConfig {
+                planner: Some(10_000),
+                reviewer: Some(5_000),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let mut state = make_state();
+        state.token_breakdown.add_planner(8_000, 2_000);
+        state.token_breakdown.add_reviewer(1_000, 500);
+
+        // A runaway planner is stopped, but the reviewer keeps its share
+        assert_eq!(
+            guardrails.check_token_budget(&state, TokenRole::Planner),
+            Err(GuardrailHardStop::RoleTokenBudgetExhausted {
+                role: TokenRole::Planner,
+                used: 10_000,
+                limit: 10_000,
+            })
+        );
+        assert!(
+            guardrails
+                .check_token_budget(&state, TokenRole::Reviewer)
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_hard_stop_iteration_token_budget() {
+        let guardrails = Guardrails {
+            token_budgets: TokenBudgetsConfig {
+                planner_per_iteration: Some(20_000),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let mut state = make_state();
+        state.add_iteration_tokens(1, 15_000, 3_000);
+        assert!(
+            guardrails
+                .check_token_budget(&state, TokenRole::Reviewer)
+                .is_ok()
+        );
+
+        // Usage accumulates within an iteration and resets on the next one;
+        // reaching the limit spends it, as with session budgets
+        state.add_iteration_tokens(1, 5_000, 0);
+        assert!(matches!(
+            guardrails.check_token_budget(&state, TokenRole::Reviewer),
+            Err(GuardrailHardStop::IterationTokenBudgetExceeded {
+                role: TokenRole::Planner,
+                iteration: 1,
+                used: 20_000,
+                ..
+            })
+        ));
+        state.add_iteration_tokens(2, 5_000, 0);
+        assert_eq!(state.iteration_tokens.planner, 5_000);
+    }
+
+    fn make_state() -> OrchestrationState {
+        OrchestrationState::new(
+            "test".to_string(),
+            "task".to_string(),
+            std::path::PathBuf::new(),
+            "slug".to_string(),
+        )
+    }
+
+    fn make_record(iteration: u32, score: Option<f32>, critical: &[&str]) -> IterationRecord {
+        IterationRecord {
+            iteration,
+            timestamp: String::new(),
+            viability_violations: critical.len() as u32,
+            viability_critical: critical.len() as u32,
+            viability_passed: critical.is_empty(),
+            review_score: score,
+            review_passed: score.map(|_| false),
+            tool_calls_this_iteration: 2,
+            tokens_this_iteration: 0,
+            reviewer_scores: Vec::new(),
+            critical_rule_ids: critical.iter().map(|id| id.to_string()).collect(),
+            rung: 0,
+            outcome: if critical.is_empty() {
+                IterationOutcome::ReviewFailed
+            } else {
+                IterationOutcome::ViabilityFailed
+            },
+            role_tokens: None,
+        }
+    }
+
+    #[test]
+    fn test_stagnation_score_plateau() {
+        let guardrails = Guardrails::default(); // window 3, epsilon 0.02
+        let mut state = make_state();
+        for (i, score) in [0.5, 0.7, 0.71, 0.65, 0.7].into_iter().enumerate() {
+            state
+                .iteration_history
+                .push(make_record(i as u32 + 1, Some(score), &[]));
+        }
+
+        let stagnation = guardrails.check_stagnation(&state).expect("plateau");
+        assert!(matches!(
+            stagnation,
+            Stagnation::ScorePlateau { best_score, window: 3, .. } if best_score == 0.71
+        ));
+        assert!(stagnation.explanation().contains("0.71, 0.65, 0.70"));
+
+        // A real improvement in the window is progress
+        state.iteration_history[4].review_score = Some(0.78);
+        assert_eq!(guardrails.check_stagnation(&state), None);
+
+        // Human input resets the window
+        state.iteration_history[4].review_score = Some(0.7);
+        state.human_inputs.push(HumanInputRecord {
+            question: "q".to_string(),
+            category: "stagnation".to_string(),
+            response: Some("Use Redis".to_string()),
+            reason: None,
+            iteration: 3,
+            timestamp: String::new(),
+            approved: false,
+        });
+        assert_eq!(guardrails.check_stagnation(&state), None);
+    }
+
+    #[test]
+    fn test_stagnation_repeated_violations() {
+        let mut guardrails = Guardrails::default(); // 3 repeats
+        let mut state = make_state();
+        state
+            .iteration_history
+            .push(make_record(1, None, &["VIABILITY-001"]));
+        state
+            .iteration_history
+            .push(make_record(2, None, &["VIABILITY-003", "VIABILITY-006"]));
+        state
+            .iteration_history
+            .push(make_record(3, None, &["VIABILITY-003", "VIABILITY-006"]));
+        assert_eq!(guardrails.check_stagnation(&state), None);
+
+        state
+            .iteration_history
+            .push(make_record(4, None, &["VIABILITY-003", "VIABILITY-006"]));
+        assert_eq!(
+            guardrails.check_stagnation(&state),
+            Some(Stagnation::RepeatedViolations {
+                rule_ids: vec!["VIABILITY-003".to_string(), "VIABILITY-006".to_string()],
+                times: 3,
+            })
+        );
+
+        guardrails.stagnation.enabled = false;
+        assert_eq!(guardrails.check_stagnation(&state), None);
+    }
 }
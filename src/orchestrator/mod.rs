@@ -1,8 +1,18 @@
 pub mod client;
+pub mod code_search;
+pub mod driver;
+pub mod escalation;
+pub mod events;
+pub mod forecast;
+pub mod gates;
 pub mod guardrails;
 pub mod orchestration_state;
 pub mod policy;
+pub mod pre_grounding;
+pub mod repo_map;
+pub mod session_lock;
 pub mod state;
+pub mod state_migration;
 pub mod viability;
 
 // State exports
@@ -10,18 +20,36 @@ pub use state::{LoopResult, ResumeState};
 
 // New orchestrator exports
 pub use client::{
-    EXTENSION_NAME, OrchestratorClient, SessionRegistry, TokenUsage, create_orchestrator_client,
-    register_orchestrator_extension,
+    EXTENSION_NAME, OrchestratorClient, SessionRegistry, SpentUsage, TokenUsage,
+    create_orchestrator_client, register_orchestrator_extension,
+};
+pub use code_search::{CodeSearch, SearchHits};
+pub use driver::{DeterministicDriver, OrchestratorAction, next_action, review_feedback};
+pub use escalation::{ModelLadder, escalation_reason, rung_config, rung_label};
+pub use events::{
+    EVENT_CHANNEL_CAPACITY, EVENTS_FILE, EventLog, EventRecord, OrchestratorEvent, read_event_log,
+};
+pub use forecast::{BudgetShortfall, Estimate, Forecast, ModelTokens, RoleTokens};
+pub use gates::{GateReport, GateResult, GateRunner, GateStatus};
+pub use guardrails::{
+    GuardrailHardStop, Guardrails, GuardrailsConfig, SessionCost, Stagnation, TokenRole,
 };
-pub use guardrails::{GuardrailHardStop, Guardrails, GuardrailsConfig};
 pub use orchestration_state::{
-    HumanInputRecord, HumanResponse, IterationOutcome, IterationRecord, OrchestrationState,
-    OrchestrationStatus, TokenBreakdown,
+    CachedReview, ErrorClass, HumanInputRecord, HumanResponse, IterationOutcome, IterationRecord,
+    IterationTokens, ModelUsage, OrchestrationState, OrchestrationStatus, RetryRecord,
+    ReviewerScore, RoleTokenCounts, TokenBreakdown,
 };
 pub use policy::{
     PolicyCategory, PolicyFileFormat, PolicyRule, PolicySet, PolicySeverity, PolicyViolation,
     detect_format, discover_policies, extract_policies, verify_policies,
 };
+pub use pre_grounding::{DirSummary, PRE_GROUNDING_FILE, PreGrounding};
+pub use repo_map::{Symbol, SymbolIndex, SymbolKind};
+pub use session_lock::{LOCK_FILE, LockInfo, SessionLock, write_atomic, write_atomic_async};
+pub use state_migration::{
+    MigrationOutcome, STATE_FILE, migrate_all, migrate_session, migrate_value, schema_version_of,
+};
 pub use viability::{
-    DagMetrics, ViabilityChecker, ViabilityResult, ViabilitySeverity, ViabilityViolation,
+    AppliedFix, DagMetrics, PlanAutoFixer, ViabilityChecker, ViabilityResult, ViabilitySeverity,
+    ViabilityViolation,
 };
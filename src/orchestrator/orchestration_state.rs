@@ -3,6 +3,7 @@
 //! This module defines the state structure that persists across orchestration
 //! iterations and can be resumed after human input or system restart.
 
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 
@@ -10,11 +11,14 @@ use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
-use super::guardrails::GuardrailHardStop;
+use super::guardrails::{GuardrailHardStop, TokenRole};
+use super::session_lock::write_atomic;
+use super::state_migration::{migrate_value, schema_version_of};
 
 /// Current schema version for state files.
-/// Bump when adding/removing/modifying fields.
-pub const SCHEMA_VERSION: u32 = 2;
+/// Bump when adding/removing/modifying fields, and add the matching migrator
+/// to `state_migration`.
+pub const SCHEMA_VERSION: u32 = 5;
 
 // ============================================================================
 // Token Breakdown
@@ -48,10 +52,19 @@ impl TokenBreakdown {
         if self.total == 0 {
             return 0.0;
         }
-        let orchestrator_total = self.orchestrator_input + self.orchestrator_output;
+        let orchestrator_total = self.role_total(TokenRole::Orchestrator);
         orchestrator_total as f64 / self.total as f64
     }
 
+    /// Input plus output tokens spent by `role`.
+    pub fn role_total(&self, role: TokenRole) -> u64 {
+        match role {
+            TokenRole::Orchestrator => self.orchestrator_input + self.orchestrator_output,
+            TokenRole::Planner => self.planner_input + self.planner_output,
+            TokenRole::Reviewer => self.reviewer_input + self.reviewer_output,
+        }
+    }
+
     /// Add orchestrator tokens.
     pub fn add_orchestrator(&mut self, input: u64, output: u64) {
         self.orchestrator_input = self.orchestrator_input.saturating_add(input);
@@ -74,6 +87,31 @@ impl TokenBreakdown {
     }
 }
 
+/// Tokens consumed by one model, used to price the session.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ModelUsage {
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+}
+
+/// Planner and reviewer tokens spent on one iteration, for per-iteration
+/// token budgets.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct IterationTokens {
+    /// Iteration (plan version) the tokens were spent on
+    pub iteration: u32,
+    pub planner: u64,
+    pub reviewer: u64,
+}
+
+/// Tokens each role spent on one recorded iteration, for forecasting.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct RoleTokenCounts {
+    pub orchestrator: u64,
+    pub planner: u64,
+    pub reviewer: u64,
+}
+
 // ============================================================================
 // Orchestration Status
 // ============================================================================
@@ -94,6 +132,8 @@ pub enum OrchestrationStatus {
     },
     /// Session failed with error
     Failed { error: String },
+    /// Session cancelled by a signal or MCP cancellation (state saved, resumable)
+    Cancelled,
     /// Session hit a hard stop (cannot be resumed)
     HardStopped { reason: GuardrailHardStop },
 }
@@ -163,6 +203,58 @@ pub struct IterationRecord {
     pub tokens_this_iteration: u64,
     /// What happened in this iteration
     pub outcome: IterationOutcome,
+    /// Per-reviewer scores when a review panel is configured
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub reviewer_scores: Vec<ReviewerScore>,
+    /// Sorted, de-duplicated rule IDs of critical viability violations
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub critical_rule_ids: Vec<String>,
+    /// Escalation ladder rung the plan was generated on
+    #[serde(default)]
+    pub rung: u32,
+    /// Tokens by role (None for records from before v5 and for turns
+    /// without a plan, whose orchestrator tokens go to the next record)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub role_tokens: Option<RoleTokenCounts>,
+}
+
+/// One review panel member's verdict on a plan.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReviewerScore {
+    /// Panel member name from config
+    pub reviewer: String,
+    pub score: f32,
+    /// Whether this reviewer's score met the threshold on its own
+    pub passed: bool,
+}
+
+/// Whether a failed provider call is worth retrying
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorClass {
+    /// Transient: rate limit, timeout, overload, 5xx, connection failure
+    Retryable,
+    /// Retrying cannot help: auth, bad request, context length, bad output
+    Fatal,
+}
+
+/// A failed provider call that was retried, fell back, or gave up.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RetryRecord {
+    /// Role that made the call (planner, reviewer, orchestrator)
+    pub component: String,
+    /// Provider/model label of the failed call
+    pub target: String,
+    /// Whether the target was a fallback provider
+    pub fallback: bool,
+    /// Attempt number on this target (1-based)
+    pub attempt: u32,
+    pub class: ErrorClass,
+    pub error: String,
+    /// Backoff before the next attempt on the same target (0 = none)
+    pub delay_ms: u64,
+    /// Timestamp in ISO8601 format
+    pub timestamp: String,
 }
 
 // ============================================================================
@@ -228,6 +320,46 @@ pub struct OrchestrationState {
     /// Used to ensure the orchestrator calls review_plan after generate_plan.
     #[serde(default)]
     pub needs_review: bool,
+    /// Review of the selected best-of-N candidate, reused by the next
+    /// review_plan call if the plan is unchanged.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cached_review: Option<CachedReview>,
+    /// Current rung of the model escalation ladders (0 = cheapest)
+    #[serde(default)]
+    pub model_rung: u32,
+    /// Failed provider calls (retries and fallbacks) for diagnostics
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub retries: Vec<RetryRecord>,
+    /// Token usage per "provider/model" label, for the cost budget
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub model_usage: BTreeMap<String, ModelUsage>,
+    /// Planner/reviewer tokens of the latest iteration
+    #[serde(default)]
+    pub iteration_tokens: IterationTokens,
+    /// Wall-clock seconds of finished runs; time paused between runs
+    /// (e.g. waiting for a human) is not included
+    #[serde(default)]
+    pub active_secs: u64,
+    /// Start of the run in progress, in ISO8601 format
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub run_started_iso: Option<String>,
+}
+
+/// A review computed during candidate selection.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CachedReview {
+    /// The plan exactly as reviewed (after auto-fixes)
+    pub plan: Value,
+    /// Review JSON (same shape as a review_plan `llm_review`)
+    pub review: Value,
+}
+
+/// Whole seconds since an ISO8601 timestamp (0 if unparseable or in the future)
+fn seconds_since(timestamp: &str) -> u64 {
+    chrono::DateTime::parse_from_rfc3339(timestamp)
+        .map(|dt| (chrono::Utc::now() - dt.with_timezone(&chrono::Utc)).num_seconds())
+        .unwrap_or(0)
+        .max(0) as u64
 }
 
 impl OrchestrationState {
@@ -256,6 +388,13 @@ impl OrchestrationState {
             best_plan: None,
             best_score: 0.0,
             needs_review: false,
+            cached_review: None,
+            model_rung: 0,
+            retries: Vec::new(),
+            model_usage: BTreeMap::new(),
+            iteration_tokens: IterationTokens::default(),
+            active_secs: 0,
+            run_started_iso: None,
         }
     }
 
@@ -270,26 +409,88 @@ impl OrchestrationState {
             .saturating_add(output);
     }
 
+    /// Attribute tokens to a model for cost tracking (same rules as `add_tokens`).
+    pub fn add_model_usage(
+        &mut self,
+        model: &str,
+        input_tokens: Option<i32>,
+        output_tokens: Option<i32>,
+    ) {
+        let usage = self.model_usage.entry(model.to_string()).or_default();
+        usage.input_tokens = usage
+            .input_tokens
+            .saturating_add(input_tokens.map(|t| t.max(0) as u64).unwrap_or(0));
+        usage.output_tokens = usage
+            .output_tokens
+            .saturating_add(output_tokens.map(|t| t.max(0) as u64).unwrap_or(0));
+    }
+
+    /// Add planner and reviewer tokens spent on `iteration`, starting a fresh
+    /// count when the iteration changes.
+    pub fn add_iteration_tokens(&mut self, iteration: u32, planner: u64, reviewer: u64) {
+        if self.iteration_tokens.iteration != iteration {
+            self.iteration_tokens = IterationTokens {
+                iteration,
+                ..Default::default()
+            };
+        }
+        self.iteration_tokens.planner = self.iteration_tokens.planner.saturating_add(planner);
+        self.iteration_tokens.reviewer = self.iteration_tokens.reviewer.saturating_add(reviewer);
+    }
+
+    /// Planner and reviewer tokens spent on `iteration` so far, for its
+    /// iteration record.
+    pub fn role_tokens_for(&self, iteration: u32) -> RoleTokenCounts {
+        let current = &self.iteration_tokens;
+        if current.iteration != iteration {
+            return RoleTokenCounts::default();
+        }
+        RoleTokenCounts {
+            orchestrator: 0,
+            planner: current.planner,
+            reviewer: current.reviewer,
+        }
+    }
+
+    /// Review of `current_plan`, if it has one.
+    ///
+    /// A plan still waiting for review only has its candidate-selection
+    /// review; after a viability failure the last review is of an older plan.
+    pub fn current_plan_review(&self) -> Option<&Value> {
+        if self.needs_review {
+            return self
+                .cached_review
+                .as_ref()
+                .filter(|cached| self.current_plan.as_ref() == Some(&cached.plan))
+                .map(|cached| &cached.review);
+        }
+        let reviewed = self
+            .iteration_history
+            .iter()
+            .rev()
+            .find(|r| r.outcome != IterationOutcome::TextResponseDetected)
+            .is_some_and(|r| r.review_score.is_some());
+        self.reviews.last().filter(|_| reviewed)
+    }
+
     /// Save state to a JSON file using atomic write pattern.
     pub fn save(&self, session_dir: &Path) -> Result<()> {
         fs::create_dir_all(session_dir).context("Failed to create session directory")?;
 
         let state_file = session_dir.join("orchestration-state.json");
-        let temp_file = session_dir.join(".orchestration-state.json.tmp");
-
         let json = serde_json::to_string_pretty(self).context("Failed to serialize state")?;
 
-        // Write to temp file first
-        fs::write(&temp_file, &json).context("Failed to write temp state file")?;
-
-        // Atomic rename
-        fs::rename(&temp_file, &state_file).context("Failed to rename state file")?;
+        // Unique temp file + rename, so concurrent writers never interleave
+        write_atomic(&state_file, json).context("Failed to write state file")?;
 
         Ok(())
     }
 
     /// Load state from a session directory.
-    /// Returns None if file doesn't exist or migration fails.
+    /// Returns None if the file doesn't exist.
+    ///
+    /// States written with an older schema are migrated in memory; the file is
+    /// upgraded on disk by `state_migration::migrate_session`.
     pub fn load(session_dir: &Path) -> Result<Option<Self>> {
         let state_file = session_dir.join("orchestration-state.json");
 
@@ -298,44 +499,20 @@ impl OrchestrationState {
         }
 
         let json = fs::read_to_string(&state_file).context("Failed to read state file")?;
-        let state: Self = serde_json::from_str(&json).context("Failed to parse state file")?;
+        let mut raw: Value = serde_json::from_str(&json).context("Failed to parse state file")?;
 
         // Check schema version and migrate if needed
-        if state.schema_version != SCHEMA_VERSION {
-            tracing::warn!(
-                "State schema version mismatch: {} vs {}. Attempting migration.",
-                state.schema_version,
+        let version = schema_version_of(&raw)?;
+        if version != SCHEMA_VERSION {
+            tracing::debug!(
+                "Migrating state schema v{} to v{} in memory",
+                version,
                 SCHEMA_VERSION
             );
-            return Self::migrate(state);
-        }
-
-        Ok(Some(state))
-    }
-
-    /// Attempt to migrate state from older schema version.
-    fn migrate(state: Self) -> Result<Option<Self>> {
-        // Currently only version 1 exists, so no migrations needed yet
-        // Future migrations would go here:
-        // if state.schema_version == 0 {
-        //     // migrate from v0 to v1
-        //     state.schema_version = 1;
-        // }
-
-        if state.schema_version != SCHEMA_VERSION {
-            tracing::warn!(
-                "Cannot migrate state from version {} to {}. Starting fresh.",
-                state.schema_version,
-                SCHEMA_VERSION
-            );
-            // Preserve human_inputs for audit trail even when starting fresh
-            tracing::info!(
-                "Preserved {} human input records from old state",
-                state.human_inputs.len()
-            );
-            return Ok(None);
+            raw = migrate_value(raw)?;
         }
 
+        let state = serde_json::from_value(raw).context("Failed to parse state file")?;
         Ok(Some(state))
     }
 
@@ -347,11 +524,46 @@ impl OrchestrationState {
         chrono::Utc::now() - start
     }
 
+    /// Mark the start of a run; only running time counts toward the
+    /// session time limit.
+    pub fn start_run(&mut self) {
+        self.run_started_iso = Some(chrono::Utc::now().to_rfc3339());
+    }
+
+    /// Add the run in progress to `active_secs`.
+    pub fn end_run(&mut self) {
+        if let Some(started) = self.run_started_iso.take() {
+            self.active_secs = self.active_secs.saturating_add(seconds_since(&started));
+        }
+    }
+
+    /// Running time across all runs, excluding pauses between them.
+    pub fn active_secs(&self) -> u64 {
+        let current = self
+            .run_started_iso
+            .as_deref()
+            .map(seconds_since)
+            .unwrap_or(0);
+        self.active_secs.saturating_add(current)
+    }
+
     /// Check if the session can be resumed (not in hard-stopped state).
     pub fn can_resume(&self) -> bool {
         !matches!(self.status, OrchestrationStatus::HardStopped { .. })
     }
 
+    /// Prepare the state for another run: hard-stopped sessions can't be
+    /// resumed, and a cancelled session picks up where it stopped.
+    pub fn resume(&mut self) -> Result<()> {
+        if !self.can_resume() {
+            anyhow::bail!("Cannot resume session in {:?} state", self.status);
+        }
+        if matches!(self.status, OrchestrationStatus::Cancelled) {
+            self.status = OrchestrationStatus::Running;
+        }
+        Ok(())
+    }
+
     /// Generate a context summary for the LLM.
     pub fn generate_context_summary(&self) -> String {
         let mut summary = Vec::new();
@@ -431,6 +643,59 @@ mod tests {
         assert!(matches!(state.status, OrchestrationStatus::Running));
     }
 
+    #[test]
+    fn test_current_plan_review() {
+        let mut state = OrchestrationState::new(
+            "test".to_string(),
+            "task".to_string(),
+            PathBuf::new(),
+            "task".to_string(),
+        );
+        let record = |review_score: Option<f32>, outcome| IterationRecord {
+            iteration: 1,
+            timestamp: String::new(),
+            viability_violations: 0,
+            viability_critical: 0,
+            viability_passed: review_score.is_some(),
+            review_score,
+            review_passed: review_score.map(|_| false),
+            tool_calls_this_iteration: 2,
+            tokens_this_iteration: 0,
+            reviewer_scores: Vec::new(),
+            critical_rule_ids: Vec::new(),
+            rung: 0,
+            role_tokens: None,
+            outcome,
+        };
+        state.current_plan = Some(serde_json::json!({ "title": "v1" }));
+        state.reviews.push(serde_json::json!({ "score": 0.6 }));
+        state
+            .iteration_history
+            .push(record(Some(0.6), IterationOutcome::ReviewFailed));
+        state
+            .iteration_history
+            .push(record(None, IterationOutcome::TextResponseDetected));
+        assert_eq!(state.current_plan_review().unwrap()["score"], 0.6);
+
+        // A revised plan that failed viability has no review of its own
+        state.current_plan = Some(serde_json::json!({ "title": "v2" }));
+        state
+            .iteration_history
+            .push(record(None, IterationOutcome::ViabilityFailed));
+        assert!(state.current_plan_review().is_none());
+
+        // A plan waiting for review uses its candidate-selection review
+        state.needs_review = true;
+        state.current_plan = Some(serde_json::json!({ "title": "v3" }));
+        state.cached_review = Some(CachedReview {
+            plan: serde_json::json!({ "title": "v3" }),
+            review: serde_json::json!({ "score": 0.8 }),
+        });
+        assert_eq!(state.current_plan_review().unwrap()["score"], 0.8);
+        state.current_plan = Some(serde_json::json!({ "title": "v4" }));
+        assert!(state.current_plan_review().is_none());
+    }
+
     #[test]
     fn test_add_tokens() {
         let mut state = OrchestrationState::new(
@@ -450,6 +715,19 @@ mod tests {
         assert_eq!(state.total_tokens, 360);
     }
 
+    #[test]
+    fn test_token_breakdown_role_total() {
+        let mut breakdown = TokenBreakdown::default();
+        breakdown.add_orchestrator(10, 5);
+        breakdown.add_planner(100, 50);
+        breakdown.add_reviewer(30, 7);
+
+        assert_eq!(breakdown.role_total(TokenRole::Orchestrator), 15);
+        assert_eq!(breakdown.role_total(TokenRole::Planner), 150);
+        assert_eq!(breakdown.role_total(TokenRole::Reviewer), 37);
+        assert_eq!(breakdown.total, 202);
+    }
+
     #[test]
     fn test_save_and_load() {
         let dir = tempdir().unwrap();
@@ -488,6 +766,9 @@ mod tests {
         };
         assert!(state.can_resume());
 
+        state.status = OrchestrationStatus::Cancelled;
+        assert!(state.can_resume());
+
         state.status = OrchestrationStatus::HardStopped {
             reason: super::super::guardrails::GuardrailHardStop::ExecutionTimeout,
         };
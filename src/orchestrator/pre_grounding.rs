@@ -0,0 +1,592 @@
+//! Deterministic pre-grounding pass run before the planner.
+//!
+//! Builds an initial `GroundingSnapshot` from the working directory (key
+//! files, build targets, `use crate::` conventions) plus a file tree summary
+//! and the project's policy rules. The result is persisted in the session
+//! directory and injected into every planner prompt, so iterations reuse it
+//! instead of re-exploring the repository with tool calls.
+
+use anyhow::{Context, Result};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::warn;
+
+use super::policy::{PolicySet, discover_policies};
+use super::session_lock::write_atomic;
+use crate::models::{ExistingPattern, GroundingSnapshot, VerifiedFile, VerifiedTarget};
+
+/// File name of the persisted pre-grounding in the session directory
+pub const PRE_GROUNDING_FILE: &str = "pre-grounding.json";
+
+/// Directories never descended into
+const IGNORED_DIRS: &[&str] = &[
+    "target",
+    "node_modules",
+    "dist",
+    "build",
+    "vendor",
+    "__pycache__",
+];
+
+/// Manifests and entry points recorded as verified files when present
+const KEY_FILES: &[&str] = &[
+    "Cargo.toml",
+    "src/lib.rs",
+    "src/main.rs",
+    "package.json",
+    "go.mod",
+    "pyproject.toml",
+    "setup.py",
+    "Makefile",
+    "MODULE.bazel",
+    "WORKSPACE",
+    "README.md",
+    "CLAUDE.md",
+    "AGENT.md",
+];
+
+/// Directory depth summarized in the file tree
+const TREE_DEPTH: usize = 2;
+/// Maximum directories listed in the file tree summary
+const MAX_TREE_ENTRIES: usize = 40;
+/// Maximum files walked (keeps the pass fast on huge repos)
+const MAX_FILES: usize = 20_000;
+/// Maximum Rust files scanned for import conventions
+const MAX_RUST_FILES: usize = 500;
+/// Files larger than this are not scanned for imports
+const MAX_SCAN_BYTES: u64 = 256 * 1024;
+
+/// `use crate::module...` (captures the top-level module)
+static USE_CRATE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^\s*(?:pub(?:\([^)]*\))?\s+)?use\s+crate::(\w+)").expect("invalid USE_CRATE regex")
+});
+
+/// `use super::...`
+static USE_SUPER: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^\s*(?:pub(?:\([^)]*\))?\s+)?use\s+super::").expect("invalid USE_SUPER regex")
+});
+
+/// Makefile target (`build:`, not `VAR := x`)
+static MAKE_TARGET: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^([A-Za-z0-9][\w.-]*)\s*:(?:[^=]|$)").expect("invalid MAKE_TARGET regex")
+});
+
+/// Summary of one directory in the file tree
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DirSummary {
+    /// Path relative to the working directory ("." for the root)
+    pub path: String,
+    /// Files in this directory and below
+    pub files: usize,
+    /// Most common file extensions, most frequent first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extensions: Vec<String>,
+}
+
+/// Repository facts computed before the planner runs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PreGrounding {
+    /// Working directory the pass ran against
+    pub working_dir: String,
+    /// When the pass ran (RFC 3339)
+    pub generated_at: String,
+    /// Initial grounding snapshot (key files, build targets, import convention)
+    pub snapshot: GroundingSnapshot,
+    /// Top-level directory summary
+    pub file_tree: Vec<DirSummary>,
+    /// Total files walked (capped)
+    pub total_files: usize,
+    /// Policy rules from CLAUDE.md / AGENT.md
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub policies: Vec<PolicySet>,
+}
+
+impl PreGrounding {
+    /// Run the pre-grounding pass against `working_dir`
+    pub fn build(working_dir: &Path) -> Result<Self> {
+        let mut walk = Walk::default();
+        walk.visit(working_dir, working_dir)
+            .with_context(|| format!("Failed to walk {:?}", working_dir))?;
+
+        let verified_files = KEY_FILES
+            .iter()
+            .filter(|f| working_dir.join(f).is_file())
+            .map(|f| VerifiedFile {
+                path: f.to_string(),
+                exists: true,
+            })
+            .collect();
+
+        let (import_convention, existing_patterns) =
+            import_conventions(working_dir, &walk.rust_files);
+
+        let snapshot = GroundingSnapshot {
+            verified_files,
+            verified_targets: detect_build_targets(working_dir),
+            import_convention,
+            existing_patterns,
+        };
+
+        Ok(Self {
+            working_dir: working_dir.display().to_string(),
+            generated_at: chrono::Utc::now().to_rfc3339(),
+            snapshot,
+            file_tree: walk.summaries(),
+            total_files: walk.total_files,
+            policies: discover_policies(working_dir),
+        })
+    }
+
+    /// Load a persisted pre-grounding from the session directory
+    pub fn load(session_dir: &Path) -> Result<Option<Self>> {
+        let path = session_dir.join(PRE_GROUNDING_FILE);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let json = fs::read_to_string(&path).context("Failed to read pre-grounding file")?;
+        let grounding =
+            serde_json::from_str(&json).context("Failed to parse pre-grounding file")?;
+        Ok(Some(grounding))
+    }
+
+    /// Persist to the session directory
+    pub fn save(&self, session_dir: &Path) -> Result<()> {
+        fs::create_dir_all(session_dir).context("Failed to create session directory")?;
+        let json =
+            serde_json::to_string_pretty(self).context("Failed to serialize pre-grounding")?;
+        write_atomic(session_dir.join(PRE_GROUNDING_FILE), json)
+            .context("Failed to write pre-grounding file")?;
+        Ok(())
+    }
+
+    /// Load the session's pre-grounding, or build and persist it
+    pub fn load_or_build(session_dir: &Path, working_dir: &Path) -> Result<Self> {
+        if let Some(existing) = Self::load(session_dir)? {
+            return Ok(existing);
+        }
+        let grounding = Self::build(working_dir)?;
+        grounding.save(session_dir)?;
+        Ok(grounding)
+    }
+
+    /// Render as a planner prompt section
+    pub fn to_prompt_section(&self) -> String {
+        let mut out = String::from(
+            "\n## Repository Grounding (pre-computed)\n\
+             These facts were verified deterministically before planning. Use them \
+             for `grounding_snapshot` and file references instead of re-exploring; \
+             only search for details not covered here.\n",
+        );
+
+        out.push_str(&format!("\n### File tree ({} files)\n", self.total_files));
+        for dir in &self.file_tree {
+            let exts = if dir.extensions.is_empty() {
+                String::new()
+            } else {
+                format!(" ({})", dir.extensions.join(", "))
+            };
+            out.push_str(&format!(
+                "- `{}/` - {} files{}\n",
+                dir.path, dir.files, exts
+            ));
+        }
+
+        if !self.snapshot.verified_files.is_empty() {
+            out.push_str("\n### Key files (exist)\n");
+            for file in &self.snapshot.verified_files {
+                out.push_str(&format!("- `{}`\n", file.path));
+            }
+        }
+
+        if !self.snapshot.verified_targets.is_empty() {
+            out.push_str("\n### Build targets\n");
+            for target in &self.snapshot.verified_targets {
+                if target.resolves {
+                    out.push_str(&format!("- `{}`\n", target.target));
+                } else {
+                    out.push_str(&format!("- `{}` (tool not on PATH)\n", target.target));
+                }
+            }
+        }
+
+        if let Some(convention) = &self.snapshot.import_convention {
+            out.push_str(&format!("\n### Import convention\n{}\n", convention));
+            for pattern in &self.snapshot.existing_patterns {
+                out.push_str(&format!(
+                    "- {} ([{}]({}:{}))\n",
+                    pattern.pattern, pattern.file, pattern.file, pattern.line
+                ));
+            }
+        }
+
+        let rules: Vec<_> = self.policies.iter().flat_map(|p| &p.rules).collect();
+        if !rules.is_empty() {
+            out.push_str("\n### Policy rules\n");
+            for rule in rules {
+                out.push_str(&format!(
+                    "- {} ({}): {}\n",
+                    rule.id, rule.source_file, rule.description
+                ));
+            }
+        }
+
+        out
+    }
+}
+
+// ============================================================================
+// File tree walk
+// ============================================================================
+
+/// Hidden, dependency, and build-output directories that are never walked
+pub(crate) fn skip_dir(name: &str) -> bool {
+    name.starts_with('.') || name.starts_with("bazel-") || IGNORED_DIRS.contains(&name)
+}
+
+#[derive(Default)]
+struct Walk {
+    total_files: usize,
+    /// Per summarized directory: file count and extension counts
+    dirs: BTreeMap<String, (usize, BTreeMap<String, usize>)>,
+    rust_files: Vec<PathBuf>,
+}
+
+impl Walk {
+    /// Walk `dir`; unreadable subdirectories are skipped, only an
+    /// unreadable root fails.
+    fn visit(&mut self, root: &Path, dir: &Path) -> Result<()> {
+        let read_dir = match fs::read_dir(dir) {
+            Ok(read_dir) => read_dir,
+            Err(e) if dir != root => {
+                warn!(
+                    "Pre-grounding skipped unreadable directory {:?}: {}",
+                    dir, e
+                );
+                return Ok(());
+            }
+            Err(e) => return Err(e.into()),
+        };
+        let mut entries: Vec<_> = read_dir.filter_map(|e| e.ok()).collect();
+        entries.sort_by_key(|e| e.file_name());
+
+        for entry in entries {
+            if self.total_files >= MAX_FILES {
+                return Ok(());
+            }
+            let name = entry.file_name().to_string_lossy().to_string();
+            if name.starts_with('.') {
+                continue;
+            }
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            let path = entry.path();
+
+            if file_type.is_dir() {
+                if skip_dir(&name) {
+                    continue;
+                }
+                self.visit(root, &path)?;
+            } else if file_type.is_file() {
+                self.total_files += 1;
+                let ext = path
+                    .extension()
+                    .map(|e| e.to_string_lossy().to_string())
+                    .unwrap_or_default();
+                if ext == "rs" && self.rust_files.len() < MAX_RUST_FILES {
+                    self.rust_files.push(path.clone());
+                }
+
+                // Attribute the file to each ancestor up to TREE_DEPTH
+                let rel = path.strip_prefix(root).unwrap_or(&path);
+                let components: Vec<String> = rel
+                    .parent()
+                    .into_iter()
+                    .flat_map(|p| p.components())
+                    .map(|c| c.as_os_str().to_string_lossy().to_string())
+                    .collect();
+                for len in 0..=components.len().min(TREE_DEPTH) {
+                    let key = if len == 0 {
+                        ".".to_string()
+                    } else {
+                        components[..len].join("/")
+                    };
+                    let (count, exts) = self.dirs.entry(key).or_default();
+                    *count += 1;
+                    if !ext.is_empty() {
+                        *exts.entry(ext.clone()).or_default() += 1;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn summaries(&self) -> Vec<DirSummary> {
+        self.dirs
+            .iter()
+            .take(MAX_TREE_ENTRIES)
+            .map(|(path, (files, exts))| {
+                let mut exts: Vec<(&String, &usize)> = exts.iter().collect();
+                exts.sort_by(|a, b| b.1.cmp(a.1).then(a.0.cmp(b.0)));
+                DirSummary {
+                    path: path.clone(),
+                    files: *files,
+                    extensions: exts.into_iter().take(3).map(|(e, _)| e.clone()).collect(),
+                }
+            })
+            .collect()
+    }
+}
+
+// ============================================================================
+// Build targets and import conventions
+// ============================================================================
+
+/// Build/test commands implied by the manifests in `working_dir`; a target
+/// resolves when its tool is on `PATH`
+fn detect_build_targets(working_dir: &Path) -> Vec<VerifiedTarget> {
+    let mut targets: Vec<String> = Vec::new();
+    let has = |f: &str| working_dir.join(f).exists();
+
+    if has("Cargo.toml") {
+        targets.extend(["cargo build", "cargo test", "cargo clippy"].map(String::from));
+    }
+    if has("MODULE.bazel") || has("WORKSPACE") || has("WORKSPACE.bazel") {
+        targets.extend(["bazel build //...", "bazel test //..."].map(String::from));
+    }
+    if has("go.mod") {
+        targets.extend(["go build ./...", "go test ./..."].map(String::from));
+    }
+    if has("pyproject.toml") || has("setup.py") {
+        targets.push("pytest".to_string());
+    }
+    if let Ok(content) = fs::read_to_string(working_dir.join("package.json"))
+        && let Ok(json) = serde_json::from_str::<serde_json::Value>(&content)
+        && let Some(scripts) = json.get("scripts").and_then(|s| s.as_object())
+    {
+        targets.extend(scripts.keys().map(|name| match name.as_str() {
+            "test" => "npm test".to_string(),
+            _ => format!("npm run {}", name),
+        }));
+    }
+    if let Ok(content) = fs::read_to_string(working_dir.join("Makefile")) {
+        targets.extend(
+            content
+                .lines()
+                .filter_map(|line| MAKE_TARGET.captures(line))
+                .map(|caps| format!("make {}", &caps[1]))
+                .take(15),
+        );
+    }
+
+    targets
+        .into_iter()
+        .map(|target| {
+            let resolves = target.split_whitespace().next().is_some_and(on_path);
+            VerifiedTarget { target, resolves }
+        })
+        .collect()
+}
+
+/// Whether an executable named `program` is in a `PATH` directory
+fn on_path(program: &str) -> bool {
+    let Some(paths) = std::env::var_os("PATH") else {
+        return false;
+    };
+    std::env::split_paths(&paths).any(|dir| {
+        let candidate = dir.join(program);
+        candidate.is_file() || (cfg!(windows) && candidate.with_extension("exe").is_file())
+    })
+}
+
+/// Summarize `use crate::` / `use super::` usage across Rust files.
+///
+/// Returns the convention line and the most-imported crate modules with their
+/// first occurrence.
+fn import_conventions(
+    working_dir: &Path,
+    rust_files: &[PathBuf],
+) -> (Option<String>, Vec<ExistingPattern>) {
+    let mut crate_uses = 0usize;
+    let mut super_uses = 0usize;
+    let mut files_with_crate = 0usize;
+    // module -> (count, first file, first line)
+    let mut modules: BTreeMap<String, (usize, String, u32)> = BTreeMap::new();
+
+    for path in rust_files {
+        if fs::metadata(path)
+            .map(|m| m.len() > MAX_SCAN_BYTES)
+            .unwrap_or(true)
+        {
+            continue;
+        }
+        let Ok(content) = fs::read_to_string(path) else {
+            continue;
+        };
+        let rel = path
+            .strip_prefix(working_dir)
+            .unwrap_or(path)
+            .display()
+            .to_string();
+        let mut found = false;
+        for (idx, line) in content.lines().enumerate() {
+            if let Some(caps) = USE_CRATE.captures(line) {
+                crate_uses += 1;
+                found = true;
+                let entry = modules
+                    .entry(caps[1].to_string())
+                    .or_insert_with(|| (0, rel.clone(), idx as u32 + 1));
+                entry.0 += 1;
+            } else if USE_SUPER.is_match(line) {
+                super_uses += 1;
+            }
+        }
+        if found {
+            files_with_crate += 1;
+        }
+    }
+
+    if crate_uses == 0 && super_uses == 0 {
+        return (None, Vec::new());
+    }
+
+    let convention = format!(
+        "`use crate::module::Type` ({} imports in {} files); `use super::` ({} imports)",
+        crate_uses, files_with_crate, super_uses
+    );
+
+    let mut ranked: Vec<_> = modules.into_iter().collect();
+    ranked.sort_by(|a, b| b.1.0.cmp(&a.1.0).then(a.0.cmp(&b.0)));
+    let patterns = ranked
+        .into_iter()
+        .take(5)
+        .map(|(module, (count, file, line))| ExistingPattern {
+            pattern: format!("use crate::{}::... ({} imports)", module, count),
+            file,
+            line,
+        })
+        .collect();
+
+    (Some(convention), patterns)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn workspace() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::write(root.join("Cargo.toml"), "[package]\nname = \"demo\"\n").unwrap();
+        fs::write(
+            root.join("Makefile"),
+            "FLAGS := -O\nbuild:\n\tcargo build\n",
+        )
+        .unwrap();
+        fs::create_dir_all(root.join("src/models")).unwrap();
+        fs::create_dir_all(root.join("target/debug")).unwrap();
+        fs::write(root.join("target/debug/junk.rs"), "use crate::junk;\n").unwrap();
+        fs::write(root.join("src/lib.rs"), "pub mod models;\n").unwrap();
+        fs::write(
+            root.join("src/main.rs"),
+            "use crate::models::Plan;\nuse crate::config::Config;\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("src/models/mod.rs"),
+            "use super::x;\npub(crate) use crate::models::plan;\n",
+        )
+        .unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_build_snapshot() {
+        let dir = workspace();
+        let grounding = PreGrounding::build(dir.path()).unwrap();
+
+        let files: Vec<&str> = grounding
+            .snapshot
+            .verified_files
+            .iter()
+            .map(|f| f.path.as_str())
+            .collect();
+        assert_eq!(
+            files,
+            vec!["Cargo.toml", "src/lib.rs", "src/main.rs", "Makefile"]
+        );
+
+        let targets: Vec<&str> = grounding
+            .snapshot
+            .verified_targets
+            .iter()
+            .map(|t| t.target.as_str())
+            .collect();
+        assert_eq!(
+            targets,
+            vec!["cargo build", "cargo test", "cargo clippy", "make build"]
+        );
+        for target in &grounding.snapshot.verified_targets {
+            let tool = target.target.split_whitespace().next().unwrap();
+            assert_eq!(target.resolves, on_path(tool));
+        }
+        assert!(!on_path("plan-forge-no-such-tool"));
+
+        // target/ is ignored
+        assert_eq!(grounding.total_files, 5);
+        assert_eq!(
+            grounding.file_tree,
+            vec![
+                DirSummary {
+                    path: ".".to_string(),
+                    files: 5,
+                    extensions: vec!["rs".to_string(), "toml".to_string()],
+                },
+                DirSummary {
+                    path: "src".to_string(),
+                    files: 3,
+                    extensions: vec!["rs".to_string()],
+                },
+                DirSummary {
+                    path: "src/models".to_string(),
+                    files: 1,
+                    extensions: vec!["rs".to_string()],
+                },
+            ]
+        );
+
+        let convention = grounding.snapshot.import_convention.as_deref().unwrap();
+        assert!(convention.contains("3 imports in 2 files"));
+        let top = &grounding.snapshot.existing_patterns[0];
+        assert_eq!(top.pattern, "use crate::models::... (2 imports)");
+        assert_eq!((top.file.as_str(), top.line), ("src/main.rs", 1));
+    }
+
+    #[test]
+    fn test_load_or_build_reuses_session_file() {
+        let dir = workspace();
+        let session = tempfile::tempdir().unwrap();
+
+        let first = PreGrounding::load_or_build(session.path(), dir.path()).unwrap();
+        assert!(session.path().join(PRE_GROUNDING_FILE).exists());
+
+        // Changes to the repo don't invalidate the session's snapshot
+        fs::write(dir.path().join("go.mod"), "module demo\n").unwrap();
+        let second = PreGrounding::load_or_build(session.path(), dir.path()).unwrap();
+        assert_eq!(second.generated_at, first.generated_at);
+        assert_eq!(
+            second.snapshot.verified_targets.len(),
+            first.snapshot.verified_targets.len()
+        );
+
+        let prompt = second.to_prompt_section();
+        assert!(prompt.contains("## Repository Grounding (pre-computed)"));
+        assert!(prompt.contains("- `cargo test`"));
+        assert!(prompt.contains("- `src/` - 3 files (rs)"));
+    }
+}
@@ -0,0 +1,489 @@
+//! Lightweight symbol index and ranked repository map.
+//!
+//! Symbols (`fn`, `struct`, `enum`, `trait`, `impl`, `class`, `def`, ...) are
+//! extracted with per-language line regexes — no parsers or external tools.
+//! Symbols are ranked by how often their name is referenced across the
+//! indexed files, and the repo map lists the highest-ranked files and their
+//! symbols until a token budget is reached.
+
+use anyhow::{Context, Result};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+use super::pre_grounding::skip_dir;
+
+/// Maximum files indexed
+const MAX_INDEXED_FILES: usize = 5_000;
+/// Files larger than this are not indexed
+const MAX_FILE_BYTES: u64 = 512 * 1024;
+/// Maximum signature length kept per symbol
+const MAX_SIGNATURE_CHARS: usize = 120;
+/// Rough characters-per-token ratio used for the budget
+const CHARS_PER_TOKEN: usize = 4;
+
+static RUST_ITEM: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r#"^\s*(?:pub(?:\([^)]*\))?\s+)?(?:(?:async|const|unsafe|extern\s+"[^"]*")\s+)*(fn|struct|enum|trait|type)\s+([A-Za-z_]\w*)"#,
+    )
+    .expect("invalid RUST_ITEM regex")
+});
+
+static RUST_IMPL: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^\s*impl(?:<[^>]*>)?\s+(?:[A-Za-z_][\w:]*(?:<[^>]*>)?\s+for\s+)?([A-Za-z_]\w*)")
+        .expect("invalid RUST_IMPL regex")
+});
+
+static PYTHON_ITEM: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^\s*(?:async\s+)?(def|class)\s+([A-Za-z_]\w*)").expect("invalid PYTHON_ITEM regex")
+});
+
+static JS_ITEM: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"^\s*(?:export\s+)?(?:default\s+)?(?:declare\s+)?(?:abstract\s+)?(?:async\s+)?(function|class|interface|enum|type)\s*\*?\s*([A-Za-z_$][\w$]*)",
+    )
+    .expect("invalid JS_ITEM regex")
+});
+
+static GO_FUNC: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^func\s+(?:\([^)]*\)\s*)?([A-Za-z_]\w*)").expect("invalid GO_FUNC regex")
+});
+
+static GO_TYPE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^type\s+([A-Za-z_]\w*)\s+(struct|interface)").expect("invalid GO_TYPE regex")
+});
+
+static JVM_ITEM: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"^\s*(?:(?:public|private|protected|internal|static|abstract|final|sealed|data|open|partial)\s+)*(class|interface|enum|record|object)\s+([A-Za-z_]\w*)",
+    )
+    .expect("invalid JVM_ITEM regex")
+});
+
+static RUBY_ITEM: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^\s*(def|class|module)\s+(?:self\.)?([A-Za-z_]\w*[?!]?)")
+        .expect("invalid RUBY_ITEM regex")
+});
+
+static IDENT: Lazy<Regex> = Lazy::new(|| Regex::new(r"[A-Za-z_]\w*").expect("invalid IDENT regex"));
+
+/// Kind of an indexed symbol
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SymbolKind {
+    Function,
+    Struct,
+    Enum,
+    Trait,
+    Impl,
+    Class,
+    Interface,
+    Type,
+}
+
+impl SymbolKind {
+    fn from_keyword(keyword: &str) -> Self {
+        match keyword {
+            "struct" => SymbolKind::Struct,
+            "enum" => SymbolKind::Enum,
+            "trait" => SymbolKind::Trait,
+            "impl" => SymbolKind::Impl,
+            "interface" => SymbolKind::Interface,
+            "type" => SymbolKind::Type,
+            "class" | "record" | "object" | "module" => SymbolKind::Class,
+            _ => SymbolKind::Function,
+        }
+    }
+}
+
+/// A symbol definition
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Symbol {
+    pub name: String,
+    pub kind: SymbolKind,
+    /// Path relative to the indexed root
+    pub file: String,
+    /// 1-based line of the definition
+    pub line: usize,
+    /// Trimmed definition line
+    pub signature: String,
+}
+
+/// Symbols across a repository with reference counts for ranking
+#[derive(Debug, Clone, Default)]
+pub struct SymbolIndex {
+    symbols: Vec<Symbol>,
+    by_name: HashMap<String, Vec<usize>>,
+    /// Occurrences of each identifier across indexed files
+    references: HashMap<String, usize>,
+}
+
+impl SymbolIndex {
+    /// Index source files under `root`
+    pub fn build(root: &Path) -> Result<Self> {
+        let mut index = Self::default();
+        let mut files = Vec::new();
+        collect_files(root, root, &mut files)
+            .with_context(|| format!("Failed to walk {:?}", root))?;
+
+        for path in files {
+            if fs::metadata(&path)
+                .map(|m| m.len() > MAX_FILE_BYTES)
+                .unwrap_or(true)
+            {
+                continue;
+            }
+            let Ok(content) = fs::read_to_string(&path) else {
+                continue;
+            };
+            let rel = path
+                .strip_prefix(root)
+                .unwrap_or(&path)
+                .display()
+                .to_string();
+            index.add_file(&rel, &content);
+        }
+        Ok(index)
+    }
+
+    /// Index one file's content (language chosen by extension)
+    pub fn add_file(&mut self, path: &str, content: &str) {
+        let ext = Path::new(path)
+            .extension()
+            .and_then(|e| e.to_str())
+            .unwrap_or_default();
+        if language_of(ext).is_none() {
+            return;
+        }
+
+        for (idx, line) in content.lines().enumerate() {
+            for ident in IDENT.find_iter(line) {
+                *self
+                    .references
+                    .entry(ident.as_str().to_string())
+                    .or_default() += 1;
+            }
+            if let Some((kind, name)) = extract_symbol(ext, line) {
+                let signature: String = line
+                    .trim()
+                    .trim_end_matches('{')
+                    .trim_end()
+                    .chars()
+                    .take(MAX_SIGNATURE_CHARS)
+                    .collect();
+                self.by_name
+                    .entry(name.clone())
+                    .or_default()
+                    .push(self.symbols.len());
+                self.symbols.push(Symbol {
+                    name,
+                    kind,
+                    file: path.to_string(),
+                    line: idx + 1,
+                    signature,
+                });
+            }
+        }
+    }
+
+    /// All indexed symbols in file order
+    pub fn symbols(&self) -> &[Symbol] {
+        &self.symbols
+    }
+
+    /// Whether any symbol has this exact name
+    pub fn contains(&self, name: &str) -> bool {
+        self.by_name.contains_key(name)
+    }
+
+    /// Definitions of `name`
+    pub fn find(&self, name: &str) -> impl Iterator<Item = &Symbol> {
+        self.by_name
+            .get(name)
+            .into_iter()
+            .flatten()
+            .map(|&i| &self.symbols[i])
+    }
+
+    /// References to a symbol's name, excluding its definitions
+    pub fn reference_count(&self, name: &str) -> usize {
+        let defs = self.by_name.get(name).map(Vec::len).unwrap_or(0);
+        self.references
+            .get(name)
+            .copied()
+            .unwrap_or(0)
+            .saturating_sub(defs)
+    }
+
+    /// Render a ranked repo map that fits within `token_budget` (approximate).
+    ///
+    /// Files are ordered by the total references to their symbols; each file
+    /// lists its symbols in source order.
+    pub fn repo_map(&self, token_budget: usize) -> String {
+        let mut files: BTreeMap<&str, Vec<&Symbol>> = BTreeMap::new();
+        for symbol in &self.symbols {
+            files.entry(symbol.file.as_str()).or_default().push(symbol);
+        }
+
+        let mut ranked: Vec<(&str, usize, Vec<&Symbol>)> = files
+            .into_iter()
+            .map(|(file, symbols)| {
+                let score = symbols
+                    .iter()
+                    .map(|s| self.reference_count(&s.name) + 1)
+                    .sum();
+                (file, score, symbols)
+            })
+            .collect();
+        ranked.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+
+        let budget_chars = token_budget * CHARS_PER_TOKEN;
+        let mut out = String::new();
+        'files: for (file, _, symbols) in ranked {
+            let header = format!("{}:\n", file);
+            if out.len() + header.len() > budget_chars {
+                break;
+            }
+            out.push_str(&header);
+            for symbol in symbols {
+                let entry = format!("  {:>5} {}\n", symbol.line, symbol.signature);
+                if out.len() + entry.len() > budget_chars {
+                    break 'files;
+                }
+                out.push_str(&entry);
+            }
+        }
+        out
+    }
+
+    /// Repo map as a prompt section (empty when nothing was indexed)
+    pub fn to_prompt_section(&self, token_budget: usize) -> String {
+        let map = self.repo_map(token_budget);
+        if map.is_empty() {
+            return String::new();
+        }
+        format!(
+            "\n## Repository Map (symbols ranked by references)\n\
+             Existing types and functions with their definition lines. Use these exact \
+             names in goals and file references.\n```text\n{}```\n",
+            map
+        )
+    }
+}
+
+/// Language family for a file extension
+fn language_of(ext: &str) -> Option<&'static str> {
+    match ext {
+        "rs" => Some("rust"),
+        "py" => Some("python"),
+        "js" | "jsx" | "mjs" | "ts" | "tsx" => Some("js"),
+        "go" => Some("go"),
+        "java" | "kt" | "cs" | "scala" => Some("jvm"),
+        "rb" => Some("ruby"),
+        _ => None,
+    }
+}
+
+/// Symbol defined on a line, if any
+fn extract_symbol(ext: &str, line: &str) -> Option<(SymbolKind, String)> {
+    let keyword_and_name = |re: &Regex| {
+        re.captures(line)
+            .map(|c| (SymbolKind::from_keyword(&c[1]), c[2].to_string()))
+    };
+    match language_of(ext)? {
+        "rust" => keyword_and_name(&RUST_ITEM).or_else(|| {
+            RUST_IMPL
+                .captures(line)
+                .map(|c| (SymbolKind::Impl, c[1].to_string()))
+        }),
+        "python" => keyword_and_name(&PYTHON_ITEM),
+        "js" => keyword_and_name(&JS_ITEM),
+        "go" => GO_FUNC
+            .captures(line)
+            .map(|c| (SymbolKind::Function, c[1].to_string()))
+            .or_else(|| {
+                GO_TYPE
+                    .captures(line)
+                    .map(|c| (SymbolKind::from_keyword(&c[2]), c[1].to_string()))
+            }),
+        "jvm" => keyword_and_name(&JVM_ITEM),
+        "ruby" => keyword_and_name(&RUBY_ITEM),
+        _ => None,
+    }
+}
+
+/// Source files under `dir` (sorted, skipping hidden and build directories)
+fn collect_files(root: &Path, dir: &Path, files: &mut Vec<std::path::PathBuf>) -> Result<()> {
+    let mut entries: Vec<_> = fs::read_dir(dir)?.filter_map(|e| e.ok()).collect();
+    entries.sort_by_key(|e| e.file_name());
+
+    for entry in entries {
+        if files.len() >= MAX_INDEXED_FILES {
+            return Ok(());
+        }
+        let name = entry.file_name().to_string_lossy().to_string();
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+        let path = entry.path();
+        if file_type.is_dir() {
+            if !skip_dir(&name) {
+                collect_files(root, &path, files)?;
+            }
+        } else if file_type.is_file()
+            && !name.starts_with('.')
+            && path
+                .extension()
+                .and_then(|e| e.to_str())
+                .and_then(language_of)
+                .is_some()
+        {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Names that don't need to be in the index (std types, keywords, common methods)
+pub(crate) fn is_well_known(name: &str) -> bool {
+    static WELL_KNOWN: Lazy<HashSet<&'static str>> = Lazy::new(|| {
+        [
+            "String",
+            "Vec",
+            "Option",
+            "Result",
+            "Box",
+            "Arc",
+            "Rc",
+            "Mutex",
+            "RwLock",
+            "HashMap",
+            "HashSet",
+            "BTreeMap",
+            "BTreeSet",
+            "Path",
+            "PathBuf",
+            "Self",
+            "Some",
+            "None",
+            "Ok",
+            "Err",
+            "Default",
+            "Clone",
+            "Debug",
+            "Display",
+            "Serialize",
+            "Deserialize",
+            "Value",
+            "Duration",
+            "Instant",
+            "unwrap",
+            "expect",
+            "clone",
+            "to_string",
+            "into",
+            "from",
+            "new",
+            "default",
+            "fmt",
+            "iter",
+            "map",
+            "collect",
+            "len",
+            "is_empty",
+            "push",
+            "insert",
+            "get",
+            "self",
+            "crate",
+            "super",
+            "main",
+        ]
+        .into_iter()
+        .collect()
+    });
+    WELL_KNOWN.contains(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn index() -> SymbolIndex {
+        let mut index = SymbolIndex::default();
+        index.add_file(
+            "src/plan.rs",
+            "pub struct Plan {\n    title: String,\n}\n\n\
+             impl Plan {\n    pub async fn parse_plan(s: &str) -> Plan {\n        todo!()\n    }\n}\n\n\
+             impl Display for Plan {}\n\npub(crate) enum Tier { Quick }\n",
+        );
+        index.add_file(
+            "src/main.rs",
+            "fn main() {\n    let p = Plan::parse_plan(\"\");\n    let q: Plan = p;\n}\n",
+        );
+        index.add_file(
+            "web/app.ts",
+            "export default class App {}\nexport async function loadUser() {}\ninterface Props {}\n",
+        );
+        index.add_file(
+            "tools/gen.py",
+            "class Generator:\n    def run(self):\n        pass\n",
+        );
+        index.add_file(
+            "cmd/main.go",
+            "func (s *Server) Serve() {}\ntype Server struct {}\n",
+        );
+        index.add_file("README.md", "fn not_code() {}\n");
+        index
+    }
+
+    #[test]
+    fn test_extracts_symbols_across_languages() {
+        let index = index();
+        let names: Vec<(&str, SymbolKind)> = index
+            .symbols()
+            .iter()
+            .map(|s| (s.name.as_str(), s.kind))
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                ("Plan", SymbolKind::Struct),
+                ("Plan", SymbolKind::Impl),
+                ("parse_plan", SymbolKind::Function),
+                ("Plan", SymbolKind::Impl),
+                ("Tier", SymbolKind::Enum),
+                ("main", SymbolKind::Function),
+                ("App", SymbolKind::Class),
+                ("loadUser", SymbolKind::Function),
+                ("Props", SymbolKind::Interface),
+                ("Generator", SymbolKind::Class),
+                ("run", SymbolKind::Function),
+                ("Serve", SymbolKind::Function),
+                ("Server", SymbolKind::Struct),
+            ]
+        );
+        assert!(index.contains("parse_plan"));
+        assert!(!index.contains("not_code"));
+        assert_eq!(
+            index.find("parse_plan").next().unwrap().signature,
+            "pub async fn parse_plan(s: &str) -> Plan"
+        );
+    }
+
+    #[test]
+    fn test_repo_map_ranked_and_budgeted() {
+        let index = index();
+        let map = index.repo_map(1000);
+        // src/plan.rs defines the most-referenced symbols
+        assert!(map.starts_with("src/plan.rs:\n      1 pub struct Plan\n"));
+
+        let small = index.repo_map(10);
+        assert!(small.len() <= 10 * CHARS_PER_TOKEN);
+        assert!(small.starts_with("src/plan.rs:\n"));
+        assert!(index.repo_map(0).is_empty());
+    }
+}
@@ -0,0 +1,486 @@
+//! Cross-process session locking and atomic writes for `.plan-forge/<slug>/`.
+//!
+//! `SessionRegistry` only coordinates sessions within one process, while the
+//! CLI and the MCP server can both work on the same session directory. A
+//! `SessionLock` is an advisory lock file (`session.lock`) recording the
+//! holder's PID, command, and a heartbeat timestamp. A lock is stale, and may
+//! be taken over, once its process has exited (where that can be checked) or
+//! its heartbeat is older than `LOCK_STALE_SECS`.
+
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::io::AsyncWriteExt;
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+
+/// Lock file name in the session directory
+pub const LOCK_FILE: &str = "session.lock";
+
+/// File locked by the OS while a stale lock is taken over
+const TAKEOVER_FILE: &str = ".session.lock.takeover";
+
+/// Interval at which a held lock refreshes its heartbeat
+pub const LOCK_REFRESH_SECS: u64 = 30;
+
+/// Heartbeat age after which a lock is considered abandoned
+pub const LOCK_STALE_SECS: i64 = 120;
+
+/// Longest command line recorded as the lock owner
+const MAX_OWNER_CHARS: usize = 200;
+
+/// Distinguishes temp files of concurrent writers within one process
+static TEMP_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// Holder of a session lock, as recorded in `session.lock`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LockInfo {
+    pub pid: u32,
+    /// Command line of the holding process (e.g. "plan-forge mcp plan-forge")
+    pub owner: String,
+    pub acquired_at: String,
+    /// Heartbeat, refreshed every `LOCK_REFRESH_SECS` while held
+    pub refreshed_at: String,
+}
+
+impl LockInfo {
+    /// Lock info for the current process.
+    pub fn current(owner: &str) -> Self {
+        let now = Utc::now().to_rfc3339();
+        Self {
+            pid: std::process::id(),
+            owner: owner.chars().take(MAX_OWNER_CHARS).collect(),
+            acquired_at: now.clone(),
+            refreshed_at: now,
+        }
+    }
+
+    /// Whether the holder is gone: its process exited, or it stopped
+    /// refreshing the heartbeat.
+    pub fn is_stale(&self, now: DateTime<Utc>) -> bool {
+        if process_alive(self.pid) == Some(false) {
+            return true;
+        }
+        match DateTime::parse_from_rfc3339(&self.refreshed_at) {
+            Ok(refreshed) => (now - refreshed.with_timezone(&Utc)).num_seconds() > LOCK_STALE_SECS,
+            Err(_) => true,
+        }
+    }
+
+    /// Same lock acquisition (a refreshed heartbeat is still the same lock)
+    fn same_lock(&self, other: &LockInfo) -> bool {
+        self.pid == other.pid && self.acquired_at == other.acquired_at
+    }
+}
+
+impl std::fmt::Display for LockInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "'{}' (pid {}) since {}, last heartbeat {}",
+            self.owner, self.pid, self.acquired_at, self.refreshed_at
+        )
+    }
+}
+
+/// Advisory lock on a session directory, released on drop.
+#[derive(Debug)]
+pub struct SessionLock {
+    path: PathBuf,
+    info: LockInfo,
+    heartbeat: Option<JoinHandle<()>>,
+}
+
+impl SessionLock {
+    /// Lock `session_dir` for this process, recording its command line.
+    pub fn acquire(session_dir: &Path) -> Result<Self> {
+        let owner = std::env::args().collect::<Vec<_>>().join(" ");
+        Self::acquire_as(session_dir, &owner)
+    }
+
+    /// Lock `session_dir`, taking over a stale lock.
+    ///
+    /// Fails with a message naming the holder when another live process
+    /// holds the lock. Within a Tokio runtime, a heartbeat task keeps the
+    /// lock fresh until it is dropped.
+    pub fn acquire_as(session_dir: &Path, owner: &str) -> Result<Self> {
+        fs::create_dir_all(session_dir).context("Failed to create session directory")?;
+        let path = session_dir.join(LOCK_FILE);
+        let info = LockInfo::current(owner);
+
+        // One retry after taking over a stale lock
+        for _ in 0..2 {
+            match create_lock_file(&path, &info) {
+                Ok(()) => {
+                    let heartbeat = spawn_heartbeat(&path, &info, None);
+                    return Ok(Self {
+                        path,
+                        info,
+                        heartbeat,
+                    });
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {
+                    let holder = read_lock(&path);
+                    match &holder {
+                        Some(holder) if !holder.is_stale(Utc::now()) => {
+                            anyhow::bail!(
+                                "Session {:?} is locked by {}. Wait for it to finish or cancel it; \
+                                 if that process is gone, remove {:?}",
+                                session_name(session_dir),
+                                holder,
+                                path
+                            );
+                        }
+                        None if !lock_file_expired(&path) => {
+                            anyhow::bail!(
+                                "Session {:?} is locked (unreadable lock file {:?})",
+                                session_name(session_dir),
+                                path
+                            );
+                        }
+                        _ => {}
+                    }
+                    // Lost the race: the next attempt reports the new holder
+                    take_over_stale_lock(&path, &holder).with_context(|| {
+                        format!("Failed to take over stale session lock {:?}", path)
+                    })?;
+                }
+                Err(e) => {
+                    return Err(e).with_context(|| format!("Failed to create lock {:?}", path));
+                }
+            }
+        }
+        anyhow::bail!("Failed to acquire session lock {:?}", path)
+    }
+
+    /// Cancel `cancel_token` if another process takes the lock over while
+    /// it is held (needs a Tokio runtime).
+    pub fn with_cancel_token(mut self, cancel_token: CancellationToken) -> Self {
+        if let Some(heartbeat) = self.heartbeat.take() {
+            heartbeat.abort();
+        }
+        self.heartbeat = spawn_heartbeat(&self.path, &self.info, Some(cancel_token));
+        self
+    }
+
+    /// Live holder of the session lock, if any (stale locks don't count).
+    pub fn holder(session_dir: &Path) -> Option<LockInfo> {
+        read_lock(&session_dir.join(LOCK_FILE)).filter(|holder| !holder.is_stale(Utc::now()))
+    }
+
+    /// Lock details recorded for this process
+    pub fn info(&self) -> &LockInfo {
+        &self.info
+    }
+}
+
+impl Drop for SessionLock {
+    fn drop(&mut self) {
+        if let Some(heartbeat) = self.heartbeat.take() {
+            heartbeat.abort();
+        }
+        // Leave a lock that was taken over by another process alone
+        if read_lock(&self.path).is_some_and(|holder| holder.same_lock(&self.info))
+            && let Err(e) = fs::remove_file(&self.path)
+        {
+            tracing::warn!("Failed to release session lock {:?}: {}", self.path, e);
+        }
+    }
+}
+
+/// Create the lock file, failing if it already exists. A concurrent reader
+/// may briefly see it empty, which counts as held (see `lock_file_expired`).
+fn create_lock_file(path: &Path, info: &LockInfo) -> std::io::Result<()> {
+    let json = serde_json::to_string_pretty(info).map_err(std::io::Error::other)?;
+    let mut file = OpenOptions::new().write(true).create_new(true).open(path)?;
+    let written = file
+        .write_all(json.as_bytes())
+        .and_then(|()| file.sync_all());
+    if written.is_err() {
+        let _ = fs::remove_file(path);
+    }
+    written
+}
+
+/// Remove a stale lock so it can be taken over.
+///
+/// Takeovers are serialized by an OS lock on `TAKEOVER_FILE`, and the lock
+/// file is only removed while it still holds the stale `holder` seen before.
+/// Of several processes racing for one stale lock, the first removes it and
+/// creates its own; the rest then find a live lock. Returns false if the
+/// lock changed in the meantime.
+fn take_over_stale_lock(path: &Path, holder: &Option<LockInfo>) -> std::io::Result<bool> {
+    let guard = OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(path.with_file_name(TAKEOVER_FILE))?;
+    guard.lock()?;
+
+    let still_stale = match (read_lock(path), holder) {
+        (Some(current), Some(holder)) => &current == holder && current.is_stale(Utc::now()),
+        (None, None) => path.exists() && lock_file_expired(path),
+        _ => false,
+    };
+    if still_stale {
+        tracing::warn!(
+            "Taking over stale session lock {:?} held by {:?}",
+            path,
+            holder
+        );
+        match fs::remove_file(path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(e),
+            _ => {}
+        }
+    }
+    // The guard file is kept: removing it could let two processes lock
+    // different files
+    guard.unlock()?;
+    Ok(still_stale)
+}
+
+/// Heartbeat task for a held lock, if a Tokio runtime is running
+fn spawn_heartbeat(
+    path: &Path,
+    info: &LockInfo,
+    cancel_token: Option<CancellationToken>,
+) -> Option<JoinHandle<()>> {
+    tokio::runtime::Handle::try_current()
+        .ok()
+        .map(|handle| handle.spawn(heartbeat(path.to_path_buf(), info.clone(), cancel_token)))
+}
+
+/// Refresh the heartbeat until aborted or the lock is taken over.
+///
+/// A late refresh (e.g. after the machine slept) keeps refreshing as long as
+/// the lock is still ours. Losing the lock cancels `cancel_token`, so the
+/// session stops instead of working on a directory another process owns.
+async fn heartbeat(path: PathBuf, mut info: LockInfo, cancel_token: Option<CancellationToken>) {
+    let mut interval = tokio::time::interval(Duration::from_secs(LOCK_REFRESH_SECS));
+    interval.tick().await;
+    loop {
+        interval.tick().await;
+        if !read_lock(&path).is_some_and(|holder| holder.same_lock(&info)) {
+            tracing::error!(
+                "Session lock {:?} was taken over by another process; heartbeat stopped",
+                path
+            );
+            if let Some(cancel_token) = &cancel_token {
+                cancel_token.cancel();
+            }
+            return;
+        }
+        let age = DateTime::parse_from_rfc3339(&info.refreshed_at)
+            .map(|refreshed| (Utc::now() - refreshed.with_timezone(&Utc)).num_seconds())
+            .unwrap_or(i64::MAX);
+        if age > LOCK_STALE_SECS - LOCK_REFRESH_SECS as i64 {
+            tracing::warn!(
+                "Session lock {:?} was last refreshed {}s ago; refreshing late",
+                path,
+                age
+            );
+        }
+        info.refreshed_at = Utc::now().to_rfc3339();
+        let json = serde_json::to_string_pretty(&info).unwrap_or_default();
+        if let Err(e) = write_atomic(&path, json) {
+            tracing::warn!("Failed to refresh session lock {:?}: {}", path, e);
+        }
+    }
+}
+
+fn read_lock(path: &Path) -> Option<LockInfo> {
+    let json = fs::read_to_string(path).ok()?;
+    serde_json::from_str(&json).ok()
+}
+
+/// Whether an unreadable lock file is old enough to be abandoned
+fn lock_file_expired(path: &Path) -> bool {
+    fs::metadata(path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|modified| modified.elapsed().ok())
+        .is_none_or(|age| age.as_secs() as i64 > LOCK_STALE_SECS)
+}
+
+/// Whether a process is running, where that can be checked (`/proc`)
+fn process_alive(pid: u32) -> Option<bool> {
+    let proc = Path::new("/proc");
+    proc.is_dir().then(|| proc.join(pid.to_string()).exists())
+}
+
+fn session_name(session_dir: &Path) -> String {
+    session_dir
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| session_dir.display().to_string())
+}
+
+/// Unique temp file next to `path`, so the final rename stays on one filesystem
+fn temp_path(path: &Path) -> PathBuf {
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    path.with_file_name(format!(
+        ".{}.{}.{}.tmp",
+        name,
+        std::process::id(),
+        TEMP_COUNTER.fetch_add(1, Ordering::Relaxed)
+    ))
+}
+
+/// Write a file atomically: readers see the old or the new contents, never a
+/// partial write.
+pub fn write_atomic(path: impl AsRef<Path>, contents: impl AsRef<[u8]>) -> std::io::Result<()> {
+    let path = path.as_ref();
+    let temp = temp_path(path);
+    let result = write_and_rename(&temp, path, contents.as_ref());
+    if result.is_err() {
+        let _ = fs::remove_file(&temp);
+    }
+    result
+}
+
+fn write_and_rename(temp: &Path, path: &Path, contents: &[u8]) -> std::io::Result<()> {
+    let mut file = OpenOptions::new().write(true).create_new(true).open(temp)?;
+    file.write_all(contents)?;
+    file.sync_all()?;
+    fs::rename(temp, path)
+}
+
+/// Async variant of [`write_atomic`].
+pub async fn write_atomic_async(
+    path: impl AsRef<Path>,
+    contents: impl AsRef<[u8]>,
+) -> std::io::Result<()> {
+    let path = path.as_ref();
+    let temp = temp_path(path);
+    let result = async {
+        let mut file = tokio::fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&temp)
+            .await?;
+        file.write_all(contents.as_ref()).await?;
+        file.sync_all().await?;
+        tokio::fs::rename(&temp, path).await
+    }
+    .await;
+    if result.is_err() {
+        let _ = tokio::fs::remove_file(&temp).await;
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_lock_is_exclusive_until_dropped() {
+        let dir = tempdir().unwrap();
+        let session_dir = dir.path().join("my-session");
+
+        let lock = SessionLock::acquire_as(&session_dir, "plan-forge run").unwrap();
+        assert_eq!(
+            SessionLock::holder(&session_dir).as_ref(),
+            Some(lock.info())
+        );
+
+        let err = SessionLock::acquire_as(&session_dir, "plan-forge mcp")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("is locked by 'plan-forge run'"), "{}", err);
+        assert!(err.contains(&format!("pid {}", std::process::id())));
+
+        drop(lock);
+        assert!(!session_dir.join(LOCK_FILE).exists());
+        assert!(SessionLock::acquire_as(&session_dir, "plan-forge mcp").is_ok());
+    }
+
+    #[test]
+    fn test_stale_lock_is_taken_over() {
+        let dir = tempdir().unwrap();
+        let session_dir = dir.path().join("my-session");
+        fs::create_dir_all(&session_dir).unwrap();
+
+        // Heartbeat long past the stale threshold
+        let old = (Utc::now() - chrono::Duration::seconds(LOCK_STALE_SECS + 60)).to_rfc3339();
+        let stale = LockInfo {
+            refreshed_at: old.clone(),
+            acquired_at: old,
+            ..LockInfo::current("crashed run")
+        };
+        assert!(stale.is_stale(Utc::now()));
+        write_atomic(
+            session_dir.join(LOCK_FILE),
+            serde_json::to_string(&stale).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(SessionLock::holder(&session_dir), None);
+
+        let lock = SessionLock::acquire_as(&session_dir, "plan-forge run").unwrap();
+        assert_eq!(lock.info().owner, "plan-forge run");
+    }
+
+    #[test]
+    fn test_concurrent_takeover_has_one_winner() {
+        let dir = tempdir().unwrap();
+        let session_dir = dir.path().join("my-session");
+        fs::create_dir_all(&session_dir).unwrap();
+
+        let old = (Utc::now() - chrono::Duration::seconds(LOCK_STALE_SECS + 60)).to_rfc3339();
+        let stale = LockInfo {
+            refreshed_at: old.clone(),
+            acquired_at: old,
+            ..LockInfo::current("crashed run")
+        };
+        write_atomic(
+            session_dir.join(LOCK_FILE),
+            serde_json::to_string(&stale).unwrap(),
+        )
+        .unwrap();
+
+        let barrier = std::sync::Barrier::new(8);
+        let locks: Vec<Result<SessionLock>> = std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..8)
+                .map(|i| {
+                    let (barrier, session_dir) = (&barrier, &session_dir);
+                    scope.spawn(move || {
+                        barrier.wait();
+                        SessionLock::acquire_as(session_dir, &format!("run {}", i))
+                    })
+                })
+                .collect();
+            handles.into_iter().map(|h| h.join().unwrap()).collect()
+        });
+
+        let winners: Vec<&SessionLock> = locks.iter().filter_map(|l| l.as_ref().ok()).collect();
+        assert_eq!(winners.len(), 1);
+        assert_eq!(
+            SessionLock::holder(&session_dir).as_ref(),
+            Some(winners[0].info())
+        );
+        assert!(session_dir.join(TAKEOVER_FILE).exists());
+    }
+
+    #[test]
+    fn test_write_atomic_replaces_contents() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("state.json");
+
+        write_atomic(&path, "old").unwrap();
+        write_atomic(&path, "new").unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "new");
+        // No temp files left behind
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+}
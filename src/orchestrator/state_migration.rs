@@ -0,0 +1,505 @@
+//! Versioned migrations for `orchestration-state.json`.
+//!
+//! Each migrator upgrades the raw JSON by one schema version, so a state file
+//! written by any earlier release is upgraded step by step (v1 → v2 → v3 …)
+//! before it is deserialized. `OrchestrationState::load` migrates in memory;
+//! `migrate_session` persists the upgrade and keeps the original file as
+//! `orchestration-state.v<N>.json.bak`.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde_json::{Map, Value, json};
+
+use super::orchestration_state::{OrchestrationState, SCHEMA_VERSION};
+use super::session_lock::{SessionLock, write_atomic};
+
+/// State file name in the session directory
+pub const STATE_FILE: &str = "orchestration-state.json";
+
+/// Upgrades a state object by one schema version.
+type Migrator = fn(&mut Map<String, Value>) -> Result<()>;
+
+/// Migrators indexed by the version they upgrade from (`MIGRATIONS[0]` is v1 → v2)
+const MIGRATIONS: &[Migrator] = &[
+    migrate_v1_to_v2,
+    migrate_v2_to_v3,
+    migrate_v3_to_v4,
+    migrate_v4_to_v5,
+];
+
+// Every schema bump needs a migrator
+const _: () = assert!(MIGRATIONS.len() as u32 + 1 == SCHEMA_VERSION);
+
+/// Result of migrating one session directory.
+#[derive(Debug, Clone, PartialEq)]
+pub enum MigrationOutcome {
+    /// The session has no orchestration state (legacy plan/review files only)
+    NoState,
+    /// The state is already at `SCHEMA_VERSION`
+    UpToDate,
+    /// The state was upgraded; the original was kept at `backup`
+    Migrated { from: u32, to: u32, backup: PathBuf },
+}
+
+/// Schema version of a raw state file. Files written before versioning
+/// have no `schema_version` and are treated as v1.
+pub fn schema_version_of(state: &Value) -> Result<u32> {
+    match state.get("schema_version") {
+        None => Ok(1),
+        Some(version) => version
+            .as_u64()
+            .and_then(|v| u32::try_from(v).ok())
+            .with_context(|| format!("Invalid state schema_version: {}", version)),
+    }
+}
+
+/// Upgrade a raw state to `SCHEMA_VERSION`, one migrator at a time.
+///
+/// Fails for states written by a newer plan-forge instead of discarding them.
+pub fn migrate_value(mut state: Value) -> Result<Value> {
+    let from = schema_version_of(&state)?;
+    if from == 0 || from > SCHEMA_VERSION {
+        anyhow::bail!(
+            "State schema version {} is not supported (this plan-forge reads up to v{}); \
+             upgrade plan-forge to resume this session",
+            from,
+            SCHEMA_VERSION
+        );
+    }
+
+    let fields = state
+        .as_object_mut()
+        .context("State file is not a JSON object")?;
+    for version in from..SCHEMA_VERSION {
+        MIGRATIONS[(version - 1) as usize](fields).with_context(|| {
+            format!(
+                "Failed to migrate state from v{} to v{}",
+                version,
+                version + 1
+            )
+        })?;
+        fields.insert("schema_version".to_string(), json!(version + 1));
+    }
+    Ok(state)
+}
+
+/// Upgrade the state file of a session directory on disk.
+///
+/// The original file is copied to `orchestration-state.v<N>.json.bak` before
+/// the migrated state replaces it. An existing backup is never overwritten,
+/// so it always holds the oldest original.
+pub fn migrate_session(session_dir: &Path) -> Result<MigrationOutcome> {
+    let state_file = session_dir.join(STATE_FILE);
+    if !state_file.exists() {
+        return Ok(MigrationOutcome::NoState);
+    }
+
+    let json = fs::read_to_string(&state_file).context("Failed to read state file")?;
+    let raw: Value = serde_json::from_str(&json).context("Failed to parse state file")?;
+    let from = schema_version_of(&raw)?;
+    if from == SCHEMA_VERSION {
+        return Ok(MigrationOutcome::UpToDate);
+    }
+
+    let migrated = migrate_value(raw)?;
+    let state: OrchestrationState = serde_json::from_value(migrated)
+        .context("Migrated state does not match the current schema")?;
+
+    let backup = session_dir.join(format!("orchestration-state.v{}.json.bak", from));
+    if !backup.exists() {
+        write_atomic(&backup, &json)
+            .with_context(|| format!("Failed to back up state to {:?}", backup))?;
+    }
+    state.save(session_dir)?;
+
+    tracing::info!(
+        "Migrated {:?} from schema v{} to v{} (backup: {:?})",
+        state_file,
+        from,
+        SCHEMA_VERSION,
+        backup
+    );
+    Ok(MigrationOutcome::Migrated {
+        from,
+        to: SCHEMA_VERSION,
+        backup,
+    })
+}
+
+/// Migrate every session with orchestration state under `runs_dir`
+/// (e.g. `.plan-forge/`), holding each session's lock while it is upgraded.
+///
+/// Returns one result per session, sorted by directory, so one locked or
+/// broken session doesn't stop the others.
+pub fn migrate_all(runs_dir: &Path) -> Result<Vec<(PathBuf, Result<MigrationOutcome>)>> {
+    let mut session_dirs: Vec<PathBuf> = fs::read_dir(runs_dir)
+        .with_context(|| format!("Failed to read sessions directory {:?}", runs_dir))?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.join(STATE_FILE).is_file())
+        .collect();
+    session_dirs.sort();
+
+    Ok(session_dirs
+        .into_iter()
+        .map(|session_dir| {
+            let outcome = SessionLock::acquire(&session_dir).and_then(|lock| {
+                let outcome = migrate_session(&session_dir);
+                drop(lock);
+                outcome
+            });
+            (session_dir, outcome)
+        })
+        .collect())
+}
+
+/// v2 added token diagnostics, iteration history, the review gating flags,
+/// and best-plan tracking.
+fn migrate_v1_to_v2(state: &mut Map<String, Value>) -> Result<()> {
+    let total_tokens = state
+        .get("total_tokens")
+        .and_then(Value::as_u64)
+        .unwrap_or(0);
+    let iteration = state.get("iteration").and_then(Value::as_u64).unwrap_or(0);
+    let current_plan = state.get("current_plan").cloned().unwrap_or(Value::Null);
+    let reviews = state
+        .get("reviews")
+        .and_then(Value::as_array)
+        .map_or(0, |reviews| reviews.len() as u64);
+    let last_score = state
+        .get("reviews")
+        .and_then(Value::as_array)
+        .and_then(|reviews| reviews.last())
+        .and_then(|review| review.get("score"))
+        .and_then(Value::as_f64);
+
+    // v1 didn't split tokens by component; keep the total, marked estimated
+    state.entry("token_breakdown").or_insert_with(|| {
+        json!({
+            "orchestrator_input": 0,
+            "orchestrator_output": 0,
+            "planner_input": 0,
+            "planner_output": 0,
+            "reviewer_input": 0,
+            "reviewer_output": 0,
+            "total": total_tokens,
+            "estimated": true,
+        })
+    });
+    state
+        .entry("iteration_history")
+        .or_insert_with(|| json!([]));
+    state
+        .entry("requires_human_input_pending")
+        .or_insert(json!(false));
+    state.entry("last_review_passed").or_insert(json!(false));
+
+    // v1 kept only the latest plan, so it seeds best-plan tracking together
+    // with the score of its last review
+    match last_score {
+        Some(score) if !current_plan.is_null() => {
+            state.entry("best_plan").or_insert(current_plan.clone());
+            state.entry("best_score").or_insert(json!(score));
+        }
+        _ => {
+            state.entry("best_plan").or_insert(Value::Null);
+            state.entry("best_score").or_insert(json!(0.0));
+        }
+    }
+
+    // A plan generated after the last review still has to be reviewed
+    state
+        .entry("needs_review")
+        .or_insert(json!(!current_plan.is_null() && reviews < iteration));
+    Ok(())
+}
+
+/// v3 added model escalation, retry diagnostics, per-model usage,
+/// per-iteration token budgets, and the `Cancelled` status; iteration records
+/// gained the escalation rung.
+fn migrate_v2_to_v3(state: &mut Map<String, Value>) -> Result<()> {
+    let iteration = state.get("iteration").and_then(Value::as_u64).unwrap_or(0);
+
+    state.entry("model_rung").or_insert(json!(0));
+    state.entry("retries").or_insert_with(|| json!([]));
+    state.entry("model_usage").or_insert_with(|| json!({}));
+    // Tokens spent before the upgrade don't count against the current
+    // iteration's budget
+    state.entry("iteration_tokens").or_insert_with(|| {
+        json!({
+            "iteration": iteration,
+            "planner": 0,
+            "reviewer": 0,
+        })
+    });
+
+    if let Some(records) = state
+        .get_mut("iteration_history")
+        .and_then(Value::as_array_mut)
+    {
+        for record in records.iter_mut().filter_map(Value::as_object_mut) {
+            record.entry("rung").or_insert(json!(0));
+        }
+    }
+    Ok(())
+}
+
+/// v4 tracks running time per run so pauses don't count toward the session
+/// time limit.
+fn migrate_v3_to_v4(state: &mut Map<String, Value>) -> Result<()> {
+    // Running time before the upgrade isn't known; only later runs count
+    state.entry("active_secs").or_insert(json!(0));
+    Ok(())
+}
+
+/// v5 records per-role tokens on iteration records for forecasting.
+fn migrate_v4_to_v5(_state: &mut Map<String, Value>) -> Result<()> {
+    // Earlier records can't be split by role; forecasts skip them
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::orchestrator::{ErrorClass, IterationOutcome, OrchestrationStatus};
+    use tempfile::tempdir;
+
+    const V1_FIXTURE: &str = include_str!("../../tests/fixtures/state/v1.json");
+    const V2_FIXTURE: &str = include_str!("../../tests/fixtures/state/v2.json");
+    const V3_FIXTURE: &str = include_str!("../../tests/fixtures/state/v3.json");
+    const V4_FIXTURE: &str = include_str!("../../tests/fixtures/state/v4.json");
+
+    fn make_state(fixture: &str) -> OrchestrationState {
+        let raw: Value = serde_json::from_str(fixture).unwrap();
+        serde_json::from_value(migrate_value(raw).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_migrate_v1_fixture() {
+        let state = make_state(V1_FIXTURE);
+
+        assert_eq!(state.schema_version, SCHEMA_VERSION);
+        assert_eq!(state.task_slug, "api-rate-limiting");
+        assert_eq!(state.iteration, 2);
+        assert_eq!(state.reviews.len(), 2);
+        assert!(matches!(state.status, OrchestrationStatus::Paused { .. }));
+        assert!(state.pending_human_input.is_some());
+        assert_eq!(state.human_inputs.len(), 1);
+
+        // v2 fields
+        assert_eq!(state.token_breakdown.total, 48210);
+        assert!(state.token_breakdown.estimated);
+        assert!(state.iteration_history.is_empty());
+        assert_eq!(state.best_score, 0.71);
+        assert_eq!(
+            state.best_plan.as_ref().unwrap()["title"],
+            "API rate limiting"
+        );
+        assert!(!state.needs_review);
+
+        // v3 fields
+        assert_eq!(state.model_rung, 0);
+        assert_eq!(state.iteration_tokens.iteration, 2);
+        assert!(state.model_usage.is_empty());
+    }
+
+    #[test]
+    fn test_migrate_v2_fixture() {
+        let state = make_state(V2_FIXTURE);
+
+        assert_eq!(state.schema_version, SCHEMA_VERSION);
+        assert_eq!(state.status, OrchestrationStatus::CompletedBestEffort);
+        assert_eq!(state.token_breakdown.total, 91544);
+        assert!(!state.token_breakdown.estimated);
+        assert_eq!(state.best_score, 0.74);
+        assert_eq!(state.iteration_history.len(), 2);
+        assert!(state.iteration_history.iter().all(|r| r.rung == 0));
+        assert!(
+            state
+                .iteration_history
+                .iter()
+                .all(|r| r.role_tokens.is_none())
+        );
+        assert_eq!(state.iteration_history[1].review_score, Some(0.74));
+        assert_eq!(state.iteration_tokens.iteration, 3);
+        assert!(state.retries.is_empty());
+
+        // v4 fields
+        assert_eq!(state.active_secs, 0);
+        assert!(state.run_started_iso.is_none());
+    }
+
+    #[test]
+    fn test_migrate_v3_fixture() {
+        let state = make_state(V3_FIXTURE);
+
+        assert_eq!(state.schema_version, SCHEMA_VERSION);
+        assert_eq!(state.status, OrchestrationStatus::Cancelled);
+        assert_eq!(state.model_rung, 1);
+        assert_eq!(state.iteration_history[1].rung, 1);
+        assert_eq!(state.iteration_history[0].critical_rule_ids, ["V-001"]);
+        assert_eq!(state.retries.len(), 1);
+        assert_eq!(state.retries[0].class, ErrorClass::Retryable);
+        assert_eq!(
+            state.model_usage["anthropic/claude-sonnet"].input_tokens,
+            22500
+        );
+        assert_eq!(state.iteration_tokens.planner, 21000);
+
+        // v4 fields
+        assert_eq!(state.active_secs, 0);
+        assert!(state.run_started_iso.is_none());
+        // v5 fields
+        assert!(
+            state
+                .iteration_history
+                .iter()
+                .all(|r| r.role_tokens.is_none())
+        );
+    }
+
+    #[test]
+    fn test_migrate_v4_fixture() {
+        let state = make_state(V4_FIXTURE);
+
+        assert_eq!(state.schema_version, SCHEMA_VERSION);
+        assert!(matches!(state.status, OrchestrationStatus::Paused { .. }));
+        assert!(state.pending_human_input.is_some());
+        assert_eq!(state.active_secs, 1260);
+        assert_eq!(state.model_usage.len(), 2);
+        assert_eq!(
+            state.iteration_history[1].outcome,
+            IterationOutcome::HumanInputRequested
+        );
+
+        // v5 fields
+        assert!(
+            state
+                .iteration_history
+                .iter()
+                .all(|r| r.role_tokens.is_none())
+        );
+    }
+
+    #[test]
+    fn test_unversioned_state_is_v1() {
+        let mut raw: Value = serde_json::from_str(V1_FIXTURE).unwrap();
+        raw.as_object_mut().unwrap().remove("schema_version");
+        assert_eq!(schema_version_of(&raw).unwrap(), 1);
+
+        let migrated = migrate_value(raw).unwrap();
+        assert_eq!(migrated["schema_version"], SCHEMA_VERSION);
+        assert_eq!(migrated["best_score"], 0.71);
+    }
+
+    #[test]
+    fn test_v1_plan_without_review_needs_review() {
+        let mut raw: Value = serde_json::from_str(V1_FIXTURE).unwrap();
+        raw["reviews"] = json!([{ "score": 0.62 }]);
+
+        let migrated = migrate_value(raw).unwrap();
+        assert_eq!(migrated["needs_review"], true);
+        assert_eq!(migrated["best_score"], 0.62);
+    }
+
+    #[test]
+    fn test_newer_schema_is_rejected() {
+        let mut raw: Value = serde_json::from_str(V2_FIXTURE).unwrap();
+        raw["schema_version"] = json!(SCHEMA_VERSION + 1);
+
+        let err = migrate_value(raw).unwrap_err();
+        assert!(err.to_string().contains("not supported"));
+    }
+
+    #[test]
+    fn test_current_state_is_unchanged() {
+        let state = OrchestrationState::new(
+            "test".to_string(),
+            "task".to_string(),
+            PathBuf::new(),
+            "slug".to_string(),
+        );
+        let raw = serde_json::to_value(&state).unwrap();
+        assert_eq!(migrate_value(raw.clone()).unwrap(), raw);
+    }
+
+    #[test]
+    fn test_migrate_session_backs_up_original() {
+        let dir = tempdir().unwrap();
+        let session_dir = dir.path().join("api-rate-limiting");
+        fs::create_dir_all(&session_dir).unwrap();
+        fs::write(session_dir.join(STATE_FILE), V1_FIXTURE).unwrap();
+
+        // Loading migrates in memory without touching the file
+        let loaded = OrchestrationState::load(&session_dir).unwrap().unwrap();
+        assert_eq!(loaded.schema_version, SCHEMA_VERSION);
+        assert_eq!(
+            fs::read_to_string(session_dir.join(STATE_FILE)).unwrap(),
+            V1_FIXTURE
+        );
+
+        let outcome = migrate_session(&session_dir).unwrap();
+        let backup = session_dir.join("orchestration-state.v1.json.bak");
+        assert_eq!(
+            outcome,
+            MigrationOutcome::Migrated {
+                from: 1,
+                to: SCHEMA_VERSION,
+                backup: backup.clone(),
+            }
+        );
+        assert_eq!(fs::read_to_string(&backup).unwrap(), V1_FIXTURE);
+
+        let raw: Value =
+            serde_json::from_str(&fs::read_to_string(session_dir.join(STATE_FILE)).unwrap())
+                .unwrap();
+        assert_eq!(schema_version_of(&raw).unwrap(), SCHEMA_VERSION);
+        assert_eq!(
+            migrate_session(&session_dir).unwrap(),
+            MigrationOutcome::UpToDate
+        );
+    }
+
+    #[test]
+    fn test_migrate_all_sessions() {
+        let dir = tempdir().unwrap();
+        for (slug, fixture) in [
+            ("a-v1", V1_FIXTURE),
+            ("b-v2", V2_FIXTURE),
+            ("c-v3", V3_FIXTURE),
+            ("d-v4", V4_FIXTURE),
+        ] {
+            let session_dir = dir.path().join(slug);
+            fs::create_dir_all(&session_dir).unwrap();
+            fs::write(session_dir.join(STATE_FILE), fixture).unwrap();
+        }
+        let broken = dir.path().join("e-broken");
+        fs::create_dir_all(&broken).unwrap();
+        fs::write(broken.join(STATE_FILE), "{ not json").unwrap();
+        // Legacy session without orchestration state is skipped
+        fs::create_dir_all(dir.path().join("f-legacy")).unwrap();
+
+        let results = migrate_all(dir.path()).unwrap();
+        assert_eq!(results.len(), 5);
+        assert!(matches!(
+            results[0].1,
+            Ok(MigrationOutcome::Migrated { from: 1, .. })
+        ));
+        assert!(matches!(
+            results[1].1,
+            Ok(MigrationOutcome::Migrated { from: 2, .. })
+        ));
+        assert!(matches!(
+            results[2].1,
+            Ok(MigrationOutcome::Migrated { from: 3, .. })
+        ));
+        assert!(matches!(
+            results[3].1,
+            Ok(MigrationOutcome::Migrated { from: 4, .. })
+        ));
+        assert!(results[4].1.is_err());
+        // Locks are released after each session
+        assert!(!dir.path().join("a-v1").join("session.lock").exists());
+    }
+}
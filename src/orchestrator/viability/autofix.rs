@@ -0,0 +1,482 @@
+//! Mechanical fixes for viability violations.
+//!
+//! Some violations have a single deterministic fix, so there is no reason to
+//! spend a planner round-trip on them:
+//!
+//! - V-006: add the missing dependency for a `${id.field}` reference
+//!   (skipped when the new edge would create a cycle)
+//! - V-010: drop dependencies that are neither referenced via `${dep.*}` nor
+//!   needed for ordering (already implied through another dependency)
+//! - V-012: fill in a default `estimated_tokens` per opcode (SEARCH_CODE
+//!   estimates are sized by dry-running the query when a `CodeSearch` is set)
+//!
+//! Every change is recorded so it can be reported back to the orchestrator.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::models::{Instruction, OpCode};
+use crate::orchestrator::code_search::CodeSearch;
+
+use super::{InstructionGraph, default_token_estimate};
+
+/// Kind of change applied by the auto-fixer
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum FixAction {
+    /// Dependency added to the instruction
+    AddDependency { dependency: String },
+    /// Dependency removed from the instruction
+    RemoveDependency { dependency: String },
+    /// Default token estimate filled in
+    SetEstimatedTokens { estimated_tokens: u32 },
+}
+
+/// A single change applied to a plan
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AppliedFix {
+    /// Rule the fix addresses (e.g., "VIABILITY-006")
+    pub rule_id: String,
+    /// Instruction that was changed
+    pub instruction_id: String,
+    /// What was changed
+    #[serde(flatten)]
+    pub action: FixAction,
+    /// Human-readable description of the change
+    pub description: String,
+}
+
+/// Applies deterministic fixes for viability violations to plan instructions
+#[derive(Debug, Clone)]
+pub struct PlanAutoFixer {
+    /// Add dependencies for unreferenced `${id.*}` refs (V-006)
+    pub add_missing_deps: bool,
+    /// Remove unreferenced dependencies already implied transitively (V-010)
+    pub remove_redundant_deps: bool,
+    /// Fill default token estimates for context-heavy opcodes (V-012)
+    pub fill_token_estimates: bool,
+    /// Size SEARCH_CODE estimates by hit count (None = opcode default)
+    pub code_search: Option<Arc<CodeSearch>>,
+}
+
+impl Default for PlanAutoFixer {
+    fn default() -> Self {
+        Self {
+            add_missing_deps: true,
+            remove_redundant_deps: true,
+            fill_token_estimates: true,
+            code_search: None,
+        }
+    }
+}
+
+impl PlanAutoFixer {
+    /// Create an auto-fixer with all fixes enabled
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Estimate SEARCH_CODE tokens from the query's hit count
+    pub fn with_code_search(mut self, code_search: Arc<CodeSearch>) -> Self {
+        self.code_search = Some(code_search);
+        self
+    }
+
+    /// Apply fixes to the `instructions` array of a plan JSON in place.
+    ///
+    /// Only the `dependencies` and `estimated_tokens` fields of changed
+    /// instructions are patched, so fields `Instruction` doesn't model are
+    /// kept. Plans without instructions are left untouched.
+    pub fn fix_plan_json(&self, plan_json: &mut Value) -> Result<Vec<AppliedFix>> {
+        let Some(instructions_value) = plan_json.get_mut("instructions") else {
+            return Ok(Vec::new());
+        };
+        if instructions_value.is_null() {
+            return Ok(Vec::new());
+        }
+
+        let original: Vec<Instruction> = serde_json::from_value(instructions_value.clone())
+            .context("Failed to parse plan instructions for auto-fix")?;
+        let mut instructions = original.clone();
+        let fixes = self.fix_instructions(&mut instructions);
+
+        // Parsing succeeded, so the value is an array of objects in the same order
+        let values = instructions_value.as_array_mut().into_iter().flatten();
+        for ((value, before), after) in values.zip(&original).zip(&instructions) {
+            let Some(object) = value.as_object_mut() else {
+                continue;
+            };
+            if after.dependencies != before.dependencies {
+                object.insert(
+                    "dependencies".to_string(),
+                    serde_json::json!(after.dependencies),
+                );
+            }
+            if after.estimated_tokens != before.estimated_tokens {
+                object.insert(
+                    "estimated_tokens".to_string(),
+                    serde_json::json!(after.estimated_tokens),
+                );
+            }
+        }
+        Ok(fixes)
+    }
+
+    /// Apply fixes to instructions in place, returning every change made
+    pub fn fix_instructions(&self, instructions: &mut [Instruction]) -> Vec<AppliedFix> {
+        let mut fixes = Vec::new();
+        if self.add_missing_deps {
+            fixes.extend(add_missing_deps(instructions));
+        }
+        if self.remove_redundant_deps {
+            fixes.extend(remove_redundant_deps(instructions));
+        }
+        if self.fill_token_estimates {
+            fixes.extend(fill_token_estimates(
+                instructions,
+                self.code_search.as_deref(),
+            ));
+        }
+        fixes
+    }
+}
+
+/// V-006: add a dependency for every `${id.*}` ref to an existing instruction
+/// that isn't already a dependency, unless the edge would close a cycle.
+fn add_missing_deps(instructions: &mut [Instruction]) -> Vec<AppliedFix> {
+    // Collect owned adjacency and candidate edges, then release the graph borrow
+    let (mut deps, candidates) = {
+        let graph = InstructionGraph::new(instructions);
+        let deps: Vec<Vec<usize>> = (0..graph.len()).map(|i| graph.deps(i).to_vec()).collect();
+
+        let mut candidates = Vec::new();
+        for (idx, instr) in graph.instructions.iter().enumerate() {
+            let mut seen = HashSet::new();
+            for var_ref in graph.var_refs(idx) {
+                let Some(ref_idx) = graph.index_of(&var_ref.instruction_id) else {
+                    continue; // V-002 territory
+                };
+                if ref_idx != idx
+                    && !instr.dependencies.contains(&var_ref.instruction_id)
+                    && seen.insert(ref_idx)
+                {
+                    candidates.push((idx, ref_idx));
+                }
+            }
+        }
+        (deps, candidates)
+    };
+
+    let mut fixes = Vec::new();
+    for (idx, ref_idx) in candidates {
+        // Adding idx -> ref_idx closes a cycle if ref_idx already depends on idx
+        if depends_on(&deps, ref_idx, idx) {
+            continue;
+        }
+        deps[idx].push(ref_idx);
+
+        let dependency = instructions[ref_idx].id.clone();
+        let instr = &mut instructions[idx];
+        instr.dependencies.push(dependency.clone());
+        fixes.push(AppliedFix {
+            rule_id: "VIABILITY-006".to_string(),
+            instruction_id: instr.id.clone(),
+            description: format!(
+                "Added dependency '{}' to '{}' (referenced via ${{{}.*}})",
+                dependency, instr.id, dependency
+            ),
+            action: FixAction::AddDependency { dependency },
+        });
+    }
+    fixes
+}
+
+/// V-010: remove dependencies that aren't referenced via `${dep.*}` and are
+/// already ancestors of another dependency, so execution order is unchanged.
+fn remove_redundant_deps(instructions: &mut [Instruction]) -> Vec<AppliedFix> {
+    let removals: Vec<(usize, String)> = {
+        let graph = InstructionGraph::new(instructions);
+        if graph.has_cycle() {
+            return Vec::new();
+        }
+
+        let mut removals = Vec::new();
+        for (idx, instr) in graph.instructions.iter().enumerate() {
+            if graph.deps(idx).len() < 2 {
+                continue;
+            }
+            let referenced = graph.referenced_ids(idx);
+
+            // Strict ancestors of the dependencies; in a DAG a dependency can
+            // only appear here by being reachable through a different one
+            let mut implied = vec![false; graph.len()];
+            let mut stack: Vec<usize> = graph
+                .deps(idx)
+                .iter()
+                .flat_map(|&d| graph.deps(d).iter().copied())
+                .collect();
+            while let Some(node) = stack.pop() {
+                if !implied[node] {
+                    implied[node] = true;
+                    stack.extend(graph.deps(node).iter().copied());
+                }
+            }
+
+            for dep in &instr.dependencies {
+                if let Some(dep_idx) = graph.index_of(dep)
+                    && implied[dep_idx]
+                    && !referenced.contains(dep.as_str())
+                {
+                    removals.push((idx, dep.clone()));
+                }
+            }
+        }
+        removals
+    };
+
+    let mut fixes = Vec::new();
+    for (idx, dependency) in removals {
+        let instr = &mut instructions[idx];
+        instr.dependencies.retain(|d| d != &dependency);
+        fixes.push(AppliedFix {
+            rule_id: "VIABILITY-010".to_string(),
+            instruction_id: instr.id.clone(),
+            description: format!(
+                "Removed dependency '{}' from '{}' (unreferenced and implied by another dependency)",
+                dependency, instr.id
+            ),
+            action: FixAction::RemoveDependency { dependency },
+        });
+    }
+    fixes
+}
+
+/// V-012: fill default `estimated_tokens` for opcodes that require one
+fn fill_token_estimates(
+    instructions: &mut [Instruction],
+    code_search: Option<&CodeSearch>,
+) -> Vec<AppliedFix> {
+    let mut fixes = Vec::new();
+    for instr in instructions.iter_mut() {
+        if instr.estimated_tokens.is_some() {
+            continue;
+        }
+        let searched = code_search
+            .filter(|_| instr.op == OpCode::SearchCode)
+            .and_then(|search| search_tokens(search, instr));
+        if let Some(estimated_tokens) = searched.or_else(|| default_token_estimate(&instr.op)) {
+            instr.estimated_tokens = Some(estimated_tokens);
+            fixes.push(AppliedFix {
+                rule_id: "VIABILITY-012".to_string(),
+                instruction_id: instr.id.clone(),
+                description: format!(
+                    "Set default estimated_tokens={} for '{}'",
+                    estimated_tokens, instr.id
+                ),
+                action: FixAction::SetEstimatedTokens { estimated_tokens },
+            });
+        }
+    }
+    fixes
+}
+
+/// Token estimate for a SEARCH_CODE instruction from its dry-run hit count
+fn search_tokens(search: &CodeSearch, instr: &Instruction) -> Option<u32> {
+    let query = instr.params.get("query")?.as_str()?;
+    if query.contains("${") {
+        return None; // resolved at execution time
+    }
+    let scope = instr.params.get("scope").and_then(|s| s.as_str());
+    Some(search.search(query, scope).estimated_tokens())
+}
+
+/// Whether `from` transitively depends on `target` (or is `target`)
+fn depends_on(deps: &[Vec<usize>], from: usize, target: usize) -> bool {
+    let mut visited = vec![false; deps.len()];
+    let mut stack = vec![from];
+    while let Some(node) = stack.pop() {
+        if node == target {
+            return true;
+        }
+        if !visited[node] {
+            visited[node] = true;
+            stack.extend(deps[node].iter().copied());
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::OpCode;
+    use crate::orchestrator::viability::ViabilityChecker;
+
+    fn make_instruction(
+        id: &str,
+        op: OpCode,
+        deps: Vec<&str>,
+        params: serde_json::Value,
+    ) -> Instruction {
+        Instruction {
+            id: id.to_string(),
+            op,
+            params,
+            dependencies: deps.iter().map(|s| s.to_string()).collect(),
+            description: format!("Test instruction {}", id),
+            estimated_tokens: Some(100),
+        }
+    }
+
+    #[test]
+    fn test_autofix_adds_missing_dependency() {
+        let mut instructions = vec![
+            make_instruction("search", OpCode::SearchCode, vec![], serde_json::json!({})),
+            make_instruction(
+                "read",
+                OpCode::ReadFiles,
+                vec![],
+                serde_json::json!({ "files": "${search.files}" }),
+            ),
+        ];
+
+        let fixes = PlanAutoFixer::new().fix_instructions(&mut instructions);
+
+        assert_eq!(fixes.len(), 1);
+        assert_eq!(fixes[0].rule_id, "VIABILITY-006");
+        assert_eq!(instructions[1].dependencies, vec!["search".to_string()]);
+        assert!(
+            ViabilityChecker::new()
+                .check_variable_refs(&instructions)
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_autofix_skips_dependency_that_creates_cycle() {
+        let mut instructions = vec![
+            make_instruction("a", OpCode::SearchCode, vec![], serde_json::json!({})),
+            make_instruction(
+                "b",
+                OpCode::ReadFiles,
+                vec!["a"],
+                serde_json::json!({ "p": "${a.files}" }),
+            ),
+        ];
+        // a references b, but b already depends on a
+        instructions[0].params = serde_json::json!({ "p": "${b.content}" });
+
+        let fixes = PlanAutoFixer::new().fix_instructions(&mut instructions);
+
+        assert!(fixes.is_empty());
+        assert!(instructions[0].dependencies.is_empty());
+    }
+
+    #[test]
+    fn test_autofix_removes_only_implied_unreferenced_deps() {
+        let mut instructions = vec![
+            make_instruction("a", OpCode::SearchCode, vec![], serde_json::json!({})),
+            make_instruction("b", OpCode::ReadFiles, vec!["a"], serde_json::json!({})),
+            // a is implied by b; c is also the only path for ordering on b
+            make_instruction("c", OpCode::EditCode, vec!["a", "b"], serde_json::json!({})),
+            // x has a single sequencing dependency, which must be kept
+            make_instruction("x", OpCode::RunTest, vec!["c"], serde_json::json!({})),
+        ];
+
+        let fixes = PlanAutoFixer::new().fix_instructions(&mut instructions);
+
+        assert_eq!(
+            fixes,
+            vec![AppliedFix {
+                rule_id: "VIABILITY-010".to_string(),
+                instruction_id: "c".to_string(),
+                action: FixAction::RemoveDependency {
+                    dependency: "a".to_string()
+                },
+                description: fixes[0].description.clone(),
+            }]
+        );
+        assert_eq!(instructions[2].dependencies, vec!["b".to_string()]);
+        assert_eq!(instructions[3].dependencies, vec!["c".to_string()]);
+    }
+
+    #[test]
+    fn test_autofix_keeps_referenced_implied_dep() {
+        let mut instructions = vec![
+            make_instruction("a", OpCode::SearchCode, vec![], serde_json::json!({})),
+            make_instruction("b", OpCode::ReadFiles, vec!["a"], serde_json::json!({})),
+            make_instruction(
+                "c",
+                OpCode::EditCode,
+                vec!["a", "b"],
+                serde_json::json!({ "p": "${a.files}" }),
+            ),
+        ];
+
+        let fixes = PlanAutoFixer::new().fix_instructions(&mut instructions);
+
+        assert!(fixes.is_empty());
+        assert_eq!(instructions[2].dependencies.len(), 2);
+    }
+
+    #[test]
+    fn test_autofix_fills_token_estimates() {
+        let mut instructions = vec![
+            make_instruction("edit", OpCode::EditCode, vec![], serde_json::json!({})),
+            make_instruction(
+                "verify",
+                OpCode::VerifyExists,
+                vec![],
+                serde_json::json!({}),
+            ),
+        ];
+        instructions[0].estimated_tokens = None;
+        instructions[1].estimated_tokens = None;
+
+        let fixes = PlanAutoFixer::new().fix_instructions(&mut instructions);
+
+        assert_eq!(fixes.len(), 1);
+        assert_eq!(fixes[0].rule_id, "VIABILITY-012");
+        assert_eq!(
+            instructions[0].estimated_tokens,
+            default_token_estimate(&OpCode::EditCode)
+        );
+        assert!(instructions[1].estimated_tokens.is_none());
+        assert!(
+            ViabilityChecker::new()
+                .check_token_estimates(&instructions)
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_autofix_plan_json_round_trip() {
+        let mut plan = serde_json::json!({
+            "title": "Test",
+            "instructions": [
+                { "id": "search", "op": "SEARCH_CODE", "params": {}, "description": "s" },
+                { "id": "read", "op": "READ_FILES", "params": { "files": "${search.files}" },
+                  "description": "r", "estimated_tokens": 100, "rationale": "keep me" }
+            ]
+        });
+
+        let fixes = PlanAutoFixer::new().fix_plan_json(&mut plan).unwrap();
+
+        assert_eq!(fixes.len(), 2);
+        assert_eq!(plan["title"], "Test");
+        // Fields the Instruction model doesn't know about survive the fix
+        assert_eq!(plan["instructions"][1]["rationale"], "keep me");
+        assert_eq!(plan["instructions"][1]["dependencies"][0], "search");
+        assert!(plan["instructions"][0]["estimated_tokens"].is_number());
+
+        let mut no_instructions = serde_json::json!({ "title": "Empty" });
+        let fixes = PlanAutoFixer::new()
+            .fix_plan_json(&mut no_instructions)
+            .unwrap();
+        assert!(fixes.is_empty());
+    }
+}
@@ -3,20 +3,22 @@
 //! - V-001: Missing test verification
 //! - V-002: Logical flow / cycle detection
 
-use std::collections::{HashMap, HashSet};
+use crate::models::{Instruction, OpCode, OpCodeCategory};
 
-use crate::models::{Instruction, OpCode};
-
-use super::{ViabilityChecker, ViabilitySeverity, ViabilityViolation};
+use super::{InstructionGraph, ViabilityChecker, ViabilitySeverity, ViabilityViolation};
 
 impl ViabilityChecker {
     /// V-001: Check that code edits have corresponding test verification
     ///
     /// If plan has instructions with op=EDIT_CODE, it MUST have a downstream
-    /// instruction with op=RUN_TEST.
+    /// instruction with op=RUN_TEST (or a custom opcode in the testing category).
     pub fn check_missing_test(&self, instructions: &[Instruction]) -> Option<ViabilityViolation> {
         let has_edit = instructions.iter().any(|i| i.op == OpCode::EditCode);
-        let has_test = instructions.iter().any(|i| i.op == OpCode::RunTest);
+        let has_test = instructions.iter().any(|i| {
+            i.op == OpCode::RunTest
+                || (matches!(i.op, OpCode::Custom(_))
+                    && self.op_category(&i.op) == Some(OpCodeCategory::Testing))
+        });
 
         if has_edit && !has_test {
             let edit_id = instructions
@@ -43,34 +45,36 @@ impl ViabilityChecker {
     /// - Dependencies must reference existing instruction IDs
     /// - No circular dependencies
     pub fn check_logical_flow(&self, instructions: &[Instruction]) -> Vec<ViabilityViolation> {
+        self.check_logical_flow_in_graph(&InstructionGraph::new(instructions))
+    }
+
+    /// V-002 over a prebuilt instruction graph
+    pub(crate) fn check_logical_flow_in_graph(
+        &self,
+        graph: &InstructionGraph,
+    ) -> Vec<ViabilityViolation> {
         let mut violations = Vec::new();
 
-        // Build set of valid instruction IDs
-        let valid_ids: HashSet<&str> = instructions.iter().map(|i| i.id.as_str()).collect();
-
-        // Check each instruction's dependencies
-        for instr in instructions {
-            for dep in &instr.dependencies {
-                if !valid_ids.contains(dep.as_str()) {
-                    violations.push(ViabilityViolation {
-                        rule_id: "VIABILITY-002".to_string(),
-                        instruction_id: Some(instr.id.clone()),
-                        severity: ViabilitySeverity::Critical,
-                        message: format!(
-                            "Instruction '{}' depends on non-existent instruction '{}'",
-                            instr.id, dep
-                        ),
-                        remediation: format!(
-                            "Either remove dependency '{}' or add the missing instruction",
-                            dep
-                        ),
-                    });
-                }
-            }
+        // Check each instruction's dependencies resolve
+        for &(idx, dep) in graph.missing_deps() {
+            let instr = &graph.instructions[idx];
+            violations.push(ViabilityViolation {
+                rule_id: "VIABILITY-002".to_string(),
+                instruction_id: Some(instr.id.clone()),
+                severity: ViabilitySeverity::Critical,
+                message: format!(
+                    "Instruction '{}' depends on non-existent instruction '{}'",
+                    instr.id, dep
+                ),
+                remediation: format!(
+                    "Either remove dependency '{}' or add the missing instruction",
+                    dep
+                ),
+            });
         }
 
-        // Check for circular dependencies using DFS
-        if let Some(cycle) = self.detect_cycle(instructions) {
+        // Check for circular dependencies (Kahn leftovers)
+        if let Some(cycle) = graph.find_cycle() {
             violations.push(ViabilityViolation {
                 rule_id: "VIABILITY-002".to_string(),
                 instruction_id: Some(cycle[0].clone()),
@@ -83,69 +87,6 @@ impl ViabilityChecker {
 
         violations
     }
-
-    /// Detect cycles in the instruction dependency graph
-    fn detect_cycle(&self, instructions: &[Instruction]) -> Option<Vec<String>> {
-        let mut visited = HashSet::new();
-        let mut rec_stack = HashSet::new();
-        let mut path = Vec::new();
-
-        // Build adjacency map
-        let adj: HashMap<&str, Vec<&str>> = instructions
-            .iter()
-            .map(|i| {
-                (
-                    i.id.as_str(),
-                    i.dependencies.iter().map(|d| d.as_str()).collect(),
-                )
-            })
-            .collect();
-
-        for instr in instructions {
-            if !visited.contains(instr.id.as_str())
-                && let Some(cycle) =
-                    self.dfs_cycle(&instr.id, &adj, &mut visited, &mut rec_stack, &mut path)
-            {
-                return Some(cycle);
-            }
-        }
-
-        None
-    }
-
-    #[allow(clippy::only_used_in_recursion)]
-    fn dfs_cycle<'a>(
-        &self,
-        node: &'a str,
-        adj: &HashMap<&'a str, Vec<&'a str>>,
-        visited: &mut HashSet<&'a str>,
-        rec_stack: &mut HashSet<&'a str>,
-        path: &mut Vec<String>,
-    ) -> Option<Vec<String>> {
-        visited.insert(node);
-        rec_stack.insert(node);
-        path.push(node.to_string());
-
-        if let Some(neighbors) = adj.get(node) {
-            for &neighbor in neighbors {
-                if !visited.contains(neighbor) {
-                    if let Some(cycle) = self.dfs_cycle(neighbor, adj, visited, rec_stack, path) {
-                        return Some(cycle);
-                    }
-                } else if rec_stack.contains(neighbor) {
-                    // Found cycle - return path from neighbor to current
-                    let cycle_start = path.iter().position(|n| n == neighbor).unwrap();
-                    let mut cycle: Vec<String> = path[cycle_start..].to_vec();
-                    cycle.push(neighbor.to_string());
-                    return Some(cycle);
-                }
-            }
-        }
-
-        path.pop();
-        rec_stack.remove(node);
-        None
-    }
 }
 
 #[cfg(test)]
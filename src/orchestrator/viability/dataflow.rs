@@ -4,43 +4,33 @@
 //! - V-007: TDD order compliance
 //! - V-008: Variable field name validation
 
-use std::collections::HashSet;
-
-use once_cell::sync::Lazy;
-use regex::Regex;
+use std::collections::HashMap;
 
 use crate::models::{Instruction, OpCode, STEP_RESULT_FIELDS};
 
-use super::{ViabilityChecker, ViabilitySeverity, ViabilityViolation};
-
-/// Pattern to match ${instruction_id.field} variable references (V-006)
-static VAR_REF_PATTERN: Lazy<Regex> = Lazy::new(|| {
-    Regex::new(r"\$\{([a-zA-Z0-9_-]+)\.([a-zA-Z0-9_]+)\}").expect("invalid VAR_REF_PATTERN regex")
-});
-
-/// Pattern to match ${instruction_id.field} for field validation (V-008)
-static VAR_FIELD_PATTERN: Lazy<Regex> =
-    Lazy::new(|| Regex::new(r"\$\{(\w+)\.(\w+)\}").expect("invalid VAR_FIELD_PATTERN regex"));
+use super::{InstructionGraph, ViabilityChecker, ViabilitySeverity, ViabilityViolation};
 
 impl ViabilityChecker {
     /// V-006: Check that variable references have corresponding dependencies
     ///
     /// If instruction B uses ${A.output}, B must list A in its dependencies.
     pub fn check_variable_refs(&self, instructions: &[Instruction]) -> Vec<ViabilityViolation> {
-        let mut violations = Vec::new();
-
-        // Collect all valid instruction IDs for reference validation
-        let valid_ids: HashSet<&str> = instructions.iter().map(|i| i.id.as_str()).collect();
+        self.check_variable_refs_in_graph(&InstructionGraph::new(instructions))
+    }
 
-        for instr in instructions {
-            let params_str = instr.params.to_string();
+    /// V-006 over a prebuilt instruction graph
+    pub(crate) fn check_variable_refs_in_graph(
+        &self,
+        graph: &InstructionGraph,
+    ) -> Vec<ViabilityViolation> {
+        let mut violations = Vec::new();
 
-            // Find all variable references in params
-            for cap in VAR_REF_PATTERN.captures_iter(&params_str) {
-                let referenced_id = cap.get(1).unwrap().as_str();
+        for (idx, instr) in graph.instructions.iter().enumerate() {
+            for var_ref in graph.var_refs(idx) {
+                let referenced_id = var_ref.instruction_id.as_str();
 
                 // Skip if referencing non-existent instruction (V-002 handles this)
-                if !valid_ids.contains(referenced_id) {
+                if !graph.contains(referenced_id) {
                     continue;
                 }
 
@@ -83,16 +73,18 @@ impl ViabilityChecker {
             .map(|(idx, _)| idx)
             .collect();
 
+        // First GENERATE_TEST and last RUN_TEST bound every EDIT_CODE in one pass
+        let first_generate_test = instructions
+            .iter()
+            .position(|i| i.op == OpCode::GenerateTest);
+        let last_run_test = instructions.iter().rposition(|i| i.op == OpCode::RunTest);
+
         for edit_idx in edit_indices {
             // Check if there's a GENERATE_TEST before this EDIT_CODE
-            let has_test_before = instructions[..edit_idx]
-                .iter()
-                .any(|i| i.op == OpCode::GenerateTest);
+            let has_test_before = first_generate_test.is_some_and(|idx| idx < edit_idx);
 
             // Check if there's a RUN_TEST after this EDIT_CODE
-            let has_test_after = instructions[edit_idx..]
-                .iter()
-                .any(|i| i.op == OpCode::RunTest);
+            let has_test_after = last_run_test.is_some_and(|idx| idx >= edit_idx);
 
             if !has_test_before && has_test_after {
                 violations.push(ViabilityViolation {
@@ -116,19 +108,39 @@ impl ViabilityChecker {
     /// V-008: Validate variable reference field names
     ///
     /// Check that all ${instruction_id.field} references use valid field names.
-    /// Valid fields: output, stdout, stderr, exit_code, artifacts, metadata
+    /// Valid fields: output, stdout, stderr, exit_code, artifacts, metadata,
+    /// plus any `produces` fields declared by a referenced custom opcode.
     pub fn check_variable_field_names(
         &self,
         instructions: &[Instruction],
+    ) -> Vec<ViabilityViolation> {
+        self.check_variable_field_names_in_graph(&InstructionGraph::new(instructions))
+    }
+
+    /// V-008 over a prebuilt instruction graph
+    pub(crate) fn check_variable_field_names_in_graph(
+        &self,
+        graph: &InstructionGraph,
     ) -> Vec<ViabilityViolation> {
         let mut violations = Vec::new();
 
-        for instr in instructions {
-            let params_str = instr.params.to_string();
+        // Extra fields produced by custom opcodes, keyed by instruction ID
+        let produced: HashMap<&str, &[String]> = graph
+            .instructions
+            .iter()
+            .filter_map(|i| {
+                self.custom_opcode(&i.op)
+                    .map(|c| (i.id.as_str(), c.produces.as_slice()))
+            })
+            .collect();
 
-            for cap in VAR_FIELD_PATTERN.captures_iter(&params_str) {
-                let field = &cap[2];
-                if !STEP_RESULT_FIELDS.contains(&field) {
+        for (idx, instr) in graph.instructions.iter().enumerate() {
+            for var_ref in graph.var_refs(idx) {
+                let field = var_ref.field.as_str();
+                let is_produced = produced
+                    .get(var_ref.instruction_id.as_str())
+                    .is_some_and(|fields| fields.iter().any(|f| f == field));
+                if !STEP_RESULT_FIELDS.contains(&field) && !is_produced {
                     violations.push(ViabilityViolation {
                         rule_id: "VIABILITY-008".to_string(),
                         instruction_id: Some(instr.id.clone()),
@@ -341,4 +353,38 @@ mod tests {
         let violations = checker.check_variable_field_names(&instructions);
         assert_eq!(violations.len(), 2);
     }
+
+    #[test]
+    fn test_v008_custom_opcode_produced_field() {
+        let checker =
+            ViabilityChecker::new().with_custom_opcodes(vec![crate::config::CustomOpCode {
+                name: "RUN_MIGRATION".to_string(),
+                category: crate::models::OpCodeCategory::Execution,
+                description: String::new(),
+                params_schema: None,
+                produces: vec!["applied".to_string()],
+            }]);
+        let instructions = vec![
+            make_instruction(
+                "migrate",
+                OpCode::Custom("RUN_MIGRATION".to_string()),
+                vec![],
+            ),
+            Instruction {
+                id: "verify".to_string(),
+                op: OpCode::RunCommand,
+                params: serde_json::json!({
+                    "command": "check ${migrate.applied}",
+                    "other": "${verify_db.applied}"
+                }),
+                dependencies: vec!["migrate".to_string()],
+                ..Default::default()
+            },
+        ];
+
+        let violations = checker.check_variable_field_names(&instructions);
+        // Only the field on a non-custom instruction is invalid
+        assert_eq!(violations.len(), 1);
+        assert!(violations[0].message.contains("applied"));
+    }
 }
@@ -0,0 +1,352 @@
+//! Indexed instruction graph shared by the DAG-based checks.
+//!
+//! Built once per plan in O(V + E + params): instruction IDs are resolved to
+//! indices, dependency and dependent adjacency lists are materialized, each
+//! instruction's params are scanned once for `${id.field}` references, and
+//! topological levels are computed with Kahn's algorithm. All traversals are
+//! iterative so very large plans cannot overflow the stack.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use crate::models::Instruction;
+
+/// Pattern to match ${instruction_id.field} variable references
+static VAR_REF_PATTERN: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\$\{([a-zA-Z0-9_-]+)\.([a-zA-Z0-9_]+)\}").expect("invalid VAR_REF_PATTERN regex")
+});
+
+/// A `${instruction_id.field}` reference found in an instruction's params
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VarRef {
+    /// Referenced instruction ID
+    pub instruction_id: String,
+    /// Referenced StepResult field
+    pub field: String,
+}
+
+/// Instruction DAG with precomputed indices, levels, and variable references
+#[derive(Debug, Clone)]
+pub struct InstructionGraph<'a> {
+    /// Instructions in plan order (node index = position)
+    pub instructions: &'a [Instruction],
+    /// Instruction ID -> node index (first occurrence wins)
+    index: HashMap<&'a str, usize>,
+    /// Resolved dependency indices per node
+    deps: Vec<Vec<usize>>,
+    /// Reverse edges: nodes that depend on each node
+    dependents: Vec<Vec<usize>>,
+    /// Dependencies that don't resolve to an instruction: (node, missing id)
+    missing_deps: Vec<(usize, &'a str)>,
+    /// Variable references per node, from a single params scan
+    var_refs: Vec<Vec<VarRef>>,
+    /// Kahn topological order (excludes nodes on or behind a cycle)
+    topo_order: Vec<usize>,
+    /// Topological level per node (0 = roots; unordered nodes stay at 0)
+    levels: Vec<usize>,
+}
+
+impl<'a> InstructionGraph<'a> {
+    /// Build the graph for a set of instructions
+    pub fn new(instructions: &'a [Instruction]) -> Self {
+        let n = instructions.len();
+
+        let mut index: HashMap<&str, usize> = HashMap::with_capacity(n);
+        for (idx, instr) in instructions.iter().enumerate() {
+            index.entry(instr.id.as_str()).or_insert(idx);
+        }
+
+        let mut deps = vec![Vec::new(); n];
+        let mut dependents = vec![Vec::new(); n];
+        let mut missing_deps = Vec::new();
+        for (idx, instr) in instructions.iter().enumerate() {
+            for dep in &instr.dependencies {
+                match index.get(dep.as_str()) {
+                    Some(&dep_idx) => {
+                        deps[idx].push(dep_idx);
+                        dependents[dep_idx].push(idx);
+                    }
+                    None => missing_deps.push((idx, dep.as_str())),
+                }
+            }
+        }
+
+        let var_refs = instructions
+            .iter()
+            .map(|instr| {
+                let params_str = instr.params.to_string();
+                VAR_REF_PATTERN
+                    .captures_iter(&params_str)
+                    .map(|cap| VarRef {
+                        instruction_id: cap[1].to_string(),
+                        field: cap[2].to_string(),
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let (topo_order, levels) = kahn_levels(&deps, &dependents);
+
+        Self {
+            instructions,
+            index,
+            deps,
+            dependents,
+            missing_deps,
+            var_refs,
+            topo_order,
+            levels,
+        }
+    }
+
+    /// Number of nodes
+    pub fn len(&self) -> usize {
+        self.instructions.len()
+    }
+
+    /// Whether the graph has no nodes
+    pub fn is_empty(&self) -> bool {
+        self.instructions.is_empty()
+    }
+
+    /// Node index for an instruction ID
+    pub fn index_of(&self, id: &str) -> Option<usize> {
+        self.index.get(id).copied()
+    }
+
+    /// Whether an instruction ID exists in the plan
+    pub fn contains(&self, id: &str) -> bool {
+        self.index.contains_key(id)
+    }
+
+    /// Resolved dependency indices of a node
+    pub fn deps(&self, idx: usize) -> &[usize] {
+        &self.deps[idx]
+    }
+
+    /// Indices of nodes that depend on a node
+    pub fn dependents(&self, idx: usize) -> &[usize] {
+        &self.dependents[idx]
+    }
+
+    /// Dependencies that reference non-existent instructions
+    pub fn missing_deps(&self) -> &[(usize, &'a str)] {
+        &self.missing_deps
+    }
+
+    /// Variable references found in a node's params
+    pub fn var_refs(&self, idx: usize) -> &[VarRef] {
+        &self.var_refs[idx]
+    }
+
+    /// Topological level of a node
+    pub fn level(&self, idx: usize) -> usize {
+        self.levels[idx]
+    }
+
+    /// Topological levels for all nodes
+    pub fn levels(&self) -> &[usize] {
+        &self.levels
+    }
+
+    /// Kahn topological order (nodes on or downstream of a cycle are excluded)
+    pub fn topo_order(&self) -> &[usize] {
+        &self.topo_order
+    }
+
+    /// Whether the dependency graph contains a cycle
+    pub fn has_cycle(&self) -> bool {
+        self.topo_order.len() < self.len()
+    }
+
+    /// Find one dependency cycle as a path of instruction IDs, ending with
+    /// the first ID repeated (e.g., `a -> b -> a`).
+    pub fn find_cycle(&self) -> Option<Vec<String>> {
+        if !self.has_cycle() {
+            return None;
+        }
+
+        // Nodes Kahn couldn't order all have at least one unordered dependency,
+        // so following those edges from any of them must close a loop.
+        let mut ordered = vec![false; self.len()];
+        for &idx in &self.topo_order {
+            ordered[idx] = true;
+        }
+        let start = (0..self.len()).find(|&i| !ordered[i])?;
+
+        let mut position: HashMap<usize, usize> = HashMap::new();
+        let mut path: Vec<usize> = Vec::new();
+        let mut current = start;
+        loop {
+            if let Some(&pos) = position.get(&current) {
+                let mut cycle: Vec<String> = path[pos..]
+                    .iter()
+                    .map(|&i| self.instructions[i].id.clone())
+                    .collect();
+                cycle.push(self.instructions[current].id.clone());
+                return Some(cycle);
+            }
+            position.insert(current, path.len());
+            path.push(current);
+            current = *self.deps[current].iter().find(|&&d| !ordered[d])?;
+        }
+    }
+
+    /// For each node, whether it or any transitive dependency satisfies `pred`.
+    ///
+    /// Computed in O(V + E) by propagating from matching nodes along reverse
+    /// edges, which also handles cyclic graphs.
+    pub fn reaches_from_ancestor(&self, pred: impl Fn(usize) -> bool) -> Vec<bool> {
+        let mut reached = vec![false; self.len()];
+        let mut queue: VecDeque<usize> = VecDeque::new();
+        for (idx, flag) in reached.iter_mut().enumerate() {
+            if pred(idx) {
+                *flag = true;
+                queue.push_back(idx);
+            }
+        }
+        while let Some(idx) = queue.pop_front() {
+            for &dependent in &self.dependents[idx] {
+                if !reached[dependent] {
+                    reached[dependent] = true;
+                    queue.push_back(dependent);
+                }
+            }
+        }
+        reached
+    }
+
+    /// Instruction IDs referenced via `${id.*}` in a node's params
+    pub fn referenced_ids(&self, idx: usize) -> HashSet<&str> {
+        self.var_refs[idx]
+            .iter()
+            .map(|r| r.instruction_id.as_str())
+            .collect()
+    }
+}
+
+/// Kahn's algorithm: returns the topological order and the level of each node
+/// (level = longest path from a root). Nodes that can't be ordered keep level 0.
+fn kahn_levels(deps: &[Vec<usize>], dependents: &[Vec<usize>]) -> (Vec<usize>, Vec<usize>) {
+    let n = deps.len();
+    let mut in_degree: Vec<usize> = deps.iter().map(|d| d.len()).collect();
+    let mut levels = vec![0usize; n];
+    let mut order = Vec::with_capacity(n);
+
+    let mut queue: VecDeque<usize> = (0..n).filter(|&i| in_degree[i] == 0).collect();
+    while let Some(idx) = queue.pop_front() {
+        order.push(idx);
+        for &dependent in &dependents[idx] {
+            levels[dependent] = levels[dependent].max(levels[idx] + 1);
+            in_degree[dependent] -= 1;
+            if in_degree[dependent] == 0 {
+                queue.push_back(dependent);
+            }
+        }
+    }
+
+    // Unordered nodes (cycles) keep level 0, matching prior behaviour
+    if order.len() < n {
+        let mut ordered = vec![false; n];
+        for &idx in &order {
+            ordered[idx] = true;
+        }
+        for (idx, level) in levels.iter_mut().enumerate() {
+            if !ordered[idx] {
+                *level = 0;
+            }
+        }
+    }
+
+    (order, levels)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::OpCode;
+
+    fn make_instruction(id: &str, deps: Vec<&str>, params: serde_json::Value) -> Instruction {
+        Instruction {
+            id: id.to_string(),
+            op: OpCode::SearchCode,
+            params,
+            dependencies: deps.iter().map(|s| s.to_string()).collect(),
+            description: format!("Test instruction {}", id),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_graph_levels_and_refs() {
+        let instructions = vec![
+            make_instruction("a", vec![], serde_json::json!({})),
+            make_instruction("b", vec!["a"], serde_json::json!({ "p": "${a.output}" })),
+            make_instruction("c", vec!["a", "missing"], serde_json::json!({})),
+            make_instruction("d", vec!["b", "c"], serde_json::json!({})),
+        ];
+        let graph = InstructionGraph::new(&instructions);
+
+        assert_eq!(graph.levels(), &[0, 1, 1, 2]);
+        assert_eq!(graph.missing_deps(), &[(2, "missing")]);
+        assert_eq!(graph.var_refs(1)[0].instruction_id, "a");
+        assert_eq!(graph.var_refs(1)[0].field, "output");
+        assert!(!graph.has_cycle());
+        assert!(graph.find_cycle().is_none());
+    }
+
+    #[test]
+    fn test_graph_find_cycle() {
+        let instructions = vec![
+            make_instruction("a", vec!["c"], serde_json::json!({})),
+            make_instruction("b", vec!["a"], serde_json::json!({})),
+            make_instruction("c", vec!["b"], serde_json::json!({})),
+            make_instruction("d", vec!["c"], serde_json::json!({})),
+        ];
+        let graph = InstructionGraph::new(&instructions);
+
+        assert!(graph.has_cycle());
+        let cycle = graph.find_cycle().unwrap();
+        assert_eq!(cycle.first(), cycle.last());
+        assert_eq!(cycle.len(), 4);
+    }
+
+    #[test]
+    fn test_graph_reaches_from_ancestor() {
+        let instructions = vec![
+            make_instruction("a", vec![], serde_json::json!({})),
+            make_instruction("b", vec!["a"], serde_json::json!({})),
+            make_instruction("c", vec![], serde_json::json!({})),
+        ];
+        let graph = InstructionGraph::new(&instructions);
+
+        let reached = graph.reaches_from_ancestor(|idx| idx == 0);
+        assert_eq!(reached, vec![true, true, false]);
+    }
+
+    #[test]
+    fn test_graph_large_chain_is_iterative() {
+        // Deep chains must not recurse (would overflow the stack)
+        let instructions: Vec<Instruction> = (0..10_000)
+            .map(|i| {
+                let deps = if i == 0 {
+                    vec![]
+                } else {
+                    vec![format!("s{}", i - 1)]
+                };
+                Instruction {
+                    id: format!("s{}", i),
+                    dependencies: deps,
+                    ..Default::default()
+                }
+            })
+            .collect();
+        let graph = InstructionGraph::new(&instructions);
+
+        assert_eq!(graph.level(9_999), 9_999);
+        assert_eq!(graph.topo_order().len(), 10_000);
+        assert!(graph.reaches_from_ancestor(|idx| idx == 0)[9_999]);
+    }
+}
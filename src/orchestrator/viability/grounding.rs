@@ -3,11 +3,11 @@
 //! - V-003: File existence validation
 //! - V-011: Context ordering validation
 
-use std::collections::{HashMap, HashSet};
+use std::collections::HashSet;
 
-use crate::models::{FileAction, FileReference, GroundingSnapshot, Instruction, OpCode};
+use crate::models::{FileAction, FileReference, GroundingSnapshot, Instruction, OpCodeCategory};
 
-use super::{ViabilityChecker, ViabilitySeverity, ViabilityViolation};
+use super::{InstructionGraph, ViabilityChecker, ViabilitySeverity, ViabilityViolation};
 
 impl ViabilityChecker {
     /// V-003: Check grounding snapshot for non-existent files
@@ -49,66 +49,35 @@ impl ViabilityChecker {
     ///
     /// SEARCH_CODE, SEARCH_SEMANTIC, READ_FILES, GET_DEPENDENCIES should come
     /// before EDIT_CODE, RUN_COMMAND in the DAG to ensure proper grounding.
+    /// Custom opcodes participate according to their declared category.
     pub fn check_grounding_order(
         &self,
         instructions: &[Instruction],
+        grounding: Option<&GroundingSnapshot>,
+    ) -> Vec<ViabilityViolation> {
+        self.check_grounding_order_in_graph(&InstructionGraph::new(instructions), grounding)
+    }
+
+    /// V-011 over a prebuilt instruction graph
+    pub(crate) fn check_grounding_order_in_graph(
+        &self,
+        graph: &InstructionGraph,
         _grounding: Option<&GroundingSnapshot>,
     ) -> Vec<ViabilityViolation> {
         let mut violations = Vec::new();
 
-        let context_ops = [
-            OpCode::SearchCode,
-            OpCode::SearchSemantic,
-            OpCode::ReadFiles,
-            OpCode::GetDependencies,
-        ];
-        let execution_ops = [OpCode::EditCode, OpCode::RunCommand];
-
-        // Build dependency graph to check if context ops properly flow to execution ops
-        let id_to_idx: HashMap<&str, usize> = instructions
-            .iter()
-            .enumerate()
-            .map(|(idx, i)| (i.id.as_str(), idx))
-            .collect();
+        // Memoized: does each node have a context op in its dependency chain (or is one)?
+        let has_context = graph.reaches_from_ancestor(|idx| {
+            self.op_category(&graph.instructions[idx].op) == Some(OpCodeCategory::Context)
+        });
 
         // Find execution ops that don't have any context op in their dependency chain
-        for (idx, instr) in instructions.iter().enumerate() {
-            if !execution_ops.contains(&instr.op) {
+        for (idx, instr) in graph.instructions.iter().enumerate() {
+            if self.op_category(&instr.op) != Some(OpCodeCategory::Execution) {
                 continue;
             }
 
-            // Check if this execution op has any context op as a transitive dependency
-            let mut visited = HashSet::new();
-
-            fn find_context_dep(
-                instructions: &[Instruction],
-                id_to_idx: &HashMap<&str, usize>,
-                current_idx: usize,
-                visited: &mut HashSet<usize>,
-                context_ops: &[OpCode],
-            ) -> bool {
-                if visited.contains(&current_idx) {
-                    return false;
-                }
-                visited.insert(current_idx);
-
-                let instr = &instructions[current_idx];
-                if context_ops.contains(&instr.op) {
-                    return true;
-                }
-
-                for dep_id in &instr.dependencies {
-                    if let Some(&dep_idx) = id_to_idx.get(dep_id.as_str())
-                        && find_context_dep(instructions, id_to_idx, dep_idx, visited, context_ops)
-                    {
-                        return true;
-                    }
-                }
-                false
-            }
-
-            let has_context_dep =
-                find_context_dep(instructions, &id_to_idx, idx, &mut visited, &context_ops);
+            let has_context_dep = has_context[idx];
 
             if !has_context_dep && !instr.dependencies.is_empty() {
                 // Has dependencies but none lead to context ops - might be missing grounding
@@ -117,7 +86,7 @@ impl ViabilityChecker {
                     instruction_id: Some(instr.id.clone()),
                     severity: ViabilitySeverity::Warning,
                     message: format!(
-                        "Execution op '{}' ({:?}) has no context-gathering ops in its dependency chain",
+                        "Execution op '{}' ({}) has no context-gathering ops in its dependency chain",
                         instr.id, instr.op
                     ),
                     remediation: "Add SEARCH_CODE or READ_FILES instructions before execution ops to establish context".to_string(),
@@ -129,7 +98,7 @@ impl ViabilityChecker {
                     instruction_id: Some(instr.id.clone()),
                     severity: ViabilitySeverity::Warning,
                     message: format!(
-                        "Execution op '{}' ({:?}) has no dependencies - missing grounding phase",
+                        "Execution op '{}' ({}) has no dependencies - missing grounding phase",
                         instr.id, instr.op
                     ),
                     remediation:
@@ -146,7 +115,8 @@ impl ViabilityChecker {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::models::VerifiedFile;
+    use crate::config::CustomOpCode;
+    use crate::models::{OpCode, VerifiedFile};
 
     fn make_instruction(id: &str, op: OpCode, deps: Vec<&str>) -> Instruction {
         Instruction {
@@ -293,4 +263,47 @@ mod tests {
         // RunTest is not a context op, so edit should flag as missing grounding
         assert!(!violations.is_empty());
     }
+
+    #[test]
+    fn test_v011_custom_opcodes_use_category() {
+        let checker = ViabilityChecker::new().with_custom_opcodes(vec![
+            CustomOpCode {
+                name: "QUERY_SCHEMA".to_string(),
+                category: OpCodeCategory::Context,
+                description: String::new(),
+                params_schema: None,
+                produces: vec![],
+            },
+            CustomOpCode {
+                name: "RUN_MIGRATION".to_string(),
+                category: OpCodeCategory::Execution,
+                description: String::new(),
+                params_schema: None,
+                produces: vec![],
+            },
+        ]);
+
+        // Custom execution op without grounding is flagged
+        let ungrounded = vec![make_instruction(
+            "migrate",
+            OpCode::Custom("RUN_MIGRATION".to_string()),
+            vec![],
+        )];
+        let violations = checker.check_grounding_order(&ungrounded, None);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].rule_id, "VIABILITY-011");
+
+        // Custom context op satisfies grounding for execution ops
+        let grounded = vec![
+            make_instruction("schema", OpCode::Custom("QUERY_SCHEMA".to_string()), vec![]),
+            make_instruction("edit", OpCode::EditCode, vec!["schema"]),
+            make_instruction(
+                "migrate",
+                OpCode::Custom("RUN_MIGRATION".to_string()),
+                vec!["edit"],
+            ),
+        ];
+        let violations = checker.check_grounding_order(&grounded, None);
+        assert!(violations.is_empty());
+    }
 }
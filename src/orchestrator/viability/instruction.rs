@@ -6,6 +6,7 @@
 //! - V-013: AgentTask params validation
 //! - V-014: Empty instructions check
 
+use crate::config::find_custom_opcode;
 use crate::models::{Instruction, OpCode, STEP_RESULT_FIELDS};
 
 use super::{ViabilityChecker, ViabilitySeverity, ViabilityViolation};
@@ -101,10 +102,10 @@ impl ViabilityChecker {
                     instruction_id: Some(instr.id.clone()),
                     severity: ViabilitySeverity::Warning,
                     message: format!(
-                        "Instruction '{}' ({:?}) missing required params",
+                        "Instruction '{}' ({}) missing required params",
                         instr.id, instr.op
                     ),
-                    remediation: format!("Add appropriate params for {:?} operation", instr.op),
+                    remediation: format!("Add appropriate params for {} operation", instr.op),
                 });
             }
         }
@@ -128,6 +129,8 @@ impl ViabilityChecker {
     /// V-009: Validate params schema for each OpCode
     ///
     /// Check that instruction params have the correct types for their operation.
+    /// Custom opcodes are validated against their declared `params_schema`;
+    /// opcodes that are not declared in config are Critical.
     pub fn check_params_schema(&self, instructions: &[Instruction]) -> Vec<ViabilityViolation> {
         let mut violations = Vec::new();
 
@@ -236,12 +239,80 @@ impl ViabilityChecker {
                 OpCode::DefineTask | OpCode::VerifyTask => {
                     // These have flexible schemas - skip strict validation
                 }
+                OpCode::Custom(ref name) => {
+                    violations.extend(self.check_custom_params_schema(instr, name));
+                }
             }
         }
 
         violations
     }
 
+    /// V-009 for custom opcodes: the opcode must be declared and its params must
+    /// satisfy the declared JSON Schema (variable references are accepted anywhere).
+    fn check_custom_params_schema(
+        &self,
+        instr: &Instruction,
+        name: &str,
+    ) -> Vec<ViabilityViolation> {
+        let Some(custom) = find_custom_opcode(&self.custom_opcodes, name) else {
+            return vec![ViabilityViolation {
+                rule_id: "VIABILITY-009".to_string(),
+                instruction_id: Some(instr.id.clone()),
+                severity: ViabilitySeverity::Critical,
+                message: format!(
+                    "Instruction '{}' uses unknown opcode '{}' (not built-in or declared in custom_opcodes)",
+                    instr.id, name
+                ),
+                remediation: "Use a built-in opcode or one of the declared custom opcodes"
+                    .to_string(),
+            }];
+        };
+
+        let Some(schema) = &custom.params_schema else {
+            return Vec::new();
+        };
+
+        let validator = match jsonschema::validator_for(schema) {
+            Ok(v) => v,
+            Err(e) => {
+                return vec![ViabilityViolation {
+                    rule_id: "VIABILITY-009".to_string(),
+                    instruction_id: Some(instr.id.clone()),
+                    severity: ViabilitySeverity::Warning,
+                    message: format!(
+                        "Custom opcode '{}' has an invalid params_schema: {}",
+                        name, e
+                    ),
+                    remediation: "Fix the params_schema in the custom_opcodes config".to_string(),
+                }];
+            }
+        };
+
+        validator
+            .iter_errors(&instr.params)
+            .filter(|e| !self.is_variable_ref_value(&e.instance))
+            .map(|e| {
+                let path = e.instance_path.to_string();
+                let location = if path.is_empty() {
+                    "/".to_string()
+                } else {
+                    path
+                };
+                ViabilityViolation {
+                    rule_id: "VIABILITY-009".to_string(),
+                    instruction_id: Some(instr.id.clone()),
+                    severity: ViabilitySeverity::Warning,
+                    message: format!(
+                        "Instruction '{}' ({}) params at '{}' do not match schema: {}",
+                        instr.id, name, location, e
+                    ),
+                    remediation: format!("Make params conform to the {} params_schema", name),
+                }
+            })
+            .collect()
+    }
+
     /// Check if a JSON value is a variable reference string like "${id.field}"
     pub(crate) fn is_variable_ref_value(&self, value: &serde_json::Value) -> bool {
         if let Some(s) = value.as_str() {
@@ -305,7 +376,7 @@ impl ViabilityChecker {
                     instruction_id: Some(instr.id.clone()),
                     severity: ViabilitySeverity::Critical,
                     message: format!(
-                        "Instruction '{}' ({:?}) missing required 'goal' or 'task' param",
+                        "Instruction '{}' ({}) missing required 'goal' or 'task' param",
                         instr.id, instr.op
                     ),
                     remediation:
@@ -609,6 +680,72 @@ mod tests {
         assert!(violations.len() >= 4);
     }
 
+    fn migration_checker() -> ViabilityChecker {
+        ViabilityChecker::new().with_custom_opcodes(vec![crate::config::CustomOpCode {
+            name: "RUN_MIGRATION".to_string(),
+            category: crate::models::OpCodeCategory::Execution,
+            description: "Apply database migrations".to_string(),
+            params_schema: Some(serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "database": { "type": "string" },
+                    "steps": { "type": "integer" }
+                },
+                "required": ["database"]
+            })),
+            produces: vec!["applied".to_string()],
+        }])
+    }
+
+    #[test]
+    fn test_v009_custom_opcode_params_ok() {
+        let checker = migration_checker();
+        let instructions = vec![Instruction {
+            id: "migrate".to_string(),
+            op: OpCode::Custom("RUN_MIGRATION".to_string()),
+            params: serde_json::json!({ "database": "${locate_db.output}", "steps": 2 }),
+            ..Default::default()
+        }];
+
+        let violations = checker.check_params_schema(&instructions);
+        assert!(violations.is_empty(), "got {:?}", violations);
+    }
+
+    #[test]
+    fn test_v009_custom_opcode_schema_mismatch() {
+        let checker = migration_checker();
+        let instructions = vec![Instruction {
+            id: "migrate".to_string(),
+            op: OpCode::Custom("RUN_MIGRATION".to_string()),
+            params: serde_json::json!({ "steps": "two" }),
+            ..Default::default()
+        }];
+
+        let violations = checker.check_params_schema(&instructions);
+        // Missing required "database" and wrong type for "steps"
+        assert_eq!(violations.len(), 2);
+        assert!(
+            violations
+                .iter()
+                .all(|v| v.severity == ViabilitySeverity::Warning)
+        );
+    }
+
+    #[test]
+    fn test_v009_undeclared_custom_opcode() {
+        let checker = ViabilityChecker::new();
+        let instructions = vec![make_instruction(
+            "deploy",
+            OpCode::Custom("DEPLOY_PREVIEW".to_string()),
+            vec![],
+        )];
+
+        let violations = checker.check_params_schema(&instructions);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].severity, ViabilitySeverity::Critical);
+        assert!(violations[0].message.contains("unknown opcode"));
+    }
+
     // V-013: AgentTask Params Tests
 
     #[test]
@@ -5,11 +5,10 @@
 //! - DAG analysis functions
 
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
 
 use crate::models::{Instruction, OpCode};
 
-use super::{ViabilityChecker, ViabilitySeverity, ViabilityViolation};
+use super::{InstructionGraph, ViabilityChecker, ViabilitySeverity, ViabilityViolation};
 
 /// Metrics describing DAG parallelization characteristics
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -34,10 +33,16 @@ pub struct DagMetrics {
 
 /// Compute DAG parallelization metrics
 pub fn analyze_dag(instructions: &[Instruction]) -> DagMetrics {
-    if instructions.is_empty() {
+    analyze_graph(&InstructionGraph::new(instructions))
+}
+
+/// Compute DAG parallelization metrics from a prebuilt instruction graph
+pub fn analyze_graph(graph: &InstructionGraph) -> DagMetrics {
+    if graph.is_empty() {
         return DagMetrics::default();
     }
 
+    let instructions = graph.instructions;
     let total_nodes = instructions.len();
     let total_edges: usize = instructions.iter().map(|i| i.dependencies.len()).sum();
 
@@ -48,25 +53,20 @@ pub fn analyze_dag(instructions: &[Instruction]) -> DagMetrics {
         .count();
 
     // Leaf nodes: no other instruction depends on them
-    let all_deps: std::collections::HashSet<&str> = instructions
-        .iter()
-        .flat_map(|i| i.dependencies.iter().map(|d| d.as_str()))
-        .collect();
-    let leaf_nodes = instructions
-        .iter()
-        .filter(|i| !all_deps.contains(i.id.as_str()))
+    let leaf_nodes = (0..total_nodes)
+        .filter(|&idx| graph.dependents(idx).is_empty())
         .count();
 
-    // Compute topological levels for critical path and max width
-    let levels = compute_topological_levels(instructions);
-    let critical_path_length = levels.values().copied().max().unwrap_or(0) + 1;
+    // Topological levels (Kahn) for critical path and max width
+    let levels = graph.levels();
+    let critical_path_length = levels.iter().copied().max().unwrap_or(0) + 1;
 
     // Max width: count instructions at each level
-    let mut level_counts: HashMap<usize, usize> = HashMap::new();
-    for level in levels.values() {
-        *level_counts.entry(*level).or_insert(0) += 1;
+    let mut level_counts = vec![0usize; critical_path_length];
+    for &level in levels {
+        level_counts[level] += 1;
     }
-    let max_width = level_counts.values().copied().max().unwrap_or(1);
+    let max_width = level_counts.iter().copied().max().unwrap_or(1);
 
     // Parallelization ratio
     let parallelization_ratio = if critical_path_length > 0 {
@@ -76,7 +76,7 @@ pub fn analyze_dag(instructions: &[Instruction]) -> DagMetrics {
     };
 
     // Find unnecessary dependencies
-    let unnecessary_deps = find_unnecessary_deps(instructions);
+    let unnecessary_deps = find_unnecessary_deps(graph);
 
     DagMetrics {
         total_nodes,
@@ -90,64 +90,15 @@ pub fn analyze_dag(instructions: &[Instruction]) -> DagMetrics {
     }
 }
 
-/// Compute topological level for each instruction
-/// Level 0 = no dependencies, Level N = max(dep levels) + 1
-fn compute_topological_levels(instructions: &[Instruction]) -> HashMap<String, usize> {
-    let mut levels: HashMap<String, usize> = HashMap::new();
-
-    // Initialize root nodes at level 0
-    for instr in instructions {
-        if instr.dependencies.is_empty() {
-            levels.insert(instr.id.clone(), 0);
-        }
-    }
-
-    // Iteratively compute levels until stable
-    let mut changed = true;
-    while changed {
-        changed = false;
-        for instr in instructions {
-            if levels.contains_key(&instr.id) {
-                continue;
-            }
-
-            // Check if all dependencies have levels
-            let all_deps_have_levels = instr
-                .dependencies
-                .iter()
-                .all(|d| levels.contains_key(d.as_str()));
-
-            if all_deps_have_levels {
-                let max_dep_level = instr
-                    .dependencies
-                    .iter()
-                    .filter_map(|d| levels.get(d.as_str()))
-                    .copied()
-                    .max()
-                    .unwrap_or(0);
-                levels.insert(instr.id.clone(), max_dep_level + 1);
-                changed = true;
-            }
-        }
-    }
-
-    // Handle any remaining unprocessed nodes (cycles or missing deps)
-    for instr in instructions {
-        levels.entry(instr.id.clone()).or_insert(0);
-    }
-
-    levels
-}
-
 /// Find dependencies where the dependent doesn't reference ${dep.*}
-fn find_unnecessary_deps(instructions: &[Instruction]) -> Vec<String> {
+fn find_unnecessary_deps(graph: &InstructionGraph) -> Vec<String> {
     let mut unnecessary = Vec::new();
 
-    for instr in instructions {
-        let params_str = instr.params.to_string();
+    for (idx, instr) in graph.instructions.iter().enumerate() {
+        let referenced = graph.referenced_ids(idx);
         for dep in &instr.dependencies {
             // Check if this dependency is actually referenced in params
-            if !params_str.contains(&format!("${{{}", dep)) {
+            if !referenced.contains(dep.as_str()) {
                 unnecessary.push(format!("{}->{}", dep, instr.id));
             }
         }
@@ -156,6 +107,20 @@ fn find_unnecessary_deps(instructions: &[Instruction]) -> Vec<String> {
     unnecessary
 }
 
+/// Default `estimated_tokens` for opcodes that V-012 requires an estimate for.
+///
+/// Returns None for opcodes that don't need one. Used by the auto-fixer.
+pub fn default_token_estimate(op: &OpCode) -> Option<u32> {
+    match op {
+        OpCode::SearchCode => Some(2_000),
+        OpCode::SearchSemantic => Some(3_000),
+        OpCode::ReadFiles => Some(8_000),
+        OpCode::EditCode => Some(12_000),
+        OpCode::GenerateTest => Some(6_000),
+        _ => None,
+    }
+}
+
 impl ViabilityChecker {
     /// V-010: Informational - Dependencies without variable references
     ///
@@ -170,29 +135,18 @@ impl ViabilityChecker {
 
     /// V-012: Check that context-heavy instructions have token estimates
     ///
-    /// EDIT_CODE, READ_FILES, SEARCH_CODE, SEARCH_SEMANTIC should have
-    /// estimated_tokens for budget planning.
+    /// EDIT_CODE, READ_FILES, SEARCH_CODE, SEARCH_SEMANTIC, GENERATE_TEST should
+    /// have estimated_tokens for budget planning (see `default_token_estimate`).
     pub fn check_token_estimates(&self, instructions: &[Instruction]) -> Vec<ViabilityViolation> {
-        let token_required_ops = [
-            OpCode::EditCode,
-            OpCode::ReadFiles,
-            OpCode::SearchCode,
-            OpCode::SearchSemantic,
-            OpCode::GenerateTest,
-        ];
-
         instructions
             .iter()
-            .filter(|i| token_required_ops.contains(&i.op))
+            .filter(|i| default_token_estimate(&i.op).is_some())
             .filter(|i| i.estimated_tokens.is_none())
             .map(|i| ViabilityViolation {
                 rule_id: "VIABILITY-012".to_string(),
                 instruction_id: Some(i.id.clone()),
                 severity: ViabilitySeverity::Warning,
-                message: format!(
-                    "Instruction '{}' ({:?}) missing estimated_tokens",
-                    i.id, i.op
-                ),
+                message: format!("Instruction '{}' ({}) missing estimated_tokens", i.id, i.op),
                 remediation: "Add estimated_tokens field for context budget planning".to_string(),
             })
             .collect()
@@ -6,24 +6,51 @@
 //! # Module Structure
 //!
 //! - `types`: Core types (ViabilityViolation, ViabilityResult)
+//! - `autofix`: Mechanical fixes for V-006, V-010, V-012 violations
+//! - `graph`: Indexed instruction graph shared by DAG-based checks and metrics
 //! - `dag`: V-001, V-002 - Cycle detection and dependency validation
 //! - `instruction`: V-004, V-005, V-009, V-013, V-014 - Instruction validation
 //! - `dataflow`: V-006, V-007, V-008 - Variable references and TDD order
 //! - `grounding`: V-003, V-011 - File existence and context ordering
 //! - `metrics`: V-010, V-012, DAG analysis - Parallelism and token estimates
+//! - `commands`: V-015 - Dangerous shell commands in RUN_COMMAND and the runbook
+//! - `runbook`: V-016 - Runbook binaries, paths, and instruction cross-references
+//! - `symbols`: V-017 - Symbols named in EDIT_CODE goals exist in the repo
+//! - `search`: V-018 - SEARCH_CODE dry runs (dead or overly broad queries)
+//! - `schedule`: Execution schedule simulation (waves, token load, wall-clock)
 
+mod autofix;
+mod commands;
 mod dag;
 mod dataflow;
+mod graph;
 mod grounding;
 mod instruction;
 mod metrics;
+mod runbook;
+mod schedule;
+mod search;
+mod symbols;
 mod types;
 
 // Re-export all public items
-pub use metrics::{DagMetrics, analyze_dag};
+pub use autofix::{AppliedFix, FixAction, PlanAutoFixer};
+pub use commands::{CommandFinding, CommandRisk, RiskyCommand, analyze_command, runbook_commands};
+pub use graph::{InstructionGraph, VarRef};
+pub use metrics::{DagMetrics, analyze_dag, analyze_graph, default_token_estimate};
+pub use runbook::{detect_build_binaries, link_runbook_steps};
+pub use schedule::{ExecutionSchedule, ScheduleWave, ScheduledStep, simulate_schedule};
 pub use types::*;
 
-use crate::models::{FileReference, GroundingSnapshot, Instruction};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use crate::config::{CommandSafetyConfig, CustomOpCode, RunbookConfig, find_custom_opcode};
+use crate::models::{
+    FileReference, GroundingSnapshot, Instruction, OpCode, OpCodeCategory, OperatorRunbook, Plan,
+};
+use crate::orchestrator::code_search::CodeSearch;
+use crate::orchestrator::repo_map::SymbolIndex;
 
 // ============================================================================
 // Viability Checker
@@ -36,6 +63,18 @@ pub struct ViabilityChecker {
     pub max_files_per_edit: usize,
     /// Minimum query length for SEARCH_CODE
     pub min_search_query_length: usize,
+    /// User-defined opcodes declared in config
+    pub custom_opcodes: Vec<CustomOpCode>,
+    /// Severities for dangerous command patterns (V-015)
+    pub command_safety: CommandSafetyConfig,
+    /// Working directory used to decide whether command paths leave the workspace
+    pub working_dir: Option<PathBuf>,
+    /// Operator runbook validation settings (V-016)
+    pub runbook: RunbookConfig,
+    /// Repository symbols for goal verification (V-017 disabled when None)
+    pub symbol_index: Option<Arc<SymbolIndex>>,
+    /// Working directory search for SEARCH_CODE dry runs (V-018 disabled when None)
+    pub code_search: Option<Arc<CodeSearch>>,
 }
 
 impl Default for ViabilityChecker {
@@ -43,6 +82,12 @@ impl Default for ViabilityChecker {
         Self {
             max_files_per_edit: 3,
             min_search_query_length: 3,
+            custom_opcodes: Vec::new(),
+            command_safety: CommandSafetyConfig::default(),
+            working_dir: None,
+            runbook: RunbookConfig::default(),
+            symbol_index: None,
+            code_search: None,
         }
     }
 }
@@ -53,7 +98,61 @@ impl ViabilityChecker {
         Self::default()
     }
 
-    /// Run all viability checks on the given plan data
+    /// Register user-defined opcodes so rules can resolve their category and schema
+    pub fn with_custom_opcodes(mut self, custom_opcodes: Vec<CustomOpCode>) -> Self {
+        self.custom_opcodes = custom_opcodes;
+        self
+    }
+
+    /// Set severities for dangerous command patterns (V-015)
+    pub fn with_command_safety(mut self, command_safety: CommandSafetyConfig) -> Self {
+        self.command_safety = command_safety;
+        self
+    }
+
+    /// Set the workspace root that command paths are checked against (V-015, V-016)
+    pub fn with_working_dir(mut self, working_dir: PathBuf) -> Self {
+        self.working_dir = Some(working_dir);
+        self
+    }
+
+    /// Set operator runbook validation settings (V-016)
+    pub fn with_runbook_config(mut self, runbook: RunbookConfig) -> Self {
+        self.runbook = runbook;
+        self
+    }
+
+    /// Verify symbols named in EDIT_CODE goals against this index (V-017)
+    pub fn with_symbol_index(mut self, symbol_index: Arc<SymbolIndex>) -> Self {
+        self.symbol_index = Some(symbol_index);
+        self
+    }
+
+    /// Dry-run SEARCH_CODE queries with this search backend (V-018)
+    pub fn with_code_search(mut self, code_search: Arc<CodeSearch>) -> Self {
+        self.code_search = Some(code_search);
+        self
+    }
+
+    /// Look up the config declaration for a custom opcode
+    pub fn custom_opcode(&self, op: &OpCode) -> Option<&CustomOpCode> {
+        match op {
+            OpCode::Custom(name) => find_custom_opcode(&self.custom_opcodes, name),
+            _ => None,
+        }
+    }
+
+    /// Category of an opcode: built-in mapping, or the declared category for
+    /// custom opcodes. Undeclared custom opcodes have no category.
+    pub fn op_category(&self, op: &OpCode) -> Option<OpCodeCategory> {
+        op.builtin_category()
+            .or_else(|| self.custom_opcode(op).map(|c| c.category))
+    }
+
+    /// Run all viability checks on the given plan data.
+    ///
+    /// The instruction graph is built once and shared by the DAG-based rules
+    /// and the returned `metrics`.
     pub fn check_all(
         &self,
         instructions: Option<&[Instruction]>,
@@ -61,9 +160,12 @@ impl ViabilityChecker {
         file_references: Option<&[FileReference]>,
     ) -> ViabilityResult {
         let mut violations = Vec::new();
+        let mut metrics = None;
 
         // Run checks on instructions if present
         if let Some(instrs) = instructions {
+            let graph = InstructionGraph::new(instrs);
+
             // V-014: Check for empty instructions FIRST
             if let Some(v) = self.check_empty_instructions(instrs) {
                 violations.push(v);
@@ -76,28 +178,36 @@ impl ViabilityChecker {
                     violations.push(v);
                 }
                 // V-002: Logical flow
-                violations.extend(self.check_logical_flow(instrs));
+                violations.extend(self.check_logical_flow_in_graph(&graph));
                 // V-004: Complexity
                 violations.extend(self.check_complexity(instrs));
                 // V-005: Params presence
                 violations.extend(self.check_params_presence(instrs));
                 // V-006: Variable references
-                violations.extend(self.check_variable_refs(instrs));
+                violations.extend(self.check_variable_refs_in_graph(&graph));
                 // V-007: TDD order
                 violations.extend(self.check_tdd_order(instrs));
                 // V-008: Variable field names
-                violations.extend(self.check_variable_field_names(instrs));
+                violations.extend(self.check_variable_field_names_in_graph(&graph));
                 // V-009: Params schema validation
                 violations.extend(self.check_params_schema(instrs));
                 // V-010: Parallelism check
                 violations.extend(self.check_parallelism(instrs));
                 // V-011: Grounding order check
-                violations.extend(self.check_grounding_order(instrs, grounding));
+                violations.extend(self.check_grounding_order_in_graph(&graph, grounding));
                 // V-012: Token estimates check
                 violations.extend(self.check_token_estimates(instrs));
                 // V-013: AgentTask params validation
                 violations.extend(self.check_agent_task_params(instrs));
+                // V-015: Dangerous RUN_COMMAND commands
+                violations.extend(self.check_risky_commands(instrs));
+                // V-017: Symbols in EDIT_CODE goals exist
+                violations.extend(self.check_goal_symbols(instrs));
+                // V-018: SEARCH_CODE dry runs
+                violations.extend(self.check_search_queries(instrs));
             }
+
+            metrics = Some(analyze_graph(&graph));
         }
 
         // Run checks on grounding if present
@@ -105,28 +215,59 @@ impl ViabilityChecker {
             violations.extend(self.check_grounding(snapshot, file_references));
         }
 
-        // Calculate score and pass status
-        let critical_count = violations
-            .iter()
-            .filter(|v| v.severity == ViabilitySeverity::Critical)
-            .count();
+        build_result(violations, metrics)
+    }
 
-        let warning_count = violations
-            .iter()
-            .filter(|v| v.severity == ViabilitySeverity::Warning)
-            .count();
+    /// Run all viability checks on a plan, including its operator runbook.
+    pub fn check_plan(&self, plan: &Plan) -> ViabilityResult {
+        let result = self.check_all(
+            plan.instructions.as_deref(),
+            plan.grounding_snapshot.as_ref(),
+            Some(&plan.file_references),
+        );
 
-        let passed = critical_count == 0;
+        let mut violations = result.violations;
+        if let Some(runbook) = &plan.operator_runbook {
+            // V-015: Dangerous runbook commands
+            violations.extend(self.check_runbook_commands(runbook));
+            // V-016: Runbook binaries, paths, and instruction cross-references
+            violations.extend(self.check_runbook_steps(
+                &OperatorRunbook::parse(runbook),
+                plan.instructions.as_deref().unwrap_or(&[]),
+                &plan.file_references,
+            ));
+        }
 
-        // Score: start at 1.0, subtract 0.2 per critical, 0.05 per warning
-        let score =
-            (1.0 - (critical_count as f32 * 0.2) - (warning_count as f32 * 0.05)).clamp(0.0, 1.0);
+        build_result(violations, result.metrics)
+    }
+}
 
-        ViabilityResult {
-            passed,
-            violations,
-            score,
-        }
+/// Score violations and determine pass status
+fn build_result(
+    violations: Vec<ViabilityViolation>,
+    metrics: Option<DagMetrics>,
+) -> ViabilityResult {
+    let critical_count = violations
+        .iter()
+        .filter(|v| v.severity == ViabilitySeverity::Critical)
+        .count();
+
+    let warning_count = violations
+        .iter()
+        .filter(|v| v.severity == ViabilitySeverity::Warning)
+        .count();
+
+    let passed = critical_count == 0;
+
+    // Score: start at 1.0, subtract 0.2 per critical, 0.05 per warning
+    let score =
+        (1.0 - (critical_count as f32 * 0.2) - (warning_count as f32 * 0.05)).clamp(0.0, 1.0);
+
+    ViabilityResult {
+        passed,
+        violations,
+        score,
+        metrics,
     }
 }
 
@@ -272,4 +413,43 @@ mod tests {
         assert!(!result.passed);
         assert!(result.violations.len() >= 2);
     }
+
+    #[test]
+    fn test_check_all_includes_dag_metrics() {
+        let checker = ViabilityChecker::new();
+        let instructions = vec![
+            make_instruction("root", OpCode::SearchCode, vec![]),
+            make_instruction("a", OpCode::ReadFiles, vec!["root"]),
+            make_instruction("b", OpCode::ReadFiles, vec!["root"]),
+        ];
+
+        let result = checker.check_all(Some(&instructions), None, None);
+        let metrics = result.metrics.expect("metrics computed with instructions");
+        assert_eq!(metrics.total_nodes, 3);
+        assert_eq!(metrics.critical_path_length, 2);
+        assert_eq!(metrics.max_width, 2);
+
+        let no_instructions = checker.check_all(None, None, None);
+        assert!(no_instructions.metrics.is_none());
+    }
+
+    #[test]
+    fn test_check_plan_includes_runbook_commands() {
+        let checker = ViabilityChecker::new();
+        let mut plan = Plan::new(
+            "Test".to_string(),
+            "Test plan".to_string(),
+            crate::models::PlanTier::Quick,
+        );
+        plan.operator_runbook = Some("```bash\ngit push --force origin main\n```".to_string());
+
+        let result = checker.check_plan(&plan);
+        assert!(!result.passed);
+        assert!(
+            result
+                .violations
+                .iter()
+                .any(|v| v.rule_id == "VIABILITY-015")
+        );
+    }
 }
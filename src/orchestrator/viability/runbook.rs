@@ -0,0 +1,561 @@
+//! V-016: Operator runbook validation.
+//!
+//! Each runbook command's binary must come from the detected build system
+//! or the allowlist, relative paths it references must exist (or be created
+//! earlier in the runbook / by the plan), and every `RUN_COMMAND` / `RUN_TEST`
+//! instruction should have a matching runbook step so the operator can
+//! reproduce the plan's verification by hand.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use super::commands::{command_name, effective_argv, parse_pipelines, tokenize};
+use super::{ViabilityChecker, ViabilitySeverity, ViabilityViolation};
+use crate::models::{FileAction, FileReference, Instruction, OpCode, OperatorRunbook};
+
+/// Marker files and the binaries they make available
+const BUILD_SYSTEMS: &[(&[&str], &[&str])] = &[
+    (&["Cargo.toml"], &["cargo", "rustc", "rustup", "rustfmt"]),
+    (
+        &["package.json"],
+        &["npm", "npx", "node", "yarn", "pnpm", "bun"],
+    ),
+    (
+        &["WORKSPACE", "WORKSPACE.bazel", "MODULE.bazel"],
+        &["bazel", "bazelisk", "buildifier"],
+    ),
+    (&["Makefile", "makefile", "GNUmakefile"], &["make"]),
+    (&["go.mod"], &["go", "gofmt"]),
+    (
+        &["pyproject.toml", "setup.py", "requirements.txt"],
+        &[
+            "python", "python3", "pip", "pip3", "pytest", "uv", "poetry", "tox",
+        ],
+    ),
+    (&["CMakeLists.txt"], &["cmake", "ctest", "make", "ninja"]),
+    (&["pom.xml"], &["mvn"]),
+    (&["build.gradle", "build.gradle.kts"], &["gradle"]),
+];
+
+/// Shell builtins and common tools assumed to be on PATH
+const COMMON_BINARIES: &[&str] = &[
+    // Shell builtins
+    "cd",
+    "echo",
+    "printf",
+    "export",
+    "set",
+    "unset",
+    "source",
+    ".",
+    "test",
+    "[",
+    "true",
+    "false",
+    "exit",
+    "read",
+    "pwd",
+    "pushd",
+    "popd",
+    "type",
+    "which",
+    // Coreutils and friends
+    "ls",
+    "cat",
+    "head",
+    "tail",
+    "grep",
+    "rg",
+    "find",
+    "fd",
+    "sed",
+    "awk",
+    "sort",
+    "uniq",
+    "wc",
+    "cut",
+    "tr",
+    "diff",
+    "cp",
+    "mv",
+    "rm",
+    "mkdir",
+    "touch",
+    "chmod",
+    "ln",
+    "tee",
+    "sleep",
+    "kill",
+    "ps",
+    "date",
+    "basename",
+    "dirname",
+    "realpath",
+    "stat",
+    "file",
+    "du",
+    "df",
+    "tar",
+    "unzip",
+    // Shells
+    "bash",
+    "sh",
+    "zsh",
+    // Tooling
+    "git",
+    "gh",
+    "curl",
+    "wget",
+    "jq",
+    "docker",
+    "plan-forge",
+];
+
+/// Commands whose arguments are text, not paths
+const TEXT_ARG_COMMANDS: &[&str] = &["echo", "printf", "export", "set", "test", "["];
+
+/// Dedicated test runners (in addition to `<tool> test`)
+const TEST_RUNNERS: &[&str] = &[
+    "pytest", "jest", "vitest", "ctest", "rspec", "mocha", "tox", "phpunit",
+];
+
+/// Binaries provided by the build systems detected in `working_dir`.
+///
+/// Without a working directory every build system's binaries are allowed.
+pub fn detect_build_binaries(working_dir: Option<&Path>) -> Vec<&'static str> {
+    BUILD_SYSTEMS
+        .iter()
+        .filter(|(markers, _)| match working_dir {
+            Some(dir) => markers.iter().any(|m| dir.join(m).exists()),
+            None => true,
+        })
+        .flat_map(|(_, binaries)| binaries.iter().copied())
+        .collect()
+}
+
+/// Instruction IDs linked to each runbook step (same order as `runbook.steps`).
+///
+/// A `RUN_COMMAND` links to the step containing its command; a `RUN_TEST`
+/// links to test steps mentioning its target, or to every test step when
+/// none does.
+pub fn link_runbook_steps(
+    runbook: &OperatorRunbook,
+    instructions: &[Instruction],
+) -> Vec<Vec<String>> {
+    let step_commands: Vec<Vec<String>> = runbook
+        .steps
+        .iter()
+        .map(|s| {
+            s.commands
+                .iter()
+                .map(|c| normalize_ws(&c.command))
+                .collect()
+        })
+        .collect();
+    let mut links = vec![Vec::new(); runbook.steps.len()];
+
+    for instr in instructions {
+        match instr.op {
+            OpCode::RunCommand => {
+                let Some(command) = instr.params.get("command").and_then(|c| c.as_str()) else {
+                    continue;
+                };
+                let command = normalize_ws(command);
+                for (i, commands) in step_commands.iter().enumerate() {
+                    if commands.iter().any(|c| c.contains(&command)) {
+                        links[i].push(instr.id.clone());
+                    }
+                }
+            }
+            OpCode::RunTest => {
+                let test_steps: Vec<usize> = step_commands
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, commands)| commands.iter().any(|c| is_test_command(c)))
+                    .map(|(i, _)| i)
+                    .collect();
+                let target = instr
+                    .params
+                    .get("target")
+                    .and_then(|t| t.as_str())
+                    .filter(|t| !t.starts_with('$'));
+                let matching: Vec<usize> = test_steps
+                    .iter()
+                    .copied()
+                    .filter(|&i| {
+                        target.is_some_and(|t| step_commands[i].iter().any(|c| c.contains(t)))
+                    })
+                    .collect();
+                let linked = if matching.is_empty() {
+                    test_steps
+                } else {
+                    matching
+                };
+                for i in linked {
+                    links[i].push(instr.id.clone());
+                }
+            }
+            _ => {}
+        }
+    }
+    links
+}
+
+/// Collapse whitespace so commands compare regardless of formatting
+fn normalize_ws(command: &str) -> String {
+    command.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// `cargo test`, `npm run test`, `pytest`, ...
+fn is_test_command(command: &str) -> bool {
+    parse_pipelines(&tokenize(command))
+        .iter()
+        .flatten()
+        .any(|simple| {
+            let (argv, _) = effective_argv(&simple.words);
+            let Some(first) = argv.first() else {
+                return false;
+            };
+            TEST_RUNNERS.contains(&command_name(first))
+                || argv[1..]
+                    .iter()
+                    .any(|a| matches!(a.as_str(), "test" | "tests" | "nextest"))
+        })
+}
+
+/// Argument that should be checked as a relative path
+fn path_candidate(arg: &str, base: &Path) -> bool {
+    if arg.starts_with('-')
+        || arg.starts_with('/')
+        || arg.contains("://")
+        || arg.contains(['$', '~', '*', '?', '{', '[', '=', ':', '@'])
+    {
+        return false;
+    }
+    if arg.starts_with("./") || arg.starts_with("../") {
+        return true;
+    }
+    // `origin/main`, `foo/bar` regexes, etc. only count as paths when their
+    // first component exists on disk
+    match arg.split_once('/') {
+        Some((first, _)) => !first.is_empty() && base.join(first).exists(),
+        None => false,
+    }
+}
+
+/// Lexically normalized path relative to the workspace root
+fn workspace_relative(base: &Path, root: &Path, arg: &str) -> PathBuf {
+    let mut out = PathBuf::new();
+    for component in base
+        .join(arg)
+        .strip_prefix(root)
+        .unwrap_or(Path::new(arg))
+        .components()
+    {
+        match component {
+            std::path::Component::CurDir => {}
+            std::path::Component::ParentDir => {
+                out.pop();
+            }
+            other => out.push(other),
+        }
+    }
+    out
+}
+
+impl ViabilityChecker {
+    /// V-016: Check runbook binaries, referenced paths, and cross-references
+    /// with RUN_COMMAND / RUN_TEST instructions
+    pub fn check_runbook_steps(
+        &self,
+        runbook: &OperatorRunbook,
+        instructions: &[Instruction],
+        file_references: &[FileReference],
+    ) -> Vec<ViabilityViolation> {
+        let mut violations = Vec::new();
+
+        let mut known: HashSet<&str> = COMMON_BINARIES.iter().copied().collect();
+        known.extend(detect_build_binaries(self.working_dir.as_deref()));
+        known.extend(self.runbook.extra_binaries.iter().map(String::as_str));
+
+        // Paths that the plan or earlier runbook commands create
+        let mut created: Vec<PathBuf> = file_references
+            .iter()
+            .filter(|r| matches!(r.action, FileAction::Create))
+            .map(|r| PathBuf::from(r.path.trim_start_matches("./")))
+            .collect();
+
+        let root = self
+            .working_dir
+            .as_deref()
+            .filter(|_| self.runbook.check_paths);
+        // Current directory while walking the runbook (None once it can't be tracked)
+        let mut cwd = root.map(Path::to_path_buf);
+
+        for command in runbook.commands() {
+            for simple in parse_pipelines(&tokenize(&command.command))
+                .iter()
+                .flatten()
+            {
+                let (argv, _) = effective_argv(&simple.words);
+                let Some(first) = argv.first() else {
+                    continue;
+                };
+                if first.contains(['$', '(', '{']) {
+                    continue;
+                }
+                let name = command_name(first);
+
+                // Path checks against the tracked working directory
+                if let (Some(root), Some(base)) = (root, cwd.clone()) {
+                    let exists = |arg: &str, created: &[PathBuf]| {
+                        let rel = workspace_relative(&base, root, arg);
+                        base.join(arg).exists() || created.iter().any(|c| rel.starts_with(c))
+                    };
+                    let mut missing = Vec::new();
+
+                    if first.contains('/') && !exists(first, &created) {
+                        missing.push(first.clone());
+                    }
+
+                    let args = &argv[1..];
+                    let creates: &[String] = match name {
+                        "mkdir" | "touch" => args,
+                        "cp" | "mv" | "ln" => args.last().map(std::slice::from_ref).unwrap_or(&[]),
+                        _ => &[],
+                    };
+                    if !TEXT_ARG_COMMANDS.contains(&name) {
+                        for arg in args {
+                            if !creates.contains(arg)
+                                && path_candidate(arg, &base)
+                                && !exists(arg, &created)
+                            {
+                                missing.push(arg.clone());
+                            }
+                        }
+                    }
+                    for path in missing {
+                        violations.push(ViabilityViolation {
+                            rule_id: "VIABILITY-016".to_string(),
+                            instruction_id: None,
+                            severity: ViabilitySeverity::Warning,
+                            message: format!(
+                                "Operator runbook line {} references `{}`, which does not exist",
+                                command.line, path
+                            ),
+                            remediation: "Fix the path, create it in an earlier runbook step, or \
+                                declare it in file_references with action 'create'"
+                                .to_string(),
+                        });
+                    }
+
+                    let targets = creates.iter().chain(
+                        simple
+                            .redirects
+                            .iter()
+                            .filter(|(op, _)| op.contains('>'))
+                            .map(|(_, t)| t),
+                    );
+                    for target in targets {
+                        if !target.starts_with('-') && !target.starts_with('/') {
+                            created.push(workspace_relative(&base, root, target));
+                        }
+                    }
+
+                    if name == "cd" {
+                        cwd = match args.first() {
+                            Some(dir) if !dir.contains(['$', '~']) && dir != "-" => {
+                                Some(base.join(dir))
+                            }
+                            _ => None,
+                        };
+                    }
+                }
+
+                if !first.contains('/') && !known.contains(name) {
+                    violations.push(ViabilityViolation {
+                        rule_id: "VIABILITY-016".to_string(),
+                        instruction_id: None,
+                        severity: ViabilitySeverity::Warning,
+                        message: format!(
+                            "Operator runbook line {} uses `{}`, which is not provided by the \
+                             detected build system or the allowlist",
+                            command.line, name
+                        ),
+                        remediation: "Use the project's build tool, or add the binary to \
+                            runbook.extra_binaries in config"
+                            .to_string(),
+                    });
+                }
+            }
+        }
+
+        // Cross-reference RUN_COMMAND / RUN_TEST instructions
+        let links = link_runbook_steps(runbook, instructions);
+        let linked: HashSet<&str> = links.iter().flatten().map(String::as_str).collect();
+        for instr in instructions {
+            if linked.contains(instr.id.as_str()) {
+                continue;
+            }
+            let message = match instr.op {
+                OpCode::RunCommand => match instr.params.get("command").and_then(|c| c.as_str()) {
+                    Some(command) => format!(
+                        "RUN_COMMAND '{}' (`{}`) has no matching operator runbook step",
+                        instr.id, command
+                    ),
+                    None => continue,
+                },
+                OpCode::RunTest => format!(
+                    "RUN_TEST '{}' has no matching test command in the operator runbook",
+                    instr.id
+                ),
+                _ => continue,
+            };
+            violations.push(ViabilityViolation {
+                rule_id: "VIABILITY-016".to_string(),
+                instruction_id: Some(instr.id.clone()),
+                severity: ViabilitySeverity::Warning,
+                message,
+                remediation: "Add a runbook step with the same command so the operator can \
+                    reproduce the plan's verification"
+                    .to_string(),
+            });
+        }
+
+        violations
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::RunbookConfig;
+
+    fn make_instruction(id: &str, op: OpCode, params: serde_json::Value) -> Instruction {
+        Instruction {
+            id: id.to_string(),
+            op,
+            params,
+            description: format!("Test instruction {}", id),
+            ..Default::default()
+        }
+    }
+
+    fn workspace() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("Cargo.toml"), "[package]\n").unwrap();
+        std::fs::create_dir(dir.path().join("src")).unwrap();
+        std::fs::write(dir.path().join("src/lib.rs"), "").unwrap();
+        dir
+    }
+
+    fn messages(violations: &[ViabilityViolation]) -> Vec<&str> {
+        violations.iter().map(|v| v.message.as_str()).collect()
+    }
+
+    #[test]
+    fn test_detect_build_binaries() {
+        let dir = workspace();
+        let binaries = detect_build_binaries(Some(dir.path()));
+        assert!(binaries.contains(&"cargo"));
+        assert!(!binaries.contains(&"npm"));
+        assert!(detect_build_binaries(None).contains(&"npm"));
+    }
+
+    #[test]
+    fn test_v016_unknown_binary_and_allowlist() {
+        let dir = workspace();
+        let runbook = OperatorRunbook::parse("```bash\ncargo check\nnpm test\nmytool run\n```\n");
+        let checker = ViabilityChecker::new().with_working_dir(dir.path().to_path_buf());
+
+        let violations = checker.check_runbook_steps(&runbook, &[], &[]);
+        assert_eq!(violations.len(), 2);
+        assert!(violations.iter().all(|v| v.rule_id == "VIABILITY-016"));
+        assert!(violations[0].message.contains("`npm`"));
+        assert!(violations[1].message.contains("`mytool`"));
+
+        let checker = checker.with_runbook_config(RunbookConfig {
+            extra_binaries: vec!["mytool".to_string()],
+            ..Default::default()
+        });
+        assert_eq!(checker.check_runbook_steps(&runbook, &[], &[]).len(), 1);
+    }
+
+    #[test]
+    fn test_v016_paths() {
+        let dir = workspace();
+        let runbook = OperatorRunbook::parse(
+            "```bash\n\
+             cat src/lib.rs src/missing.rs\n\
+             git diff origin/main\n\
+             mkdir -p out/reports\n\
+             cargo test > out/reports/test.log\n\
+             cat out/reports/test.log src/new.rs\n\
+             ./scripts/deploy.sh\n\
+             ```\n",
+        );
+        let refs = vec![FileReference {
+            path: "src/new.rs".to_string(),
+            exists: Some(false),
+            action: FileAction::Create,
+            description: "new module".to_string(),
+        }];
+        let checker = ViabilityChecker::new().with_working_dir(dir.path().to_path_buf());
+
+        let violations = checker.check_runbook_steps(&runbook, &[], &refs);
+        assert_eq!(
+            messages(&violations),
+            vec![
+                "Operator runbook line 2 references `src/missing.rs`, which does not exist",
+                "Operator runbook line 7 references `./scripts/deploy.sh`, which does not exist",
+            ]
+        );
+
+        let checker = checker.with_runbook_config(RunbookConfig {
+            check_paths: false,
+            ..Default::default()
+        });
+        assert!(checker.check_runbook_steps(&runbook, &[], &refs).is_empty());
+    }
+
+    #[test]
+    fn test_v016_cross_references() {
+        let runbook = OperatorRunbook::parse(
+            "### 1) Build\n```bash\ncargo build  --release\n```\n\
+             ### 2) Test\n```bash\ncargo test parser\n```\n",
+        );
+        let instructions = vec![
+            make_instruction(
+                "build",
+                OpCode::RunCommand,
+                serde_json::json!({ "command": "cargo build --release" }),
+            ),
+            make_instruction(
+                "lint",
+                OpCode::RunCommand,
+                serde_json::json!({ "command": "cargo clippy" }),
+            ),
+            make_instruction(
+                "test",
+                OpCode::RunTest,
+                serde_json::json!({ "target": "parser" }),
+            ),
+        ];
+
+        let links = link_runbook_steps(&runbook, &instructions);
+        assert_eq!(
+            links,
+            vec![vec!["build".to_string()], vec!["test".to_string()]]
+        );
+
+        let violations = ViabilityChecker::new().check_runbook_steps(&runbook, &instructions, &[]);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].instruction_id.as_deref(), Some("lint"));
+
+        let no_tests =
+            OperatorRunbook::parse("```bash\ncargo build --release\ncargo clippy\n```\n");
+        let violations = ViabilityChecker::new().check_runbook_steps(&no_tests, &instructions, &[]);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].instruction_id.as_deref(), Some("test"));
+    }
+}
@@ -0,0 +1,304 @@
+//! Execution schedule simulation.
+//!
+//! Turns the instruction DAG into a concrete wave-by-wave schedule for a
+//! fixed number of workers. Each step costs its `estimated_tokens` (or a
+//! default) and takes `ceil(tokens / tokens_per_unit)` abstract time units.
+//! A wave runs up to `workers` ready steps and lasts as long as its slowest
+//! step; ready steps are prioritized by their remaining critical path.
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::ScheduleConfig;
+
+use super::InstructionGraph;
+
+/// A step placed in the simulated schedule
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduledStep {
+    /// Instruction ID
+    pub id: String,
+    /// Context tokens for this step (estimate or default)
+    pub tokens: u32,
+    /// Whether `tokens` came from the step's `estimated_tokens` (false = default)
+    pub estimated: bool,
+    /// Start time in abstract units
+    pub start: u64,
+    /// Duration in abstract units
+    pub duration: u64,
+    /// Step exceeds the per-step context token budget
+    pub over_budget: bool,
+}
+
+/// One wave of concurrently executing steps
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduleWave {
+    /// Wave number (1-based)
+    pub wave: usize,
+    /// Start time in abstract units
+    pub start: u64,
+    /// Wave duration (slowest step) in abstract units
+    pub duration: u64,
+    /// Sum of context tokens across the wave's steps
+    pub token_load: u64,
+    /// Steps executed in this wave
+    pub steps: Vec<ScheduledStep>,
+}
+
+/// Simulated execution schedule for a plan
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ExecutionSchedule {
+    /// Worker count used for the simulation
+    pub workers: usize,
+    /// Per-step context token budget used for the simulation
+    pub max_step_tokens: u32,
+    /// Waves in execution order
+    pub waves: Vec<ScheduleWave>,
+    /// Estimated total wall-clock in abstract units
+    pub total_units: u64,
+    /// Highest per-wave token load
+    pub peak_wave_tokens: u64,
+    /// Total context tokens across all steps
+    pub total_tokens: u64,
+    /// Steps whose tokens exceed `max_step_tokens`
+    pub over_budget_steps: Vec<String>,
+    /// Steps that could not be scheduled (dependency cycles)
+    pub unscheduled: Vec<String>,
+}
+
+/// Simulate executing the instruction graph with the given worker and token limits
+pub fn simulate_schedule(graph: &InstructionGraph, config: &ScheduleConfig) -> ExecutionSchedule {
+    let n = graph.len();
+    let workers = config.workers.max(1);
+    let tokens_per_unit = config.tokens_per_unit.max(1);
+
+    let tokens: Vec<u32> = graph
+        .instructions
+        .iter()
+        .map(|i| i.estimated_tokens.unwrap_or(config.default_step_tokens))
+        .collect();
+    let durations: Vec<u64> = tokens
+        .iter()
+        .map(|&t| u64::from(t.div_ceil(tokens_per_unit)).max(1))
+        .collect();
+
+    // Remaining critical path (including the step itself), computed in reverse
+    // topological order so the longest chains are started first
+    let mut priority = durations.clone();
+    for &idx in graph.topo_order().iter().rev() {
+        let downstream = graph
+            .dependents(idx)
+            .iter()
+            .map(|&d| priority[d])
+            .max()
+            .unwrap_or(0);
+        priority[idx] = durations[idx] + downstream;
+    }
+
+    let mut remaining_deps: Vec<usize> = (0..n).map(|idx| graph.deps(idx).len()).collect();
+    let mut ready: Vec<usize> = (0..n).filter(|&idx| remaining_deps[idx] == 0).collect();
+    let mut scheduled = vec![false; n];
+
+    let mut waves = Vec::new();
+    let mut clock = 0u64;
+    while !ready.is_empty() {
+        ready.sort_by(|&a, &b| priority[b].cmp(&priority[a]).then(a.cmp(&b)));
+        let take = ready.len().min(workers);
+        let batch: Vec<usize> = ready.drain(..take).collect();
+
+        let duration = batch.iter().map(|&idx| durations[idx]).max().unwrap_or(1);
+        let steps: Vec<ScheduledStep> = batch
+            .iter()
+            .map(|&idx| ScheduledStep {
+                id: graph.instructions[idx].id.clone(),
+                tokens: tokens[idx],
+                estimated: graph.instructions[idx].estimated_tokens.is_some(),
+                start: clock,
+                duration: durations[idx],
+                over_budget: tokens[idx] > config.max_step_tokens,
+            })
+            .collect();
+        let token_load = batch.iter().map(|&idx| u64::from(tokens[idx])).sum();
+
+        for &idx in &batch {
+            scheduled[idx] = true;
+            for &dependent in graph.dependents(idx) {
+                remaining_deps[dependent] -= 1;
+                if remaining_deps[dependent] == 0 {
+                    ready.push(dependent);
+                }
+            }
+        }
+
+        waves.push(ScheduleWave {
+            wave: waves.len() + 1,
+            start: clock,
+            duration,
+            token_load,
+            steps,
+        });
+        clock += duration;
+    }
+
+    let over_budget_steps = waves
+        .iter()
+        .flat_map(|w| w.steps.iter())
+        .filter(|s| s.over_budget)
+        .map(|s| s.id.clone())
+        .collect();
+    let unscheduled = (0..n)
+        .filter(|&idx| !scheduled[idx])
+        .map(|idx| graph.instructions[idx].id.clone())
+        .collect();
+
+    ExecutionSchedule {
+        workers,
+        max_step_tokens: config.max_step_tokens,
+        peak_wave_tokens: waves.iter().map(|w| w.token_load).max().unwrap_or(0),
+        total_tokens: waves.iter().map(|w| w.token_load).sum(),
+        waves,
+        total_units: clock,
+        over_budget_steps,
+        unscheduled,
+    }
+}
+
+impl ExecutionSchedule {
+    /// Render the schedule as a Mermaid gantt chart (one section per wave)
+    pub fn to_mermaid_gantt(&self) -> String {
+        let mut out = String::new();
+        out.push_str("gantt\n");
+        out.push_str(&format!(
+            "    title Execution schedule ({} workers, {} units)\n",
+            self.workers, self.total_units
+        ));
+        out.push_str("    dateFormat X\n");
+        out.push_str("    axisFormat %s\n");
+        for wave in &self.waves {
+            out.push_str(&format!("    section Wave {}\n", wave.wave));
+            for step in &wave.steps {
+                // Task names can't contain ':' or '#' in gantt syntax
+                let name: String = step
+                    .id
+                    .chars()
+                    .map(|c| if c == ':' || c == '#' { '_' } else { c })
+                    .collect();
+                out.push_str(&format!(
+                    "    {} :{}{}, {}\n",
+                    name,
+                    if step.over_budget { "crit, " } else { "" },
+                    step.start,
+                    step.start + step.duration
+                ));
+            }
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Instruction, OpCode};
+
+    fn make_instruction(id: &str, deps: Vec<&str>, tokens: Option<u32>) -> Instruction {
+        Instruction {
+            id: id.to_string(),
+            op: OpCode::SearchCode,
+            params: serde_json::json!({}),
+            dependencies: deps.iter().map(|s| s.to_string()).collect(),
+            description: format!("Test instruction {}", id),
+            estimated_tokens: tokens,
+        }
+    }
+
+    fn config(workers: usize) -> ScheduleConfig {
+        ScheduleConfig {
+            workers,
+            max_step_tokens: 4_000,
+            default_step_tokens: 500,
+            tokens_per_unit: 1_000,
+        }
+    }
+
+    #[test]
+    fn test_schedule_respects_worker_limit() {
+        let instructions = vec![
+            make_instruction("root", vec![], Some(1_000)),
+            make_instruction("a", vec!["root"], Some(1_000)),
+            make_instruction("b", vec!["root"], Some(1_000)),
+            make_instruction("c", vec!["root"], Some(1_000)),
+        ];
+        let graph = InstructionGraph::new(&instructions);
+
+        let wide = simulate_schedule(&graph, &config(3));
+        assert_eq!(wide.waves.len(), 2);
+        assert_eq!(wide.total_units, 2);
+        assert_eq!(wide.peak_wave_tokens, 3_000);
+
+        let narrow = simulate_schedule(&graph, &config(2));
+        assert_eq!(narrow.waves.len(), 3);
+        assert_eq!(narrow.waves[1].steps.len(), 2);
+        assert_eq!(narrow.total_units, 3);
+    }
+
+    #[test]
+    fn test_schedule_durations_and_budget() {
+        let instructions = vec![
+            make_instruction("small", vec![], None),
+            make_instruction("large", vec![], Some(5_500)),
+            make_instruction("after", vec!["small", "large"], Some(200)),
+        ];
+        let graph = InstructionGraph::new(&instructions);
+        let schedule = simulate_schedule(&graph, &config(4));
+
+        // Wave 1 lasts as long as the 6-unit step
+        assert_eq!(schedule.waves[0].duration, 6);
+        assert_eq!(schedule.waves[1].start, 6);
+        assert_eq!(schedule.total_units, 7);
+        assert_eq!(schedule.over_budget_steps, vec!["large".to_string()]);
+        assert!(
+            schedule.waves[0]
+                .steps
+                .iter()
+                .any(|s| s.id == "small" && !s.estimated && s.tokens == 500)
+        );
+        assert!(
+            schedule.waves[0]
+                .steps
+                .iter()
+                .any(|s| s.id == "large" && s.estimated && s.tokens == 5_500)
+        );
+    }
+
+    #[test]
+    fn test_schedule_prioritizes_critical_path() {
+        let instructions = vec![
+            make_instruction("short", vec![], Some(1_000)),
+            make_instruction("long_1", vec![], Some(1_000)),
+            make_instruction("long_2", vec!["long_1"], Some(1_000)),
+        ];
+        let graph = InstructionGraph::new(&instructions);
+        let schedule = simulate_schedule(&graph, &config(1));
+
+        assert_eq!(schedule.waves[0].steps[0].id, "long_1");
+        assert_eq!(schedule.total_units, 3);
+    }
+
+    #[test]
+    fn test_schedule_cycle_unscheduled_and_gantt() {
+        let instructions = vec![
+            make_instruction("ok", vec![], Some(1_000)),
+            make_instruction("x", vec!["y"], None),
+            make_instruction("y", vec!["x"], None),
+        ];
+        let graph = InstructionGraph::new(&instructions);
+        let schedule = simulate_schedule(&graph, &config(2));
+
+        assert_eq!(schedule.unscheduled, vec!["x".to_string(), "y".to_string()]);
+
+        let gantt = schedule.to_mermaid_gantt();
+        assert!(gantt.starts_with("gantt\n"));
+        assert!(gantt.contains("section Wave 1"));
+        assert!(gantt.contains("    ok :0, 1\n"));
+    }
+}
@@ -0,0 +1,131 @@
+//! SEARCH_CODE dry runs (V-018).
+//!
+//! - V-018: SEARCH_CODE queries return a useful number of hits
+
+use crate::models::{Instruction, OpCode};
+
+use super::{ViabilityChecker, ViabilitySeverity, ViabilityViolation};
+
+impl ViabilityChecker {
+    /// V-018: Dry-run SEARCH_CODE queries against the working directory.
+    ///
+    /// Only runs when a code search backend is configured. Queries with zero
+    /// hits (dead search) or more than `max_hits` (useless search) are
+    /// reported. Queries containing `${...}` refs are resolved at execution
+    /// time and skipped.
+    pub fn check_search_queries(&self, instructions: &[Instruction]) -> Vec<ViabilityViolation> {
+        let Some(search) = &self.code_search else {
+            return Vec::new();
+        };
+
+        let mut violations = Vec::new();
+        for instr in instructions.iter().filter(|i| i.op == OpCode::SearchCode) {
+            let Some(query) = instr.params.get("query").and_then(|q| q.as_str()) else {
+                continue; // V-005 territory
+            };
+            if query.trim().is_empty() || query.contains("${") {
+                continue;
+            }
+            let scope = instr.params.get("scope").and_then(|s| s.as_str());
+            let hits = search.search(query, scope);
+            let in_scope = scope.map(|s| format!(" in '{}'", s)).unwrap_or_default();
+
+            if hits.matches == 0 {
+                violations.push(ViabilityViolation {
+                    rule_id: "VIABILITY-018".to_string(),
+                    instruction_id: Some(instr.id.clone()),
+                    severity: ViabilitySeverity::Warning,
+                    message: format!(
+                        "SEARCH_CODE '{}' query '{}' matches nothing{}",
+                        instr.id, query, in_scope
+                    ),
+                    remediation: "Check the pattern and scope against the repository, or drop \
+                        the search if the code doesn't exist yet"
+                        .to_string(),
+                });
+            } else if hits.truncated {
+                violations.push(ViabilityViolation {
+                    rule_id: "VIABILITY-018".to_string(),
+                    instruction_id: Some(instr.id.clone()),
+                    severity: ViabilitySeverity::Warning,
+                    message: format!(
+                        "SEARCH_CODE '{}' query '{}' matches more than {} lines{} (~{} tokens)",
+                        instr.id,
+                        query,
+                        search.max_hits(),
+                        in_scope,
+                        hits.estimated_tokens()
+                    ),
+                    remediation: "Narrow the query or add a `scope` so results fit in context"
+                        .to_string(),
+                });
+            }
+        }
+        violations
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::CodeSearchConfig;
+    use crate::orchestrator::code_search::CodeSearch;
+    use std::sync::Arc;
+
+    fn make_instruction(id: &str, params: serde_json::Value) -> Instruction {
+        Instruction {
+            id: id.to_string(),
+            op: OpCode::SearchCode,
+            params,
+            description: format!("Test instruction {}", id),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_v018_dead_and_broad_searches() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("src")).unwrap();
+        std::fs::write(
+            dir.path().join("src/lib.rs"),
+            "use std::fmt;\nuse std::io;\nuse std::fs;\npub fn parse() {}\n",
+        )
+        .unwrap();
+        let config = CodeSearchConfig {
+            max_hits: 2,
+            ..CodeSearchConfig::default()
+        };
+        let search = CodeSearch::from_config(dir.path(), &config).unwrap();
+        let checker = ViabilityChecker::new().with_code_search(Arc::new(search));
+
+        let instructions = vec![
+            make_instruction("found", serde_json::json!({ "query": "fn parse" })),
+            make_instruction("dead", serde_json::json!({ "query": "impl.*Handler" })),
+            make_instruction(
+                "scoped",
+                serde_json::json!({ "query": "fn parse", "scope": "tests/" }),
+            ),
+            make_instruction("broad", serde_json::json!({ "query": "use std" })),
+            make_instruction(
+                "dynamic",
+                serde_json::json!({ "query": "${found.pattern}" }),
+            ),
+        ];
+
+        let violations = checker.check_search_queries(&instructions);
+        let ids: Vec<&str> = violations
+            .iter()
+            .filter_map(|v| v.instruction_id.as_deref())
+            .collect();
+        assert_eq!(ids, vec!["dead", "scoped", "broad"]);
+        assert!(violations[1].message.contains("in 'tests/'"));
+        assert!(violations[2].message.contains("more than 2 lines"));
+
+        // Without a search backend the rule is disabled
+        assert!(
+            ViabilityChecker::new()
+                .check_search_queries(&instructions)
+                .is_empty()
+        );
+    }
+}
@@ -0,0 +1,203 @@
+//! Symbol existence check (V-017).
+//!
+//! - V-017: Symbols named in EDIT_CODE goals exist in the repository
+
+use std::collections::HashSet;
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use crate::models::{Instruction, OpCode};
+use crate::orchestrator::repo_map::is_well_known;
+
+use super::{ViabilityChecker, ViabilitySeverity, ViabilityViolation};
+
+/// Inline code span
+static INLINE_CODE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"`([^`]+)`").expect("invalid INLINE_CODE regex"));
+
+/// `Name`, `name()`, `Type::method`, `Type::method()`
+static SYMBOL_REF: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^([A-Za-z_]\w*(?:::[A-Za-z_]\w*)*)(\(\))?$").expect("invalid SYMBOL_REF regex")
+});
+
+/// Verbs indicating the goal introduces the symbol rather than editing it
+static CREATE_VERB: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)\b(add|adds|adding|create|creates|creating|new|introduce|introduces|define|defines|implement|implements|rename|renames|extract|extracts|generate|generates)\b")
+        .expect("invalid CREATE_VERB regex")
+});
+
+/// A symbol reference in a goal
+struct GoalSymbol {
+    /// Path segments (`Type::method` -> ["Type", "method"])
+    segments: Vec<String>,
+    /// Whether the sentence introduces the symbol
+    created: bool,
+}
+
+/// Symbol-like inline code spans in a goal.
+///
+/// Plain lowercase words (`config`) are ignored since they are usually
+/// modules, variables, or prose; a reference needs `::`, `()`, `_`, or an
+/// uppercase letter to count.
+fn goal_symbols(goal: &str) -> Vec<GoalSymbol> {
+    let mut symbols = Vec::new();
+    for caps in INLINE_CODE.captures_iter(goal) {
+        let code = caps[1].trim();
+        let Some(sym) = SYMBOL_REF.captures(code) else {
+            continue;
+        };
+        let path = &sym[1];
+        let is_call = sym.get(2).is_some();
+        if !(is_call
+            || path.contains("::")
+            || path.contains('_')
+            || path.chars().any(|c| c.is_ascii_uppercase()))
+        {
+            continue;
+        }
+
+        // Sentence leading up to the span
+        let start = caps.get(0).map(|m| m.start()).unwrap_or(0);
+        let sentence_start = goal[..start]
+            .rfind(['.', ';', '\n'])
+            .map(|i| i + 1)
+            .unwrap_or(0);
+        let created = CREATE_VERB.is_match(&goal[sentence_start..start]);
+
+        symbols.push(GoalSymbol {
+            segments: path
+                .split("::")
+                .filter(|s| !matches!(*s, "crate" | "self" | "super"))
+                .map(str::to_string)
+                .collect(),
+            created,
+        });
+    }
+    symbols
+}
+
+impl ViabilityChecker {
+    /// V-017: Check that symbols named in EDIT_CODE goals exist.
+    ///
+    /// Only runs when a symbol index is configured. Symbols that any goal
+    /// introduces ("Add struct `Foo`") are exempt everywhere in the plan.
+    pub fn check_goal_symbols(&self, instructions: &[Instruction]) -> Vec<ViabilityViolation> {
+        let Some(index) = &self.symbol_index else {
+            return Vec::new();
+        };
+
+        let goals: Vec<(&Instruction, Vec<GoalSymbol>)> = instructions
+            .iter()
+            .filter(|i| i.op == OpCode::EditCode)
+            .filter_map(|i| {
+                let goal = i.params.get("goal").and_then(|g| g.as_str())?;
+                Some((i, goal_symbols(goal)))
+            })
+            .collect();
+
+        let created: HashSet<&str> = goals
+            .iter()
+            .flat_map(|(_, symbols)| symbols)
+            .filter(|s| s.created)
+            .flat_map(|s| s.segments.iter().map(String::as_str))
+            .collect();
+
+        let mut violations = Vec::new();
+        for (instr, symbols) in &goals {
+            let mut reported = HashSet::new();
+            for symbol in symbols {
+                if symbol.created || symbol.segments.is_empty() {
+                    continue;
+                }
+                // `Type::member` resolves when any segment is indexed
+                // (fields and enum variants aren't symbols)
+                let resolves = symbol
+                    .segments
+                    .iter()
+                    .any(|s| index.contains(s) || is_well_known(s) || created.contains(s.as_str()));
+                let name = symbol.segments.join("::");
+                if !resolves && reported.insert(name.clone()) {
+                    violations.push(ViabilityViolation {
+                        rule_id: "VIABILITY-017".to_string(),
+                        instruction_id: Some(instr.id.clone()),
+                        severity: ViabilitySeverity::Warning,
+                        message: format!(
+                            "EDIT_CODE '{}' goal mentions `{}`, which was not found in the repository symbol index",
+                            instr.id, name
+                        ),
+                        remediation: "Use the exact name from the repo map, or state that the \
+                            instruction creates it (e.g., \"Add struct `Name`\")"
+                            .to_string(),
+                    });
+                }
+            }
+        }
+        violations
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::orchestrator::repo_map::SymbolIndex;
+    use std::sync::Arc;
+
+    fn make_instruction(id: &str, goal: &str) -> Instruction {
+        Instruction {
+            id: id.to_string(),
+            op: OpCode::EditCode,
+            params: serde_json::json!({ "goal": goal }),
+            description: format!("Test instruction {}", id),
+            ..Default::default()
+        }
+    }
+
+    fn checker() -> ViabilityChecker {
+        let mut index = SymbolIndex::default();
+        index.add_file(
+            "src/plan.rs",
+            "pub struct Plan {}\nimpl Plan {\n    pub fn parse_plan() {}\n}\n",
+        );
+        ViabilityChecker::new().with_symbol_index(Arc::new(index))
+    }
+
+    #[test]
+    fn test_v017_flags_unknown_symbols() {
+        let instructions = vec![
+            make_instruction(
+                "edit_1",
+                "Update `Plan::parse_plan()` and `Plan::title` to call `validate_plan()`; keep `config` as is",
+            ),
+            make_instruction("edit_2", "Refactor `PlanParser` and `String` handling"),
+        ];
+
+        let violations = checker().check_goal_symbols(&instructions);
+        let messages: Vec<(&str, &str)> = violations
+            .iter()
+            .map(|v| (v.instruction_id.as_deref().unwrap(), v.message.as_str()))
+            .collect();
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0].0, "edit_1");
+        assert!(messages[0].1.contains("`validate_plan`"));
+        assert_eq!(messages[1].0, "edit_2");
+        assert!(messages[1].1.contains("`PlanParser`"));
+        assert!(violations.iter().all(|v| v.rule_id == "VIABILITY-017"));
+    }
+
+    #[test]
+    fn test_v017_created_symbols_are_exempt() {
+        let instructions = vec![
+            make_instruction("edit_1", "Add struct `PlanParser` with `parse_all()`"),
+            make_instruction("edit_2", "Use `PlanParser` from `Plan::parse_plan`"),
+        ];
+
+        assert!(checker().check_goal_symbols(&instructions).is_empty());
+        // Without an index the rule is disabled
+        assert!(
+            ViabilityChecker::new()
+                .check_goal_symbols(&[make_instruction("e", "Fix `Missing`")])
+                .is_empty()
+        );
+    }
+}
@@ -2,6 +2,8 @@
 
 use serde::{Deserialize, Serialize};
 
+use super::DagMetrics;
+
 /// Severity level of a viability violation
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
@@ -36,6 +38,9 @@ pub struct ViabilityResult {
     pub violations: Vec<ViabilityViolation>,
     /// Overall viability score (0.0 - 1.0)
     pub score: f32,
+    /// DAG metrics computed from the same instruction graph (if instructions present)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metrics: Option<DagMetrics>,
 }
 
 impl Default for ViabilityResult {
@@ -44,6 +49,7 @@ impl Default for ViabilityResult {
             passed: true,
             violations: Vec::new(),
             score: 1.0,
+            metrics: None,
         }
     }
 }
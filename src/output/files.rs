@@ -3,9 +3,16 @@ use async_trait::async_trait;
 use tokio::fs;
 use tracing::info;
 
-use crate::config::OutputConfig;
-use crate::models::{GroundingGate, GroundingSnapshot, Instruction, Plan, ReviewResult};
-use crate::orchestrator::viability::{DagMetrics, analyze_dag};
+use crate::config::{OutputConfig, ScheduleConfig};
+use crate::models::{
+    DimensionScore, GateCheck, GroundingGate, GroundingSnapshot, Instruction, OperatorRunbook,
+    Plan, ReviewResult,
+};
+use crate::orchestrator::viability::{
+    DagMetrics, ExecutionSchedule, InstructionGraph, RiskyCommand, ViabilityChecker,
+    ViabilitySeverity, analyze_graph, link_runbook_steps, simulate_schedule,
+};
+use crate::orchestrator::write_atomic_async;
 use crate::slugify;
 
 use super::OutputWriter;
@@ -24,11 +31,37 @@ pub enum PlanStatus {
 /// File-based output writer that generates markdown files
 pub struct FileOutputWriter {
     config: OutputConfig,
+    schedule: ScheduleConfig,
+    viability: ViabilityChecker,
+    review_dimensions: Vec<DimensionScore>,
 }
 
 impl FileOutputWriter {
     pub fn new(config: OutputConfig) -> Self {
-        Self { config }
+        Self {
+            config,
+            schedule: ScheduleConfig::default(),
+            viability: ViabilityChecker::new(),
+            review_dimensions: Vec::new(),
+        }
+    }
+
+    /// Set the worker/token settings used for the execution schedule simulation
+    pub fn with_schedule_config(mut self, schedule: ScheduleConfig) -> Self {
+        self.schedule = schedule;
+        self
+    }
+
+    /// Set the checker used for the "Risky commands" section (severities, working dir)
+    pub fn with_viability_checker(mut self, viability: ViabilityChecker) -> Self {
+        self.viability = viability;
+        self
+    }
+
+    /// Set the specialist review scores rendered in the "Review Dimensions" section
+    pub fn with_review_dimensions(mut self, review_dimensions: Vec<DimensionScore>) -> Self {
+        self.review_dimensions = review_dimensions;
+        self
     }
 
     async fn ensure_runs_dir(&self) -> Result<()> {
@@ -72,10 +105,28 @@ impl FileOutputWriter {
         // =========================================================================
         // Operator Runbook (FIRST actionable section)
         // =========================================================================
-        if let Some(runbook) = &plan.operator_runbook {
+        if let (Some(runbook), Some(parsed)) = (&plan.operator_runbook, plan.parsed_runbook()) {
             md.push_str("## How to execute this plan (operator runbook)\n\n");
-            md.push_str(runbook);
-            md.push_str("\n\n---\n\n");
+            if parsed.steps.is_empty() {
+                md.push_str(runbook);
+                md.push_str("\n\n");
+            } else {
+                let links =
+                    link_runbook_steps(&parsed, plan.instructions.as_deref().unwrap_or(&[]));
+                md.push_str(&self.render_runbook(&parsed, &links));
+            }
+            md.push_str("---\n\n");
+        }
+
+        // =========================================================================
+        // Risky commands (V-015 findings in RUN_COMMAND and the runbook)
+        // =========================================================================
+        let risky = self.viability.find_risky_commands(
+            plan.instructions.as_deref(),
+            plan.operator_runbook.as_deref(),
+        );
+        if !risky.is_empty() {
+            md.push_str(&self.render_risky_commands(&risky));
         }
 
         // =========================================================================
@@ -181,6 +232,13 @@ impl FileOutputWriter {
             }
         }
 
+        // =========================================================================
+        // Review Dimensions (specialist review scores)
+        // =========================================================================
+        if !self.review_dimensions.is_empty() {
+            md.push_str(&self.render_review_dimensions(&self.review_dimensions));
+        }
+
         // =========================================================================
         // Appendix: Technical Details (for reference/automation)
         // =========================================================================
@@ -256,6 +314,97 @@ impl FileOutputWriter {
         md
     }
 
+    /// Render parsed runbook steps as a numbered list with copy-safe code blocks
+    fn render_runbook(&self, runbook: &OperatorRunbook, links: &[Vec<String>]) -> String {
+        let mut md = String::new();
+
+        for line in &runbook.preamble {
+            md.push_str(line);
+            md.push_str("\n\n");
+        }
+
+        for (step, linked) in runbook.steps.iter().zip(links) {
+            if step.title.is_empty() {
+                md.push_str(&format!("{}. Run:\n", step.number));
+            } else {
+                md.push_str(&format!("{}. **{}**\n", step.number, step.title));
+            }
+
+            if !step.commands.is_empty() {
+                // Fence comments are kept around their command
+                let lines: Vec<&str> = step
+                    .commands
+                    .iter()
+                    .flat_map(|c| {
+                        c.comments_before
+                            .iter()
+                            .map(String::as_str)
+                            .chain(std::iter::once(c.command.as_str()))
+                            .chain(c.comments_after.iter().map(String::as_str))
+                    })
+                    .collect();
+                // Fence must be longer than any backtick run inside the block
+                let longest = lines
+                    .iter()
+                    .map(|line| longest_backtick_run(line))
+                    .max()
+                    .unwrap_or(0);
+                let fence = "`".repeat(longest.max(2) + 1);
+                md.push_str(&format!("\n   {}bash\n", fence));
+                for line in lines {
+                    md.push_str(&format!("   {}\n", line));
+                }
+                md.push_str(&format!("   {}\n", fence));
+            }
+
+            if let Some(expected) = &step.expected_outcome {
+                md.push_str(&format!("\n   **Expected**: {}\n", expected));
+            }
+            if !linked.is_empty() {
+                let ids: Vec<String> = linked.iter().map(|id| format!("`{}`", id)).collect();
+                md.push_str(&format!("\n   **Instructions**: {}\n", ids.join(", ")));
+            }
+            for note in &step.notes {
+                md.push_str(&format!("\n   {}\n", note));
+            }
+            md.push('\n');
+        }
+
+        md
+    }
+
+    /// Render dangerous command findings as a markdown table
+    fn render_risky_commands(&self, risky: &[RiskyCommand]) -> String {
+        let mut md = String::new();
+
+        md.push_str("## Risky commands\n\n");
+        md.push_str("Review these commands before running the plan.\n\n");
+        md.push_str("| Severity | Risk | Location | Command | Detail |\n");
+        md.push_str("|----------|------|----------|---------|--------|\n");
+        for r in risky {
+            let severity = match r.severity {
+                ViabilitySeverity::Critical => "Critical",
+                ViabilitySeverity::Warning => "Warning",
+            };
+            let location = match (&r.instruction_id, r.runbook_line) {
+                (_, Some(line)) => format!("runbook line {}", line),
+                (Some(id), None) => format!("`{}`", id),
+                (None, None) => "-".to_string(),
+            };
+            md.push_str(&format!(
+                "| {} | {} | {} | `{}` | {} |\n",
+                severity,
+                r.risk.label(),
+                location,
+                r.command.replace('|', "\\|").replace('`', "'"),
+                r.detail.replace('|', "\\|")
+            ));
+        }
+
+        md.push_str("\n---\n\n");
+        md
+    }
+
     /// Render grounding gates (Phase 0.0) to markdown
     fn render_grounding_gates(&self, gates: &[GroundingGate]) -> String {
         let mut md = String::new();
@@ -267,6 +416,9 @@ impl FileOutputWriter {
             md.push_str(&format!("- [ ] **{} {}**\n", gate.id, gate.verification));
             md.push_str(&format!("  - **Pass criteria**: {}\n", gate.pass_criteria));
             md.push_str(&format!("  - **Rule**: {}\n", gate.rule));
+            if let Some(check) = &gate.check {
+                md.push_str(&format!("  - **Check**: {}\n", describe_gate_check(check)));
+            }
             md.push('\n');
         }
 
@@ -345,18 +497,18 @@ impl FileOutputWriter {
 
         md.push_str("### Execution Instructions (ISA)\n\n");
 
-        // Compute and show DAG parallelization metrics
-        let metrics = analyze_dag(instructions);
+        // Compute and show DAG parallelization metrics and simulated schedule
+        let graph = InstructionGraph::new(instructions);
+        let metrics = analyze_graph(&graph);
         self.render_dag_metrics(&mut md, &metrics);
+        let schedule = simulate_schedule(&graph, &self.schedule);
+        self.render_schedule(&mut md, &schedule);
 
         // Mermaid DAG diagram
         md.push_str("```mermaid\ngraph TD\n");
         for instr in instructions {
-            // Node with description (use serde for SCREAMING_SNAKE_CASE OpCodes)
-            let label = serde_json::to_string(&instr.op)
-                .unwrap_or_else(|_| format!("{:?}", instr.op))
-                .trim_matches('"')
-                .to_string();
+            // Node with description
+            let label = instr.op.to_string();
             let desc = self.escape_mermaid(&instr.description);
             // Use <br/> for line breaks (works in all mermaid versions)
             md.push_str(&format!("    {}[\"{}<br/>{}\"]\n", instr.id, label, desc));
@@ -371,7 +523,8 @@ impl FileOutputWriter {
         // Detailed instruction list
         md.push_str("**Instruction Details:**\n\n");
         for (i, instr) in instructions.iter().enumerate() {
-            md.push_str(&format!("{}. **{}** (`{:?}`)\n", i + 1, instr.id, instr.op));
+            let op_name = instr.op.to_string();
+            md.push_str(&format!("{}. **{}** (`{}`)\n", i + 1, instr.id, op_name));
             md.push_str(&format!("   - {}\n", instr.description));
             if !instr.dependencies.is_empty() {
                 md.push_str(&format!(
@@ -411,6 +564,44 @@ impl FileOutputWriter {
         md
     }
 
+    /// Render specialist review scores as a table followed by their gaps
+    fn render_review_dimensions(&self, dimensions: &[DimensionScore]) -> String {
+        let mut md = String::from("## Review Dimensions\n\n");
+        md.push_str("| Dimension | Score | Weight | Minimum | Status |\n");
+        md.push_str("|-----------|-------|--------|---------|--------|\n");
+        for d in dimensions {
+            md.push_str(&format!(
+                "| {} | {:.2} | {:.1} | {} | {} |\n",
+                d.name,
+                d.score,
+                d.weight,
+                d.min_score
+                    .map(|m| format!("{:.2}", m))
+                    .unwrap_or_else(|| "-".to_string()),
+                if d.passed {
+                    "pass"
+                } else {
+                    "**below minimum**"
+                }
+            ));
+        }
+        md.push('\n');
+
+        for d in dimensions.iter().filter(|d| !d.gaps.is_empty()) {
+            md.push_str(&format!("**{}**:\n", d.name));
+            for gap in &d.gaps {
+                let severity = match gap.severity {
+                    crate::models::Severity::Error => "HIGH",
+                    crate::models::Severity::Warning => "MEDIUM",
+                    crate::models::Severity::Info => "LOW",
+                };
+                md.push_str(&format!("- **[{}]** {}\n", severity, gap.description));
+            }
+            md.push('\n');
+        }
+        md
+    }
+
     /// Render DAG parallelization metrics as a markdown table
     fn render_dag_metrics(&self, md: &mut String, metrics: &DagMetrics) {
         if metrics.total_nodes == 0 {
@@ -455,6 +646,66 @@ impl FileOutputWriter {
         }
     }
 
+    /// Render the simulated execution schedule as a wave table and Mermaid gantt chart
+    fn render_schedule(&self, md: &mut String, schedule: &ExecutionSchedule) {
+        if schedule.waves.is_empty() {
+            return;
+        }
+
+        md.push_str(&format!(
+            "**Execution Schedule** ({} workers, est. {} units, peak {} tokens/wave):\n\n",
+            schedule.workers, schedule.total_units, schedule.peak_wave_tokens
+        ));
+        md.push_str("| Wave | Start | Duration | Token Load | Steps |\n");
+        md.push_str("|------|-------|----------|------------|-------|\n");
+        for wave in &schedule.waves {
+            let steps: Vec<&str> = wave.steps.iter().map(|s| s.id.as_str()).collect();
+            md.push_str(&format!(
+                "| {} | {} | {} | {} | {} |\n",
+                wave.wave,
+                wave.start,
+                wave.duration,
+                wave.token_load,
+                steps.join(", ")
+            ));
+        }
+        md.push('\n');
+
+        if !schedule.over_budget_steps.is_empty() {
+            md.push_str(&format!(
+                "> **Note**: {} step(s) exceed the {}-token per-step budget: {}\n\n",
+                schedule.over_budget_steps.len(),
+                schedule.max_step_tokens,
+                schedule.over_budget_steps.join(", ")
+            ));
+        }
+        if !schedule.unscheduled.is_empty() {
+            md.push_str(&format!(
+                "> **Note**: Unschedulable steps (dependency cycle): {}\n\n",
+                schedule.unscheduled.join(", ")
+            ));
+        }
+
+        md.push_str("```mermaid\n");
+        md.push_str(&schedule.to_mermaid_gantt());
+        md.push_str("```\n\n");
+    }
+
+    /// Write the simulated execution schedule JSON next to the DAG JSON
+    async fn write_schedule_json(
+        &self,
+        task_dir: &std::path::Path,
+        task_name: &str,
+        instructions: &[Instruction],
+    ) -> Result<()> {
+        let graph = InstructionGraph::new(instructions);
+        let schedule = simulate_schedule(&graph, &self.schedule);
+        let schedule_path = task_dir.join(format!("{}-schedule.json", task_name));
+        write_atomic_async(&schedule_path, serde_json::to_string_pretty(&schedule)?).await?;
+        info!("Wrote {:?}", schedule_path);
+        Ok(())
+    }
+
     /// Write final plan with optional draft status indicator
     pub async fn write_final_with_status(&self, plan: &Plan, is_draft: bool) -> Result<()> {
         self.ensure_active_dir().await?;
@@ -473,7 +724,7 @@ impl FileOutputWriter {
 
         // Write single consolidated execution plan (matches reference plan format)
         let plan_path = task_dir.join(format!("{}-plan.md", task_name));
-        fs::write(
+        write_atomic_async(
             &plan_path,
             self.plan_to_markdown_with_status(plan, is_draft),
         )
@@ -491,8 +742,11 @@ impl FileOutputWriter {
                 "reasoning": plan.reasoning.clone(),
                 "instructions": instructions
             });
-            fs::write(&dag_path, serde_json::to_string_pretty(&dag_content)?).await?;
+            write_atomic_async(&dag_path, serde_json::to_string_pretty(&dag_content)?).await?;
             info!("Wrote {:?}", dag_path);
+
+            self.write_schedule_json(&task_dir, &task_name, instructions)
+                .await?;
         }
 
         // Write final JSON to runs_dir (for machine processing, not committed)
@@ -500,7 +754,7 @@ impl FileOutputWriter {
             .config
             .runs_dir
             .join(format!("{}-final.json", task_name));
-        fs::write(&json_path, serde_json::to_string_pretty(plan)?).await?;
+        write_atomic_async(&json_path, serde_json::to_string_pretty(plan)?).await?;
         info!("Wrote {:?}", json_path);
 
         Ok(())
@@ -528,7 +782,7 @@ impl FileOutputWriter {
 
         // Write single consolidated execution plan (matches reference plan format)
         let plan_path = task_dir.join(format!("{}-plan.md", task_name));
-        fs::write(
+        write_atomic_async(
             &plan_path,
             self.plan_to_markdown_with_plan_status(plan, status),
         )
@@ -546,8 +800,11 @@ impl FileOutputWriter {
                 "reasoning": plan.reasoning.clone(),
                 "instructions": instructions
             });
-            fs::write(&dag_path, serde_json::to_string_pretty(&dag_content)?).await?;
+            write_atomic_async(&dag_path, serde_json::to_string_pretty(&dag_content)?).await?;
             info!("Wrote {:?}", dag_path);
+
+            self.write_schedule_json(&task_dir, &task_name, instructions)
+                .await?;
         }
 
         // Write final JSON to runs_dir (for machine processing, not committed)
@@ -555,13 +812,36 @@ impl FileOutputWriter {
             .config
             .runs_dir
             .join(format!("{}-final.json", task_name));
-        fs::write(&json_path, serde_json::to_string_pretty(plan)?).await?;
+        write_atomic_async(&json_path, serde_json::to_string_pretty(plan)?).await?;
         info!("Wrote {:?}", json_path);
 
         Ok(())
     }
 }
 
+/// One-line description of a machine-checkable gate
+fn describe_gate_check(check: &GateCheck) -> String {
+    match check {
+        GateCheck::FileExists { path } => format!("file exists `{}`", path),
+        GateCheck::GlobMatches { pattern, min, max } => match max {
+            Some(max) if max == min => format!("`{}` matches {} file(s)", pattern, min),
+            Some(max) => format!("`{}` matches {}-{} files", pattern, min, max),
+            None => format!("`{}` matches at least {} file(s)", pattern, min),
+        },
+        GateCheck::RegexInFile { path, pattern } => {
+            format!("`{}` matches regex `{}`", path, pattern)
+        }
+        GateCheck::CommandSucceeds { command } => {
+            format!("`{}` exits 0 (requires `--allow-commands`)", command)
+        }
+    }
+}
+
+/// Length of the longest run of consecutive backticks in `text`
+fn longest_backtick_run(text: &str) -> usize {
+    text.split(|c| c != '`').map(str::len).max().unwrap_or(0)
+}
+
 #[async_trait]
 impl OutputWriter for FileOutputWriter {
     async fn write_intermediate(&self, plan: &Plan, iteration: u32) -> Result<()> {
@@ -571,7 +851,7 @@ impl OutputWriter for FileOutputWriter {
         let path = self.config.runs_dir.join(&filename);
 
         let json = serde_json::to_string_pretty(plan)?;
-        fs::write(&path, json)
+        write_atomic_async(&path, json)
             .await
             .context("Failed to write intermediate plan")?;
 
@@ -586,7 +866,7 @@ impl OutputWriter for FileOutputWriter {
         let path = self.config.runs_dir.join(&filename);
 
         let json = serde_json::to_string_pretty(review)?;
-        fs::write(&path, json)
+        write_atomic_async(&path, json)
             .await
             .context("Failed to write review")?;
 
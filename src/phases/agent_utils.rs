@@ -10,7 +10,8 @@ use anyhow::{Context, Result};
 use tracing::info;
 
 use goose::agents::Agent;
-use goose::providers::{base::Provider, create_with_named_model};
+use goose::model::ModelConfig;
+use goose::providers::{base::Provider, create, create_with_named_model};
 use goose::recipe::Recipe;
 use goose::session::{Session, SessionManager, session_manager::SessionType};
 
@@ -29,6 +30,8 @@ pub struct ProviderConfig<'a> {
     pub default_model: &'static str,
     /// Component name for logging (e.g., "planner", "reviewer", "orchestrator")
     pub component_name: &'static str,
+    /// Sampling temperature (provider default when unset)
+    pub temperature: Option<f32>,
 }
 
 impl<'a> ProviderConfig<'a> {
@@ -43,6 +46,7 @@ impl<'a> ProviderConfig<'a> {
             default_provider: "anthropic",
             default_model: "claude-opus-4-5-20251101",
             component_name: "planner",
+            temperature: None,
         }
     }
 
@@ -57,6 +61,7 @@ impl<'a> ProviderConfig<'a> {
             default_provider: "anthropic",
             default_model: "claude-opus-4-5-20251101",
             component_name: "reviewer",
+            temperature: None,
         }
     }
 
@@ -71,41 +76,70 @@ impl<'a> ProviderConfig<'a> {
             default_provider: "anthropic",
             default_model: "claude-sonnet-4-20250514",
             component_name: "orchestrator",
+            temperature: None,
         }
     }
+
+    /// Set the sampling temperature.
+    pub fn with_temperature(mut self, temperature: Option<f32>) -> Self {
+        self.temperature = temperature;
+        self
+    }
 }
 
-/// Create an LLM provider from config and recipe settings.
+/// Provider and model names `create_provider` would use.
 ///
 /// Priority: config override > recipe setting > default
-pub async fn create_provider(
-    config: &ProviderConfig<'_>,
-    recipe: &Recipe,
-) -> Result<Arc<dyn Provider>> {
+pub fn resolve_provider_model<'a>(
+    config: &ProviderConfig<'a>,
+    recipe: Option<&'a Recipe>,
+) -> (&'a str, &'a str) {
+    let settings = recipe.and_then(|r| r.settings.as_ref());
     let provider_name = config
         .provider_override
-        .or(recipe
-            .settings
-            .as_ref()
-            .and_then(|s| s.goose_provider.as_deref()))
+        .or(settings.and_then(|s| s.goose_provider.as_deref()))
         .unwrap_or(config.default_provider);
-
     let model_name = config
         .model_override
-        .or(recipe
-            .settings
-            .as_ref()
-            .and_then(|s| s.goose_model.as_deref()))
+        .or(settings.and_then(|s| s.goose_model.as_deref()))
         .unwrap_or(config.default_model);
+    (provider_name, model_name)
+}
+
+/// "provider/model" label of the model `create_provider` would use, for
+/// attributing token usage and pricing.
+pub fn resolve_model_label(config: &ProviderConfig<'_>, recipe: Option<&Recipe>) -> String {
+    let (provider_name, model_name) = resolve_provider_model(config, recipe);
+    format!("{}/{}", provider_name, model_name)
+}
+
+/// Create an LLM provider from config and recipe settings.
+///
+/// Priority: config override > recipe setting > default
+pub async fn create_provider(
+    config: &ProviderConfig<'_>,
+    recipe: &Recipe,
+) -> Result<Arc<dyn Provider>> {
+    let (provider_name, model_name) = resolve_provider_model(config, Some(recipe));
 
     info!(
         "Creating {} provider: {} with model: {}",
         config.component_name, provider_name, model_name
     );
 
-    create_with_named_model(provider_name, model_name)
-        .await
-        .with_context(|| format!("Failed to create {} provider", config.component_name))
+    match config.temperature {
+        Some(temperature) => {
+            let model = ModelConfig::new(model_name)
+                .with_context(|| format!("Invalid {} model config", config.component_name))?
+                .with_temperature(Some(temperature));
+            create(provider_name, model)
+                .await
+                .with_context(|| format!("Failed to create {} provider", config.component_name))
+        }
+        None => create_with_named_model(provider_name, model_name)
+            .await
+            .with_context(|| format!("Failed to create {} provider", config.component_name)),
+    }
 }
 
 /// Set up an agent session with provider and extensions from recipe.
@@ -177,6 +211,28 @@ mod tests {
         assert_eq!(config.component_name, "orchestrator");
     }
 
+    #[test]
+    fn test_resolve_model_label() {
+        let recipe: Recipe = serde_yaml::from_str(crate::recipes::DEFAULT_ORCHESTRATOR_RECIPE)
+            .expect("orchestrator recipe should parse");
+
+        let config = ProviderConfig::for_planner(None, None);
+        assert_eq!(
+            resolve_model_label(&config, Some(&recipe)),
+            "anthropic/claude-sonnet-4-20250514"
+        );
+        assert_eq!(
+            resolve_model_label(&config, None),
+            "anthropic/claude-opus-4-5-20251101"
+        );
+
+        let config = ProviderConfig::for_reviewer(None, Some("claude-haiku-4-5"));
+        assert_eq!(
+            resolve_model_label(&config, Some(&recipe)),
+            "anthropic/claude-haiku-4-5"
+        );
+    }
+
     #[test]
     fn test_resolve_working_dir_with_path() {
         let path = std::path::Path::new("/tmp/test");
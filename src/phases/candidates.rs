@@ -0,0 +1,179 @@
+//! Best-of-N candidate plan generation.
+//!
+//! Several planners (each optionally with its own provider, model, or
+//! temperature) produce candidate plans concurrently. The orchestrator client
+//! screens each candidate with viability and hard checks, reviews the
+//! survivors, and keeps the top scorer.
+
+use std::sync::Arc;
+
+use anyhow::Result;
+use futures::future::join_all;
+use serde::Serialize;
+use serde_json::Value;
+use tracing::info;
+
+use crate::config::CandidateVariant;
+use crate::orchestrator::TokenUsage;
+
+use super::GoosePlanner;
+
+/// Planners that generate candidate plans concurrently
+pub struct CandidatePlanners {
+    planners: Vec<(String, Arc<GoosePlanner>)>,
+    initial_only: bool,
+}
+
+impl CandidatePlanners {
+    /// Create an empty set; with `initial_only`, revisions use the single planner
+    pub fn new(initial_only: bool) -> Self {
+        Self {
+            planners: Vec::new(),
+            initial_only,
+        }
+    }
+
+    /// Add a labelled candidate planner
+    pub fn with_planner(mut self, label: impl Into<String>, planner: Arc<GoosePlanner>) -> Self {
+        self.planners.push((label.into(), planner));
+        self
+    }
+
+    /// Number of candidates generated per call
+    pub fn len(&self) -> usize {
+        self.planners.len()
+    }
+
+    /// Whether there are no candidate planners
+    pub fn is_empty(&self) -> bool {
+        self.planners.is_empty()
+    }
+
+    /// Whether candidates are only generated for the initial plan
+    pub fn initial_only(&self) -> bool {
+        self.initial_only
+    }
+
+    /// "provider/model" of the candidate planner labelled `label`
+    pub fn model_label(&self, label: &str) -> Option<String> {
+        self.planners
+            .iter()
+            .find(|(l, _)| l == label)
+            .map(|(_, planner)| planner.model_label())
+    }
+
+    /// Generate every candidate concurrently, in planner order.
+    pub async fn generate(
+        &self,
+        task: &str,
+        feedback: Option<&[String]>,
+        previous_plan: Option<&Value>,
+        working_dir: Option<&str>,
+    ) -> Vec<(String, Result<(Value, TokenUsage)>)> {
+        info!("Generating {} candidate plans", self.planners.len());
+        join_all(self.planners.iter().map(|(label, planner)| async move {
+            let result = planner
+                .generate_plan_json(task, feedback, previous_plan, working_dir)
+                .await;
+            (label.clone(), result)
+        }))
+        .await
+    }
+}
+
+/// Label for the candidate at `index` (0-based) using `variant`
+pub fn candidate_label(index: usize, variant: &CandidateVariant) -> String {
+    let mut parts = Vec::new();
+    if let Some(provider) = &variant.provider {
+        parts.push(provider.clone());
+    }
+    if let Some(model) = &variant.model {
+        parts.push(model.clone());
+    }
+    if let Some(temperature) = variant.temperature {
+        parts.push(format!("t{}", temperature));
+    }
+    if parts.is_empty() {
+        format!("candidate-{}", index + 1)
+    } else {
+        format!("candidate-{}-{}", index + 1, parts.join("-"))
+    }
+}
+
+/// One screened (and possibly reviewed) candidate plan
+#[derive(Debug, Clone, Serialize)]
+pub struct CandidateOutcome {
+    pub label: String,
+    pub plan: Value,
+    /// Review score (None if disqualified before review)
+    pub score: Option<f32>,
+    /// Why the candidate was not reviewed or could not be scored
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub disqualified: Option<String>,
+    /// Full review JSON
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub review: Option<Value>,
+}
+
+/// Index of the highest-scoring qualified candidate (earliest wins ties)
+pub fn select_best(outcomes: &[CandidateOutcome]) -> Option<usize> {
+    let mut best: Option<(usize, f32)> = None;
+    for (index, outcome) in outcomes.iter().enumerate() {
+        if outcome.disqualified.is_some() {
+            continue;
+        }
+        let Some(score) = outcome.score else {
+            continue;
+        };
+        if best.is_none_or(|(_, best_score)| score > best_score) {
+            best = Some((index, score));
+        }
+    }
+    best.map(|(index, _)| index)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_outcome(
+        label: &str,
+        score: Option<f32>,
+        disqualified: Option<&str>,
+    ) -> CandidateOutcome {
+        CandidateOutcome {
+            label: label.to_string(),
+            plan: serde_json::json!({ "title": label }),
+            score,
+            disqualified: disqualified.map(str::to_string),
+            review: None,
+        }
+    }
+
+    #[test]
+    fn test_select_best_skips_disqualified() {
+        let outcomes = vec![
+            make_outcome("a", Some(0.7), None),
+            make_outcome("b", None, Some("2 critical viability violation(s)")),
+            make_outcome("c", Some(0.85), None),
+            make_outcome("d", Some(0.85), None),
+        ];
+        assert_eq!(select_best(&outcomes), Some(2));
+        assert_eq!(select_best(&outcomes[1..2]), None);
+        assert_eq!(select_best(&[]), None);
+    }
+
+    #[test]
+    fn test_candidate_label() {
+        assert_eq!(
+            candidate_label(0, &CandidateVariant::default()),
+            "candidate-1"
+        );
+        let variant = CandidateVariant {
+            provider: None,
+            model: Some("gpt-5".to_string()),
+            temperature: Some(0.7),
+        };
+        assert_eq!(candidate_label(2, &variant), "candidate-3-gpt-5-t0.7");
+    }
+}
@@ -1,13 +1,23 @@
 mod agent_utils;
+pub mod candidates;
 mod json_utils;
 pub mod orchestrator;
 pub mod planner;
+pub mod retry;
+pub mod review_dimensions;
+pub mod review_panel;
 pub mod reviewer;
 
-pub use agent_utils::{ProviderConfig, create_provider, resolve_working_dir, setup_agent_session};
+pub use agent_utils::{
+    ProviderConfig, create_provider, resolve_model_label, resolve_working_dir, setup_agent_session,
+};
+pub use candidates::*;
 pub use json_utils::extract_json_block;
 pub use orchestrator::*;
 pub use planner::*;
+pub use retry::*;
+pub use review_dimensions::*;
+pub use review_panel::*;
 pub use reviewer::*;
 
 use std::path::PathBuf;
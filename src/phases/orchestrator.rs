@@ -3,7 +3,7 @@
 //! This module implements an orchestrator agent that uses goose's Agent with
 //! in-process MCP extensions to coordinate the plan-review workflow.
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -11,23 +11,37 @@ use anyhow::{Context, Result};
 use futures::StreamExt;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use tokio::sync::{Mutex, broadcast};
+use tokio_util::sync::CancellationToken;
 use tracing::{debug, info, warn};
 
 use goose::agents::{Agent, AgentEvent, SessionConfig};
 use goose::conversation::message::{Message, MessageContent};
+use goose::providers::base::Provider;
 use goose::session::{SessionManager, session_manager::SessionType};
 
-use super::{ProviderConfig, create_provider};
+use super::{ProviderConfig, create_provider, resolve_model_label};
 
-use crate::config::{GuardrailsConfig, OrchestratorConfig, OutputConfig};
+use crate::config::{
+    CandidatesConfig, CodeSearchConfig, CommandSafetyConfig, CustomOpCode, GatesConfig,
+    GuardrailsConfig, LadderConfig, ModelRung, OrchestratorConfig, OrchestratorMode, OutputConfig,
+    RepoMapConfig, RetryConfig, ReviewDimensionsConfig, ReviewPanelConfig, RunbookConfig,
+    ScheduleConfig,
+};
 use crate::models::Plan;
 use crate::orchestrator::{
-    GuardrailHardStop, Guardrails, HumanResponse, IterationOutcome, IterationRecord,
-    OrchestrationState, OrchestrationStatus, SessionRegistry, TokenBreakdown,
-    create_orchestrator_client, register_orchestrator_extension,
+    CodeSearch, DeterministicDriver, EVENT_CHANNEL_CAPACITY, ErrorClass, EventLog, Forecast,
+    GateRunner, GuardrailHardStop, Guardrails, HumanResponse, IterationOutcome, IterationRecord,
+    ModelLadder, OrchestrationState, OrchestrationStatus, OrchestratorClient, OrchestratorEvent,
+    PlanAutoFixer, PreGrounding, RetryRecord, SessionLock, SessionRegistry, SymbolIndex,
+    TokenBreakdown, TokenRole, ViabilityChecker, create_orchestrator_client, migrate_session,
+    register_orchestrator_extension, review_feedback, rung_config,
 };
 use crate::output::FileOutputWriter;
-use crate::phases::{GoosePlanner, GooseReviewer};
+use crate::phases::{
+    CandidatePlanners, FailureProbe, GoosePlanner, GooseReviewer, RetryPolicy, ReviewPanel,
+    SpecialistReviews, call_with_retry, candidate_label, classify_error, jitter_sample,
+};
 use crate::recipes::load_recipe;
 // ============================================================================
 // OrchestrationResult
@@ -62,9 +76,9 @@ pub struct OrchestrationResult {
 
 /// LLM-powered orchestrator that coordinates plan generation and review.
 ///
-/// Unlike the deterministic LoopController, the GooseOrchestrator uses an LLM
-/// agent to make decisions about when to generate, review, pause for human input,
-/// and finalize plans.
+/// By default an LLM agent decides when to generate, review, pause for human
+/// input, and finalize plans. With `orchestrator.mode: deterministic`, a
+/// `DeterministicDriver` makes the same decisions without an orchestrator LLM.
 pub struct GooseOrchestrator {
     /// Orchestrator configuration
     config: OrchestratorConfig,
@@ -78,6 +92,40 @@ pub struct GooseOrchestrator {
     session_dir: PathBuf,
     /// Session registry for concurrent session management
     session_registry: Arc<SessionRegistry>,
+    /// User-defined opcodes passed to the planner and viability checks
+    custom_opcodes: Vec<CustomOpCode>,
+    /// Execution schedule simulation settings for plan output
+    schedule_config: ScheduleConfig,
+    /// Severities for dangerous command checks (V-015)
+    command_safety: CommandSafetyConfig,
+    /// Operator runbook validation settings (V-016)
+    runbook_config: RunbookConfig,
+    /// Executable grounding gates evaluated before finalize
+    gates_config: GatesConfig,
+    /// Run `command_succeeds` gates (set only by `--allow-commands`)
+    allow_gate_commands: bool,
+    /// Symbol index / repo map settings (V-017)
+    repo_map_config: RepoMapConfig,
+    /// SEARCH_CODE dry-run settings (V-018)
+    code_search_config: CodeSearchConfig,
+    /// Multi-reviewer panel (empty = single reviewer)
+    review_panel_config: ReviewPanelConfig,
+    /// Specialist review dimensions (empty = main review only)
+    review_dimensions_config: ReviewDimensionsConfig,
+    /// Best-of-N candidate plan generation
+    candidates_config: CandidatesConfig,
+    /// Planner escalation ladder (no rungs = single planner model)
+    planning_ladder: LadderConfig,
+    /// Reviewer escalation ladder (empty = single reviewer model)
+    review_ladder: Vec<ModelRung>,
+    /// Retry backoff and fallback providers for all roles
+    retry_config: RetryConfig,
+    /// Cancelled on SIGINT/SIGTERM or MCP cancellation
+    cancel_token: CancellationToken,
+    /// Progress events published by the tools and at session end
+    events: broadcast::Sender<OrchestratorEvent>,
+    /// Orchestrator provider used instead of the configured chain
+    provider: Option<Arc<dyn Provider>>,
 }
 
 impl GooseOrchestrator {
@@ -97,9 +145,139 @@ impl GooseOrchestrator {
             base_dir,
             session_dir: runs_dir,
             session_registry,
+            custom_opcodes: Vec::new(),
+            schedule_config: ScheduleConfig::default(),
+            command_safety: CommandSafetyConfig::default(),
+            runbook_config: RunbookConfig::default(),
+            gates_config: GatesConfig::default(),
+            allow_gate_commands: false,
+            repo_map_config: RepoMapConfig::default(),
+            code_search_config: CodeSearchConfig::default(),
+            review_panel_config: ReviewPanelConfig::default(),
+            review_dimensions_config: ReviewDimensionsConfig::default(),
+            candidates_config: CandidatesConfig::default(),
+            planning_ladder: LadderConfig::default(),
+            review_ladder: Vec::new(),
+            retry_config: RetryConfig::default(),
+            cancel_token: CancellationToken::new(),
+            events: broadcast::channel(EVENT_CHANNEL_CAPACITY).0,
+            provider: None,
         }
     }
 
+    /// Register user-defined opcodes from config.
+    pub fn with_custom_opcodes(mut self, custom_opcodes: Vec<CustomOpCode>) -> Self {
+        self.custom_opcodes = custom_opcodes;
+        self
+    }
+
+    /// Set the execution schedule simulation settings used for plan output.
+    pub fn with_schedule_config(mut self, schedule_config: ScheduleConfig) -> Self {
+        self.schedule_config = schedule_config;
+        self
+    }
+
+    /// Set severities for dangerous command checks in plans.
+    pub fn with_command_safety(mut self, command_safety: CommandSafetyConfig) -> Self {
+        self.command_safety = command_safety;
+        self
+    }
+
+    /// Set operator runbook validation settings.
+    pub fn with_runbook_config(mut self, runbook_config: RunbookConfig) -> Self {
+        self.runbook_config = runbook_config;
+        self
+    }
+
+    /// Set executable grounding gate settings.
+    pub fn with_gates_config(mut self, gates_config: GatesConfig) -> Self {
+        self.gates_config = gates_config;
+        self
+    }
+
+    /// Run `command_succeeds` gates. Only the CLI's `--allow-commands`
+    /// flag should set this; MCP sessions never run gate commands.
+    pub fn with_allow_gate_commands(mut self, allow: bool) -> Self {
+        self.allow_gate_commands = allow;
+        self
+    }
+
+    /// Set symbol index and repo map settings.
+    pub fn with_repo_map_config(mut self, repo_map_config: RepoMapConfig) -> Self {
+        self.repo_map_config = repo_map_config;
+        self
+    }
+
+    /// Set SEARCH_CODE dry-run settings.
+    pub fn with_code_search_config(mut self, code_search_config: CodeSearchConfig) -> Self {
+        self.code_search_config = code_search_config;
+        self
+    }
+
+    /// Set the multi-reviewer panel.
+    pub fn with_review_panel_config(mut self, review_panel_config: ReviewPanelConfig) -> Self {
+        self.review_panel_config = review_panel_config;
+        self
+    }
+
+    /// Set the specialist review dimensions.
+    pub fn with_review_dimensions_config(
+        mut self,
+        review_dimensions_config: ReviewDimensionsConfig,
+    ) -> Self {
+        self.review_dimensions_config = review_dimensions_config;
+        self
+    }
+
+    /// Set best-of-N candidate plan generation.
+    pub fn with_candidates_config(mut self, candidates_config: CandidatesConfig) -> Self {
+        self.candidates_config = candidates_config;
+        self
+    }
+
+    /// Set the planner escalation ladder.
+    pub fn with_planning_ladder(mut self, planning_ladder: LadderConfig) -> Self {
+        self.planning_ladder = planning_ladder;
+        self
+    }
+
+    /// Set the reviewer escalation ladder.
+    pub fn with_review_ladder(mut self, review_ladder: Vec<ModelRung>) -> Self {
+        self.review_ladder = review_ladder;
+        self
+    }
+
+    /// Set retry backoff and fallback providers.
+    pub fn with_retry_config(mut self, retry_config: RetryConfig) -> Self {
+        self.retry_config = retry_config;
+        self
+    }
+
+    /// Stop the session gracefully when `cancel_token` is cancelled.
+    ///
+    /// In-flight planner, reviewer, and orchestrator streams are interrupted,
+    /// the state is saved as `Cancelled`, and the current plan is written as
+    /// a draft so the session can be resumed later.
+    pub fn with_cancel_token(mut self, cancel_token: CancellationToken) -> Self {
+        self.cancel_token = cancel_token;
+        self
+    }
+
+    /// Drive the orchestrator agent with `provider` instead of the
+    /// configured provider chain.
+    pub fn with_provider(mut self, provider: Arc<dyn Provider>) -> Self {
+        self.provider = Some(provider);
+        self
+    }
+
+    /// Receive progress events for every session this orchestrator runs.
+    ///
+    /// The receiver closes once the orchestrator is dropped; events sent
+    /// before subscribing are not replayed.
+    pub fn subscribe(&self) -> broadcast::Receiver<OrchestratorEvent> {
+        self.events.subscribe()
+    }
+
     /// Run the orchestrator for a task.
     ///
     /// # Arguments
@@ -134,6 +312,14 @@ impl GooseOrchestrator {
 
         info!("Starting orchestrator session: {}", session_id);
 
+        // Held until the session ends; refuses to run while another process
+        // works on the same session directory, and cancels the session if
+        // the lock is taken over
+        let _session_lock =
+            SessionLock::acquire(&session_dir)?.with_cancel_token(self.cancel_token.clone());
+        // Upgrade state written by an older release before resuming (keeps a backup)
+        migrate_session(&session_dir)?;
+
         // Create shared components
         let guardrails = Arc::new(Guardrails::from_config(&self.guardrails_config));
 
@@ -146,58 +332,321 @@ impl GooseOrchestrator {
         // Use score_threshold from guardrails config (single source of truth)
         let score_threshold = self.guardrails_config.score_threshold;
 
-        let planner = Arc::new(GoosePlanner::new(
-            crate::config::PlanningConfig {
-                recipe: PathBuf::from("recipes/planner.yaml"),
-                provider_override: planner_provider,
-                model_override: planner_model,
-            },
-            self.base_dir.clone(),
+        let working_dir_path = working_dir.unwrap_or_else(|| self.base_dir.clone());
+
+        // Deterministic pre-grounding, built once per session and reused by
+        // every iteration (and on resume)
+        let grounding_context = if self.config.pre_grounding {
+            match PreGrounding::load_or_build(&session_dir, &working_dir_path) {
+                Ok(grounding) => {
+                    info!(
+                        "Pre-grounding: {} files, {} build targets",
+                        grounding.total_files,
+                        grounding.snapshot.verified_targets.len()
+                    );
+                    Some(grounding.to_prompt_section())
+                }
+                Err(e) => {
+                    warn!("Pre-grounding skipped: {}", e);
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
+        // Symbol index for the repo map and V-017
+        let symbol_index = if self.repo_map_config.enabled {
+            match SymbolIndex::build(&working_dir_path) {
+                Ok(index) => {
+                    info!("Repo map: indexed {} symbols", index.symbols().len());
+                    Some(Arc::new(index))
+                }
+                Err(e) => {
+                    warn!("Repo map skipped: {}", e);
+                    None
+                }
+            }
+        } else {
+            None
+        };
+        // Working directory search for SEARCH_CODE dry runs
+        let code_search = if self.code_search_config.enabled {
+            match CodeSearch::from_config(&working_dir_path, &self.code_search_config) {
+                Ok(search) => {
+                    info!("Code search: {} files", search.file_count());
+                    Some(Arc::new(search))
+                }
+                Err(e) => {
+                    warn!("Code search skipped: {}", e);
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
+        let repo_map = symbol_index
+            .as_ref()
+            .map(|index| index.to_prompt_section(self.repo_map_config.token_budget));
+
+        let build_planner = |provider: Option<String>, model: Option<String>| {
+            let mut planner = GoosePlanner::new(
+                crate::config::PlanningConfig {
+                    recipe: PathBuf::from("recipes/planner.yaml"),
+                    provider_override: provider,
+                    model_override: model,
+                    ladder: LadderConfig::default(),
+                },
+                self.base_dir.clone(),
+            )
+            .with_custom_opcodes(self.custom_opcodes.clone())
+            .with_cancel_token(self.cancel_token.clone());
+            if let Some(context) = &grounding_context {
+                planner = planner.with_grounding_context(context.clone());
+            }
+            if let Some(repo_map) = &repo_map {
+                planner = planner.with_repo_map(repo_map.clone());
+            }
+            planner
+        };
+        let planner = Arc::new(build_planner(
+            planner_provider.clone(),
+            planner_model.clone(),
         ));
 
-        let reviewer = Arc::new(GooseReviewer::new(
-            crate::config::ReviewConfig {
-                recipe: PathBuf::from("recipes/reviewer.yaml"),
-                provider_override: reviewer_provider,
-                model_override: reviewer_model,
-            },
-            self.base_dir.clone(),
-            score_threshold,
+        // Best-of-N: one planner per candidate, variants assigned round-robin
+        let candidates = if self.candidates_config.enabled() {
+            let mut candidates = CandidatePlanners::new(self.candidates_config.initial_only);
+            for index in 0..self.candidates_config.count {
+                let variant = if self.candidates_config.variants.is_empty() {
+                    Default::default()
+                } else {
+                    let variants = &self.candidates_config.variants;
+                    variants[index % variants.len()].clone()
+                };
+                let candidate = build_planner(
+                    variant.provider.clone().or(planner_provider.clone()),
+                    variant.model.clone().or(planner_model.clone()),
+                )
+                .with_temperature(variant.temperature);
+                candidates =
+                    candidates.with_planner(candidate_label(index, &variant), Arc::new(candidate));
+            }
+            info!("Best-of-N enabled with {} candidates", candidates.len());
+            Some(Arc::new(candidates))
+        } else {
+            None
+        };
+
+        // Escalation ladder: one planner per rung; unset rung fields fall
+        // back to the planner overrides
+        let planner_ladder = (!self.planning_ladder.rungs.is_empty()).then(|| {
+            let ladder =
+                self.planning_ladder
+                    .rungs
+                    .iter()
+                    .fold(ModelLadder::new(), |ladder, rung| {
+                        let planner = build_planner(
+                            rung.provider.clone().or(planner_provider.clone()),
+                            rung.model.clone().or(planner_model.clone()),
+                        );
+                        ladder.with_rung(planner.model_label(), Arc::new(planner))
+                    });
+            info!("Planner escalation ladder with {} rungs", ladder.len());
+            Arc::new(ladder)
+        });
+
+        let build_reviewer = |provider: Option<String>, model: Option<String>| {
+            let mut reviewer = GooseReviewer::new(
+                crate::config::ReviewConfig {
+                    recipe: PathBuf::from("recipes/reviewer.yaml"),
+                    provider_override: provider,
+                    model_override: model,
+                    ladder: Vec::new(),
+                },
+                self.base_dir.clone(),
+                score_threshold,
+            )
+            .with_cancel_token(self.cancel_token.clone());
+            if let Some(repo_map) = &repo_map {
+                reviewer = reviewer.with_repo_map(repo_map.clone());
+            }
+            reviewer
+        };
+        let reviewer = Arc::new(build_reviewer(
+            reviewer_provider.clone(),
+            reviewer_model.clone(),
         ));
+        let reviewer_ladder = (!self.review_ladder.is_empty()).then(|| {
+            let ladder = self
+                .review_ladder
+                .iter()
+                .fold(ModelLadder::new(), |ladder, rung| {
+                    let reviewer = build_reviewer(
+                        rung.provider.clone().or(reviewer_provider.clone()),
+                        rung.model.clone().or(reviewer_model.clone()),
+                    );
+                    ladder.with_rung(reviewer.model_label(), Arc::new(reviewer))
+                });
+            Arc::new(ladder)
+        });
 
-        // Check for existing state (resume scenario)
-        let existing_state = OrchestrationState::load(&session_dir)?;
+        // Usage is priced by the model each role actually resolves to
+        // (override > recipe setting > default)
+        let planner_label = planner.model_label();
+        let reviewer_label = reviewer.model_label();
+
+        // Fallback providers: unset fields keep the role's provider/model
+        let fallback_planners: Vec<(String, Arc<GoosePlanner>)> = self
+            .retry_config
+            .fallbacks
+            .iter()
+            .map(|fallback| {
+                let planner = build_planner(
+                    fallback.provider.clone().or(planner_provider.clone()),
+                    fallback.model.clone().or(planner_model.clone()),
+                );
+                (planner.model_label(), Arc::new(planner))
+            })
+            .collect();
+        let fallback_reviewers: Vec<(String, Arc<GooseReviewer>)> = self
+            .retry_config
+            .fallbacks
+            .iter()
+            .map(|fallback| {
+                let reviewer = build_reviewer(
+                    fallback.provider.clone().or(reviewer_provider.clone()),
+                    fallback.model.clone().or(reviewer_model.clone()),
+                );
+                (reviewer.model_label(), Arc::new(reviewer))
+            })
+            .collect();
 
-        // Get or create session state
-        let working_dir_path = working_dir.unwrap_or_else(|| self.base_dir.clone());
+        let review_panel = if self.review_panel_config.reviewers.is_empty() {
+            None
+        } else {
+            let mut panel = ReviewPanel::new(self.review_panel_config.aggregation, score_threshold);
+            for member in &self.review_panel_config.reviewers {
+                let mut member_reviewer = GooseReviewer::new(
+                    crate::config::ReviewConfig {
+                        recipe: member.recipe.clone(),
+                        provider_override: member.provider.clone(),
+                        model_override: member.model.clone(),
+                        ladder: Vec::new(),
+                    },
+                    self.base_dir.clone(),
+                    score_threshold,
+                )
+                .with_cancel_token(self.cancel_token.clone());
+                if let Some(repo_map) = &repo_map {
+                    member_reviewer = member_reviewer.with_repo_map(repo_map.clone());
+                }
+                panel = panel.with_reviewer(member.name.clone(), Arc::new(member_reviewer));
+            }
+            info!("Review panel enabled with {} reviewers", panel.len());
+            Some(Arc::new(panel))
+        };
 
-        let initial_state = if let Some(state) = existing_state {
-            // Check if we can resume
-            if !state.can_resume() {
-                return Err(anyhow::anyhow!(
-                    "Cannot resume session in {:?} state",
-                    state.status
-                ));
+        let specialists = if self.review_dimensions_config.dimensions.is_empty() {
+            None
+        } else {
+            let mut specialists =
+                SpecialistReviews::new(self.review_dimensions_config.primary_weight);
+            for dimension in &self.review_dimensions_config.dimensions {
+                // Bundled fallback is looked up by file stem (e.g., "review-security")
+                let recipe_name = dimension
+                    .recipe
+                    .file_stem()
+                    .and_then(|s| s.to_str())
+                    .unwrap_or("reviewer")
+                    .to_string();
+                let mut pass = GooseReviewer::new(
+                    crate::config::ReviewConfig {
+                        recipe: dimension.recipe.clone(),
+                        provider_override: dimension.provider.clone(),
+                        model_override: dimension.model.clone(),
+                        ladder: Vec::new(),
+                    },
+                    self.base_dir.clone(),
+                    dimension.min_score.unwrap_or(score_threshold),
+                )
+                .with_recipe_name(recipe_name)
+                .with_focus(dimension.name.clone())
+                .with_cancel_token(self.cancel_token.clone());
+                if let Some(repo_map) = &repo_map {
+                    pass = pass.with_repo_map(repo_map.clone());
+                }
+                specialists = specialists.with_pass(dimension.clone(), Arc::new(pass));
             }
             info!(
-                "Resuming orchestrator session from iteration {}",
-                state.iteration
+                "Specialist review enabled with {} dimensions",
+                specialists.len()
             );
-            state
-        } else {
+            Some(Arc::new(specialists))
+        };
+
+        // Check for existing state (resume scenario)
+        let existing_state = OrchestrationState::load(&session_dir)?;
+
+        // Per-iteration usage of the other sessions, for budget projections
+        let forecast = Arc::new(
+            session_dir
+                .parent()
+                .map(|runs_root| Forecast::from_runs_dir(runs_root, Some(&session_dir)))
+                .unwrap_or_default(),
+        );
+        if !forecast.is_empty() {
+            info!(
+                "Budget forecast from {} past sessions ({} iterations)",
+                forecast.sessions, forecast.iterations
+            );
+        }
+
+        // Get or create session state
+        let mut viability = ViabilityChecker::new()
+            .with_custom_opcodes(self.custom_opcodes.clone())
+            .with_command_safety(self.command_safety.clone())
+            .with_runbook_config(self.runbook_config.clone())
+            .with_working_dir(working_dir_path.clone());
+        if self.repo_map_config.verify_symbols
+            && let Some(index) = symbol_index
+        {
+            viability = viability.with_symbol_index(index);
+        }
+        if let Some(search) = &code_search {
+            viability = viability.with_code_search(search.clone());
+        }
+
+        let resuming = existing_state.is_some();
+        let initial_state = existing_state.unwrap_or_else(|| {
             OrchestrationState::new(
                 session_id.clone(),
                 task.clone(),
                 working_dir_path.clone(),
                 task_slug.clone(),
             )
-        };
+        });
 
-        // Get or create session state in registry
+        // Get or create session state in registry. A session still registered
+        // from an earlier run in this process is used instead of the state
+        // loaded from disk, so resume checks apply to the registry copy.
         let session_state = self
             .session_registry
             .get_or_create(&session_id, initial_state)
             .await;
+        {
+            let mut state = session_state.lock().await;
+            state.resume()?;
+            if resuming {
+                info!(
+                    "Resuming orchestrator session from iteration {}",
+                    state.iteration
+                );
+            }
+            // Only running time counts toward the session time limit
+            state.start_run();
+        }
 
         // Handle human response if provided
         if let Some(hr) = human_response {
@@ -225,13 +674,370 @@ impl GooseOrchestrator {
             }
         }
 
-        // Load orchestrator recipe and create provider (shared across iterations)
-        let recipe = load_recipe(&self.config.recipe, &self.base_dir, "orchestrator")?;
-        let provider_config = ProviderConfig::for_orchestrator(
-            self.config.provider_override.as_deref(),
-            self.config.model_override.as_deref(),
+        // Tools share one session-scoped client configuration in both modes
+        let make_client = || {
+            let mut client = create_orchestrator_client(
+                session_id.clone(),
+                session_dir.clone(),
+                session_state.clone(),
+                guardrails.clone(),
+                planner.clone(),
+                reviewer.clone(),
+            )
+            .with_viability_checker(viability.clone())
+            .with_retry_policy(RetryPolicy::from_config(&self.retry_config))
+            .with_fallback_planners(fallback_planners.clone())
+            .with_fallback_reviewers(fallback_reviewers.clone())
+            .with_model_labels(planner_label.clone(), reviewer_label.clone())
+            .with_forecast(forecast.clone())
+            .with_cancel_token(self.cancel_token.clone())
+            .with_events(self.events.clone());
+            if let Some(ladder) = &planner_ladder {
+                client = client
+                    .with_planner_ladder(ladder.clone())
+                    .with_escalation_thresholds(
+                        self.planning_ladder.escalate_after_viability_failures,
+                        self.planning_ladder.escalate_after_stalled_reviews,
+                    );
+            }
+            if let Some(ladder) = &reviewer_ladder {
+                client = client.with_reviewer_ladder(ladder.clone());
+            }
+            if let Some(candidates) = &candidates {
+                client = client.with_candidates(candidates.clone());
+            }
+            if let Some(panel) = &review_panel {
+                client = client.with_review_panel(panel.clone());
+            }
+            if let Some(specialists) = &specialists {
+                client = client.with_specialist_reviews(specialists.clone());
+            }
+            if self.config.auto_fix {
+                let mut auto_fixer = PlanAutoFixer::new();
+                if let Some(search) = &code_search {
+                    auto_fixer = auto_fixer.with_code_search(search.clone());
+                }
+                client = client.with_auto_fixer(auto_fixer);
+            }
+            if self.gates_config.enabled {
+                client = client.with_gate_runner(
+                    GateRunner::from_config(working_dir_path.clone(), &self.gates_config)
+                        .with_allow_commands(self.allow_gate_commands),
+                );
+            }
+            client
+        };
+
+        // Session event log (events.jsonl) for the rest of the run
+        let event_log = EventLog::spawn(&session_dir, self.events.subscribe());
+
+        let outcome = match self.config.mode {
+            OrchestratorMode::Llm => {
+                self.run_agent_loop(
+                    &task,
+                    &session_dir,
+                    &working_dir_path,
+                    &session_state,
+                    &make_client,
+                )
+                .await
+            }
+            OrchestratorMode::Deterministic => {
+                info!("Running deterministic orchestration (no orchestrator LLM)");
+                DeterministicDriver::new(
+                    make_client(),
+                    session_state.clone(),
+                    session_dir.clone(),
+                    Duration::from_secs(self.guardrails_config.execution_timeout_secs),
+                )
+                .run()
+                .await
+            }
+        };
+
+        session_state.lock().await.end_run();
+
+        // Errors caused by interrupting a stream are expected on cancellation;
+        // the session stays resumable instead of failing
+        if self.cancel_token.is_cancelled() {
+            let mut state = session_state.lock().await;
+            if matches!(
+                state.status,
+                OrchestrationStatus::Running | OrchestrationStatus::Failed { .. }
+            ) {
+                state.status = OrchestrationStatus::Cancelled;
+            }
+            if let Err(e) = outcome {
+                debug!("Session cancelled: {:#}", e);
+            }
+            info!("Orchestrator session {} cancelled", session_id);
+        } else if let Err(e) = outcome {
+            let state = session_state.lock().await;
+            if let Err(save_err) = state.save(&session_dir) {
+                warn!("Failed to save state after error: {:#}", save_err);
+            }
+            if let Some(event) = OrchestratorEvent::terminal(&state) {
+                let _ = self.events.send(event);
+            }
+            drop(state);
+            self.session_registry.remove(&session_id).await;
+            event_log.finish().await;
+            return Err(e);
+        }
+
+        // Token tracking now happens per-iteration inside the loop
+        // Check if we got any token data (for warning about estimation)
+        {
+            let state = session_state.lock().await;
+            if state.token_breakdown.estimated {
+                warn!(
+                    "Token tracking not available from provider - budget enforcement may be inaccurate"
+                );
+            }
+        }
+
+        // Read final state and save
+        let final_state = {
+            let state = session_state.lock().await;
+            state.clone()
+        };
+        final_state.save(&session_dir)?;
+        // The next run reloads the saved state
+        self.session_registry.remove(&session_id).await;
+
+        // Write plan to active_dir for completed, best-effort, and paused states
+        // - Completed: Final approved plan (passed review)
+        // - CompletedBestEffort: Best plan seen (did not pass review threshold)
+        // - Paused: Draft plan for user review before providing feedback
+        // - Cancelled: Draft of the latest plan, so interrupted work is kept
+        let should_write_plan = matches!(
+            final_state.status,
+            OrchestrationStatus::Completed
+                | OrchestrationStatus::CompletedBestEffort
+                | OrchestrationStatus::Paused { .. }
+                | OrchestrationStatus::Cancelled
         );
-        let provider = create_provider(&provider_config, &recipe).await?;
+
+        if should_write_plan {
+            // Use best_plan for CompletedBestEffort, otherwise current_plan
+            let plan_to_write =
+                if matches!(final_state.status, OrchestrationStatus::CompletedBestEffort) {
+                    final_state
+                        .best_plan
+                        .as_ref()
+                        .or(final_state.current_plan.as_ref())
+                } else if matches!(final_state.status, OrchestrationStatus::Cancelled) {
+                    final_state
+                        .current_plan
+                        .as_ref()
+                        .or(final_state.best_plan.as_ref())
+                } else {
+                    final_state.current_plan.as_ref()
+                };
+
+            if let Some(plan_json) = plan_to_write {
+                match serde_json::from_value::<Plan>(plan_json.clone()) {
+                    Ok(plan) => {
+                        let output_config = OutputConfig {
+                            runs_dir: session_dir.clone(),
+                            active_dir: self.output_config.active_dir.clone(),
+                            slug: Some(final_state.task_slug.clone()),
+                        };
+                        // Dimension scores from the review of the plan being written
+                        let review = if matches!(
+                            final_state.status,
+                            OrchestrationStatus::CompletedBestEffort
+                        ) {
+                            final_state.reviews.iter().max_by(|a, b| {
+                                let score = |r: &Value| {
+                                    r.get("score").and_then(|s| s.as_f64()).unwrap_or(0.0)
+                                };
+                                score(a).total_cmp(&score(b))
+                            })
+                        } else {
+                            final_state.current_plan_review()
+                        };
+                        let review_dimensions = review
+                            .and_then(|r| r.get("dimensions"))
+                            .and_then(|d| serde_json::from_value(d.clone()).ok())
+                            .unwrap_or_default();
+                        let output = FileOutputWriter::new(output_config)
+                            .with_schedule_config(self.schedule_config.clone())
+                            .with_viability_checker(viability.clone())
+                            .with_review_dimensions(review_dimensions);
+
+                        // Determine status for output
+                        let output_status = match &final_state.status {
+                            OrchestrationStatus::Completed => crate::output::PlanStatus::Approved,
+                            OrchestrationStatus::CompletedBestEffort => {
+                                crate::output::PlanStatus::BestEffort {
+                                    score: final_state.best_score,
+                                }
+                            }
+                            OrchestrationStatus::Paused { .. } | OrchestrationStatus::Cancelled => {
+                                crate::output::PlanStatus::Draft
+                            }
+                            _ => crate::output::PlanStatus::Draft,
+                        };
+
+                        if let Err(e) = output
+                            .write_final_with_plan_status(&plan, output_status)
+                            .await
+                        {
+                            warn!(
+                                "Failed to write plan to {}: {}",
+                                self.output_config.active_dir.display(),
+                                e
+                            );
+                        }
+                    }
+                    Err(e) => {
+                        warn!("Failed to parse plan JSON for output: {}", e);
+                    }
+                }
+            }
+        }
+
+        if let Some(event) = OrchestratorEvent::terminal(&final_state) {
+            let _ = self.events.send(event);
+        }
+        event_log.finish().await;
+
+        info!(
+            "Orchestrator session complete: status={:?}, iterations={}, tokens={}",
+            final_state.status, final_state.iteration, final_state.total_tokens
+        );
+
+        // Log warning if orchestrator overhead is high
+        let overhead = final_state.token_breakdown.overhead_ratio();
+        if overhead > 0.2 {
+            warn!(
+                "High orchestrator overhead: {:.1}% of total tokens used by orchestrator agent",
+                overhead * 100.0
+            );
+        }
+
+        Ok(OrchestrationResult {
+            final_plan: final_state.current_plan,
+            best_plan: final_state.best_plan,
+            best_score: final_state.best_score,
+            status: final_state.status,
+            iterations: final_state.iteration,
+            tool_calls: final_state.tool_calls,
+            total_tokens: final_state.total_tokens,
+            session_id,
+            token_breakdown: final_state.token_breakdown,
+        })
+    }
+
+    /// Orchestrator providers for an escalation rung: the rung itself, then
+    /// each configured fallback.
+    ///
+    /// Unset rung fields (or an empty ladder) fall back to the overrides;
+    /// unset fallback fields keep the rung's provider/model. Labels name the
+    /// model each target resolves to with `recipe`, for pricing.
+    fn orchestrator_chain(
+        &self,
+        rung: u32,
+        recipe: &goose::recipe::Recipe,
+    ) -> Vec<(String, ModelRung)> {
+        let rung = rung_config(&self.config.ladder, rung);
+        let primary = ModelRung {
+            provider: rung
+                .and_then(|r| r.provider.clone())
+                .or(self.config.provider_override.clone()),
+            model: rung
+                .and_then(|r| r.model.clone())
+                .or(self.config.model_override.clone()),
+        };
+        let fallbacks = self
+            .retry_config
+            .fallbacks
+            .iter()
+            .map(|fallback| ModelRung {
+                provider: fallback.provider.clone().or(primary.provider.clone()),
+                model: fallback.model.clone().or(primary.model.clone()),
+            });
+        std::iter::once(primary.clone())
+            .chain(fallbacks)
+            .map(|target| {
+                let provider_config = ProviderConfig::for_orchestrator(
+                    target.provider.as_deref(),
+                    target.model.as_deref(),
+                );
+                (resolve_model_label(&provider_config, Some(recipe)), target)
+            })
+            .collect()
+    }
+
+    /// Create the first working orchestrator provider in `chain[start..]`,
+    /// retrying transient failures. Returns the chain index used; failed
+    /// attempts are recorded in the session state.
+    async fn connect_orchestrator(
+        &self,
+        chain: &[(String, ModelRung)],
+        start: usize,
+        recipe: &goose::recipe::Recipe,
+        session_state: &Arc<Mutex<OrchestrationState>>,
+    ) -> Result<(usize, Arc<dyn Provider>)> {
+        if let Some(provider) = &self.provider {
+            return Ok((start, provider.clone()));
+        }
+        let targets: Vec<(String, (usize, ModelRung))> = chain
+            .iter()
+            .enumerate()
+            .skip(start)
+            .map(|(index, (label, target))| (label.clone(), (index, target.clone())))
+            .collect();
+        let mut retries = Vec::new();
+        let result = call_with_retry(
+            &RetryPolicy::from_config(&self.retry_config)
+                .with_cancel_token(self.cancel_token.clone()),
+            "orchestrator",
+            &targets,
+            move |(index, target)| async move {
+                let provider_config = ProviderConfig::for_orchestrator(
+                    target.provider.as_deref(),
+                    target.model.as_deref(),
+                );
+                create_provider(&provider_config, recipe)
+                    .await
+                    .map(|provider| (index, provider))
+            },
+            &mut retries,
+        )
+        .await;
+        session_state.lock().await.retries.extend(retries);
+        result
+    }
+
+    /// Drive the session with a fresh orchestrator agent per iteration.
+    ///
+    /// Each agent sees an explicit context message and chooses one action via
+    /// the orchestrator tools. Returns once the session reaches a terminal
+    /// state; agent errors and timeouts are saved to state and returned.
+    async fn run_agent_loop(
+        &self,
+        task: &str,
+        session_dir: &Path,
+        working_dir_path: &Path,
+        session_state: &Arc<Mutex<OrchestrationState>>,
+        make_client: &dyn Fn() -> OrchestratorClient,
+    ) -> Result<()> {
+        // Load orchestrator recipe and create provider (shared across
+        // iterations until the escalation rung changes)
+        let recipe = load_recipe(&self.config.recipe, &self.base_dir, "orchestrator")?;
+        let retry = RetryPolicy::from_config(&self.retry_config)
+            .with_cancel_token(self.cancel_token.clone());
+        let guardrails = Guardrails::from_config(&self.guardrails_config);
+        let top_rung = self.config.ladder.len().saturating_sub(1) as u32;
+        let mut provider_rung = session_state.lock().await.model_rung.min(top_rung);
+        let mut chain = self.orchestrator_chain(provider_rung, &recipe);
+        let (mut provider_index, mut provider) = self
+            .connect_orchestrator(&chain, 0, &recipe, session_state)
+            .await?;
+        // Consecutive agent failures on the current provider
+        let mut failures: u32 = 0;
 
         // Run agent with timeout
         let timeout_duration = Duration::from_secs(self.guardrails_config.execution_timeout_secs);
@@ -239,13 +1045,27 @@ impl GooseOrchestrator {
 
         // Track sessions created for token accounting
         let mut iteration_sessions: Vec<String> = Vec::new();
+        // Orchestrator tokens of turns that didn't record an iteration yet
+        let mut unrecorded_orchestrator_tokens: u64 = 0;
+        // Why the last forced finalization was rejected, for the next turn
+        let mut finalize_feedback: Vec<String> = Vec::new();
 
         // Stateless iteration loop: create fresh agent per iteration
         loop {
+            // 0. Stop between iterations once cancelled; run() saves the state
+            if self.cancel_token.is_cancelled() {
+                session_state.lock().await.status = OrchestrationStatus::Cancelled;
+                return Ok(());
+            }
+
             // 1. Check iteration limit BEFORE creating agent
-            let current_iteration = {
+            let (current_iteration, rung, history_len) = {
                 let state = session_state.lock().await;
-                state.iteration
+                (
+                    state.iteration,
+                    state.model_rung.min(top_rung),
+                    state.iteration_history.len(),
+                )
             };
 
             if current_iteration >= max_iterations {
@@ -260,13 +1080,38 @@ impl GooseOrchestrator {
                         limit: max_iterations,
                     },
                 };
-                break;
+                return Ok(());
+            }
+
+            // Wall-clock, cost, and orchestrator token budgets also cover
+            // orchestrator turns
+            {
+                let mut state = session_state.lock().await;
+                if let Err(hard_stop) = guardrails
+                    .check_budgets(&state)
+                    .and_then(|_| guardrails.check_token_budget(&state, TokenRole::Orchestrator))
+                {
+                    warn!("Hard stop before orchestrator turn: {:?}", hard_stop);
+                    state.status = OrchestrationStatus::HardStopped { reason: hard_stop };
+                    return Ok(());
+                }
+            }
+
+            // Follow the planner up the escalation ladder
+            if rung != provider_rung {
+                chain = self.orchestrator_chain(rung, &recipe);
+                (provider_index, provider) = self
+                    .connect_orchestrator(&chain, 0, &recipe, session_state)
+                    .await?;
+                provider_rung = rung;
+                failures = 0;
+                info!("Orchestrator escalated to rung {}", rung);
             }
 
             // 2. Create FRESH agent for this iteration
             let agent = Agent::new();
             let iteration_session = SessionManager::create_session(
-                working_dir_path.clone(),
+                working_dir_path.to_path_buf(),
                 format!("orchestrator-iter-{}", current_iteration),
                 SessionType::Hidden,
             )
@@ -276,24 +1121,18 @@ impl GooseOrchestrator {
             let iteration_session_id = iteration_session.id.clone();
             iteration_sessions.push(iteration_session_id.clone());
 
-            // Apply provider and recipe instructions
+            // Apply provider (recording failures the agent turns into text)
+            // and recipe instructions
+            let probe = FailureProbe::new(provider.clone());
             agent
-                .update_provider(provider.clone(), &iteration_session_id)
+                .update_provider(probe.clone(), &iteration_session_id)
                 .await?;
             if let Some(instructions) = &recipe.instructions {
                 agent.override_system_prompt(instructions.clone()).await;
             }
 
             // Register orchestrator extension for this fresh agent
-            let orchestrator_client = create_orchestrator_client(
-                session_id.clone(),
-                session_dir.clone(),
-                session_state.clone(),
-                guardrails.clone(),
-                planner.clone(),
-                reviewer.clone(),
-            );
-            register_orchestrator_extension(&agent.extension_manager, orchestrator_client).await;
+            register_orchestrator_extension(&agent.extension_manager, make_client()).await;
 
             info!(
                 "Iteration {}: Created fresh agent with session {}",
@@ -302,7 +1141,7 @@ impl GooseOrchestrator {
 
             // 3. Build EXPLICIT context message (no conversation history dependency)
             let context_message = self
-                .build_iteration_context(&task, &session_state, max_iterations)
+                .build_iteration_context(task, session_state, max_iterations, &finalize_feedback)
                 .await;
             let user_message = Message::user().with_text(&context_message);
 
@@ -316,35 +1155,39 @@ impl GooseOrchestrator {
             // 4. Run agent ONCE for this iteration
             let stream_result = tokio::time::timeout(
                 timeout_duration,
-                agent.reply(user_message, session_config, None),
+                agent.reply(
+                    user_message,
+                    session_config,
+                    Some(self.cancel_token.clone()),
+                ),
             )
             .await;
 
-            let stream = match stream_result {
-                Ok(Ok(s)) => s,
-                Ok(Err(e)) => {
-                    // Agent error - save state and return
-                    let mut state = session_state.lock().await;
-                    state.status = OrchestrationStatus::Failed {
-                        error: format!("Agent error: {}", e),
-                    };
-                    state.save(&session_dir)?;
-                    return Err(e);
+            // 5. Process stream (tools update shared state via OrchestratorClient).
+            // A provider or stream error before any tool call counts as a
+            // provider failure.
+            let (got_tool_call, failure) = match stream_result {
+                Ok(Ok(stream)) => {
+                    let (got_tool_call, stream_error) = self.process_agent_stream(stream).await?;
+                    let failure = match probe.take_failure() {
+                        Some(failure) => Some(anyhow::Error::from(failure)),
+                        None => stream_error.map(|e| e.context("Stream error")),
+                    }
+                    .filter(|_| !got_tool_call);
+                    (got_tool_call, failure)
                 }
+                Ok(Err(e)) => (false, Some(e)),
                 Err(_) => {
                     // Timeout - save state with hard stop
                     let mut state = session_state.lock().await;
                     state.status = OrchestrationStatus::HardStopped {
                         reason: GuardrailHardStop::ExecutionTimeout,
                     };
-                    state.save(&session_dir)?;
+                    state.save(session_dir)?;
                     return Err(anyhow::anyhow!("Orchestrator execution timeout"));
                 }
             };
 
-            // 5. Process stream (tools update shared state via OrchestratorClient)
-            let got_tool_call = self.process_agent_stream(stream).await?;
-
             // Track tokens from this iteration's session
             if let Ok(sess) = SessionManager::get_session(&iteration_session_id, false).await {
                 let mut state = session_state.lock().await;
@@ -362,18 +1205,141 @@ impl GooseOrchestrator {
                     .map(|t| t.max(0) as u64)
                     .unwrap_or(0);
                 state.token_breakdown.add_orchestrator(input, output);
+                state.add_model_usage(
+                    &chain[provider_index].0,
+                    sess.accumulated_input_tokens,
+                    sess.accumulated_output_tokens,
+                );
+
+                // Attribute the turn to the iteration it recorded, if any
+                unrecorded_orchestrator_tokens += input + output;
+                if state.iteration_history.len() > history_len
+                    && let Some(record) = state.iteration_history.last_mut()
+                {
+                    record.role_tokens.get_or_insert_default().orchestrator +=
+                        unrecorded_orchestrator_tokens;
+                    unrecorded_orchestrator_tokens = 0;
+                }
             }
 
-            // 6. DETERMINISTIC FINALIZATION: Force completion when review passed
-            // This is critical because the LLM may not always call finalize even when instructed to.
-            // We enforce this deterministically to prevent wasted iterations after a passing review.
-            {
+            // Cancellation interrupts the stream mid-iteration; tokens are
+            // already recorded and the iteration is redone on resume
+            if self.cancel_token.is_cancelled() {
                 let mut state = session_state.lock().await;
-                if state.last_review_passed && !state.requires_human_input_pending {
-                    info!(
-                        "Review passed (score passed threshold) - forcing completion (deterministic finalization)"
-                    );
-                    state.status = OrchestrationStatus::Completed;
+                if matches!(state.status, OrchestrationStatus::Running) {
+                    state.status = OrchestrationStatus::Cancelled;
+                }
+                return Ok(());
+            }
+
+            // Retry transient agent failures with a fresh agent, then fall
+            // back to the next provider; fatal errors fail the session
+            if let Some(error) = failure {
+                failures += 1;
+                let class = classify_error(&error);
+                let retry_same = class == ErrorClass::Retryable && failures < retry.max_attempts;
+                let delay = if retry_same {
+                    retry.delay(failures, jitter_sample())
+                } else {
+                    Duration::ZERO
+                };
+                warn!(
+                    "Orchestrator call via {} failed (attempt {}/{}, {:?}): {:#}",
+                    chain[provider_index].0, failures, retry.max_attempts, class, error
+                );
+                session_state.lock().await.retries.push(RetryRecord {
+                    component: "orchestrator".to_string(),
+                    target: chain[provider_index].0.clone(),
+                    fallback: provider_index > 0,
+                    attempt: failures,
+                    class,
+                    error: format!("{:#}", error),
+                    delay_ms: delay.as_millis() as u64,
+                    timestamp: chrono::Utc::now().to_rfc3339(),
+                });
+
+                if retry_same {
+                    // Cancelled during the backoff: the next pass stops the loop
+                    retry.backoff(delay).await;
+                    continue;
+                }
+                if class == ErrorClass::Retryable && provider_index + 1 < chain.len() {
+                    match self
+                        .connect_orchestrator(&chain, provider_index + 1, &recipe, session_state)
+                        .await
+                    {
+                        Ok((index, next)) => {
+                            info!("Orchestrator falling back to {}", chain[index].0);
+                            provider_index = index;
+                            provider = next;
+                            failures = 0;
+                            continue;
+                        }
+                        Err(e) => warn!("Orchestrator fallback failed: {:#}", e),
+                    }
+                }
+
+                // Agent error - save state and return
+                let mut state = session_state.lock().await;
+                state.status = OrchestrationStatus::Failed {
+                    error: format!("Agent error: {}", error),
+                };
+                state.save(session_dir)?;
+                return Err(error);
+            }
+            failures = 0;
+
+            if got_tool_call {
+                finalize_feedback.clear();
+            }
+
+            // 6. DETERMINISTIC FINALIZATION: Finalize when review passed
+            // This is critical because the LLM may not always call finalize even when instructed to.
+            // We finalize on its behalf through the client so grounding gates still run; a
+            // rejection resets the review and its feedback goes into the next iteration.
+            let plan_to_finalize = {
+                let state = session_state.lock().await;
+                let pending = matches!(state.status, OrchestrationStatus::Running)
+                    && state.last_review_passed
+                    && !state.requires_human_input_pending;
+                state.current_plan.clone().filter(|_| pending)
+            };
+            if let Some(plan) = plan_to_finalize {
+                info!(
+                    "Review passed (score passed threshold) - finalizing (deterministic finalization)"
+                );
+                match make_client()
+                    .invoke("finalize", serde_json::json!({ "plan_json": plan }))
+                    .await
+                {
+                    Ok(response)
+                        if response.get("status").and_then(|s| s.as_str()) == Some("rejected") =>
+                    {
+                        warn!(
+                            "Deterministic finalization rejected: {}",
+                            response
+                                .get("message")
+                                .and_then(|m| m.as_str())
+                                .unwrap_or("(no message)")
+                        );
+                        finalize_feedback = review_feedback(&response);
+                    }
+                    Ok(_) => {}
+                    Err(e) => {
+                        let mut state = session_state.lock().await;
+                        if !matches!(
+                            state.status,
+                            OrchestrationStatus::HardStopped { .. }
+                                | OrchestrationStatus::Cancelled
+                        ) {
+                            state.status = OrchestrationStatus::Failed {
+                                error: e.to_string(),
+                            };
+                            state.save(session_dir)?;
+                            return Err(e);
+                        }
+                        warn!("{}", e);
+                    }
                 }
             }
 
@@ -383,15 +1349,17 @@ impl GooseOrchestrator {
                 matches!(
                     state.status,
                     OrchestrationStatus::Completed
+                        | OrchestrationStatus::CompletedBestEffort
                         | OrchestrationStatus::Paused { .. }
                         | OrchestrationStatus::HardStopped { .. }
                         | OrchestrationStatus::Failed { .. }
+                        | OrchestrationStatus::Cancelled
                 )
             };
 
             if is_terminal {
                 info!("Orchestrator reached terminal state");
-                break;
+                return Ok(());
             }
 
             // Handle text-only response (no tool calls) - record and continue
@@ -412,6 +1380,10 @@ impl GooseOrchestrator {
                     review_passed: None,
                     tool_calls_this_iteration: 0,
                     tokens_this_iteration: 0,
+                    reviewer_scores: Vec::new(),
+                    critical_rule_ids: Vec::new(),
+                    rung: state.model_rung,
+                    role_tokens: None,
                     outcome: IterationOutcome::TextResponseDetected,
                 };
                 state.iteration_history.push(record);
@@ -420,113 +1392,6 @@ impl GooseOrchestrator {
 
             // No continuation prompt needed - next iteration creates fresh agent with full context
         }
-
-        // Token tracking now happens per-iteration inside the loop
-        // Check if we got any token data (for warning about estimation)
-        {
-            let state = session_state.lock().await;
-            if state.token_breakdown.estimated {
-                warn!(
-                    "Token tracking not available from provider - budget enforcement may be inaccurate"
-                );
-            }
-        }
-
-        // Read final state and save
-        let final_state = {
-            let state = session_state.lock().await;
-            state.clone()
-        };
-        final_state.save(&session_dir)?;
-
-        // Write plan to active_dir for completed, best-effort, and paused states
-        // - Completed: Final approved plan (passed review)
-        // - CompletedBestEffort: Best plan seen (did not pass review threshold)
-        // - Paused: Draft plan for user review before providing feedback
-        let should_write_plan = matches!(
-            final_state.status,
-            OrchestrationStatus::Completed
-                | OrchestrationStatus::CompletedBestEffort
-                | OrchestrationStatus::Paused { .. }
-        );
-
-        if should_write_plan {
-            // Use best_plan for CompletedBestEffort, otherwise current_plan
-            let plan_to_write =
-                if matches!(final_state.status, OrchestrationStatus::CompletedBestEffort) {
-                    final_state
-                        .best_plan
-                        .as_ref()
-                        .or(final_state.current_plan.as_ref())
-                } else {
-                    final_state.current_plan.as_ref()
-                };
-
-            if let Some(plan_json) = plan_to_write {
-                match serde_json::from_value::<Plan>(plan_json.clone()) {
-                    Ok(plan) => {
-                        let output_config = OutputConfig {
-                            runs_dir: session_dir.clone(),
-                            active_dir: self.output_config.active_dir.clone(),
-                            slug: Some(final_state.task_slug.clone()),
-                        };
-                        let output = FileOutputWriter::new(output_config);
-
-                        // Determine status for output
-                        let output_status = match &final_state.status {
-                            OrchestrationStatus::Completed => crate::output::PlanStatus::Approved,
-                            OrchestrationStatus::CompletedBestEffort => {
-                                crate::output::PlanStatus::BestEffort {
-                                    score: final_state.best_score,
-                                }
-                            }
-                            OrchestrationStatus::Paused { .. } => crate::output::PlanStatus::Draft,
-                            _ => crate::output::PlanStatus::Draft,
-                        };
-
-                        if let Err(e) = output
-                            .write_final_with_plan_status(&plan, output_status)
-                            .await
-                        {
-                            warn!(
-                                "Failed to write plan to {}: {}",
-                                self.output_config.active_dir.display(),
-                                e
-                            );
-                        }
-                    }
-                    Err(e) => {
-                        warn!("Failed to parse plan JSON for output: {}", e);
-                    }
-                }
-            }
-        }
-
-        info!(
-            "Orchestrator session complete: status={:?}, iterations={}, tokens={}",
-            final_state.status, final_state.iteration, final_state.total_tokens
-        );
-
-        // Log warning if orchestrator overhead is high
-        let overhead = final_state.token_breakdown.overhead_ratio();
-        if overhead > 0.2 {
-            warn!(
-                "High orchestrator overhead: {:.1}% of total tokens used by orchestrator agent",
-                overhead * 100.0
-            );
-        }
-
-        Ok(OrchestrationResult {
-            final_plan: final_state.current_plan,
-            best_plan: final_state.best_plan,
-            best_score: final_state.best_score,
-            status: final_state.status,
-            iterations: final_state.iteration,
-            tool_calls: final_state.tool_calls,
-            total_tokens: final_state.total_tokens,
-            session_id,
-            token_breakdown: final_state.token_breakdown,
-        })
     }
 
     /// Build comprehensive context message for a single orchestrator iteration.
@@ -540,6 +1405,7 @@ impl GooseOrchestrator {
         task: &str,
         session_state: &tokio::sync::Mutex<OrchestrationState>,
         max_iterations: u32,
+        finalize_feedback: &[String],
     ) -> String {
         let state = session_state.lock().await;
 
@@ -609,6 +1475,20 @@ impl GooseOrchestrator {
             ""
         };
 
+        // A passing plan whose finalization was rejected (e.g., failed gates)
+        let finalize_context = if finalize_feedback.is_empty() {
+            String::new()
+        } else {
+            format!(
+                "\n## Finalization Rejected\nThe last passing plan could not be finalized, so the review no longer counts as passed. Call `plan-forge-orchestrator__generate_plan` with this feedback:\n{}",
+                finalize_feedback
+                    .iter()
+                    .map(|f| format!("- {}", f))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            )
+        };
+
         format!(
             r#"## Task
 {task}
@@ -619,7 +1499,7 @@ Tokens used: {tokens}
 Tool calls: {tool_calls}
 Status: {status:?}
 Needs review: {needs_review}
-{human_input_context}{pending_context}
+{human_input_context}{pending_context}{finalize_context}
 
 ## Iteration History (recent)
 {iteration_history}
@@ -645,6 +1525,7 @@ IMPORTANT: Respond ONLY with tool calls. Make your decision now."#,
             needs_review = state.needs_review,
             human_input_context = human_input_context,
             pending_context = pending_context,
+            finalize_context = finalize_context,
             iteration_history = iteration_history,
             last_review_summary = last_review_summary,
         )
@@ -652,14 +1533,18 @@ IMPORTANT: Respond ONLY with tool calls. Make your decision now."#,
 
     /// Process the agent event stream for a single iteration.
     ///
-    /// Returns whether any tool calls were made during this iteration.
-    /// Tool responses are handled by the OrchestratorClient extension.
-    async fn process_agent_stream<S, E>(&self, mut stream: S) -> Result<bool>
+    /// Returns whether any tool calls were made during this iteration and the
+    /// last stream error, if any. Tool responses are handled by the OrchestratorClient extension.
+    async fn process_agent_stream<S, E>(
+        &self,
+        mut stream: S,
+    ) -> Result<(bool, Option<anyhow::Error>)>
     where
         S: futures::Stream<Item = Result<AgentEvent, E>> + Unpin,
-        E: std::fmt::Debug,
+        E: Into<anyhow::Error>,
     {
         let mut got_tool_call = false;
+        let mut last_error = None;
 
         while let Some(event) = stream.next().await {
             match event {
@@ -683,12 +1568,14 @@ IMPORTANT: Respond ONLY with tool calls. Make your decision now."#,
                     // Other events (McpNotification, ModelChange, HistoryReplaced)
                 }
                 Err(e) => {
-                    warn!("Stream error: {:?}", e);
+                    let e = e.into();
+                    warn!("Stream error: {:#}", e);
+                    last_error = Some(e);
                 }
             }
         }
 
-        Ok(got_tool_call)
+        Ok((got_tool_call, last_error))
     }
 }
 
@@ -773,6 +1660,7 @@ impl From<OrchestrationResult> for LoopResult {
                 },
                 requires_human_input: false,
                 human_input_reason: None,
+                dimensions: vec![],
             },
         };
 
@@ -787,3 +1675,178 @@ impl From<OrchestrationResult> for LoopResult {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use goose::providers::errors::ProviderError;
+
+    use super::*;
+    use crate::phases::retry::testing::ScriptedProvider;
+
+    #[tokio::test]
+    async fn test_connect_orchestrator_records_fatal_error() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let config = OrchestratorConfig {
+            provider_override: Some("no-such-provider".to_string()),
+            model_override: Some("test-model".to_string()),
+            ..Default::default()
+        };
+        let orchestrator = GooseOrchestrator::new(
+            config,
+            GuardrailsConfig::default(),
+            crate::config::Config::default().output,
+            PathBuf::from(env!("CARGO_MANIFEST_DIR")),
+            temp_dir.path().to_path_buf(),
+            Arc::new(SessionRegistry::new()),
+        );
+        let recipe = load_recipe(
+            &orchestrator.config.recipe,
+            &orchestrator.base_dir,
+            "orchestrator",
+        )
+        .unwrap();
+        let chain = orchestrator.orchestrator_chain(0, &recipe);
+        let state = Arc::new(Mutex::new(OrchestrationState::new(
+            "session".to_string(),
+            "task".to_string(),
+            temp_dir.path().to_path_buf(),
+            "task".to_string(),
+        )));
+
+        let result = orchestrator
+            .connect_orchestrator(&chain, 0, &recipe, &state)
+            .await;
+
+        assert!(result.is_err());
+        let state = state.lock().await;
+        assert_eq!(state.retries.len(), 1);
+        assert_eq!(state.retries[0].component, "orchestrator");
+        assert_eq!(state.retries[0].class, ErrorClass::Fatal);
+    }
+
+    #[tokio::test]
+    async fn test_agent_loop_retries_transient_provider_error() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let session_dir = temp_dir.path().join("session");
+        let provider = Arc::new(ScriptedProvider::new(
+            "orchestrator",
+            vec![
+                Err(ProviderError::ServerError("503 Service Unavailable".into())),
+                Ok("Let me think about the plan first."),
+            ],
+        ));
+        let config = OrchestratorConfig {
+            pre_grounding: false,
+            ..Default::default()
+        };
+        let guardrails_config = GuardrailsConfig {
+            max_iterations: 1,
+            ..Default::default()
+        };
+        let orchestrator = GooseOrchestrator::new(
+            config,
+            guardrails_config,
+            crate::config::Config::default().output,
+            PathBuf::from(env!("CARGO_MANIFEST_DIR")),
+            session_dir.clone(),
+            Arc::new(SessionRegistry::new()),
+        )
+        .with_retry_config(RetryConfig {
+            initial_backoff_ms: 0,
+            max_backoff_ms: 0,
+            ..Default::default()
+        })
+        .with_provider(provider);
+
+        let result = orchestrator
+            .run(
+                "task".to_string(),
+                Some(temp_dir.path().to_path_buf()),
+                None,
+                Some("session".to_string()),
+            )
+            .await
+            .unwrap();
+
+        // The retried turn answered with text, so the session ran out of
+        // iterations instead of failing
+        assert!(matches!(
+            result.status,
+            OrchestrationStatus::HardStopped {
+                reason: GuardrailHardStop::MaxIterationsExceeded { .. }
+            }
+        ));
+        let state = OrchestrationState::load(&session_dir).unwrap().unwrap();
+        assert_eq!(state.retries.len(), 1);
+        assert_eq!(state.retries[0].class, ErrorClass::Retryable);
+        assert_eq!(
+            state.iteration_history[0].outcome,
+            IterationOutcome::TextResponseDetected
+        );
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_deterministic_run_writes_draft_plan() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let session_dir = temp_dir.path().join("add-caching");
+        let mut state = OrchestrationState::new(
+            "session".to_string(),
+            "Add caching".to_string(),
+            temp_dir.path().to_path_buf(),
+            "add-caching".to_string(),
+        );
+        let plan = Plan::new(
+            "Add caching".to_string(),
+            "Cache repository lookups".to_string(),
+            crate::models::PlanTier::Quick,
+        );
+        state.current_plan = Some(serde_json::to_value(plan).unwrap());
+        state.needs_review = true;
+        std::fs::create_dir_all(&session_dir).unwrap();
+        state.save(&session_dir).unwrap();
+
+        let config = OrchestratorConfig {
+            mode: OrchestratorMode::Deterministic,
+            pre_grounding: false,
+            ..Default::default()
+        };
+        let output_config = OutputConfig {
+            runs_dir: temp_dir.path().to_path_buf(),
+            active_dir: temp_dir.path().join("active"),
+            slug: None,
+        };
+        let cancel_token = CancellationToken::new();
+        cancel_token.cancel();
+        let orchestrator = GooseOrchestrator::new(
+            config,
+            GuardrailsConfig::default(),
+            output_config,
+            PathBuf::from(env!("CARGO_MANIFEST_DIR")),
+            session_dir.clone(),
+            Arc::new(SessionRegistry::new()),
+        )
+        .with_cancel_token(cancel_token);
+
+        let result = orchestrator
+            .run(
+                "Add caching".to_string(),
+                Some(temp_dir.path().to_path_buf()),
+                None,
+                Some("session".to_string()),
+            )
+            .await
+            .unwrap();
+
+        // The driver stops at the first refused tool call; the session stays
+        // resumable and its plan is kept as a draft
+        assert!(matches!(result.status, OrchestrationStatus::Cancelled));
+        let state = OrchestrationState::load(&session_dir).unwrap().unwrap();
+        assert!(matches!(state.status, OrchestrationStatus::Cancelled));
+        assert!(
+            temp_dir
+                .path()
+                .join("active/add-caching/add-caching-plan.md")
+                .exists()
+        );
+    }
+}
@@ -3,43 +3,132 @@ use async_trait::async_trait;
 use futures::StreamExt;
 use serde_json::Value;
 use std::path::PathBuf;
+use std::sync::Arc;
+use tokio_util::sync::CancellationToken;
 use tracing::{debug, info};
 
 use goose::agents::{Agent, AgentEvent, SessionConfig};
 use goose::conversation::message::Message;
+use goose::providers::base::Provider;
+use goose::recipe::Recipe;
 use goose::session::SessionManager;
 
-use crate::config::PlanningConfig;
+use crate::config::{CustomOpCode, PlanningConfig};
 use crate::models::Plan;
-use crate::orchestrator::TokenUsage;
+use crate::orchestrator::{SpentUsage, TokenUsage};
 use crate::recipes::load_recipe;
 
 use super::{
-    Planner, PlanningContext, ProviderConfig, create_provider, extract_json_block,
-    resolve_working_dir, setup_agent_session,
+    FailureProbe, Planner, PlanningContext, ProviderConfig, create_provider, extract_json_block,
+    resolve_model_label, resolve_working_dir, setup_agent_session,
 };
 
 /// Planner implementation using goose Agent
 pub struct GoosePlanner {
     config: PlanningConfig,
     base_dir: PathBuf,
+    custom_opcodes: Vec<CustomOpCode>,
+    /// Pre-computed repository grounding appended to orchestrator prompts
+    grounding_context: Option<String>,
+    /// Ranked repository symbol map appended to orchestrator prompts
+    repo_map: Option<String>,
+    /// Sampling temperature for orchestrator plan generation
+    temperature: Option<f32>,
+    /// Stops the agent stream when the session is cancelled
+    cancel_token: Option<CancellationToken>,
+    /// Used instead of creating a provider from config
+    provider: Option<Arc<dyn Provider>>,
 }
 
 impl GoosePlanner {
     pub fn new(config: PlanningConfig, base_dir: PathBuf) -> Self {
-        Self { config, base_dir }
+        Self {
+            config,
+            base_dir,
+            custom_opcodes: Vec::new(),
+            grounding_context: None,
+            repo_map: None,
+            temperature: None,
+            cancel_token: None,
+            provider: None,
+        }
     }
 
-    async fn run_agent(&self, prompt: &str, ctx: &PlanningContext) -> Result<String> {
-        // Load recipe (with fallback to bundled default)
-        let recipe = load_recipe(&self.config.recipe, &self.base_dir, "planner")?;
+    /// Make user-defined opcodes available to the planner.
+    pub fn with_custom_opcodes(mut self, custom_opcodes: Vec<CustomOpCode>) -> Self {
+        self.custom_opcodes = custom_opcodes;
+        self
+    }
+
+    /// Append pre-computed repository grounding to every plan prompt so
+    /// iterations don't re-explore the repo.
+    pub fn with_grounding_context(mut self, grounding_context: String) -> Self {
+        self.grounding_context = Some(grounding_context);
+        self
+    }
+
+    /// Append a repository symbol map to every plan prompt.
+    pub fn with_repo_map(mut self, repo_map: String) -> Self {
+        self.repo_map = Some(repo_map);
+        self
+    }
+
+    /// Sample plans at a specific temperature (e.g., to diversify candidates).
+    pub fn with_temperature(mut self, temperature: Option<f32>) -> Self {
+        self.temperature = temperature;
+        self
+    }
+
+    /// Stop in-flight plan generation when `cancel_token` is cancelled.
+    pub fn with_cancel_token(mut self, cancel_token: CancellationToken) -> Self {
+        self.cancel_token = Some(cancel_token);
+        self
+    }
+
+    /// Call `provider` instead of the one named by config.
+    pub fn with_provider(mut self, provider: Arc<dyn Provider>) -> Self {
+        self.provider = Some(provider);
+        self
+    }
 
-        // Create provider using shared utility
+    /// "provider/model" this planner calls, resolved the same way as its
+    /// provider (override > recipe setting > default).
+    pub fn model_label(&self) -> String {
         let provider_config = ProviderConfig::for_planner(
             self.config.provider_override.as_deref(),
             self.config.model_override.as_deref(),
         );
-        let provider = create_provider(&provider_config, &recipe).await?;
+        let recipe = load_recipe(&self.config.recipe, &self.base_dir, "planner").ok();
+        resolve_model_label(&provider_config, recipe.as_ref())
+    }
+
+    /// Fail instead of parsing a partial response after cancellation.
+    fn check_cancelled(&self) -> Result<()> {
+        if self.cancel_token.as_ref().is_some_and(|t| t.is_cancelled()) {
+            anyhow::bail!("Planner cancelled");
+        }
+        Ok(())
+    }
+
+    /// The injected provider, or one created from config and `recipe`.
+    async fn provider(&self, recipe: &Recipe) -> Result<Arc<dyn Provider>> {
+        if let Some(provider) = &self.provider {
+            return Ok(provider.clone());
+        }
+        let provider_config = ProviderConfig::for_planner(
+            self.config.provider_override.as_deref(),
+            self.config.model_override.as_deref(),
+        )
+        .with_temperature(self.temperature);
+        create_provider(&provider_config, recipe).await
+    }
+
+    async fn run_agent(&self, prompt: &str, ctx: &PlanningContext) -> Result<String> {
+        // Load recipe (with fallback to bundled default)
+        let recipe = load_recipe(&self.config.recipe, &self.base_dir, "planner")?;
+
+        // Record provider failures the agent turns into text
+        let probe = FailureProbe::new(self.provider(&recipe).await?);
 
         // Create agent and session using shared utility
         let agent = Agent::new();
@@ -48,7 +137,7 @@ impl GoosePlanner {
         let session = setup_agent_session(
             &agent,
             &recipe,
-            provider,
+            probe.clone(),
             &working_dir,
             &session_name,
             "planner",
@@ -80,9 +169,9 @@ impl GoosePlanner {
         // Create user message with the prompt
         let user_message = Message::user().with_text(prompt);
 
-        // Stream response (cancel_token is None)
+        // Stream response (ends early when the session is cancelled)
         let mut stream = agent
-            .reply(user_message, session_config, None)
+            .reply(user_message, session_config, self.cancel_token.clone())
             .await
             .context("Failed to start agent reply")?;
 
@@ -101,6 +190,10 @@ impl GoosePlanner {
                 }
             }
         }
+        self.check_cancelled()?;
+        if let Some(failure) = probe.take_failure() {
+            return Err(anyhow::Error::from(failure).context("Planner agent failed"));
+        }
 
         Ok(last_message)
     }
@@ -277,15 +370,27 @@ Return ONLY the JSON plan.
             )
         };
 
-        // Load recipe
-        let recipe = load_recipe(&self.config.recipe, &self.base_dir, "planner")?;
-
-        // Create provider using shared utility
-        let provider_config = ProviderConfig::for_planner(
-            self.config.provider_override.as_deref(),
-            self.config.model_override.as_deref(),
+        // Describe custom opcodes so the planner can use them
+        let prompt = format!(
+            "{}{}{}{}",
+            prompt,
+            custom_opcodes_prompt_section(&self.custom_opcodes),
+            self.grounding_context.as_deref().unwrap_or_default(),
+            self.repo_map.as_deref().unwrap_or_default()
         );
-        let provider = create_provider(&provider_config, &recipe).await?;
+
+        // Load recipe and allow custom opcode names in the response schema
+        let mut recipe = load_recipe(&self.config.recipe, &self.base_dir, "planner")?;
+        if let Some(schema) = recipe
+            .response
+            .as_mut()
+            .and_then(|r| r.json_schema.as_mut())
+        {
+            allow_custom_opcodes_in_schema(schema, &self.custom_opcodes);
+        }
+
+        // Record provider failures the agent turns into text
+        let probe = FailureProbe::new(self.provider(&recipe).await?);
 
         // Create agent and session using shared utility
         let agent = Agent::new();
@@ -294,8 +399,15 @@ Return ONLY the JSON plan.
             .map(|p| p.to_path_buf())
             .unwrap_or_else(|| std::env::current_dir().unwrap_or_default());
         let session_name = format!("orchestrator-planner-{}", chrono::Utc::now().timestamp());
-        let session =
-            setup_agent_session(&agent, &recipe, provider, &wd, &session_name, "planner").await?;
+        let session = setup_agent_session(
+            &agent,
+            &recipe,
+            probe.clone(),
+            &wd,
+            &session_name,
+            "planner",
+        )
+        .await?;
         let session_id = session.id.clone();
 
         agent
@@ -315,11 +427,12 @@ Return ONLY the JSON plan.
 
         let user_message = Message::user().with_text(&prompt);
         let mut stream = agent
-            .reply(user_message, session_config, None)
+            .reply(user_message, session_config, self.cancel_token.clone())
             .await
             .context("Failed to start planner agent")?;
 
         let mut last_message = String::new();
+        let mut stream_error = None;
         while let Some(event) = stream.next().await {
             match event {
                 Ok(AgentEvent::Message(msg)) => {
@@ -328,6 +441,7 @@ Return ONLY the JSON plan.
                 Ok(_) => {}
                 Err(e) => {
                     tracing::warn!("Planner error: {:?}", e);
+                    stream_error = Some(e);
                 }
             }
         }
@@ -342,6 +456,24 @@ Return ONLY the JSON plan.
             TokenUsage::default()
         };
 
+        // Cancelled and failed calls still charge what they spent
+        let model_label = self.model_label();
+        let spent = |message: &str| SpentUsage {
+            message: message.to_string(),
+            usage: vec![(model_label.clone(), token_usage.clone())],
+        };
+        self.check_cancelled()
+            .context(spent("Planner stream interrupted"))?;
+
+        // Return provider failures typed so the retry layer can classify them
+        let failure = probe
+            .take_failure()
+            .map(anyhow::Error::from)
+            .or_else(|| stream_error.filter(|_| last_message.is_empty()));
+        if let Some(e) = failure {
+            return Err(e.context(spent("Planner agent failed")));
+        }
+
         // Parse response as JSON Value (flexible schema)
         let plan_json: Value = if let Ok(json) = serde_json::from_str(&last_message) {
             json
@@ -375,6 +507,64 @@ impl Planner for GoosePlanner {
     }
 }
 
+/// Build the "Custom OpCodes" prompt section (empty when none are declared).
+fn custom_opcodes_prompt_section(custom_opcodes: &[CustomOpCode]) -> String {
+    if custom_opcodes.is_empty() {
+        return String::new();
+    }
+
+    let entries = custom_opcodes
+        .iter()
+        .map(|c| {
+            let mut entry = format!(
+                "### {} ({:?})\n{}",
+                c.name,
+                c.category,
+                if c.description.is_empty() {
+                    "(no description)"
+                } else {
+                    &c.description
+                }
+            );
+            if let Some(schema) = &c.params_schema {
+                entry.push_str(&format!(
+                    "\nParams schema:\n```json\n{}\n```",
+                    serde_json::to_string_pretty(schema).unwrap_or_default()
+                ));
+            }
+            if !c.produces.is_empty() {
+                entry.push_str(&format!(
+                    "\nProduces fields: {}",
+                    c.produces
+                        .iter()
+                        .map(|f| format!("${{id.{}}}", f))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ));
+            }
+            entry
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n");
+
+    format!("\n## Custom OpCodes\n{}\n", entries)
+}
+
+/// Extend the `op` enum of the recipe response schema with custom opcode names.
+fn allow_custom_opcodes_in_schema(schema: &mut Value, custom_opcodes: &[CustomOpCode]) {
+    if let Some(ops) = schema
+        .pointer_mut("/properties/instructions/items/properties/op/enum")
+        .and_then(|v| v.as_array_mut())
+    {
+        for custom in custom_opcodes {
+            let name = Value::String(custom.name.clone());
+            if !ops.contains(&name) {
+                ops.push(name);
+            }
+        }
+    }
+}
+
 /// Parse a Plan from the agent's response
 /// With final_output_tool enabled, the response is schema-validated JSON
 fn parse_plan_from_response(response: &str) -> Result<Plan> {
@@ -0,0 +1,795 @@
+//! Retries with exponential backoff and provider fallback.
+//!
+//! Planner, reviewer, and orchestrator calls go through `call_with_retry`:
+//! retryable errors (rate limits, timeouts, 5xx, dropped connections) are
+//! retried on the same provider with jittered exponential backoff, then the
+//! next provider in the fallback chain is tried. Fatal errors (bad
+//! credentials, invalid requests, unparseable output) fail immediately.
+//! Every failed attempt is returned as a `RetryRecord` for the state file.
+
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use goose::conversation::message::Message;
+use goose::model::ModelConfig;
+use goose::providers::base::{Provider, ProviderMetadata, ProviderUsage};
+use goose::providers::errors::ProviderError;
+use rmcp::model::Tool;
+use tokio_util::sync::CancellationToken;
+use tracing::warn;
+
+use crate::config::RetryConfig;
+use crate::orchestrator::{ErrorClass, RetryRecord};
+
+/// Classify an error as retryable or fatal by the first typed cause in its
+/// context chain: goose `ProviderError`, `reqwest::Error`, I/O error, or
+/// timeout.
+///
+/// Errors without a recognized cause (unparseable output, config mistakes)
+/// are fatal so real bugs aren't retried.
+pub fn classify_error(error: &anyhow::Error) -> ErrorClass {
+    error
+        .chain()
+        .find_map(classify_cause)
+        .unwrap_or(ErrorClass::Fatal)
+}
+
+/// Class of a single error in a chain, or `None` when its type says nothing.
+fn classify_cause(cause: &(dyn std::error::Error + 'static)) -> Option<ErrorClass> {
+    if let Some(e) = cause.downcast_ref::<ProviderError>() {
+        Some(classify_provider_error(e))
+    } else if let Some(e) = cause.downcast_ref::<ProviderFailure>() {
+        Some(e.class)
+    } else if let Some(e) = cause.downcast_ref::<reqwest::Error>() {
+        Some(classify_reqwest_error(e))
+    } else if let Some(e) = cause.downcast_ref::<std::io::Error>() {
+        Some(classify_io_error(e))
+    } else if cause.is::<tokio::time::error::Elapsed>() {
+        Some(ErrorClass::Retryable)
+    } else {
+        None
+    }
+}
+
+fn classify_provider_error(error: &ProviderError) -> ErrorClass {
+    match error {
+        ProviderError::RateLimitExceeded { .. } | ProviderError::ServerError(_) => {
+            ErrorClass::Retryable
+        }
+        // Authentication, context length, bad requests, usage and
+        // unsupported-operation errors fail the same way every time
+        _ => ErrorClass::Fatal,
+    }
+}
+
+fn classify_reqwest_error(error: &reqwest::Error) -> ErrorClass {
+    if let Some(status) = error.status() {
+        return classify_status(status.as_u16());
+    }
+    if error.is_timeout() || error.is_connect() || error.is_request() || error.is_body() {
+        ErrorClass::Retryable
+    } else {
+        ErrorClass::Fatal
+    }
+}
+
+/// HTTP status: 408, 429 and 5xx are transient
+fn classify_status(status: u16) -> ErrorClass {
+    match status {
+        408 | 429 | 500..=599 => ErrorClass::Retryable,
+        _ => ErrorClass::Fatal,
+    }
+}
+
+fn classify_io_error(error: &std::io::Error) -> ErrorClass {
+    use std::io::ErrorKind;
+    match error.kind() {
+        ErrorKind::TimedOut
+        | ErrorKind::ConnectionReset
+        | ErrorKind::ConnectionAborted
+        | ErrorKind::ConnectionRefused
+        | ErrorKind::NotConnected
+        | ErrorKind::BrokenPipe
+        | ErrorKind::UnexpectedEof
+        | ErrorKind::Interrupted => ErrorClass::Retryable,
+        _ => ErrorClass::Fatal,
+    }
+}
+
+/// A provider call that failed inside a goose agent.
+///
+/// The agent reports provider errors to its stream as assistant text, so
+/// `FailureProbe` keeps the class of the original `ProviderError` here.
+#[derive(Debug, Clone)]
+pub struct ProviderFailure {
+    pub class: ErrorClass,
+    pub message: String,
+}
+
+impl std::fmt::Display for ProviderFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for ProviderFailure {}
+
+/// Provider wrapper that remembers whether the latest completion failed.
+///
+/// Wrap an agent's provider with it and call `take_failure` once the reply
+/// stream ends to turn a swallowed provider error back into a typed one.
+pub struct FailureProbe {
+    inner: Arc<dyn Provider>,
+    failure: Mutex<Option<ProviderFailure>>,
+}
+
+impl FailureProbe {
+    pub fn new(inner: Arc<dyn Provider>) -> Arc<Self> {
+        Arc::new(Self {
+            inner,
+            failure: Mutex::new(None),
+        })
+    }
+
+    /// Failure of the latest completion, if it failed; clears it.
+    pub fn take_failure(&self) -> Option<ProviderFailure> {
+        self.failure.lock().unwrap().take()
+    }
+}
+
+#[async_trait]
+impl Provider for FailureProbe {
+    fn metadata() -> ProviderMetadata {
+        ProviderMetadata::empty()
+    }
+
+    fn get_name(&self) -> &str {
+        self.inner.get_name()
+    }
+
+    fn get_model_config(&self) -> ModelConfig {
+        self.inner.get_model_config()
+    }
+
+    async fn complete_with_model(
+        &self,
+        session_id: Option<&str>,
+        model_config: &ModelConfig,
+        system: &str,
+        messages: &[Message],
+        tools: &[Tool],
+    ) -> Result<(Message, ProviderUsage), ProviderError> {
+        let result = self
+            .inner
+            .complete_with_model(session_id, model_config, system, messages, tools)
+            .await;
+        *self.failure.lock().unwrap() = result.as_ref().err().map(|e| ProviderFailure {
+            class: classify_provider_error(e),
+            message: e.to_string(),
+        });
+        result
+    }
+}
+
+/// Backoff settings for one provider in the chain
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// Attempts per provider, including the first
+    pub max_attempts: u32,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+    pub multiplier: f64,
+    /// Fraction of each delay randomized up or down (0.25 = ±25%)
+    pub jitter: f64,
+    /// Ends the backoff early when the session is cancelled
+    pub cancel_token: Option<CancellationToken>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::from_config(&RetryConfig::default())
+    }
+}
+
+impl RetryPolicy {
+    /// Create a policy from configuration.
+    pub fn from_config(config: &RetryConfig) -> Self {
+        Self {
+            max_attempts: config.max_attempts.max(1),
+            initial_backoff: Duration::from_millis(config.initial_backoff_ms),
+            max_backoff: Duration::from_millis(config.max_backoff_ms),
+            multiplier: config.multiplier.max(1.0),
+            jitter: config.jitter.clamp(0.0, 1.0),
+            cancel_token: None,
+        }
+    }
+
+    /// Stop retrying (and waiting out a backoff) once `cancel_token` is
+    /// cancelled.
+    pub fn with_cancel_token(mut self, cancel_token: CancellationToken) -> Self {
+        self.cancel_token = Some(cancel_token);
+        self
+    }
+
+    /// Sleep for `delay`, returning false if cancelled first.
+    pub async fn backoff(&self, delay: Duration) -> bool {
+        match &self.cancel_token {
+            Some(cancel_token) => tokio::select! {
+                _ = cancel_token.cancelled() => false,
+                _ = tokio::time::sleep(delay) => true,
+            },
+            None => {
+                tokio::time::sleep(delay).await;
+                true
+            }
+        }
+    }
+
+    /// Delay after failed attempt `attempt` (1-based).
+    ///
+    /// `sample` in [0, 1) picks the jitter: 0.5 is the unjittered delay.
+    pub fn delay(&self, attempt: u32, sample: f64) -> Duration {
+        let exponent = attempt.saturating_sub(1).min(32) as i32;
+        let base = (self.initial_backoff.as_secs_f64() * self.multiplier.powi(exponent))
+            .min(self.max_backoff.as_secs_f64());
+        let factor = 1.0 + self.jitter * (2.0 * sample.clamp(0.0, 1.0) - 1.0);
+        Duration::from_secs_f64((base * factor).max(0.0))
+    }
+}
+
+/// Cheap jitter sample in [0, 1) without an RNG dependency
+pub fn jitter_sample() -> f64 {
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.subsec_nanos())
+        .unwrap_or(0);
+    (nanos % 1_000_000) as f64 / 1_000_000.0
+}
+
+/// Run `op` against each `(label, target)` in `chain` until one succeeds.
+///
+/// Each target gets `policy.max_attempts` tries with backoff between them;
+/// a fatal error stops immediately, as does cancelling the policy's token.
+/// Failed attempts are appended to `records`. Returns the last error when
+/// every target fails.
+pub async fn call_with_retry<C, T, F, Fut>(
+    policy: &RetryPolicy,
+    component: &str,
+    chain: &[(String, C)],
+    mut op: F,
+    records: &mut Vec<RetryRecord>,
+) -> Result<T>
+where
+    C: Clone,
+    F: FnMut(C) -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    let mut last_error = None;
+    for (position, (label, target)) in chain.iter().enumerate() {
+        for attempt in 1..=policy.max_attempts {
+            let error = match op(target.clone()).await {
+                Ok(value) => return Ok(value),
+                Err(e) => e,
+            };
+            let class = classify_error(&error);
+            let retry_same = class == ErrorClass::Retryable && attempt < policy.max_attempts;
+            let delay = if retry_same {
+                policy.delay(attempt, jitter_sample())
+            } else {
+                Duration::ZERO
+            };
+            warn!(
+                "{} call via {} failed (attempt {}/{}, {:?}): {:#}",
+                component, label, attempt, policy.max_attempts, class, error
+            );
+            records.push(RetryRecord {
+                component: component.to_string(),
+                target: label.clone(),
+                fallback: position > 0,
+                attempt,
+                class,
+                error: format!("{:#}", error),
+                delay_ms: delay.as_millis() as u64,
+                timestamp: chrono::Utc::now().to_rfc3339(),
+            });
+
+            if class == ErrorClass::Fatal
+                || policy
+                    .cancel_token
+                    .as_ref()
+                    .is_some_and(|t| t.is_cancelled())
+            {
+                return Err(error);
+            }
+            if retry_same && !policy.backoff(delay).await {
+                return Err(error);
+            }
+            last_error = Some(error);
+        }
+    }
+    Err(last_error.unwrap_or_else(|| anyhow::anyhow!("No {} provider configured", component)))
+}
+
+/// Label of the chain target that served a successful `call_with_retry`,
+/// judged from the failed attempts it recorded.
+pub fn served_by<'a, C>(
+    policy: &RetryPolicy,
+    chain: &'a [(String, C)],
+    records: &[RetryRecord],
+) -> Option<&'a str> {
+    let position = match records.last() {
+        None => 0,
+        Some(last) => {
+            let failed = chain.iter().position(|(label, _)| *label == last.target)?;
+            if last.attempt < policy.max_attempts {
+                failed
+            } else {
+                failed + 1
+            }
+        }
+    };
+    chain.get(position).map(|(label, _)| label.as_str())
+}
+
+#[cfg(test)]
+pub mod testing {
+    //! Scripted goose provider that fails on cue with `ProviderError`s,
+    //! for retry tests.
+
+    use std::collections::VecDeque;
+    use std::path::{Path, PathBuf};
+    use std::sync::Mutex;
+
+    use anyhow::Result;
+    use async_trait::async_trait;
+    use goose::conversation::message::Message;
+    use goose::model::ModelConfig;
+    use goose::providers::base::{Provider, ProviderMetadata, ProviderUsage, Usage};
+    use goose::providers::errors::ProviderError;
+    use rmcp::model::Tool;
+
+    /// Returns scripted responses in order; each entry is `Ok(text)` or the
+    /// provider error to fail with. Calls past the end of the script succeed.
+    pub struct ScriptedProvider {
+        name: String,
+        model_config: ModelConfig,
+        script: Mutex<VecDeque<Result<String, ProviderError>>>,
+        calls: Mutex<u32>,
+    }
+
+    impl ScriptedProvider {
+        pub fn new(name: &str, script: Vec<Result<&str, ProviderError>>) -> Self {
+            Self {
+                name: name.to_string(),
+                model_config: ModelConfig::new("scripted-model").unwrap(),
+                script: Mutex::new(script.into_iter().map(|r| r.map(str::to_string)).collect()),
+                calls: Mutex::new(0),
+            }
+        }
+
+        fn next(&self) -> Result<String, ProviderError> {
+            *self.calls.lock().unwrap() += 1;
+            self.script
+                .lock()
+                .unwrap()
+                .pop_front()
+                .unwrap_or_else(|| Ok(format!("{} response", self.name)))
+        }
+
+        /// Take the next scripted response outside an agent
+        pub async fn next_response(&self) -> Result<String> {
+            self.next().map_err(|error| {
+                anyhow::Error::from(error).context(format!("{} call failed", self.name))
+            })
+        }
+
+        /// Number of calls made so far
+        pub fn calls(&self) -> u32 {
+            *self.calls.lock().unwrap()
+        }
+    }
+
+    /// Recipe without extensions or a response schema, so the agent makes
+    /// exactly one provider call per reply
+    pub fn write_recipe(dir: &Path) -> PathBuf {
+        let path = dir.join("recipe.yaml");
+        std::fs::write(
+            &path,
+            "version: \"1.0.0\"\ntitle: \"Test\"\ndescription: \"Test recipe\"\ninstructions: \"Reply with JSON.\"\n",
+        )
+        .unwrap();
+        path
+    }
+
+    #[async_trait]
+    impl Provider for ScriptedProvider {
+        fn metadata() -> ProviderMetadata {
+            ProviderMetadata::empty()
+        }
+
+        fn get_name(&self) -> &str {
+            &self.name
+        }
+
+        fn get_model_config(&self) -> ModelConfig {
+            self.model_config.clone()
+        }
+
+        async fn complete_with_model(
+            &self,
+            _session_id: Option<&str>,
+            model_config: &ModelConfig,
+            _system: &str,
+            _messages: &[Message],
+            _tools: &[Tool],
+        ) -> Result<(Message, ProviderUsage), ProviderError> {
+            let text = self.next()?;
+            let usage = ProviderUsage::new(
+                model_config.model_name.clone(),
+                Usage::new(Some(100), Some(50), Some(150)),
+            );
+            Ok((Message::assistant().with_text(text), usage))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+    use std::sync::Arc;
+
+    use super::testing::{ScriptedProvider, write_recipe};
+    use super::*;
+    use crate::config::Config;
+    use crate::phases::{GoosePlanner, GooseReviewer};
+
+    fn make_policy(max_attempts: u32) -> RetryPolicy {
+        RetryPolicy {
+            max_attempts,
+            initial_backoff: Duration::ZERO,
+            max_backoff: Duration::ZERO,
+            multiplier: 2.0,
+            jitter: 0.0,
+            cancel_token: None,
+        }
+    }
+
+    fn make_chain(providers: &[&Arc<ScriptedProvider>]) -> Vec<(String, Arc<ScriptedProvider>)> {
+        providers
+            .iter()
+            .enumerate()
+            .map(|(i, p)| (format!("provider-{}", i), Arc::clone(p)))
+            .collect()
+    }
+
+    fn server_error(message: &str) -> ProviderError {
+        ProviderError::ServerError(message.to_string())
+    }
+
+    #[tokio::test]
+    async fn test_classify_error() {
+        let classify = |e: ProviderError| classify_error(&anyhow::Error::from(e));
+        assert_eq!(
+            classify(server_error("503 Service Unavailable")),
+            ErrorClass::Retryable
+        );
+        assert_eq!(
+            classify(ProviderError::Authentication("invalid API key".into())),
+            ErrorClass::Fatal
+        );
+        assert_eq!(
+            classify(ProviderError::ContextLengthExceeded("too long".into())),
+            ErrorClass::Fatal
+        );
+
+        // Typed causes are found through context chains
+        let reset = std::io::Error::new(std::io::ErrorKind::ConnectionReset, "reset by peer");
+        let error = anyhow::Error::from(reset).context("Planner failed");
+        assert_eq!(classify_error(&error), ErrorClass::Retryable);
+        let elapsed = tokio::time::timeout(Duration::ZERO, std::future::pending::<()>())
+            .await
+            .unwrap_err();
+        assert_eq!(
+            classify_error(&anyhow::Error::from(elapsed)),
+            ErrorClass::Retryable
+        );
+
+        // Untyped errors are fatal whatever their message says
+        assert_eq!(
+            classify_error(&anyhow::anyhow!("Failed to parse plan JSON")),
+            ErrorClass::Fatal
+        );
+        assert_eq!(
+            classify_error(&anyhow::anyhow!("request timed out")),
+            ErrorClass::Fatal
+        );
+    }
+
+    #[test]
+    fn test_classify_status() {
+        assert_eq!(classify_status(429), ErrorClass::Retryable);
+        assert_eq!(classify_status(408), ErrorClass::Retryable);
+        assert_eq!(classify_status(502), ErrorClass::Retryable);
+        assert_eq!(classify_status(400), ErrorClass::Fatal);
+        assert_eq!(classify_status(401), ErrorClass::Fatal);
+    }
+    #[test]
+    fn test_backoff_grows_and_caps() {
+        let policy = RetryPolicy {
+            max_attempts: 5,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_millis(1000),
+            multiplier: 2.0,
+            jitter: 0.5,
+            cancel_token: None,
+        };
+        assert_eq!(policy.delay(1, 0.5), Duration::from_millis(100));
+        assert_eq!(policy.delay(3, 0.5), Duration::from_millis(400));
+        assert_eq!(policy.delay(10, 0.5), Duration::from_millis(1000));
+        // ±50% jitter
+        assert_eq!(policy.delay(1, 0.0), Duration::from_millis(50));
+        assert_eq!(policy.delay(2, 1.0), Duration::from_millis(300));
+    }
+
+    #[tokio::test]
+    async fn test_retries_then_succeeds() {
+        let primary = Arc::new(ScriptedProvider::new(
+            "primary",
+            vec![Err(server_error("503 Service Unavailable")), Ok("plan")],
+        ));
+        let mut records = Vec::new();
+        let result = call_with_retry(
+            &make_policy(3),
+            "planner",
+            &make_chain(&[&primary]),
+            |p| async move { p.next_response().await },
+            &mut records,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(result, "plan");
+        assert_eq!(primary.calls(), 2);
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].class, ErrorClass::Retryable);
+        assert!(!records[0].fallback);
+        assert_eq!(
+            served_by(&make_policy(3), &make_chain(&[&primary]), &records),
+            Some("provider-0")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_falls_back_after_exhausting_retries() {
+        let primary = Arc::new(ScriptedProvider::new(
+            "primary",
+            vec![
+                Err(server_error("overloaded")),
+                Err(server_error("overloaded")),
+            ],
+        ));
+        let fallback = Arc::new(ScriptedProvider::new("fallback", vec![]));
+        let mut records = Vec::new();
+        let result = call_with_retry(
+            &make_policy(2),
+            "reviewer",
+            &make_chain(&[&primary, &fallback]),
+            |p| async move { p.next_response().await },
+            &mut records,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(result, "fallback response");
+        assert_eq!(primary.calls(), 2);
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[1].attempt, 2);
+        assert_eq!(
+            served_by(
+                &make_policy(2),
+                &make_chain(&[&primary, &fallback]),
+                &records
+            ),
+            Some("provider-1")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fatal_error_stops_immediately() {
+        let primary = Arc::new(ScriptedProvider::new(
+            "primary",
+            vec![Err(ProviderError::Authentication(
+                "401 Unauthorized".into(),
+            ))],
+        ));
+        let fallback = Arc::new(ScriptedProvider::new("fallback", vec![]));
+        let mut records = Vec::new();
+        let result = call_with_retry(
+            &make_policy(3),
+            "orchestrator",
+            &make_chain(&[&primary, &fallback]),
+            |p| async move { p.next_response().await },
+            &mut records,
+        )
+        .await;
+
+        assert!(result.is_err());
+        assert_eq!(fallback.calls(), 0);
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].class, ErrorClass::Fatal);
+    }
+
+    #[tokio::test]
+    async fn test_cancel_ends_backoff() {
+        let primary = Arc::new(ScriptedProvider::new(
+            "primary",
+            vec![Err(server_error("503 Service Unavailable"))],
+        ));
+        let cancel_token = CancellationToken::new();
+        let policy = RetryPolicy {
+            initial_backoff: Duration::from_secs(3600),
+            max_backoff: Duration::from_secs(3600),
+            ..make_policy(3)
+        }
+        .with_cancel_token(cancel_token.clone());
+
+        let cancel = cancel_token.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            cancel.cancel();
+        });
+        let mut records = Vec::new();
+        let result = tokio::time::timeout(
+            Duration::from_secs(10),
+            call_with_retry(
+                &policy,
+                "planner",
+                &make_chain(&[&primary]),
+                |p| async move { p.next_response().await },
+                &mut records,
+            ),
+        )
+        .await
+        .expect("backoff should end on cancel");
+
+        assert!(result.is_err());
+        assert_eq!(primary.calls(), 1);
+        assert_eq!(records.len(), 1);
+    }
+
+    fn make_config(provider: &str) -> Config {
+        let mut config = Config::default();
+        config.planning.provider_override = Some(provider.to_string());
+        config.planning.model_override = Some("test-model".to_string());
+        config.review.provider_override = Some(provider.to_string());
+        config.review.model_override = Some("test-model".to_string());
+        config
+    }
+
+    fn base_dir() -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+    }
+
+    #[tokio::test]
+    async fn test_planner_path_stops_on_fatal_error() {
+        let planner = |provider: &str| {
+            Arc::new(GoosePlanner::new(
+                make_config(provider).planning,
+                base_dir(),
+            ))
+        };
+        let chain = vec![
+            ("primary".to_string(), planner("no-such-provider")),
+            ("fallback".to_string(), planner("no-such-fallback")),
+        ];
+        let mut records = Vec::new();
+        let result = call_with_retry(
+            &make_policy(3),
+            "planner",
+            &chain,
+            |p| async move { p.generate_plan_json("task", None, None, None).await },
+            &mut records,
+        )
+        .await;
+
+        assert!(result.is_err());
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].target, "primary");
+        assert_eq!(records[0].class, ErrorClass::Fatal);
+    }
+
+    #[tokio::test]
+    async fn test_reviewer_path_stops_on_fatal_error() {
+        let reviewer = Arc::new(GooseReviewer::new(
+            make_config("no-such-provider").review,
+            base_dir(),
+            0.8,
+        ));
+        let chain = vec![("primary".to_string(), reviewer)];
+        let plan = serde_json::json!({ "title": "plan" });
+        let mut records = Vec::new();
+        let result = call_with_retry(
+            &make_policy(3),
+            "reviewer",
+            &chain,
+            |r| {
+                let plan = plan.clone();
+                async move { r.review_plan_json(&plan).await }
+            },
+            &mut records,
+        )
+        .await;
+
+        assert!(result.is_err());
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].class, ErrorClass::Fatal);
+    }
+
+    #[tokio::test]
+    async fn test_planner_path_retries_transient_provider_error() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut config = make_config("scripted").planning;
+        config.recipe = write_recipe(temp_dir.path());
+        let provider = Arc::new(ScriptedProvider::new(
+            "primary",
+            vec![
+                Err(server_error("503 Service Unavailable")),
+                Ok(r#"{"title": "plan"}"#),
+            ],
+        ));
+        let planner = GoosePlanner::new(config, base_dir()).with_provider(provider);
+        let chain = vec![("primary".to_string(), Arc::new(planner))];
+        let mut records = Vec::new();
+        let (plan, _) = call_with_retry(
+            &make_policy(3),
+            "planner",
+            &chain,
+            |p| async move { p.generate_plan_json("task", None, None, None).await },
+            &mut records,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(plan["title"], "plan");
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].class, ErrorClass::Retryable);
+        assert!(records[0].error.contains("503"));
+    }
+
+    #[tokio::test]
+    async fn test_reviewer_path_retries_transient_provider_error() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut config = make_config("scripted").review;
+        config.recipe = write_recipe(temp_dir.path());
+        let provider = Arc::new(ScriptedProvider::new(
+            "primary",
+            vec![
+                Err(server_error("502 Bad Gateway")),
+                Ok(r#"{"overall_assessment": "good", "score": 0.9}"#),
+            ],
+        ));
+        let reviewer = GooseReviewer::new(config, base_dir(), 0.8).with_provider(provider);
+        let chain = vec![("primary".to_string(), Arc::new(reviewer))];
+        let plan = serde_json::json!({ "title": "plan" });
+        let mut records = Vec::new();
+        let (review, _) = call_with_retry(
+            &make_policy(3),
+            "reviewer",
+            &chain,
+            |r| {
+                let plan = plan.clone();
+                async move { r.review_plan_json(&plan).await }
+            },
+            &mut records,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(review["passed"], true);
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].class, ErrorClass::Retryable);
+    }
+}
@@ -0,0 +1,199 @@
+//! Specialist review dimensions.
+//!
+//! Each dimension (security, testing strategy, performance, migration
+//! safety, ...) is a separate reviewer pass with its own recipe. Passes run
+//! concurrently and produce a `DimensionScore` with the dimension's gaps.
+//! The combined score is a weighted mean of the main review score and the
+//! dimension scores; per-dimension minimums are enforced separately.
+
+use std::sync::Arc;
+
+use anyhow::Result;
+use futures::future::join_all;
+use serde_json::Value;
+use tracing::info;
+
+use crate::config::ReviewDimensionConfig;
+use crate::models::{DimensionScore, Gap};
+use crate::orchestrator::{SpentUsage, TokenUsage};
+
+use super::GooseReviewer;
+
+/// Specialist reviewer passes scored as separate dimensions
+pub struct SpecialistReviews {
+    passes: Vec<(ReviewDimensionConfig, Arc<GooseReviewer>)>,
+    primary_weight: f32,
+}
+
+impl SpecialistReviews {
+    /// Create an empty set of passes; `primary_weight` weights the main review
+    pub fn new(primary_weight: f32) -> Self {
+        Self {
+            passes: Vec::new(),
+            primary_weight,
+        }
+    }
+
+    /// Add a specialist pass
+    pub fn with_pass(
+        mut self,
+        config: ReviewDimensionConfig,
+        reviewer: Arc<GooseReviewer>,
+    ) -> Self {
+        self.passes.push((config, reviewer));
+        self
+    }
+
+    /// Number of specialist passes
+    pub fn len(&self) -> usize {
+        self.passes.len()
+    }
+
+    /// Whether there are no specialist passes
+    pub fn is_empty(&self) -> bool {
+        self.passes.is_empty()
+    }
+
+    /// Weight of the main review score in the combined score
+    pub fn primary_weight(&self) -> f32 {
+        self.primary_weight
+    }
+
+    /// Run every pass concurrently and return the dimension scores.
+    ///
+    /// A failed pass fails the whole review, like a failed main review, so a
+    /// dimension with a minimum is never silently skipped; the error carries
+    /// the other passes' usage as [`SpentUsage`]. Token usage is returned per
+    /// pass, labelled with the pass's "provider/model".
+    pub async fn review_plan_json(
+        &self,
+        plan_json: &Value,
+    ) -> Result<(Vec<DimensionScore>, Vec<(String, TokenUsage)>)> {
+        info!("Running {} specialist review passes", self.passes.len());
+
+        let results = join_all(self.passes.iter().map(|(config, reviewer)| async move {
+            let result = reviewer.review_plan_json(plan_json).await;
+            (config, reviewer.model_label(), result)
+        }))
+        .await;
+
+        let mut dimensions = Vec::new();
+        let mut usage = Vec::new();
+        let mut failure = None;
+        for (config, model, result) in results {
+            match result {
+                Ok((review, pass_usage)) => {
+                    usage.push((model, pass_usage));
+                    dimensions.push(dimension_score(config, &review));
+                }
+                Err(e) => {
+                    usage.extend(SpentUsage::of(&e));
+                    failure.get_or_insert((config, e));
+                }
+            }
+        }
+        if let Some((config, e)) = failure {
+            return Err(e.context(SpentUsage {
+                message: format!("{} review pass failed", config.name),
+                usage,
+            }));
+        }
+        Ok((dimensions, usage))
+    }
+}
+
+/// Build a dimension score from a specialist review JSON.
+///
+/// Gaps that don't match the `Gap` schema are skipped.
+pub fn dimension_score(config: &ReviewDimensionConfig, review: &Value) -> DimensionScore {
+    let score = review.get("score").and_then(|v| v.as_f64()).unwrap_or(0.0) as f32;
+    let gaps = review
+        .get("gaps")
+        .and_then(|v| v.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|gap| serde_json::from_value::<Gap>(gap.clone()).ok())
+        .collect();
+
+    DimensionScore {
+        name: config.name.clone(),
+        score,
+        weight: config.weight,
+        min_score: config.min_score,
+        passed: config.min_score.is_none_or(|min| score >= min),
+        gaps,
+    }
+}
+
+/// Weighted mean of the main review score and the dimension scores.
+///
+/// Falls back to the main score when all weights are zero.
+pub fn combined_score(primary: f32, primary_weight: f32, dimensions: &[DimensionScore]) -> f32 {
+    let primary_weight = primary_weight.max(0.0);
+    let total_weight = primary_weight + dimensions.iter().map(|d| d.weight.max(0.0)).sum::<f32>();
+    if total_weight <= 0.0 {
+        return primary;
+    }
+    let weighted = primary * primary_weight
+        + dimensions
+            .iter()
+            .map(|d| d.score * d.weight.max(0.0))
+            .sum::<f32>();
+    weighted / total_weight
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Severity;
+    use std::path::PathBuf;
+
+    fn make_dimension(name: &str, weight: f32, min_score: Option<f32>) -> ReviewDimensionConfig {
+        ReviewDimensionConfig {
+            name: name.to_string(),
+            recipe: PathBuf::from(format!("recipes/review-{}.yaml", name)),
+            weight,
+            min_score,
+            provider: None,
+            model: None,
+        }
+    }
+
+    #[test]
+    fn test_dimension_score_enforces_minimum() {
+        let review = serde_json::json!({
+            "score": 0.85,
+            "gaps": [
+                {"description": "Token logged in plain text", "severity": "error", "confidence": "high"},
+                {"description": "Malformed gap"}
+            ]
+        });
+
+        let security = dimension_score(&make_dimension("security", 2.0, Some(0.9)), &review);
+        assert!(!security.passed);
+        assert_eq!(security.weight, 2.0);
+        assert_eq!(security.gaps.len(), 1);
+        assert_eq!(security.gaps[0].severity, Severity::Error);
+
+        let testing = dimension_score(&make_dimension("testing", 1.0, None), &review);
+        assert!(testing.passed);
+    }
+
+    #[test]
+    fn test_combined_score_weights() {
+        let dims = vec![
+            dimension_score(
+                &make_dimension("security", 2.0, Some(0.9)),
+                &serde_json::json!({ "score": 0.6 }),
+            ),
+            dimension_score(
+                &make_dimension("performance", 0.0, None),
+                &serde_json::json!({ "score": 0.1 }),
+            ),
+        ];
+        // (0.9 * 1 + 0.6 * 2) / 3; zero-weight dimensions are report-only
+        assert!((combined_score(0.9, 1.0, &dims) - 0.7).abs() < 1e-6);
+        assert_eq!(combined_score(0.9, 1.0, &[]), 0.9);
+        assert_eq!(combined_score(0.9, 0.0, &dims[1..]), 0.9);
+    }
+}
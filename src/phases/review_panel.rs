@@ -0,0 +1,366 @@
+//! Review panel: several reviewers scoring the same plan.
+//!
+//! Members run concurrently, each with its own recipe/provider/model. Scores
+//! are combined with a `ScoreAggregation` strategy and the findings (gaps,
+//! unclear areas, suggestions) are merged with de-duplication, so the result
+//! has the same shape as a single `GooseReviewer` review plus a `panel` array
+//! of per-reviewer scores.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use anyhow::Result;
+use futures::future::join_all;
+use serde_json::Value;
+use tracing::info;
+
+use crate::config::ScoreAggregation;
+use crate::models::Severity;
+use crate::orchestrator::{ReviewerScore, SpentUsage, TokenUsage};
+
+use super::GooseReviewer;
+
+/// Reviewers whose verdicts are aggregated into one review
+pub struct ReviewPanel {
+    members: Vec<(String, Arc<GooseReviewer>)>,
+    aggregation: ScoreAggregation,
+    score_threshold: f32,
+}
+
+impl ReviewPanel {
+    /// Create an empty panel
+    pub fn new(aggregation: ScoreAggregation, score_threshold: f32) -> Self {
+        Self {
+            members: Vec::new(),
+            aggregation,
+            score_threshold,
+        }
+    }
+
+    /// Add a named reviewer to the panel
+    pub fn with_reviewer(mut self, name: impl Into<String>, reviewer: Arc<GooseReviewer>) -> Self {
+        self.members.push((name.into(), reviewer));
+        self
+    }
+
+    /// Number of reviewers on the panel
+    pub fn len(&self) -> usize {
+        self.members.len()
+    }
+
+    /// Whether the panel has no reviewers
+    pub fn is_empty(&self) -> bool {
+        self.members.is_empty()
+    }
+
+    /// Review a plan with every member concurrently and aggregate the results.
+    ///
+    /// A failed member fails the whole review, so a `Min` or `Majority`
+    /// panel never passes without one of its reviewers; the error carries the
+    /// other members' usage as [`SpentUsage`]. Token usage is returned per
+    /// member, labelled with the member's "provider/model".
+    pub async fn review_plan_json(
+        &self,
+        plan_json: &Value,
+    ) -> Result<(Value, Vec<(String, TokenUsage)>)> {
+        info!(
+            "Running review panel ({} reviewers, {:?})",
+            self.members.len(),
+            self.aggregation
+        );
+
+        let results = join_all(self.members.iter().map(|(name, reviewer)| async move {
+            let result = reviewer.review_plan_json(plan_json).await;
+            (name, reviewer.model_label(), result)
+        }))
+        .await;
+
+        let mut reviews = Vec::new();
+        let mut usage = Vec::new();
+        let mut failure = None;
+        for (name, model, result) in results {
+            match result {
+                Ok((review, member_usage)) => {
+                    usage.push((model, member_usage));
+                    reviews.push((name.clone(), review));
+                }
+                Err(e) => {
+                    usage.extend(SpentUsage::of(&e));
+                    failure.get_or_insert((name, e));
+                }
+            }
+        }
+        if let Some((name, e)) = failure {
+            return Err(e.context(SpentUsage {
+                message: format!("Panel reviewer '{}' failed", name),
+                usage,
+            }));
+        }
+
+        Ok((
+            aggregate_reviews(&reviews, self.aggregation, self.score_threshold),
+            usage,
+        ))
+    }
+}
+
+/// Combine member scores.
+///
+/// `Majority` returns the score reached by more than half of the members, so
+/// comparing it to the threshold passes exactly when a majority passes.
+pub fn aggregate_score(scores: &[f32], aggregation: ScoreAggregation) -> f32 {
+    if scores.is_empty() {
+        return 0.0;
+    }
+    let mut sorted = scores.to_vec();
+    sorted.sort_by(|a, b| a.total_cmp(b));
+    let n = sorted.len();
+    match aggregation {
+        ScoreAggregation::Mean => sorted.iter().sum::<f32>() / n as f32,
+        ScoreAggregation::Min => sorted[0],
+        ScoreAggregation::Median if n % 2 == 0 => (sorted[n / 2 - 1] + sorted[n / 2]) / 2.0,
+        ScoreAggregation::Median => sorted[n / 2],
+        ScoreAggregation::Majority => sorted[n - (n / 2 + 1)],
+    }
+}
+
+/// Merge member reviews into a single review JSON.
+///
+/// Findings are de-duplicated by normalized description; each merged item
+/// lists the `reviewers` that raised it and keeps the highest severity any
+/// of them gave. Human input is required if any member asks for it.
+pub fn aggregate_reviews(
+    reviews: &[(String, Value)],
+    aggregation: ScoreAggregation,
+    score_threshold: f32,
+) -> Value {
+    let panel: Vec<ReviewerScore> = reviews
+        .iter()
+        .map(|(name, review)| {
+            let score = review.get("score").and_then(|v| v.as_f64()).unwrap_or(0.0) as f32;
+            ReviewerScore {
+                reviewer: name.clone(),
+                score,
+                passed: score >= score_threshold,
+            }
+        })
+        .collect();
+    let scores: Vec<f32> = panel.iter().map(|p| p.score).collect();
+    let score = aggregate_score(&scores, aggregation);
+    let passed = score >= score_threshold;
+
+    let overall_assessment = reviews
+        .iter()
+        .filter_map(|(name, review)| {
+            let text = review.get("overall_assessment")?.as_str()?;
+            Some(format!("[{}] {}", name, text))
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n");
+
+    let requesting: Vec<&Value> = reviews
+        .iter()
+        .map(|(_, review)| review)
+        .filter(|review| {
+            review
+                .get("requires_human_input")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false)
+        })
+        .collect();
+    let mut reasons: Vec<&str> = Vec::new();
+    for reason in requesting
+        .iter()
+        .filter_map(|review| review.get("human_input_reason")?.as_str())
+    {
+        if !reasons.contains(&reason) {
+            reasons.push(reason);
+        }
+    }
+
+    let aggregation_name = serde_json::to_value(aggregation)
+        .ok()
+        .and_then(|v| v.as_str().map(str::to_string))
+        .unwrap_or_default();
+
+    let mut merged = serde_json::json!({
+        "overall_assessment": overall_assessment,
+        "gaps": merge_findings(reviews, "gaps"),
+        "unclear_areas": merge_findings(reviews, "unclear_areas"),
+        "suggestions": merge_findings(reviews, "suggestions"),
+        "score": score,
+        "requires_human_input": !requesting.is_empty(),
+        "passed": passed,
+        "summary": format!(
+            "Panel score: {:.2} ({} of {} reviewers; threshold: {:.2}) - {}",
+            score,
+            aggregation_name,
+            panel.len(),
+            score_threshold,
+            if passed { "PASSED" } else { "NEEDS REVISION" }
+        ),
+        "aggregation": aggregation_name,
+        "panel": panel,
+    });
+    if !reasons.is_empty()
+        && let Some(obj) = merged.as_object_mut()
+    {
+        obj.insert(
+            "human_input_reason".to_string(),
+            Value::String(reasons.join("; ")),
+        );
+    }
+    merged
+}
+
+/// Union of one findings array across reviews, de-duplicated by description.
+/// Duplicates keep the most severe `severity`.
+fn merge_findings(reviews: &[(String, Value)], key: &str) -> Vec<Value> {
+    let mut merged: Vec<Value> = Vec::new();
+    let mut index: HashMap<String, usize> = HashMap::new();
+
+    for (name, review) in reviews {
+        let items = review.get(key).and_then(|v| v.as_array());
+        for item in items.into_iter().flatten() {
+            let description = item
+                .get("description")
+                .and_then(|v| v.as_str())
+                .unwrap_or_default();
+            let normalized = normalize(description);
+
+            if let Some(&idx) = index.get(&normalized) {
+                if severity_rank(item.get("severity")) > severity_rank(merged[idx].get("severity"))
+                    && let Some(obj) = merged[idx].as_object_mut()
+                {
+                    obj.insert("severity".to_string(), item["severity"].clone());
+                }
+                if let Some(reviewers) = merged[idx]
+                    .get_mut("reviewers")
+                    .and_then(|r| r.as_array_mut())
+                    && !reviewers.iter().any(|r| r.as_str() == Some(name.as_str()))
+                {
+                    reviewers.push(Value::String(name.clone()));
+                }
+                continue;
+            }
+
+            let mut item = item.clone();
+            if let Some(obj) = item.as_object_mut() {
+                obj.insert(
+                    "reviewers".to_string(),
+                    Value::Array(vec![Value::String(name.clone())]),
+                );
+            }
+            if !normalized.is_empty() {
+                index.insert(normalized, merged.len());
+            }
+            merged.push(item);
+        }
+    }
+    merged
+}
+
+/// Order of `Severity` values (unknown or missing ranks lowest)
+fn severity_rank(severity: Option<&Value>) -> u8 {
+    match severity.and_then(|v| serde_json::from_value::<Severity>(v.clone()).ok()) {
+        Some(Severity::Error) => 3,
+        Some(Severity::Warning) => 2,
+        Some(Severity::Info) => 1,
+        None => 0,
+    }
+}
+
+/// Lowercase alphanumeric words, so punctuation and spacing don't matter
+fn normalize(text: &str) -> String {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_aggregate_score_strategies() {
+        let scores = [0.9, 0.6, 0.85];
+        assert!((aggregate_score(&scores, ScoreAggregation::Mean) - 0.7833).abs() < 1e-3);
+        assert_eq!(aggregate_score(&scores, ScoreAggregation::Min), 0.6);
+        assert_eq!(aggregate_score(&scores, ScoreAggregation::Median), 0.85);
+        // 2 of 3 reach 0.85
+        assert_eq!(aggregate_score(&scores, ScoreAggregation::Majority), 0.85);
+        assert_eq!(aggregate_score(&[0.9, 0.7], ScoreAggregation::Median), 0.8);
+        // A 1-1 split is not a majority: both must reach the score
+        assert_eq!(
+            aggregate_score(&[0.9, 0.7], ScoreAggregation::Majority),
+            0.7
+        );
+        assert_eq!(aggregate_score(&[], ScoreAggregation::Mean), 0.0);
+    }
+
+    #[test]
+    fn test_aggregate_reviews_merges_findings() {
+        let reviews = vec![
+            (
+                "fast".to_string(),
+                serde_json::json!({
+                    "overall_assessment": "Solid",
+                    "score": 0.9,
+                    "gaps": [{"description": "No rollback plan.", "severity": "warning"}],
+                    "unclear_areas": [],
+                    "suggestions": [{"description": "Add metrics", "priority": "optional"}],
+                    "requires_human_input": false
+                }),
+            ),
+            (
+                "strong".to_string(),
+                serde_json::json!({
+                    "overall_assessment": "Missing rollback",
+                    "score": 0.7,
+                    "gaps": [
+                        {"description": "no rollback  plan", "severity": "error"},
+                        {"description": "Cache keys undefined", "severity": "error"}
+                    ],
+                    "unclear_areas": [{"description": "Which cache?", "questions": ["Redis?"]}],
+                    "suggestions": [],
+                    "requires_human_input": true,
+                    "human_input_reason": "Pick a cache backend"
+                }),
+            ),
+        ];
+
+        let merged = aggregate_reviews(&reviews, ScoreAggregation::Min, 0.8);
+        assert_eq!(merged["score"].as_f64().unwrap() as f32, 0.7);
+        assert_eq!(merged["passed"], false);
+        assert_eq!(merged["requires_human_input"], true);
+        assert_eq!(merged["human_input_reason"], "Pick a cache backend");
+        assert_eq!(merged["aggregation"], "min");
+
+        let gaps = merged["gaps"].as_array().unwrap();
+        assert_eq!(gaps.len(), 2);
+        assert_eq!(gaps[0]["reviewers"], serde_json::json!(["fast", "strong"]));
+        // The duplicate keeps the more severe rating
+        assert_eq!(gaps[0]["severity"], "error");
+        assert_eq!(gaps[1]["reviewers"], serde_json::json!(["strong"]));
+        assert_eq!(merged["unclear_areas"].as_array().unwrap().len(), 1);
+
+        let panel: Vec<ReviewerScore> = serde_json::from_value(merged["panel"].clone()).unwrap();
+        assert_eq!(
+            panel,
+            vec![
+                ReviewerScore {
+                    reviewer: "fast".to_string(),
+                    score: 0.9,
+                    passed: true
+                },
+                ReviewerScore {
+                    reviewer: "strong".to_string(),
+                    score: 0.7,
+                    passed: false
+                },
+            ]
+        );
+    }
+}
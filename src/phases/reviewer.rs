@@ -3,20 +3,24 @@ use async_trait::async_trait;
 use futures::StreamExt;
 use serde_json::Value;
 use std::path::PathBuf;
+use std::sync::Arc;
+use tokio_util::sync::CancellationToken;
 use tracing::{debug, info};
 
 use goose::agents::{Agent, AgentEvent, SessionConfig};
 use goose::conversation::message::Message;
+use goose::providers::base::Provider;
+use goose::recipe::Recipe;
 use goose::session::SessionManager;
 
 use crate::config::{HardChecklist, ReviewConfig};
 use crate::models::{LlmReview, Plan, ReviewResult};
-use crate::orchestrator::TokenUsage;
+use crate::orchestrator::{SpentUsage, TokenUsage};
 use crate::recipes::load_recipe;
 
 use super::{
-    ProviderConfig, ReviewContext, Reviewer, create_provider, extract_json_block,
-    resolve_working_dir, setup_agent_session,
+    FailureProbe, ProviderConfig, ReviewContext, Reviewer, create_provider, extract_json_block,
+    resolve_model_label, resolve_working_dir, setup_agent_session,
 };
 
 /// Reviewer implementation using goose Agent with hard checklist
@@ -26,6 +30,16 @@ pub struct GooseReviewer {
     base_dir: PathBuf,
     /// Score threshold for passing review (from guardrails.score_threshold)
     score_threshold: f32,
+    /// Ranked repository symbol map appended to orchestrator review prompts
+    repo_map: Option<String>,
+    /// Bundled recipe used when `config.recipe` is not found
+    recipe_name: String,
+    /// Specialist dimension this reviewer scores (None = general review)
+    focus: Option<String>,
+    /// Stops the agent stream when the session is cancelled
+    cancel_token: Option<CancellationToken>,
+    /// Used instead of creating a provider from config
+    provider: Option<Arc<dyn Provider>>,
 }
 
 impl GooseReviewer {
@@ -35,19 +49,82 @@ impl GooseReviewer {
             checklist: HardChecklist::default(),
             base_dir,
             score_threshold,
+            repo_map: None,
+            recipe_name: "reviewer".to_string(),
+            focus: None,
+            cancel_token: None,
+            provider: None,
         }
     }
 
-    async fn run_llm_review(&self, plan: &Plan, ctx: &ReviewContext) -> Result<LlmReview> {
-        // Load recipe (with fallback to bundled default)
-        let recipe = load_recipe(&self.config.recipe, &self.base_dir, "reviewer")?;
+    /// Fall back to a different bundled recipe (e.g., "review-security").
+    pub fn with_recipe_name(mut self, recipe_name: impl Into<String>) -> Self {
+        self.recipe_name = recipe_name.into();
+        self
+    }
+
+    /// Stop in-flight reviews when `cancel_token` is cancelled.
+    pub fn with_cancel_token(mut self, cancel_token: CancellationToken) -> Self {
+        self.cancel_token = Some(cancel_token);
+        self
+    }
 
-        // Create provider using shared utility
+    /// Call `provider` instead of the one named by config.
+    pub fn with_provider(mut self, provider: Arc<dyn Provider>) -> Self {
+        self.provider = Some(provider);
+        self
+    }
+
+    /// "provider/model" this reviewer calls, resolved the same way as its
+    /// provider (override > recipe setting > default).
+    pub fn model_label(&self) -> String {
         let provider_config = ProviderConfig::for_reviewer(
             self.config.provider_override.as_deref(),
             self.config.model_override.as_deref(),
         );
-        let provider = create_provider(&provider_config, &recipe).await?;
+        let recipe = load_recipe(&self.config.recipe, &self.base_dir, &self.recipe_name).ok();
+        resolve_model_label(&provider_config, recipe.as_ref())
+    }
+
+    /// Fail instead of parsing a partial response after cancellation.
+    fn check_cancelled(&self) -> Result<()> {
+        if self.cancel_token.as_ref().is_some_and(|t| t.is_cancelled()) {
+            anyhow::bail!("Review cancelled");
+        }
+        Ok(())
+    }
+
+    /// The injected provider, or one created from config and `recipe`.
+    async fn provider(&self, recipe: &Recipe) -> Result<Arc<dyn Provider>> {
+        if let Some(provider) = &self.provider {
+            return Ok(provider.clone());
+        }
+        let provider_config = ProviderConfig::for_reviewer(
+            self.config.provider_override.as_deref(),
+            self.config.model_override.as_deref(),
+        );
+        create_provider(&provider_config, recipe).await
+    }
+
+    /// Restrict orchestrator reviews to one specialist dimension.
+    pub fn with_focus(mut self, dimension: impl Into<String>) -> Self {
+        self.focus = Some(dimension.into());
+        self
+    }
+
+    /// Append a repository symbol map to review prompts so the reviewer can
+    /// check names without tool calls.
+    pub fn with_repo_map(mut self, repo_map: String) -> Self {
+        self.repo_map = Some(repo_map);
+        self
+    }
+
+    async fn run_llm_review(&self, plan: &Plan, ctx: &ReviewContext) -> Result<LlmReview> {
+        // Load recipe (with fallback to bundled default)
+        let recipe = load_recipe(&self.config.recipe, &self.base_dir, &self.recipe_name)?;
+
+        // Record provider failures the agent turns into text
+        let probe = FailureProbe::new(self.provider(&recipe).await?);
 
         // Create agent and session using shared utility
         let agent = Agent::new();
@@ -56,7 +133,7 @@ impl GooseReviewer {
         let session = setup_agent_session(
             &agent,
             &recipe,
-            provider,
+            probe.clone(),
             &working_dir,
             &session_name,
             "reviewer",
@@ -91,7 +168,7 @@ impl GooseReviewer {
 
         // Stream response
         let mut stream = agent
-            .reply(user_message, session_config, None)
+            .reply(user_message, session_config, self.cancel_token.clone())
             .await
             .context("Failed to start reviewer agent")?;
 
@@ -110,6 +187,10 @@ impl GooseReviewer {
                 }
             }
         }
+        self.check_cancelled()?;
+        if let Some(failure) = probe.take_failure() {
+            return Err(anyhow::Error::from(failure).context("Reviewer agent failed"));
+        }
 
         parse_llm_review(&last_message)
     }
@@ -191,17 +272,23 @@ Score guidelines:
         info!("Running plan review JSON for orchestrator");
 
         let plan_str = serde_json::to_string_pretty(plan_json).unwrap_or_default();
-        let prompt = self.build_review_prompt_from_json(&plan_str);
+        let mut prompt = self.build_review_prompt_from_json(&plan_str);
+        if let Some(focus) = &self.focus {
+            prompt.push_str(&format!(
+                "\n## Focus\nThis is the **{}** review pass. Score and report gaps for this \
+                 dimension only; other reviewers cover the rest of the plan.\n",
+                focus
+            ));
+        }
+        if let Some(repo_map) = &self.repo_map {
+            prompt.push_str(repo_map);
+        }
 
         // Load recipe
-        let recipe = load_recipe(&self.config.recipe, &self.base_dir, "reviewer")?;
+        let recipe = load_recipe(&self.config.recipe, &self.base_dir, &self.recipe_name)?;
 
-        // Create provider using shared utility
-        let provider_config = ProviderConfig::for_reviewer(
-            self.config.provider_override.as_deref(),
-            self.config.model_override.as_deref(),
-        );
-        let provider = create_provider(&provider_config, &recipe).await?;
+        // Record provider failures the agent turns into text
+        let probe = FailureProbe::new(self.provider(&recipe).await?);
 
         // Create agent and session using shared utility
         let agent = Agent::new();
@@ -210,7 +297,7 @@ Score guidelines:
         let session = setup_agent_session(
             &agent,
             &recipe,
-            provider,
+            probe.clone(),
             &working_dir,
             &session_name,
             "reviewer",
@@ -236,11 +323,12 @@ Score guidelines:
 
         let user_message = Message::user().with_text(&prompt);
         let mut stream = agent
-            .reply(user_message, session_config, None)
+            .reply(user_message, session_config, self.cancel_token.clone())
             .await
             .context("Failed to start reviewer agent")?;
 
         let mut last_message = String::new();
+        let mut stream_error = None;
         while let Some(event) = stream.next().await {
             match event {
                 Ok(AgentEvent::Message(msg)) => {
@@ -249,10 +337,10 @@ Score guidelines:
                 Ok(_) => {}
                 Err(e) => {
                     tracing::warn!("Reviewer error: {:?}", e);
+                    stream_error = Some(e);
                 }
             }
         }
-
         // Get token usage from session
         let token_usage = if let Ok(sess) = SessionManager::get_session(&session_id, false).await {
             TokenUsage::new(
@@ -263,6 +351,25 @@ Score guidelines:
             TokenUsage::default()
         };
 
+        // Cancelled and failed calls still charge what they spent
+        let model_label = self.model_label();
+        let spent = |message: &str| SpentUsage {
+            message: message.to_string(),
+            usage: vec![(model_label.clone(), token_usage.clone())],
+        };
+        self.check_cancelled()
+            .context(spent("Reviewer stream interrupted"))?;
+
+        // A failed provider call (or an empty reply after a stream error)
+        // keeps its typed error
+        let failure = probe
+            .take_failure()
+            .map(anyhow::Error::from)
+            .or_else(|| stream_error.filter(|_| last_message.is_empty()));
+        if let Some(e) = failure {
+            return Err(e.context(spent("Reviewer agent failed")));
+        }
+
         // Parse response as JSON Value (flexible schema)
         let review_json: Value = if let Ok(json) = serde_json::from_str(&last_message) {
             json
@@ -17,6 +17,19 @@ pub const DEFAULT_REVIEWER_RECIPE: &str = include_str!("../recipes/reviewer.yaml
 /// Bundled default orchestrator recipe
 pub const DEFAULT_ORCHESTRATOR_RECIPE: &str = include_str!("../recipes/orchestrator.yaml");
 
+/// Bundled security review dimension recipe
+pub const DEFAULT_REVIEW_SECURITY_RECIPE: &str = include_str!("../recipes/review-security.yaml");
+
+/// Bundled testing strategy review dimension recipe
+pub const DEFAULT_REVIEW_TESTING_RECIPE: &str = include_str!("../recipes/review-testing.yaml");
+
+/// Bundled performance review dimension recipe
+pub const DEFAULT_REVIEW_PERFORMANCE_RECIPE: &str =
+    include_str!("../recipes/review-performance.yaml");
+
+/// Bundled migration safety review dimension recipe
+pub const DEFAULT_REVIEW_MIGRATION_RECIPE: &str = include_str!("../recipes/review-migration.yaml");
+
 /// Recipe resolution result
 pub enum RecipeSource {
     /// Recipe loaded from a file path
@@ -54,11 +67,17 @@ impl RecipeSource {
 /// 2. Project-local `.plan-forge/recipes/<name>.yaml`
 /// 3. Bundled default
 ///
+/// Fails when no file exists and `recipe_name` has no bundled default.
+///
 /// # Arguments
 /// * `recipe_path` - Configured recipe path (may be relative)
 /// * `base_dir` - Base directory to resolve relative paths from
 /// * `recipe_name` - Recipe name for lookup (e.g., "planner", "reviewer")
-pub fn resolve_recipe(recipe_path: &Path, base_dir: &Path, recipe_name: &str) -> RecipeSource {
+pub fn resolve_recipe(
+    recipe_path: &Path,
+    base_dir: &Path,
+    recipe_name: &str,
+) -> Result<RecipeSource> {
     // 1. Try explicit path
     let explicit_path = if recipe_path.is_absolute() {
         recipe_path.to_path_buf()
@@ -68,7 +87,7 @@ pub fn resolve_recipe(recipe_path: &Path, base_dir: &Path, recipe_name: &str) ->
 
     if explicit_path.exists() {
         debug!("Using recipe from explicit path: {:?}", explicit_path);
-        return RecipeSource::File(explicit_path);
+        return Ok(RecipeSource::File(explicit_path));
     }
 
     // 2. Try project-local .plan-forge/recipes/
@@ -77,29 +96,35 @@ pub fn resolve_recipe(recipe_path: &Path, base_dir: &Path, recipe_name: &str) ->
         .join(format!("{}.yaml", recipe_name));
     if plan_forge_path.exists() {
         debug!("Using recipe from .plan-forge: {:?}", plan_forge_path);
-        return RecipeSource::File(plan_forge_path);
+        return Ok(RecipeSource::File(plan_forge_path));
     }
 
     // 3. Fall back to bundled default
     debug!("Using bundled default recipe for: {}", recipe_name);
-    match recipe_name {
-        "planner" => RecipeSource::Bundled(DEFAULT_PLANNER_RECIPE),
-        "reviewer" => RecipeSource::Bundled(DEFAULT_REVIEWER_RECIPE),
-        "orchestrator" => RecipeSource::Bundled(DEFAULT_ORCHESTRATOR_RECIPE),
-        _ => {
-            // Unknown recipe name - try to use the first bundled recipe as fallback
-            // This shouldn't happen in normal usage
-            RecipeSource::Bundled(DEFAULT_PLANNER_RECIPE)
-        }
-    }
+    let content = match recipe_name {
+        "planner" => DEFAULT_PLANNER_RECIPE,
+        "reviewer" => DEFAULT_REVIEWER_RECIPE,
+        "orchestrator" => DEFAULT_ORCHESTRATOR_RECIPE,
+        "review-security" => DEFAULT_REVIEW_SECURITY_RECIPE,
+        "review-testing" => DEFAULT_REVIEW_TESTING_RECIPE,
+        "review-performance" => DEFAULT_REVIEW_PERFORMANCE_RECIPE,
+        "review-migration" => DEFAULT_REVIEW_MIGRATION_RECIPE,
+        // e.g. a specialist dimension without a recipe file
+        _ => anyhow::bail!(
+            "No recipe found for '{}': create {:?} or {:?}",
+            recipe_name,
+            explicit_path,
+            plan_forge_path
+        ),
+    };
+    Ok(RecipeSource::Bundled(content))
 }
 
 /// Convenience function to resolve and load a recipe.
 ///
 /// This combines resolution and loading in one step.
 pub fn load_recipe(recipe_path: &Path, base_dir: &Path, recipe_name: &str) -> Result<Recipe> {
-    let source = resolve_recipe(recipe_path, base_dir, recipe_name);
-    source.load_recipe()
+    resolve_recipe(recipe_path, base_dir, recipe_name)?.load_recipe()
 }
 
 #[cfg(test)]
@@ -122,6 +147,15 @@ mod tests {
             .expect("reviewer recipe should be valid YAML");
         let _: serde_yaml::Value = serde_yaml::from_str(DEFAULT_ORCHESTRATOR_RECIPE)
             .expect("orchestrator recipe should be valid YAML");
+        for recipe in [
+            DEFAULT_REVIEW_SECURITY_RECIPE,
+            DEFAULT_REVIEW_TESTING_RECIPE,
+            DEFAULT_REVIEW_PERFORMANCE_RECIPE,
+            DEFAULT_REVIEW_MIGRATION_RECIPE,
+        ] {
+            let _: serde_yaml::Value =
+                serde_yaml::from_str(recipe).expect("specialist recipe should be valid YAML");
+        }
     }
 
     #[test]
@@ -131,7 +165,22 @@ mod tests {
             Path::new("nonexistent/path.yaml"),
             Path::new("/tmp"),
             "planner",
-        );
+        )
+        .unwrap();
         assert!(matches!(source, RecipeSource::Bundled(_)));
     }
+
+    #[test]
+    fn test_resolve_recipe_unknown_name_fails() {
+        let result = resolve_recipe(
+            Path::new("recipes/review-accessibility.yaml"),
+            Path::new("/tmp"),
+            "review-accessibility",
+        );
+        let error = result
+            .err()
+            .expect("unknown recipe should fail")
+            .to_string();
+        assert!(error.contains("review-accessibility"));
+    }
 }
@@ -11,8 +11,10 @@
 use std::path::PathBuf;
 use std::sync::Arc;
 
+use plan_forge::config::CliConfig;
 use plan_forge::models::{
-    ExistingPattern, GroundingSnapshot, Instruction, OpCode, VerifiedFile, VerifiedTarget,
+    ExistingPattern, GroundingSnapshot, Instruction, OpCode, OpCodeCategory, VerifiedFile,
+    VerifiedTarget,
 };
 use plan_forge::orchestrator::{
     GuardrailHardStop, Guardrails, GuardrailsConfig, HumanInputRecord, OrchestrationState,
@@ -344,6 +346,41 @@ async fn test_session_registry_get_or_create() {
     }
 }
 
+#[tokio::test]
+async fn test_session_registry_resumes_cancelled_session() {
+    let registry = SessionRegistry::new();
+
+    // A cancelled run leaves its state registered (e.g., in the MCP server)
+    let mut cancelled = OrchestrationState::new(
+        "session-1".to_string(),
+        "Task 1".to_string(),
+        PathBuf::from("/tmp"),
+        "task-1".to_string(),
+    );
+    cancelled.iteration = 2;
+    cancelled.status = OrchestrationStatus::Cancelled;
+    registry.get_or_create("session-1", cancelled.clone()).await;
+
+    // Resuming gets the registered copy, not the state loaded from disk
+    let session = registry.get_or_create("session-1", cancelled).await;
+    {
+        let mut s = session.lock().await;
+        assert_eq!(s.status, OrchestrationStatus::Cancelled);
+        s.resume().unwrap();
+        assert_eq!(s.status, OrchestrationStatus::Running);
+        assert_eq!(s.iteration, 2);
+    }
+
+    // Hard-stopped sessions stay stopped
+    {
+        let mut s = session.lock().await;
+        s.status = OrchestrationStatus::HardStopped {
+            reason: GuardrailHardStop::ExecutionTimeout,
+        };
+        assert!(s.resume().is_err());
+    }
+}
+
 #[tokio::test]
 async fn test_session_registry_concurrent_sessions() {
     let registry = Arc::new(SessionRegistry::new());
@@ -533,6 +570,94 @@ fn test_opcode_serialization() {
     }
 }
 
+#[test]
+fn test_custom_opcode_deserialization() {
+    // Unknown names fall through to OpCode::Custom and roundtrip unchanged
+    let op: OpCode = serde_json::from_str("\"RUN_MIGRATION\"").unwrap();
+    assert_eq!(op, OpCode::Custom("RUN_MIGRATION".to_string()));
+    assert_eq!(serde_json::to_string(&op).unwrap(), "\"RUN_MIGRATION\"");
+
+    // Built-in names still resolve to their variants
+    let op: OpCode = serde_json::from_str("\"EDIT_CODE\"").unwrap();
+    assert_eq!(op, OpCode::EditCode);
+
+    let instruction: Instruction = serde_json::from_value(serde_json::json!({
+        "id": "migrate",
+        "op": "RUN_MIGRATION",
+        "params": { "database": "main" },
+        "description": "Apply migrations"
+    }))
+    .unwrap();
+    assert_eq!(instruction.op, OpCode::Custom("RUN_MIGRATION".to_string()));
+    assert_eq!(instruction.op.to_string(), "RUN_MIGRATION");
+    assert_eq!(OpCode::EditCode.to_string(), "EDIT_CODE");
+}
+
+#[test]
+fn test_custom_opcode_config() {
+    let yaml = r#"
+planning:
+  recipe: recipes/planner.yaml
+  provider_override: null
+  model_override: null
+review:
+  recipe: recipes/reviewer.yaml
+  provider_override: null
+  model_override: null
+output:
+  runs_dir: ./.plan-forge
+  active_dir: ./plans/active
+custom_opcodes:
+  - name: UPDATE_SNAPSHOT
+    category: testing
+    params_schema:
+      type: object
+      required: [target]
+    produces: [snapshot_files]
+"#;
+    let config: CliConfig = serde_yaml::from_str(yaml).unwrap();
+    assert_eq!(config.custom_opcodes.len(), 1);
+
+    let custom = &config.custom_opcodes[0];
+    assert_eq!(custom.category, OpCodeCategory::Testing);
+    assert_eq!(custom.produces, vec!["snapshot_files".to_string()]);
+    assert!(config.validate().is_ok());
+
+    // Built-in names can't be redeclared
+    let yaml = yaml.replace("UPDATE_SNAPSHOT", "RUN_TEST");
+    let config: CliConfig = serde_yaml::from_str(&yaml).unwrap();
+    let err = config.validate().unwrap_err();
+    assert!(err.to_string().contains("RUN_TEST"));
+}
+
+#[test]
+fn test_review_panel_config_rejects_duplicate_names() {
+    let yaml = r#"
+planning:
+  recipe: recipes/planner.yaml
+  provider_override: null
+  model_override: null
+review:
+  recipe: recipes/reviewer.yaml
+  provider_override: null
+  model_override: null
+output:
+  runs_dir: ./.plan-forge
+  active_dir: ./plans/active
+review_panel:
+  reviewers:
+    - { name: fast, model: claude-haiku-4-5 }
+    - { name: strong, model: claude-opus-4-5 }
+"#;
+    let config: CliConfig = serde_yaml::from_str(yaml).unwrap();
+    assert!(config.validate().is_ok());
+
+    let yaml = yaml.replace("name: strong", "name: fast");
+    let config: CliConfig = serde_yaml::from_str(&yaml).unwrap();
+    let err = config.validate().unwrap_err();
+    assert!(err.to_string().contains("'fast'"));
+}
+
 #[test]
 fn test_instruction_model() {
     let instruction = Instruction {